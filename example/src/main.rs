@@ -1,11 +1,28 @@
 use std::sync::Arc;
 
 use iced::widget::{
-    button, checkbox, column, container, progress_bar, radio, row, slider, text, text_input,
+    button, checkbox, column, progress_bar, radio, row, slider, text, text_input,
     toggler,
 };
 use iced::{Element, Length};
-use iced_themer::{ThemeConfig, Themed};
+use iced_themer::style::{
+    ButtonStyle, CheckboxStyle, ProgressBarStyle, RadioStyle, SliderStyle, TextInputStyle,
+    TogglerStyle,
+};
+use iced_themer::{ThemeConfig, Themed, watch_styles};
+
+watch_styles! {
+    /// The styles the demo draws with, re-read when the theme switches.
+    struct Styles {
+        input: TextInputStyle,
+        clear: ButtonStyle => "danger",
+        check: CheckboxStyle,
+        dark_mode: TogglerStyle,
+        volume: SliderStyle,
+        progress: ProgressBarStyle,
+        radio: RadioStyle,
+    }
+}
 
 fn main() -> iced::Result {
     let light = Arc::new(
@@ -40,6 +57,7 @@ struct App {
     selected_option: Option<&'static str>,
     light: Arc<ThemeConfig>,
     dark: Arc<ThemeConfig>,
+    styles: Styles,
 }
 
 #[derive(Debug, Clone)]
@@ -60,6 +78,7 @@ impl App {
             is_dark: true,
             slider_value: 50.0,
             selected_option: None,
+            styles: Styles::from_config(&dark),
             light,
             dark,
         }
@@ -74,47 +93,49 @@ impl App {
             Message::InputChanged(value) => self.input_value = value,
             Message::ButtonPressed => self.input_value.clear(),
             Message::CheckboxToggled(value) => self.is_checked = value,
-            Message::TogglerToggled(value) => self.is_dark = value,
+            Message::TogglerToggled(value) => {
+                self.is_dark = value;
+                let config = Arc::clone(if value { &self.dark } else { &self.light });
+                self.styles.refresh(&config);
+            }
             Message::SliderChanged(value) => self.slider_value = value,
             Message::RadioSelected(value) => self.selected_option = Some(value),
         }
     }
 
     fn view(&self) -> Element<'_, Message> {
-        let cfg = self.active_config();
-
         let heading = text("iced-themer Demo").size(28);
 
         let input = text_input("Type something...", &self.input_value)
             .on_input(Message::InputChanged)
-            .themed(cfg.text_input());
+            .themed(self.styles.input.as_ref());
 
         let btn = button("Clear")
             .on_press(Message::ButtonPressed)
-            .themed(cfg.button());
+            .themed(self.styles.clear.as_ref());
 
         let check = checkbox(self.is_checked)
             .label("Enable feature")
             .on_toggle(Message::CheckboxToggled)
-            .themed(cfg.checkbox());
+            .themed(self.styles.check.as_ref());
 
         let tog = toggler(self.is_dark)
             .label("Dark mode")
             .on_toggle(Message::TogglerToggled)
-            .themed(cfg.toggler());
+            .themed(self.styles.dark_mode.as_ref());
 
         let sld = slider(0.0..=100.0, self.slider_value, Message::SliderChanged)
-            .themed(cfg.slider());
+            .themed(self.styles.volume.as_ref());
 
         let prog = progress_bar(0.0..=100.0, self.slider_value)
-            .themed(cfg.progress_bar());
+            .themed(self.styles.progress.as_ref());
 
         let options = ["Option A", "Option B", "Option C"];
         let radios: Vec<Element<'_, Message>> = options
             .iter()
             .map(|&opt| {
                 radio(opt, opt, self.selected_option, Message::RadioSelected)
-                    .themed(cfg.radio())
+                    .themed(self.styles.radio.as_ref())
                     .into()
             })
             .collect();
@@ -137,10 +158,10 @@ impl App {
         .padding(40)
         .max_width(600);
 
-        container(content)
+        self.active_config()
+            .themed_root(content)
             .center_x(Length::Fill)
             .center_y(Length::Fill)
-            .themed(cfg.container())
             .into()
     }
 }
@@ -0,0 +1,213 @@
+//! The 16 ANSI colors, from the theme's optional `[ansi]` section with the
+//! rest derived from the palette.
+
+use iced_core::Color;
+use iced_core::theme::Palette;
+use serde::Deserialize;
+
+use crate::ThemeConfig;
+use crate::color::{self, HexColor};
+use crate::style::{MapColors, impl_approx_eq};
+
+/// Raw `[ansi]` section: any of the colors, each replacing the derived one.
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub(crate) struct AnsiRaw {
+    foreground:     Option<HexColor>,
+    background:     Option<HexColor>,
+    black:          Option<HexColor>,
+    red:            Option<HexColor>,
+    green:          Option<HexColor>,
+    yellow:         Option<HexColor>,
+    blue:           Option<HexColor>,
+    magenta:        Option<HexColor>,
+    cyan:           Option<HexColor>,
+    white:          Option<HexColor>,
+    bright_black:   Option<HexColor>,
+    bright_red:     Option<HexColor>,
+    bright_green:   Option<HexColor>,
+    bright_yellow:  Option<HexColor>,
+    bright_blue:    Option<HexColor>,
+    bright_magenta: Option<HexColor>,
+    bright_cyan:    Option<HexColor>,
+    bright_white:   Option<HexColor>,
+}
+
+/// The colors for text with ANSI color codes, such as log output, diffs, or
+/// an embedded terminal: a default foreground and background, and the 16
+/// ANSI colors.
+///
+/// Every theme has one. Colors the `[ansi]` section doesn't set are derived
+/// from the palette: red, green, yellow, and blue are the danger, success,
+/// warning, and primary colors; magenta and cyan are mixes of those; black
+/// and white are the darker and lighter of the background and text colors.
+/// Bright versions are lighter.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnsiPalette {
+    pub foreground: Color,
+    pub background: Color,
+    pub black: Color,
+    pub red: Color,
+    pub green: Color,
+    pub yellow: Color,
+    pub blue: Color,
+    pub magenta: Color,
+    pub cyan: Color,
+    pub white: Color,
+    pub bright_black: Color,
+    pub bright_red: Color,
+    pub bright_green: Color,
+    pub bright_yellow: Color,
+    pub bright_blue: Color,
+    pub bright_magenta: Color,
+    pub bright_cyan: Color,
+    pub bright_white: Color,
+}
+
+impl AnsiPalette {
+    /// The 16 ANSI colors in index order: black, red, green, yellow, blue,
+    /// magenta, cyan, white, then their bright versions. Index with an SGR
+    /// color code minus 30 (or 90, minus 8, for bright).
+    pub fn colors(&self) -> [Color; 16] {
+        [
+            self.black,
+            self.red,
+            self.green,
+            self.yellow,
+            self.blue,
+            self.magenta,
+            self.cyan,
+            self.white,
+            self.bright_black,
+            self.bright_red,
+            self.bright_green,
+            self.bright_yellow,
+            self.bright_blue,
+            self.bright_magenta,
+            self.bright_cyan,
+            self.bright_white,
+        ]
+    }
+}
+
+impl AnsiRaw {
+    /// The palette with these colors over those derived from `palette` and
+    /// its `warning` color.
+    pub(crate) fn resolve(self, palette: &Palette, warning: Color) -> AnsiPalette {
+        let (dark, light) = if color::is_dark(palette.background) {
+            (palette.background, palette.text)
+        } else {
+            (palette.text, palette.background)
+        };
+        let magenta = color::mix(palette.danger, palette.primary, 0.5);
+        let cyan = color::mix(palette.primary, palette.success, 0.5);
+        let pick = |set: Option<HexColor>, derived: Color| set.map_or(derived, |c| c.0);
+
+        AnsiPalette {
+            foreground: pick(self.foreground, palette.text),
+            background: pick(self.background, palette.background),
+            black: pick(self.black, color::mix(dark, light, 0.1)),
+            red: pick(self.red, palette.danger),
+            green: pick(self.green, palette.success),
+            yellow: pick(self.yellow, warning),
+            blue: pick(self.blue, palette.primary),
+            magenta: pick(self.magenta, magenta),
+            cyan: pick(self.cyan, cyan),
+            white: pick(self.white, color::mix(light, dark, 0.1)),
+            bright_black: pick(self.bright_black, color::mix(dark, light, 0.3)),
+            bright_red: pick(self.bright_red, brighten(palette.danger)),
+            bright_green: pick(self.bright_green, brighten(palette.success)),
+            bright_yellow: pick(self.bright_yellow, brighten(warning)),
+            bright_blue: pick(self.bright_blue, brighten(palette.primary)),
+            bright_magenta: pick(self.bright_magenta, brighten(magenta)),
+            bright_cyan: pick(self.bright_cyan, brighten(cyan)),
+            bright_white: pick(self.bright_white, light),
+        }
+    }
+}
+
+fn brighten(color: Color) -> Color {
+    let mut oklch = color::to_oklch(color);
+    oklch.l = (oklch.l + 0.1).min(1.0);
+    color::from_oklch(oklch)
+}
+
+impl MapColors for AnsiPalette {
+    fn map_colors(self, f: &impl Fn(Color) -> Color) -> Self {
+        AnsiPalette {
+            foreground: f(self.foreground),
+            background: f(self.background),
+            black: f(self.black),
+            red: f(self.red),
+            green: f(self.green),
+            yellow: f(self.yellow),
+            blue: f(self.blue),
+            magenta: f(self.magenta),
+            cyan: f(self.cyan),
+            white: f(self.white),
+            bright_black: f(self.bright_black),
+            bright_red: f(self.bright_red),
+            bright_green: f(self.bright_green),
+            bright_yellow: f(self.bright_yellow),
+            bright_blue: f(self.bright_blue),
+            bright_magenta: f(self.bright_magenta),
+            bright_cyan: f(self.bright_cyan),
+            bright_white: f(self.bright_white),
+        }
+    }
+}
+
+impl_approx_eq!(AnsiPalette {
+    foreground, background,
+    black, red, green, yellow, blue, magenta, cyan, white,
+    bright_black, bright_red, bright_green, bright_yellow, bright_blue, bright_magenta, bright_cyan, bright_white,
+});
+
+impl ThemeConfig {
+    /// The theme's ANSI colors: its `[ansi]` section, with any color it
+    /// doesn't set derived from the palette.
+    pub fn ansi(&self) -> &AnsiPalette {
+        &self.ansi
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::PALETTE;
+
+    #[test]
+    fn colors_derive_from_the_palette() {
+        let config: ThemeConfig = PALETTE.parse().unwrap();
+        let ansi = config.ansi();
+        assert_eq!(ansi.background, Color::from_rgb8(0xFA, 0xFA, 0xFA));
+        assert_eq!(ansi.blue, Color::from_rgb8(0x1E, 0x88, 0xE5));
+        assert_eq!(ansi.yellow, Color::from_rgb8(0xFB, 0x8C, 0x00));
+        assert!(color::luminance(ansi.bright_red) > color::luminance(ansi.red));
+        assert!(color::luminance(ansi.black) < color::luminance(ansi.white));
+    }
+
+    #[test]
+    fn ansi_section_overrides() {
+        let config: ThemeConfig = format!("{PALETTE}[ansi]\nred = \"#FF0000\"\nbright-black = \"$palette.primary\"\n")
+            .parse()
+            .unwrap();
+        assert_eq!(config.ansi().colors()[1], Color::from_rgb8(0xFF, 0, 0));
+        assert_eq!(config.ansi().bright_black, Color::from_rgb8(0x1E, 0x88, 0xE5));
+    }
+
+    #[test]
+    fn invalid_entries_fail_the_load() {
+        let err = format!("{PALETTE}[ansi]\npurple = \"#FF00FF\"\n").parse::<ThemeConfig>().err().unwrap();
+        assert_eq!(err.field(), Some("ansi.purple"));
+        let err = format!("{PALETTE}[ansi]\nred = \"#FF00F\"\n").parse::<ThemeConfig>().err().unwrap();
+        assert_eq!(err.field(), Some("ansi.red"));
+    }
+
+    #[test]
+    fn derived_themes_map_ansi_colors() {
+        let config: ThemeConfig = PALETTE.parse().unwrap();
+        let dark = config.derive_dark();
+        assert!(color::is_dark(dark.ansi().background));
+    }
+}
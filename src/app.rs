@@ -0,0 +1,125 @@
+//! A one-call bootstrap around [`iced::application`].
+
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+use std::time::Duration;
+
+use iced::application::{IntoBoot, UpdateFn, ViewFn};
+use iced::futures::StreamExt;
+use iced::futures::channel::mpsc;
+use iced::{Application, Program, Renderer, Subscription, Theme};
+
+use crate::diff::ThemeDiff;
+use crate::error::Error;
+use crate::watch::{ThemeHandle, ThemeWatcher};
+
+/// How often [`application()`] checks the theme file for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Like [`iced::application`], but themed from the TOML file at `path`.
+///
+/// Loads the theme, sets its font as the default font, and drives
+/// `.theme()` from a [`ThemeHandle`] that a background [`ThemeWatcher`]
+/// reloads whenever the file changes. `new` receives the handle so your state
+/// can keep it for widget styles and for [`reloads()`]:
+///
+/// ```no_run
+/// use iced::widget::button;
+/// use iced::{Element, Subscription};
+/// use iced_themer::{ThemeDiff, ThemeHandle};
+///
+/// struct App {
+///     theme: ThemeHandle,
+/// }
+///
+/// #[derive(Debug, Clone)]
+/// enum Message {
+///     Pressed,
+///     ThemeReloaded(ThemeDiff),
+/// }
+///
+/// impl App {
+///     fn update(&mut self, _message: Message) {}
+///
+///     fn view(&self) -> Element<'_, Message> {
+///         let config = self.theme.current();
+///         let mut b = button("Hello").on_press(Message::Pressed);
+///         if let Some(style) = config.button() {
+///             b = b.style(style.style_fn());
+///         }
+///         b.into()
+///     }
+///
+///     fn subscription(&self) -> Subscription<Message> {
+///         iced_themer::reloads(&self.theme).map(Message::ThemeReloaded)
+///     }
+/// }
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     iced_themer::application("theme.toml", |theme| App { theme }, App::update, App::view)?
+///         .subscription(App::subscription)
+///         .run()?;
+///     Ok(())
+/// }
+/// ```
+///
+/// The [`reloads()`] subscription is optional: without it a reloaded theme
+/// still takes effect, but only on the next redraw your app triggers itself.
+///
+/// Returns an error if the theme can't be loaded at startup; later reload
+/// errors keep the last good theme.
+///
+/// Requires the `application` feature.
+pub fn application<State, Message, Boot>(
+    path: impl AsRef<Path>,
+    new: impl Fn(ThemeHandle) -> Boot + 'static,
+    update: impl UpdateFn<State, Message>,
+    view: impl for<'a> ViewFn<'a, State, Message, Theme, Renderer>,
+) -> Result<Application<impl Program<State = State, Message = Message, Theme = Theme>>, Error>
+where
+    State: 'static,
+    Message: Send + 'static,
+    Boot: IntoBoot<State, Message>,
+{
+    let handle = ThemeWatcher::new(path)?.spawn(POLL_INTERVAL);
+    let font = handle.current().font();
+
+    let boot = {
+        let handle = handle.clone();
+        move || new(handle.clone())
+    };
+    let app = iced::application(boot, update, view).theme(move |_: &State| handle.current().theme());
+
+    Ok(match font {
+        Some(font) => app.default_font(font),
+        None => app,
+    })
+}
+
+/// A subscription producing a [`ThemeDiff`] every time `handle` is reloaded,
+/// so the app redraws with the new theme and can react to what changed.
+///
+/// Requires the `application` feature.
+pub fn reloads(handle: &ThemeHandle) -> Subscription<ThemeDiff> {
+    Subscription::run_with(Reloads(handle.clone()), |Reloads(handle)| {
+        let (sender, receiver) = mpsc::unbounded();
+        // Unregisters the hook once iced drops the stream, so restarting the
+        // subscription doesn't pile up hooks.
+        let guard = handle.on_reload_scoped(move |_, _, diff| {
+            let _ = sender.unbounded_send(diff.clone());
+        });
+        receiver.map(move |diff| {
+            let _ = &guard;
+            diff
+        })
+    })
+}
+
+/// Identifies a [`reloads()`] subscription by the handle it listens to.
+struct Reloads(ThemeHandle);
+
+impl Hash for Reloads {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.id().hash(state);
+    }
+}
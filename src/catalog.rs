@@ -0,0 +1,322 @@
+//! Styling every widget automatically through iced's `Catalog` traits.
+//!
+//! [`ThemerTheme`] stands in for iced's `Theme` as an application's theme type.
+//! Widgets with a TOML section pick up its style as their default; every
+//! other widget (and every widget without a section) falls back to iced's own
+//! default for the palette.
+
+use iced_core::theme::{self, Base, Mode};
+use iced_core::widget::text;
+use iced_core::Theme;
+use iced_widget::overlay::menu;
+use iced_widget::{
+    button, checkbox, combo_box, container, float, pane_grid, pick_list, progress_bar, radio,
+    rule, scrollable, slider, text_editor, text_input, toggler,
+};
+#[cfg(feature = "svg")]
+use iced_widget::svg;
+#[cfg(feature = "table")]
+use iced_widget::table;
+
+use crate::ThemeConfig;
+use crate::style::widget_sections;
+
+/// A [`ThemeConfig`] usable as an iced application's theme type, so every
+/// widget is themed without a single `.style()` call:
+///
+/// ```no_run
+/// use iced::Element;
+/// use iced::widget::{button, column, container, text};
+/// use iced_themer::{ThemeConfig, ThemerTheme};
+///
+/// struct App;
+///
+/// impl App {
+///     fn update(&mut self, _message: ()) {}
+///
+///     fn view(&self) -> Element<'_, (), ThemerTheme> {
+///         column![
+///             // Styled from [button] without calling .style()
+///             button("Press me").on_press(()),
+///             // Styled from [container.variants.card]
+///             container(text("Hello")).class("card"),
+///         ]
+///         .into()
+///     }
+/// }
+///
+/// fn main() -> iced::Result {
+///     let theme = ThemerTheme::from(ThemeConfig::from_file("theme.toml").unwrap());
+///     iced::application(|| App, App::update, App::view)
+///         .theme(move |_: &App| theme.clone())
+///         .run()
+/// }
+/// ```
+///
+/// Widgets with a TOML section also accept a [`ThemerClass`], so
+/// `.class("card")` picks a named variant.
+///
+/// Views then produce `Element<'_, Message, ThemerTheme>`, and explicit
+/// `.style()` closures receive a `&ThemerTheme`; reach iced's `Theme` with
+/// [`theme()`](Self::theme) to reuse iced's built-in styles such as
+/// `button::secondary`.
+///
+/// Requires iced 0.14.
+#[derive(Clone)]
+pub struct ThemerTheme(pub ThemeConfig);
+
+impl ThemerTheme {
+    /// The iced [`Theme`] built from the palette.
+    pub fn theme(&self) -> &Theme {
+        &self.0.theme
+    }
+}
+
+impl From<ThemeConfig> for ThemerTheme {
+    fn from(config: ThemeConfig) -> Self {
+        ThemerTheme(config)
+    }
+}
+
+impl Base for ThemerTheme {
+    fn default(preference: Mode) -> Self {
+        ThemerTheme(ThemeConfig::from_theme(<Theme as Base>::default(preference)))
+    }
+
+    fn mode(&self) -> Mode {
+        self.theme().mode()
+    }
+
+    fn base(&self) -> theme::Style {
+        self.theme().base()
+    }
+
+    fn palette(&self) -> Option<theme::Palette> {
+        Base::palette(self.theme())
+    }
+
+    fn name(&self) -> &str {
+        self.0.name()
+    }
+}
+
+/// The style class of a widget with a TOML section under [`ThemerTheme`].
+///
+/// `.class("danger")` selects `[<widget>.variants.danger]`, falling back to
+/// the widget's default style if the theme doesn't define that variant;
+/// `.style()` closures become [`Style`](Self::Style) classes. `.class()` on
+/// some widgets needs iced's `advanced` feature.
+pub enum ThemerClass<'a, F> {
+    /// A named variant from the theme file.
+    Variant(&'a str),
+    /// A style function.
+    Style(F),
+}
+
+impl<'a, F> From<&'a str> for ThemerClass<'a, F> {
+    fn from(name: &'a str) -> Self {
+        ThemerClass::Variant(name)
+    }
+}
+
+/// Implements `$widget::Catalog` with [`ThemerClass`] classes, in the shape
+/// `widget_sections!` gives for the section of the same name. The default
+/// class uses the config's section style when there is one, and iced's
+/// default style otherwise.
+macro_rules! section_catalog {
+    ($widget:ident, none) => {};
+    (svg, status) => {
+        #[cfg(feature = "svg")]
+        section_catalog!(svg, status, svg);
+    };
+    ($widget:ident, status) => {
+        section_catalog!($widget, status, $widget);
+    };
+    ($widget:ident, plain) => {
+        section_catalog!($widget, plain, $widget);
+    };
+    ($widget:ident, status, $section:ident) => {
+        const _: () = {
+            fn default_style(theme: &ThemerTheme, status: $widget::Status) -> $widget::Style {
+                match theme.0.$section() {
+                    Some(style) => style.style_fn()(theme.theme(), status),
+                    None => <Theme as $widget::Catalog>::style(theme.theme(), &<Theme as $widget::Catalog>::default(), status),
+                }
+            }
+
+            impl<'a> From<$widget::StyleFn<'a, ThemerTheme>> for ThemerClass<'a, $widget::StyleFn<'a, ThemerTheme>> {
+                fn from(style: $widget::StyleFn<'a, ThemerTheme>) -> Self {
+                    ThemerClass::Style(style)
+                }
+            }
+
+            impl $widget::Catalog for ThemerTheme {
+                type Class<'a> = ThemerClass<'a, $widget::StyleFn<'a, Self>>;
+
+                fn default<'a>() -> <Self as $widget::Catalog>::Class<'a> {
+                    ThemerClass::Style(Box::new(default_style))
+                }
+
+                fn style(&self, class: &<Self as $widget::Catalog>::Class<'_>, status: $widget::Status) -> $widget::Style {
+                    match class {
+                        ThemerClass::Style(style) => style(self, status),
+                        ThemerClass::Variant(name) => match self.0.variants.$section.get(*name) {
+                            Some(style) => style.style_fn()(self.theme(), status),
+                            None => default_style(self, status),
+                        },
+                    }
+                }
+            }
+        };
+    };
+    ($widget:ident, plain, $section:ident) => {
+        const _: () = {
+            fn default_style(theme: &ThemerTheme) -> $widget::Style {
+                match theme.0.$section() {
+                    Some(style) => style.style_fn()(theme.theme()),
+                    None => <Theme as $widget::Catalog>::style(theme.theme(), &<Theme as $widget::Catalog>::default()),
+                }
+            }
+
+            impl<'a> From<$widget::StyleFn<'a, ThemerTheme>> for ThemerClass<'a, $widget::StyleFn<'a, ThemerTheme>> {
+                fn from(style: $widget::StyleFn<'a, ThemerTheme>) -> Self {
+                    ThemerClass::Style(style)
+                }
+            }
+
+            impl $widget::Catalog for ThemerTheme {
+                type Class<'a> = ThemerClass<'a, $widget::StyleFn<'a, Self>>;
+
+                fn default<'a>() -> <Self as $widget::Catalog>::Class<'a> {
+                    ThemerClass::Style(Box::new(default_style))
+                }
+
+                fn style(&self, class: &<Self as $widget::Catalog>::Class<'_>) -> $widget::Style {
+                    match class {
+                        ThemerClass::Style(style) => style(self),
+                        ThemerClass::Variant(name) => match self.0.variants.$section.get(*name) {
+                            Some(style) => style.style_fn()(self.theme()),
+                            None => default_style(self),
+                        },
+                    }
+                }
+            }
+        };
+    };
+}
+
+/// Implements `$widget::Catalog` with `$widget::StyleFn` classes defaulting
+/// to iced's own style, like iced's `Theme`.
+macro_rules! catalog {
+    ($widget:ident, status) => {
+        impl $widget::Catalog for ThemerTheme {
+            type Class<'a> = $widget::StyleFn<'a, Self>;
+
+            fn default<'a>() -> <Self as $widget::Catalog>::Class<'a> {
+                Box::new(|theme: &ThemerTheme, status| {
+                    <Theme as $widget::Catalog>::style(theme.theme(), &<Theme as $widget::Catalog>::default(), status)
+                })
+            }
+
+            fn style(&self, class: &<Self as $widget::Catalog>::Class<'_>, status: $widget::Status) -> $widget::Style {
+                class(self, status)
+            }
+        }
+    };
+    ($widget:ident) => {
+        impl $widget::Catalog for ThemerTheme {
+            type Class<'a> = $widget::StyleFn<'a, Self>;
+
+            fn default<'a>() -> <Self as $widget::Catalog>::Class<'a> {
+                Box::new(|theme: &ThemerTheme| {
+                    <Theme as $widget::Catalog>::style(theme.theme(), &<Theme as $widget::Catalog>::default())
+                })
+            }
+
+            fn style(&self, class: &<Self as $widget::Catalog>::Class<'_>) -> $widget::Style {
+                class(self)
+            }
+        }
+    };
+}
+
+macro_rules! section_catalogs {
+    ($($(#[$attr:meta])* $field:ident $name:literal $style:ident $raw:ident $resolve:ident $catalog:ident $statuses:tt;)*) => {
+        $($(#[$attr])* section_catalog!($field, $catalog);)*
+    };
+}
+widget_sections!(section_catalogs);
+
+catalog!(float);
+catalog!(text_editor, status);
+
+impl combo_box::Catalog for ThemerTheme {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::PALETTE;
+
+    fn themer(toml: &str) -> ThemerTheme {
+        ThemerTheme(format!("{PALETTE}{toml}").parse().unwrap())
+    }
+
+    #[test]
+    fn default_class_uses_section_style() {
+        let theme = themer("[button]\nbackground = \"#FF0000\"\n");
+        let class = <ThemerTheme as button::Catalog>::default();
+        let style = button::Catalog::style(&theme, &class, button::Status::Active);
+        assert_eq!(style, theme.0.button().unwrap().style_fn()(theme.theme(), button::Status::Active));
+    }
+
+    #[test]
+    fn default_class_falls_back_to_iced() {
+        let theme = themer("");
+        let class = <ThemerTheme as button::Catalog>::default();
+        let style = button::Catalog::style(&theme, &class, button::Status::Hovered);
+        assert_eq!(style, button::primary(theme.theme(), button::Status::Hovered));
+    }
+
+    #[test]
+    fn drop_down_menus_use_the_menu_section() {
+        let theme = themer("[menu]\nselected-background = \"#66C0F4\"\nshadow-blur-radius = 8.0\n");
+        let style = menu::Catalog::style(&theme, &<ThemerTheme as menu::Catalog>::default());
+        assert_eq!(style.selected_background, iced_core::Color::from_rgb8(0x66, 0xC0, 0xF4).into());
+        assert_eq!(style.shadow.blur_radius, 8.0);
+    }
+
+    #[test]
+    fn text_classes_are_text_variants() {
+        let theme = themer("[text]\ncolor = \"#C7D5E0\"\n\n[text.variants.muted]\ncolor = \"#8F98A0\"\n");
+        let default = text::Catalog::style(&theme, &<ThemerTheme as text::Catalog>::default());
+        assert_eq!(default.color, Some(iced_core::Color::from_rgb8(0xC7, 0xD5, 0xE0)));
+        let muted = text::Catalog::style(&theme, &ThemerClass::Variant("muted"));
+        assert_eq!(muted.color, Some(iced_core::Color::from_rgb8(0x8F, 0x98, 0xA0)));
+        let unknown = text::Catalog::style(&theme, &ThemerClass::Variant("heading"));
+        assert_eq!(unknown.color, default.color);
+    }
+
+    #[cfg(feature = "svg")]
+    #[test]
+    fn svg_icons_take_the_hovered_tint() {
+        let theme = themer("[svg]\ncolor = \"#C7D5E0\"\n\n[svg.hovered]\ncolor = \"#66C0F4\"\n");
+        let class = <ThemerTheme as svg::Catalog>::default();
+        let hovered = svg::Catalog::style(&theme, &class, svg::Status::Hovered);
+        assert_eq!(hovered.color, Some(iced_core::Color::from_rgb8(0x66, 0xC0, 0xF4)));
+    }
+
+    #[test]
+    fn variant_class_uses_variant_style() {
+        let theme = themer("[button]\nbackground = \"#FF0000\"\n\n[button.variants.quiet]\nbackground = \"#00FF00\"\n");
+        let class = ThemerClass::from("quiet");
+        let style = button::Catalog::style(&theme, &class, button::Status::Active);
+        assert_eq!(style.background, Some(iced_core::Color::from_rgb(0.0, 1.0, 0.0).into()));
+    }
+
+    #[test]
+    fn unknown_variant_class_falls_back_to_default() {
+        let theme = themer("[button]\nbackground = \"#FF0000\"\n");
+        let style = button::Catalog::style(&theme, &ThemerClass::from("quiet"), button::Status::Active);
+        assert_eq!(style.background, Some(iced_core::Color::from_rgb(1.0, 0.0, 0.0).into()));
+    }
+}
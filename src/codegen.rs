@@ -0,0 +1,708 @@
+//! Freezing a theme into Rust source.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Write as _};
+
+use iced_core::border::{Border, Radius};
+use iced_core::font::Font;
+use iced_core::gradient::{ColorStop, Gradient, Linear};
+use iced_core::theme::palette::{self, Extended, Pair};
+use iced_core::theme::Palette;
+use iced_core::widget::text;
+use iced_core::{Background, Color, Shadow, Vector};
+use iced_widget::overlay::menu;
+use iced_widget::{
+    button, checkbox, container, pane_grid, pick_list, progress_bar, radio, rule, scrollable, slider, text_input, toggler,
+};
+#[cfg(feature = "table")]
+use iced_widget::table;
+
+use crate::ThemeConfig;
+use crate::color::HexColor;
+use crate::error::{Error, Reason};
+use crate::style::{
+    ButtonStyle, CheckboxStyle, ComboBoxStyle, ContainerStyle, MenuStyle, PaneGridStyle, PickListStyle,
+    ProgressBarStyle, RadioStyle, RuleStyle, ScrollableStyle, SliderStyle, SvgStyle, TextInputStyle, TextStyle,
+    TogglerStyle, root_style_fn, widget_sections,
+};
+#[cfg(feature = "table")]
+use crate::style::TableStyle;
+
+/// A value that can be written as a Rust expression using `iced::` paths.
+trait Rust {
+    fn rust(&self) -> String;
+}
+
+impl Rust for f32 {
+    fn rust(&self) -> String {
+        format!("{self:?}")
+    }
+}
+
+impl Rust for bool {
+    fn rust(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl Rust for u16 {
+    fn rust(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl<T: Rust> Rust for Option<T> {
+    fn rust(&self) -> String {
+        match self {
+            Some(value) => format!("Some({})", value.rust()),
+            None => "None".to_string(),
+        }
+    }
+}
+
+impl<A: Rust, B: Rust> Rust for (A, B) {
+    fn rust(&self) -> String {
+        format!("({}, {})", self.0.rust(), self.1.rust())
+    }
+}
+
+impl Rust for Background {
+    fn rust(&self) -> String {
+        match self {
+            Background::Color(color) => format!("iced::Background::Color({})", color.rust()),
+            Background::Gradient(gradient) => format!("iced::Background::Gradient({})", gradient.rust()),
+        }
+    }
+}
+
+impl Rust for Gradient {
+    fn rust(&self) -> String {
+        match self {
+            Gradient::Linear(linear) => format!("iced::Gradient::Linear({})", linear.rust()),
+        }
+    }
+}
+
+impl Rust for Linear {
+    fn rust(&self) -> String {
+        let stops: Vec<String> = self.stops.iter().map(Rust::rust).collect();
+        format!("iced::gradient::Linear {{ angle: iced::Radians({}), stops: [{}] }}", self.angle.0.rust(), stops.join(", "))
+    }
+}
+
+impl Rust for slider::HandleShape {
+    fn rust(&self) -> String {
+        match self {
+            slider::HandleShape::Circle { radius } => {
+                format!("iced::widget::slider::HandleShape::Circle {{ radius: {} }}", radius.rust())
+            }
+            slider::HandleShape::Rectangle { width, border_radius } => format!(
+                "iced::widget::slider::HandleShape::Rectangle {{ width: {}, border_radius: {} }}",
+                width.rust(),
+                border_radius.rust()
+            ),
+        }
+    }
+}
+
+impl Rust for rule::FillMode {
+    fn rust(&self) -> String {
+        match self {
+            rule::FillMode::Full => "iced::widget::rule::FillMode::Full".to_string(),
+            rule::FillMode::Percent(percent) => format!("iced::widget::rule::FillMode::Percent({})", percent.rust()),
+            rule::FillMode::Padded(padding) => format!("iced::widget::rule::FillMode::Padded({})", padding.rust()),
+            rule::FillMode::AsymmetricPadding(start, end) => {
+                format!("iced::widget::rule::FillMode::AsymmetricPadding({}, {})", start.rust(), end.rust())
+            }
+        }
+    }
+}
+
+/// Implements [`Rust`] for a struct as a literal of `$path` with `$field`s.
+macro_rules! rust_struct {
+    ($ty:ty => $path:literal { $($field:ident),* $(,)? }) => {
+        impl Rust for $ty {
+            fn rust(&self) -> String {
+                let fields: &[String] = &[$(format!("{}: {}", stringify!($field), self.$field.rust())),*];
+                format!("{} {{ {} }}", $path, fields.join(", "))
+            }
+        }
+    };
+}
+
+/// Colors are written as `iced::color!` hex literals, canonical like
+/// [`HexColor`], so generated files diff as cleanly as theme files.
+impl Rust for Color {
+    fn rust(&self) -> String {
+        let hex = HexColor(*self).to_string();
+        match hex[1..].split_at(6) {
+            (rgb, "") => format!("iced::color!(0x{rgb})"),
+            (rgb, alpha) => {
+                let alpha = u8::from_str_radix(alpha, 16).expect("HexColor writes hex digits");
+                format!("iced::color!(0x{rgb}, {})", (alpha as f32 / 255.0).rust())
+            }
+        }
+    }
+}
+
+rust_struct!(ColorStop => "iced::gradient::ColorStop" { offset, color });
+rust_struct!(Vector => "iced::Vector" { x, y });
+rust_struct!(Radius => "iced::border::Radius" { top_left, top_right, bottom_right, bottom_left });
+rust_struct!(Border => "iced::Border" { color, width, radius });
+rust_struct!(Shadow => "iced::Shadow" { color, offset, blur_radius });
+
+rust_struct!(Palette => "iced::theme::Palette" { background, text, primary, success, warning, danger });
+rust_struct!(Pair => "iced::theme::palette::Pair" { color, text });
+rust_struct!(palette::Background => "iced::theme::palette::Background" {
+    base, weakest, weaker, weak, neutral, strong, stronger, strongest
+});
+rust_struct!(palette::Primary => "iced::theme::palette::Primary" { base, weak, strong });
+rust_struct!(palette::Secondary => "iced::theme::palette::Secondary" { base, weak, strong });
+rust_struct!(palette::Success => "iced::theme::palette::Success" { base, weak, strong });
+rust_struct!(palette::Warning => "iced::theme::palette::Warning" { base, weak, strong });
+rust_struct!(palette::Danger => "iced::theme::palette::Danger" { base, weak, strong });
+rust_struct!(Extended => "iced::theme::palette::Extended" {
+    background, primary, secondary, success, warning, danger, is_dark
+});
+
+rust_struct!(button::Style => "iced::widget::button::Style" { background, text_color, border, shadow, snap });
+rust_struct!(checkbox::Style => "iced::widget::checkbox::Style" { background, icon_color, border, text_color });
+rust_struct!(container::Style => "iced::widget::container::Style" { text_color, background, border, shadow, snap });
+rust_struct!(menu::Style => "iced::overlay::menu::Style" {
+    background, border, text_color, selected_text_color, selected_background, shadow
+});
+rust_struct!(pane_grid::Style => "iced::widget::pane_grid::Style" { hovered_region, picked_split, hovered_split });
+rust_struct!(pane_grid::Highlight => "iced::widget::pane_grid::Highlight" { background, border });
+rust_struct!(pane_grid::Line => "iced::widget::pane_grid::Line" { color, width });
+rust_struct!(pick_list::Style => "iced::widget::pick_list::Style" {
+    text_color, placeholder_color, handle_color, background, border
+});
+rust_struct!(progress_bar::Style => "iced::widget::progress_bar::Style" { background, bar, border });
+rust_struct!(radio::Style => "iced::widget::radio::Style" {
+    background, dot_color, border_width, border_color, text_color
+});
+rust_struct!(rule::Style => "iced::widget::rule::Style" { color, radius, fill_mode, snap });
+rust_struct!(scrollable::Rail => "iced::widget::scrollable::Rail" { background, border, scroller });
+rust_struct!(scrollable::Scroller => "iced::widget::scrollable::Scroller" { background, border });
+rust_struct!(slider::Style => "iced::widget::slider::Style" { rail, handle });
+rust_struct!(slider::Rail => "iced::widget::slider::Rail" { backgrounds, width, border });
+rust_struct!(slider::Handle => "iced::widget::slider::Handle" { shape, background, border_width, border_color });
+#[cfg(feature = "table")]
+rust_struct!(table::Style => "iced::widget::table::Style" { separator_x, separator_y });
+rust_struct!(text::Style => "iced::widget::text::Style" { color });
+rust_struct!(text_input::Style => "iced::widget::text_input::Style" {
+    background, border, icon, placeholder, value, selection
+});
+rust_struct!(toggler::Style => "iced::widget::toggler::Style" {
+    background,
+    background_border_width,
+    background_border_color,
+    foreground,
+    foreground_border_width,
+    foreground_border_color,
+    text_color,
+    border_radius,
+    padding_ratio,
+});
+
+fn font(font: Font) -> String {
+    format!(
+        "iced::font::Font {{ family: iced::font::Family::{:?}, weight: iced::font::Weight::{:?}, \
+         stretch: iced::font::Stretch::{:?}, style: iced::font::Style::{:?} }}",
+        font.family, font.weight, font.stretch, font.style
+    )
+}
+
+/// Generated source, and the theme key each function was generated from.
+#[derive(Default)]
+struct Code {
+    out: String,
+    keys: BTreeMap<String, String>,
+    clash: Option<Error>,
+}
+
+impl Code {
+    /// Records that the function `name` is generated from the theme key
+    /// `key`, noting the first name two keys generate.
+    fn declare(&mut self, name: &str, key: &str) {
+        if let Some(first) = self.keys.insert(name.to_string(), key.to_string()) {
+            self.clash.get_or_insert_with(|| Error::InvalidValue {
+                field: key.to_string(),
+                value: format!("`{name}`"),
+                reason: Reason::Other(format!("generates the same function name as `{first}`")),
+            });
+        }
+    }
+}
+
+impl fmt::Write for Code {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.out.write_str(s)
+    }
+}
+
+/// A widget's section style and its variants, with the names of the
+/// functions generated for them: `button`, then `button_danger`, ...
+fn named<'a, T>(
+    out: &mut Code,
+    section_key: &str,
+    section: Option<&'a T>,
+    variants: &'a BTreeMap<String, T>,
+) -> Vec<(String, &'a T)> {
+    let widget = section_key.replace('-', "_");
+    let section = section.map(|style| (widget.clone(), section_key.to_string(), style));
+    let variants = variants.iter().map(|(name, style)| {
+        let suffix: String =
+            name.chars().map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' }).collect();
+        (format!("{widget}_{suffix}"), format!("{section_key}.variants.{name}"), style)
+    });
+    section
+        .into_iter()
+        .chain(variants)
+        .map(|(name, key, style)| {
+            out.declare(&name, &key);
+            (name, style)
+        })
+        .collect()
+}
+
+/// Writes `pub fn name(_theme, status) -> Style` returning `style(status)`
+/// for every status.
+fn status_fn<S: std::fmt::Debug + Copy, T: Rust>(
+    out: &mut Code,
+    name: &str,
+    widget: &str,
+    statuses: &[S],
+    style: impl Fn(S) -> T,
+) {
+    let _ = writeln!(
+        out,
+        "\npub fn {name}(_theme: &iced::Theme, status: iced::widget::{widget}::Status) -> iced::widget::{widget}::Style {{"
+    );
+    let _ = writeln!(out, "    match status {{");
+    for &status in statuses {
+        let _ = writeln!(out, "        iced::widget::{widget}::Status::{status:?} => {},", style(status).rust());
+    }
+    let _ = writeln!(out, "    }}\n}}");
+}
+
+/// Writes `pub fn name(_theme) -> Style` returning `style`.
+fn static_fn(out: &mut Code, name: &str, widget: &str, style: impl Rust) {
+    let _ = writeln!(
+        out,
+        "\npub fn {name}(_theme: &iced::Theme) -> iced::widget::{widget}::Style {{\n    {}\n}}",
+        style.rust()
+    );
+}
+
+/// Writes `pub fn name(_theme) -> Style` returning the drop-down menu style
+/// `style`.
+fn menu_fn(out: &mut Code, name: &str, style: menu::Style) {
+    let _ = writeln!(
+        out,
+        "\npub fn {name}(_theme: &iced::Theme) -> iced::overlay::menu::Style {{\n    {}\n}}",
+        style.rust()
+    );
+}
+
+/// Writes `pub fn name(_theme, status) -> Style` for an svg, written out
+/// by hand since iced's svg module needs its `svg` feature.
+fn svg_fn(out: &mut Code, name: &str, style: &SvgStyle) {
+    let _ = writeln!(
+        out,
+        "\npub fn {name}(_theme: &iced::Theme, status: iced::widget::svg::Status) -> iced::widget::svg::Style {{"
+    );
+    let _ = writeln!(out, "    match status {{");
+    for (status, color) in [("Idle", style.idle()), ("Hovered", style.hovered())] {
+        let _ = writeln!(
+            out,
+            "        iced::widget::svg::Status::{status} => iced::widget::svg::Style {{ color: {} }},",
+            color.rust()
+        );
+    }
+    let _ = writeln!(out, "    }}\n}}");
+}
+
+/// Writes `pub fn name(theme, status) -> Style` for a scrollable: the
+/// theme's scrollbars over iced's default style, which it calls at runtime
+/// for the parts a `[scrollable]` section doesn't set.
+fn scrollable_fn(out: &mut Code, name: &str, style: &ScrollableStyle, theme: &iced_core::Theme) {
+    let style = |status| style.style_fn()(theme, status);
+    let active = style(scrollable::Status::Active {
+        is_horizontal_scrollbar_disabled: false,
+        is_vertical_scrollbar_disabled: false,
+    });
+    let hovered = style(scrollable::Status::Hovered {
+        is_horizontal_scrollbar_hovered: false,
+        is_vertical_scrollbar_hovered: true,
+        is_horizontal_scrollbar_disabled: false,
+        is_vertical_scrollbar_disabled: false,
+    });
+    let dragged = style(scrollable::Status::Dragged {
+        is_horizontal_scrollbar_dragged: false,
+        is_vertical_scrollbar_dragged: true,
+        is_horizontal_scrollbar_disabled: false,
+        is_vertical_scrollbar_disabled: false,
+    });
+
+    let _ = writeln!(
+        out,
+        "\npub fn {name}(theme: &iced::Theme, status: iced::widget::scrollable::Status) -> iced::widget::scrollable::Style {{"
+    );
+    let _ = writeln!(out, "    use iced::widget::scrollable::Status;");
+    let _ = writeln!(out, "    let active = {};", active.vertical_rail.rust());
+    let _ = writeln!(out, "    let hovered = {};", hovered.vertical_rail.rust());
+    let _ = writeln!(out, "    let dragged = {};", dragged.vertical_rail.rust());
+    let _ = writeln!(
+        out,
+        "    let (vertical_rail, horizontal_rail) = match status {{
+        Status::Active {{ .. }} => (active, active),
+        Status::Hovered {{ is_vertical_scrollbar_hovered: v, is_horizontal_scrollbar_hovered: h, .. }} => {{
+            (if v {{ hovered }} else {{ active }}, if h {{ hovered }} else {{ active }})
+        }}
+        Status::Dragged {{ is_vertical_scrollbar_dragged: v, is_horizontal_scrollbar_dragged: h, .. }} => {{
+            (if v {{ dragged }} else {{ active }}, if h {{ dragged }} else {{ active }})
+        }}
+    }};"
+    );
+    let _ = writeln!(out, "    let gap = {};", active.gap.rust());
+    let _ = writeln!(
+        out,
+        "    iced::widget::scrollable::Style {{\n        vertical_rail,\n        horizontal_rail,\n        gap,\n        \
+         ..iced::widget::scrollable::default(theme, status)\n    }}\n}}"
+    );
+}
+
+const BUTTON: [button::Status; 4] =
+    [button::Status::Active, button::Status::Hovered, button::Status::Pressed, button::Status::Disabled];
+const PICK_LIST: [pick_list::Status; 4] = [
+    pick_list::Status::Active,
+    pick_list::Status::Hovered,
+    pick_list::Status::Opened { is_hovered: false },
+    pick_list::Status::Opened { is_hovered: true },
+];
+const SLIDER: [slider::Status; 3] = [slider::Status::Active, slider::Status::Hovered, slider::Status::Dragged];
+const TEXT_INPUT: [text_input::Status; 5] = [
+    text_input::Status::Active,
+    text_input::Status::Hovered,
+    text_input::Status::Focused { is_hovered: false },
+    text_input::Status::Focused { is_hovered: true },
+    text_input::Status::Disabled,
+];
+
+fn checkbox_statuses() -> Vec<checkbox::Status> {
+    [false, true]
+        .into_iter()
+        .flat_map(|is_checked| {
+            [
+                checkbox::Status::Active { is_checked },
+                checkbox::Status::Hovered { is_checked },
+                checkbox::Status::Disabled { is_checked },
+            ]
+        })
+        .collect()
+}
+
+fn radio_statuses() -> Vec<radio::Status> {
+    [false, true]
+        .into_iter()
+        .flat_map(|is_selected| [radio::Status::Active { is_selected }, radio::Status::Hovered { is_selected }])
+        .collect()
+}
+
+fn toggler_statuses() -> Vec<toggler::Status> {
+    [false, true]
+        .into_iter()
+        .flat_map(|is_toggled| {
+            [
+                toggler::Status::Active { is_toggled },
+                toggler::Status::Hovered { is_toggled },
+                toggler::Status::Disabled { is_toggled },
+            ]
+        })
+        .collect()
+}
+
+/// A section style, or one of its variants, that can be written as style
+/// functions.
+trait Generate {
+    /// Writes the style functions for `self`, the main one named `name`.
+    fn generate(&self, out: &mut Code, name: &str, theme: &iced_core::Theme);
+}
+
+impl Generate for ButtonStyle {
+    fn generate(&self, out: &mut Code, name: &str, theme: &iced_core::Theme) {
+        status_fn(out, name, "button", &BUTTON, |s| self.style_fn()(theme, s));
+    }
+}
+
+impl Generate for CheckboxStyle {
+    fn generate(&self, out: &mut Code, name: &str, theme: &iced_core::Theme) {
+        status_fn(out, name, "checkbox", &checkbox_statuses(), |s| self.style_fn()(theme, s));
+    }
+}
+
+impl Generate for ComboBoxStyle {
+    fn generate(&self, out: &mut Code, name: &str, theme: &iced_core::Theme) {
+        status_fn(out, name, "text_input", &TEXT_INPUT, |s| self.input().style_fn()(theme, s));
+        if let Some(menu) = self.menu() {
+            let (menu_name, key) = (format!("{name}_menu"), format!("{}.menu", out.keys[name]));
+            out.declare(&menu_name, &key);
+            menu_fn(out, &menu_name, menu.style_fn()(theme));
+        }
+    }
+}
+
+impl Generate for ContainerStyle {
+    fn generate(&self, out: &mut Code, name: &str, theme: &iced_core::Theme) {
+        static_fn(out, name, "container", self.style_fn()(theme));
+    }
+}
+
+impl Generate for MenuStyle {
+    fn generate(&self, out: &mut Code, name: &str, theme: &iced_core::Theme) {
+        menu_fn(out, name, self.style_fn()(theme));
+    }
+}
+
+impl Generate for PaneGridStyle {
+    fn generate(&self, out: &mut Code, name: &str, theme: &iced_core::Theme) {
+        static_fn(out, name, "pane_grid", self.style_fn()(theme));
+    }
+}
+
+impl Generate for PickListStyle {
+    fn generate(&self, out: &mut Code, name: &str, theme: &iced_core::Theme) {
+        status_fn(out, name, "pick_list", &PICK_LIST, |s| self.style_fn()(theme, s));
+    }
+}
+
+impl Generate for ProgressBarStyle {
+    fn generate(&self, out: &mut Code, name: &str, theme: &iced_core::Theme) {
+        static_fn(out, name, "progress_bar", self.style_fn()(theme));
+    }
+}
+
+impl Generate for RadioStyle {
+    fn generate(&self, out: &mut Code, name: &str, theme: &iced_core::Theme) {
+        status_fn(out, name, "radio", &radio_statuses(), |s| self.style_fn()(theme, s));
+    }
+}
+
+impl Generate for RuleStyle {
+    fn generate(&self, out: &mut Code, name: &str, theme: &iced_core::Theme) {
+        static_fn(out, name, "rule", self.style_fn()(theme));
+        let _ = writeln!(out, "\npub const {}_WIDTH: u16 = {};", name.to_uppercase(), self.width());
+    }
+}
+
+impl Generate for ScrollableStyle {
+    fn generate(&self, out: &mut Code, name: &str, theme: &iced_core::Theme) {
+        scrollable_fn(out, name, self, theme);
+    }
+}
+
+impl Generate for SliderStyle {
+    fn generate(&self, out: &mut Code, name: &str, theme: &iced_core::Theme) {
+        status_fn(out, name, "slider", &SLIDER, |s| self.style_fn()(theme, s));
+    }
+}
+
+impl Generate for SvgStyle {
+    fn generate(&self, out: &mut Code, name: &str, _theme: &iced_core::Theme) {
+        svg_fn(out, name, self);
+    }
+}
+
+#[cfg(feature = "table")]
+impl Generate for TableStyle {
+    fn generate(&self, out: &mut Code, name: &str, theme: &iced_core::Theme) {
+        static_fn(out, name, "table", self.style_fn()(theme));
+    }
+}
+
+impl Generate for TextStyle {
+    fn generate(&self, out: &mut Code, name: &str, theme: &iced_core::Theme) {
+        static_fn(out, name, "text", self.style_fn()(theme));
+    }
+}
+
+impl Generate for TextInputStyle {
+    fn generate(&self, out: &mut Code, name: &str, theme: &iced_core::Theme) {
+        status_fn(out, name, "text_input", &TEXT_INPUT, |s| self.style_fn()(theme, s));
+    }
+}
+
+impl Generate for TogglerStyle {
+    fn generate(&self, out: &mut Code, name: &str, theme: &iced_core::Theme) {
+        status_fn(out, name, "toggler", &toggler_statuses(), |s| self.style_fn()(theme, s));
+    }
+}
+
+macro_rules! generate_sections {
+    ($($(#[$attr:meta])* $field:ident $name:literal $style:ident $raw:ident $resolve:ident $catalog:ident $statuses:tt;)*) => {
+        impl ThemeConfig {
+            /// Writes the style functions of every widget section and its
+            /// variants.
+            fn generate_sections(&self, out: &mut Code) {
+                $($(#[$attr])* for (name, style) in named(out, $name, self.$field.as_ref(), &self.variants.$field) {
+                    style.generate(out, &name, &self.theme);
+                })*
+            }
+        }
+    };
+}
+widget_sections!(generate_sections);
+
+impl ThemeConfig {
+    /// Rust source that builds this theme with no TOML parsing at runtime.
+    ///
+    /// The output defines `PALETTE` and `EXTENDED` constants, a `theme()`
+    /// function, `FONT` if the theme sets one, and one style function per
+    /// widget section and variant (`button`, `button_danger`, `root`, ...)
+    /// with every status resolved to a literal. The style functions have the
+    /// signatures iced's `.style()` expects:
+    ///
+    /// ```no_run
+    /// # use iced_themer::ThemeConfig;
+    /// let config = ThemeConfig::from_file("theme.toml")?;
+    /// std::fs::write("src/theme.rs", config.to_rust_code()?)?;
+    /// // then, in the app: button("Save").style(theme::button)
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// A `[combo-box]` section yields a text input style function for
+    /// `.input_style()`, plus `combo_box_menu` for `.menu_style()` when it has
+    /// a `menu` table.
+    ///
+    /// Checkbox and radio `label-*` keys, the checkbox `icon` table, and
+    /// button and text input `text-size` and `padding`, set widget
+    /// properties rather than styles, so they aren't emitted.
+    ///
+    /// A `[pane-grid]` section yields its style with iced's defaults for
+    /// this theme filled in where the section leaves them out.
+    ///
+    /// A `[rule]` section yields its style and a `RULE_WIDTH` constant to
+    /// pass to `rule::horizontal` or `rule::vertical`.
+    ///
+    /// A `[scrollable]` section yields its scrollbars, calling iced's default
+    /// scrollable style for the rest.
+    ///
+    /// An `[svg]` section yields a style function for iced's `Svg`, which
+    /// needs iced's `svg` feature in the app.
+    ///
+    /// With the `table` feature, a `[table]` section yields only its divider
+    /// colors, as a `table` function returning iced's `table::Style`; row
+    /// styles have no iced counterpart to freeze.
+    ///
+    /// Variant names are turned into snake case for their functions, so
+    /// variants like `danger-zone` and `danger_zone` would generate the same
+    /// function; that's an error naming the second one.
+    ///
+    /// Colors are written as `iced::color!(0xRRGGBB)` hex literals, with an
+    /// alpha when translucent, so regenerating a changed theme gives a small
+    /// diff. Like [`to_toml`](Self::to_toml), that rounds each channel to 8
+    /// bits.
+    ///
+    /// The generated code depends only on `iced`. Requires iced 0.14.
+    pub fn to_rust_code(&self) -> Result<String, Error> {
+        let theme = &self.theme;
+        let mut out = Code::default();
+        let _ = writeln!(out, "// Generated by iced-themer from the theme {:?}. Do not edit.", self.name);
+
+        let _ = writeln!(out, "\npub const PALETTE: iced::theme::Palette = {};", theme.palette().rust());
+        let _ = writeln!(out, "\npub const EXTENDED: iced::theme::palette::Extended = {};", theme.extended_palette().rust());
+        let _ = writeln!(
+            out,
+            "\npub fn theme() -> iced::Theme {{\n    iced::Theme::custom_with_fn({:?}, PALETTE, |_| EXTENDED)\n}}",
+            self.name
+        );
+        if let Some(f) = self.font {
+            let _ = writeln!(out, "\npub const FONT: iced::font::Font = {};", font(f));
+        }
+
+        self.generate_sections(&mut out);
+        if self.root.is_some() || self.container.is_some() {
+            out.declare("root", "root");
+            static_fn(&mut out, "root", "container", root_style_fn(self.root.or(self.container))(theme));
+        }
+
+        match out.clash {
+            Some(err) => Err(err),
+            None => Ok(out.out),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::palette;
+
+    const THEME: &str = concat!(
+        "\nname = \"Paper\"\n",
+        palette!(),
+        r##"
+[button]
+background = "#FF0000"
+
+[button.variants.danger-zone]
+background = "#000000"
+
+[scrollable]
+scroller-background = "#9E9E9E"
+
+[pane-grid.picked-split]
+width = 4.0
+
+[rule]
+width        = 2
+fill-padding = [4, 8]
+
+[svg.hovered]
+color = "#1E88E5"
+"##
+    );
+
+    #[test]
+    fn emits_a_function_per_section_and_variant() {
+        let code = THEME.parse::<ThemeConfig>().unwrap().to_rust_code().unwrap();
+        assert!(code.contains("iced::Theme::custom_with_fn(\"Paper\", PALETTE, |_| EXTENDED)"));
+        assert!(code.contains("pub fn button(_theme: &iced::Theme, status: iced::widget::button::Status)"));
+        assert!(code.contains("pub fn button_danger_zone("));
+        assert!(code.contains("iced::widget::button::Status::Pressed => iced::widget::button::Style {"));
+        assert!(!code.contains("pub fn slider("));
+        assert!(code.contains("pub fn scrollable(theme: &iced::Theme, status: iced::widget::scrollable::Status)"));
+        assert!(code.contains("fill_mode: iced::widget::rule::FillMode::AsymmetricPadding(4, 8)"));
+        assert!(code.contains("pub const RULE_WIDTH: u16 = 2;"));
+        assert!(code.contains("picked_split: iced::widget::pane_grid::Line {"));
+        assert!(code.contains("iced::widget::svg::Status::Idle => iced::widget::svg::Style { color: None },"));
+        assert!(!code.contains("FONT"));
+    }
+
+    #[test]
+    fn output_parses_as_rust() {
+        let code = THEME.parse::<ThemeConfig>().unwrap().to_rust_code().unwrap();
+        syn::parse_file(&code).unwrap();
+    }
+
+    #[test]
+    fn clashing_function_names_are_an_error() {
+        let clash = format!("{THEME}\n[button.variants.danger_zone]\nbackground = \"#000000\"\n");
+        let err = clash.parse::<ThemeConfig>().unwrap().to_rust_code().unwrap_err();
+        assert_eq!(err.field(), Some("button.variants.danger_zone"));
+        assert_eq!(err.value(), Some("`button_danger_zone`"));
+
+        let clash = format!("{THEME}\n[text-input]\n\n[text.variants.input]\n");
+        let err = clash.parse::<ThemeConfig>().unwrap().to_rust_code().unwrap_err();
+        assert_eq!(err.field(), Some("text-input"));
+    }
+
+    #[test]
+    fn literals_are_rust_expressions() {
+        let color = Color::from_rgba(1.0, 0.5, 0.0, 0.25);
+        assert_eq!(color.rust(), "iced::color!(0xFF8000, 0.2509804)");
+        assert_eq!(Color::from_rgb8(0x1e, 0x88, 0xe5).rust(), "iced::color!(0x1E88E5)");
+        assert_eq!(Some(Background::Color(Color::BLACK)).rust(), format!("Some(iced::Background::Color({}))", Color::BLACK.rust()));
+    }
+}
@@ -1,28 +1,121 @@
 use iced_core::Color;
-use serde::de;
+use serde::{Serialize, Serializer, de};
 use std::fmt;
+use std::str::FromStr;
+
+use crate::error::Reason;
 
 /// A newtype around [`Color`] that deserializes from hex strings and named colors.
 ///
-/// Supported formats: `#RGB`, `#RRGGBB`, `#RRGGBBAA`, and named colors
-/// (`black`, `white`, `transparent`).
-#[derive(Debug, Clone, Copy)]
+/// Supported formats: `#RGB`, `#RRGGBB`, `#RRGGBBAA`, named colors
+/// (`black`, `white`, `transparent`), integers like `0x1B2838`, and arrays
+/// of `0.0..=1.0` floats like `[0.1, 0.2, 0.3]` or `[0.1, 0.2, 0.3, 0.5]`.
+///
+/// It serializes and displays as `"#RRGGBB"`, or `"#RRGGBBAA"` when
+/// translucent, and parses the string formats with [`FromStr`], so it can
+/// stand in for a color in configs of your own:
+///
+/// ```
+/// # use iced_themer::HexColor;
+/// let accent: HexColor = "#1E88E5".parse().unwrap();
+/// assert_eq!(accent.to_string(), "#1E88E5");
+/// assert!("#1E88E".parse::<HexColor>().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct HexColor(pub Color);
 
+impl From<Color> for HexColor {
+    fn from(color: Color) -> Self {
+        HexColor(color)
+    }
+}
+
+impl From<HexColor> for Color {
+    fn from(color: HexColor) -> Self {
+        color.0
+    }
+}
+
+impl FromStr for HexColor {
+    type Err = Reason;
+
+    fn from_str(s: &str) -> Result<Self, Reason> {
+        parse_color(s).map(HexColor)
+    }
+}
+
+impl Serialize for HexColor {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
 impl<'de> de::Deserialize<'de> for HexColor {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: de::Deserializer<'de>,
     {
-        let s = String::deserialize(deserializer)?;
-        parse_color(&s).map(HexColor).map_err(de::Error::custom)
+        deserializer.deserialize_any(HexColorVisitor)
     }
 }
 
+struct HexColorVisitor;
+
+impl<'de> de::Visitor<'de> for HexColorVisitor {
+    type Value = HexColor;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a color string like \"#RRGGBB\", an integer like 0xRRGGBB, or an [r, g, b, a] array")
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<HexColor, E> {
+        parse_color(s).map(HexColor).map_err(E::custom)
+    }
+
+    fn visit_i64<E: de::Error>(self, n: i64) -> Result<HexColor, E> {
+        let hex = integer_color(n).ok_or_else(|| E::invalid_value(de::Unexpected::Signed(n), &self))?;
+        self.visit_str(&hex)
+    }
+
+    fn visit_u64<E: de::Error>(self, n: u64) -> Result<HexColor, E> {
+        match i64::try_from(n) {
+            Ok(n) => self.visit_i64(n),
+            Err(_) => Err(E::invalid_value(de::Unexpected::Unsigned(n), &self)),
+        }
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<HexColor, A::Error> {
+        let mut channels = Vec::with_capacity(4);
+        while let Some(channel) = seq.next_element::<f32>()? {
+            channels.push(channel);
+        }
+        array_color(&channels).map(HexColor).map_err(de::Error::custom)
+    }
+}
+
+/// The color for an `[r, g, b]` or `[r, g, b, a]` array of `0.0..=1.0`
+/// channels, matching [`Color`]'s fields.
+pub(crate) fn array_color(channels: &[f32]) -> Result<Color, Reason> {
+    if let Some(channel) = channels.iter().find(|c| !(0.0..=1.0).contains(*c)) {
+        return Err(Reason::Other(format!("color channel {channel} is outside 0.0..=1.0")));
+    }
+    match *channels {
+        [r, g, b] => Ok(Color::from_rgb(r, g, b)),
+        [r, g, b, a] => Ok(Color::from_rgba(r, g, b, a)),
+        _ => Err(Reason::Other(format!("expected 3 or 4 color channels, got {}", channels.len()))),
+    }
+}
+
+/// The `#RRGGBB` string for an integer color literal like `0x1B2838`, or
+/// `None` if `n` is outside `0..=0xFFFFFF`.
+pub(crate) fn integer_color(n: i64) -> Option<String> {
+    (0..=0xFF_FFFF).contains(&n).then(|| format!("#{n:06X}"))
+}
+
 /// Parse a color string into an iced [`Color`].
 ///
 /// Accepts `#RGB`, `#RRGGBB`, `#RRGGBBAA`, and named colors.
-pub fn parse_color(s: &str) -> Result<Color, String> {
+pub fn parse_color(s: &str) -> Result<Color, Reason> {
     match s.to_ascii_lowercase().as_str() {
         "black" => return Ok(Color::BLACK),
         "white" => return Ok(Color::WHITE),
@@ -30,9 +123,11 @@ pub fn parse_color(s: &str) -> Result<Color, String> {
         _ => {}
     }
 
-    let hex = s.strip_prefix('#').ok_or_else(|| {
-        format!("expected '#' prefix or a named color, got \"{s}\"")
-    })?;
+    let hex = s.strip_prefix('#').ok_or(Reason::MissingHashPrefix)?;
+    if !hex.is_ascii() {
+        let pos = hex.char_indices().find(|(_, c)| !c.is_ascii_hexdigit()).map_or(0, |(i, _)| i);
+        return Err(Reason::InvalidHexDigit(pos));
+    }
 
     match hex.len() {
         3 => {
@@ -54,43 +149,186 @@ pub fn parse_color(s: &str) -> Result<Color, String> {
             let a = parse_hex_byte(hex, 6)?;
             Ok(Color::from_rgba8(r, g, b, a as f32 / 255.0))
         }
-        n => Err(format!(
-            "expected 3, 6, or 8 hex digits after '#', got {n}"
-        )),
+        n => Err(Reason::HexDigitCount(n)),
     }
 }
 
-fn parse_hex_digit(hex: &str, pos: usize) -> Result<u8, String> {
-    u8::from_str_radix(&hex[pos..pos + 1], 16)
-        .map_err(|_| format!("invalid hex digit at position {pos}"))
+fn parse_hex_digit(hex: &str, pos: usize) -> Result<u8, Reason> {
+    u8::from_str_radix(&hex[pos..pos + 1], 16).map_err(|_| Reason::InvalidHexDigit(pos))
+}
+
+fn parse_hex_byte(hex: &str, pos: usize) -> Result<u8, Reason> {
+    let digit = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).map_err(|_| Reason::InvalidHexDigit(i));
+    Ok(digit(pos)? << 4 | digit(pos + 1)?)
 }
 
-fn parse_hex_byte(hex: &str, pos: usize) -> Result<u8, String> {
-    u8::from_str_radix(&hex[pos..pos + 2], 16)
-        .map_err(|_| format!("invalid hex byte at position {pos}"))
+/// A color in HSL space. Hue is in degrees, the other channels in `0.0..=1.0`.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Hsl {
+    pub h: f32,
+    pub s: f32,
+    pub l: f32,
+    pub a: f32,
+}
+
+/// Convert an sRGB [`Color`] to HSL.
+pub(crate) fn to_hsl(c: Color) -> Hsl {
+    let max = c.r.max(c.g).max(c.b);
+    let min = c.r.min(c.g).min(c.b);
+    let l = (max + min) / 2.0;
+    let d = max - min;
+
+    if d <= f32::EPSILON {
+        return Hsl { h: 0.0, s: 0.0, l, a: c.a };
+    }
+
+    let s = if l > 0.5 { d / (2.0 - max - min) } else { d / (max + min) };
+    let h = if max == c.r {
+        (c.g - c.b) / d + if c.g < c.b { 6.0 } else { 0.0 }
+    } else if max == c.g {
+        (c.b - c.r) / d + 2.0
+    } else {
+        (c.r - c.g) / d + 4.0
+    };
+
+    Hsl { h: h * 60.0, s, l, a: c.a }
+}
+
+/// Convert an HSL color back to sRGB.
+pub(crate) fn from_hsl(hsl: Hsl) -> Color {
+    let Hsl { h, s, l, a } = hsl;
+    let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let h = h.rem_euclid(360.0) / 60.0;
+    let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let m = l - c / 2.0;
+
+    Color::from_rgba(
+        (r + m).clamp(0.0, 1.0),
+        (g + m).clamp(0.0, 1.0),
+        (b + m).clamp(0.0, 1.0),
+        a,
+    )
+}
+
+/// A color in the perceptual Oklch space: lightness and chroma, with hue in radians.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Oklch {
+    pub l: f32,
+    pub c: f32,
+    pub h: f32,
+    pub a: f32,
+}
+
+/// Convert an sRGB [`Color`] to Oklch.
+///
+/// See <https://bottosson.github.io/posts/oklab/>.
+pub(crate) fn to_oklch(color: Color) -> Oklch {
+    let [r, g, b, alpha] = color.into_linear();
+
+    let l = (0.41222146 * r + 0.53633255 * g + 0.051445995 * b).cbrt();
+    let m = (0.2119035 * r + 0.6806995 * g + 0.10739696 * b).cbrt();
+    let s = (0.08830246 * r + 0.28171885 * g + 0.6299787 * b).cbrt();
+
+    let lightness = 0.21045426 * l + 0.7936178 * m - 0.004072047 * s;
+    let a = 1.9779985 * l - 2.4285922 * m + 0.4505937 * s;
+    let b = 0.025904037 * l + 0.78277177 * m - 0.80867577 * s;
+
+    Oklch {
+        l: lightness,
+        c: (a * a + b * b).sqrt(),
+        h: b.atan2(a),
+        a: alpha,
+    }
+}
+
+/// Convert an Oklch color back to sRGB, clamping out-of-gamut channels.
+pub(crate) fn from_oklch(oklch: Oklch) -> Color {
+    let Oklch { l, c, h, a: alpha } = oklch;
+    let a = c * h.cos();
+    let b = c * h.sin();
+
+    let l_ = l + 0.39633778 * a + 0.21580376 * b;
+    let m_ = l - 0.105561346 * a - 0.06385417 * b;
+    let s_ = l - 0.08948418 * a - 1.2914855 * b;
+
+    let (l, m, s) = (l_ * l_ * l_, m_ * m_ * m_, s_ * s_ * s_);
+
+    let r = 4.0767417 * l - 3.3077116 * m + 0.23096994 * s;
+    let g = -1.268438 * l + 2.6097574 * m - 0.34131938 * s;
+    let b = -0.0041960863 * l - 0.7034186 * m + 1.7076147 * s;
+
+    Color::from_linear_rgba(r.clamp(0.0, 1.0), g.clamp(0.0, 1.0), b.clamp(0.0, 1.0), alpha)
+}
+
+/// The perceptual difference between two colors: their distance in Oklab,
+/// `0.0` for the same color and about `1.0` for black and white. Alpha is
+/// ignored.
+pub(crate) fn distance(a: Color, b: Color) -> f32 {
+    let ([la, aa, ba], [lb, ab, bb]) = (oklab(a), oklab(b));
+    ((la - lb).powi(2) + (aa - ab).powi(2) + (ba - bb).powi(2)).sqrt()
+}
+
+fn oklab(color: Color) -> [f32; 3] {
+    let Oklch { l, c, h, .. } = to_oklch(color);
+    [l, c * h.cos(), c * h.sin()]
+}
+
+/// Mix two colors in linear RGB: `factor = 0.0` is `a`, `1.0` is `b`.
+pub(crate) fn mix(a: Color, b: Color, factor: f32) -> Color {
+    let t = factor.clamp(0.0, 1.0);
+    let [ar, ag, ab, aa] = a.into_linear();
+    let [br, bg, bb, ba] = b.into_linear();
+    Color::from_linear_rgba(
+        ar + (br - ar) * t,
+        ag + (bg - ag) * t,
+        ab + (bb - ab) * t,
+        aa + (ba - aa) * t,
+    )
+}
+
+/// WCAG relative luminance: `0.0` for black, `1.0` for white.
+pub(crate) fn luminance(color: Color) -> f32 {
+    let [r, g, b, _] = color.into_linear();
+    0.2126 * r + 0.7152 * g + 0.0722 * b
+}
+
+/// WCAG contrast ratio between two colors, from `1.0` (identical luminance)
+/// to `21.0` (black on white).
+pub(crate) fn contrast_ratio(a: Color, b: Color) -> f32 {
+    let (la, lb) = (luminance(a), luminance(b));
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+/// Whether `background` is dark: white text contrasts with it better than
+/// black text does.
+pub(crate) fn is_dark(background: Color) -> bool {
+    luminance(background) < 0.179
+}
+
+/// Mirror a color's perceptual lightness (`L → 1 − L`), keeping hue and chroma.
+pub(crate) fn invert_lightness(color: Color) -> Color {
+    let mut oklch = to_oklch(color);
+    oklch.l = 1.0 - oklch.l;
+    from_oklch(oklch)
 }
 
 // Implement Display so HexColor can be used in error messages.
 impl fmt::Display for HexColor {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let Color { r, g, b, a } = self.0;
+        let byte = |v: f32| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
         if (a - 1.0).abs() < f32::EPSILON {
-            write!(
-                f,
-                "#{:02X}{:02X}{:02X}",
-                (r * 255.0) as u8,
-                (g * 255.0) as u8,
-                (b * 255.0) as u8,
-            )
+            write!(f, "#{:02X}{:02X}{:02X}", byte(r), byte(g), byte(b))
         } else {
-            write!(
-                f,
-                "#{:02X}{:02X}{:02X}{:02X}",
-                (r * 255.0) as u8,
-                (g * 255.0) as u8,
-                (b * 255.0) as u8,
-                (a * 255.0) as u8,
-            )
+            write!(f, "#{:02X}{:02X}{:02X}{:02X}", byte(r), byte(g), byte(b), byte(a))
         }
     }
 }
@@ -158,4 +396,70 @@ mod tests {
     fn parse_invalid_hex() {
         assert!(parse_color("#ZZZZZZ").is_err());
     }
+
+    #[test]
+    fn display_round_trips_through_parse() {
+        let c = Color::from_rgb8(0x66, 0xC0, 0xF4);
+        assert_eq!(HexColor(c).to_string(), "#66C0F4");
+        let translucent = Color::from_rgba8(0x66, 0xC0, 0xF4, 0x40 as f32 / 255.0);
+        assert_eq!(HexColor(translucent).to_string(), "#66C0F440");
+    }
+
+    #[test]
+    fn serialize_round_trips_through_deserialize() {
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct Colors {
+            accent: HexColor,
+        }
+        let accent = HexColor::from(Color::from_rgba8(0x1E, 0x88, 0xE5, 0.5));
+        let text = toml::to_string(&Colors { accent }).unwrap();
+        assert_eq!(text, "accent = \"#1E88E580\"\n");
+        assert_eq!(toml::from_str::<Colors>(&text).unwrap().accent.to_string(), accent.to_string());
+        assert_eq!("white".parse::<HexColor>(), Ok(HexColor(Color::WHITE)));
+        assert!(matches!("1E88E5".parse::<HexColor>(), Err(Reason::MissingHashPrefix)));
+    }
+
+    #[test]
+    fn deserialize_integer_literal() {
+        #[derive(serde::Deserialize)]
+        struct Colors {
+            a: HexColor,
+            b: HexColor,
+        }
+        let colors: Colors = toml::from_str("a = 0x1B2838\nb = \"#1B2838\"").unwrap();
+        assert!(approx_eq(colors.a.0, colors.b.0));
+        assert!(toml::from_str::<Colors>("a = 0x1000000\nb = 0").is_err());
+    }
+
+    #[test]
+    fn deserialize_array() {
+        #[derive(serde::Deserialize)]
+        struct Colors {
+            rgb: HexColor,
+            rgba: HexColor,
+        }
+        let colors: Colors = toml::from_str("rgb = [1.0, 0.5, 0]\nrgba = [0.0, 0.0, 0.0, 0.25]").unwrap();
+        assert!(approx_eq(colors.rgb.0, Color::from_rgb(1.0, 0.5, 0.0)));
+        assert!(approx_eq(colors.rgba.0, Color::from_rgba(0.0, 0.0, 0.0, 0.25)));
+        assert!(toml::from_str::<Colors>("rgb = [1.0, 0.5]\nrgba = [0.0, 0.0, 0.0]").is_err());
+        assert!(toml::from_str::<Colors>("rgb = [2.0, 0.5, 0.0]\nrgba = [0.0, 0.0, 0.0]").is_err());
+    }
+
+    #[test]
+    fn hsl_round_trip() {
+        let c = Color::from_rgb8(0x66, 0xC0, 0xF4);
+        assert!(approx_eq(from_hsl(to_hsl(c)), c));
+    }
+
+    #[test]
+    fn oklch_round_trip() {
+        let c = Color::from_rgb8(0xF4, 0x43, 0x36);
+        assert!(approx_eq(from_oklch(to_oklch(c)), c));
+    }
+
+    #[test]
+    fn hsl_of_grey_has_no_saturation() {
+        let hsl = to_hsl(Color::from_rgb8(0x80, 0x80, 0x80));
+        assert!(hsl.s.abs() < f32::EPSILON);
+    }
 }
@@ -0,0 +1,53 @@
+//! Thin adapters over the iced APIs that differ between supported releases.
+//!
+//! Exactly one of the `iced-0-13` / `iced-0-14` features is enabled, and the
+//! matching submodule is re-exported here under a single set of names. Code
+//! elsewhere in the crate uses `iced_core`/`iced_widget` directly for
+//! everything the releases share, and goes through `compat` for the rest.
+
+use iced_core::border::Radius;
+use iced_core::{Background, Color};
+
+#[cfg(feature = "iced-0-13")]
+mod v0_13;
+#[cfg(feature = "iced-0-13")]
+pub(crate) use v0_13::*;
+
+#[cfg(feature = "iced-0-14")]
+mod v0_14;
+#[cfg(feature = "iced-0-14")]
+pub(crate) use v0_14::*;
+
+/// The interaction part of a widget status, without its toggled/checked state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Interaction {
+    Active,
+    Hovered,
+    Disabled,
+}
+
+/// The status of one scrollbar of a scrollable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Scrollbar {
+    Active,
+    Hovered,
+    Dragged,
+}
+
+/// Version-neutral toggler appearance, converted to the native style by
+/// [`toggler_style`].
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TogglerParts {
+    pub background: Background,
+    pub foreground: Background,
+    pub background_border_width: f32,
+    pub background_border_color: Color,
+    pub foreground_border_width: f32,
+    pub foreground_border_color: Color,
+    /// Not representable in iced 0.13; ignored there.
+    #[cfg_attr(feature = "iced-0-13", allow(dead_code))]
+    pub border_radius: Option<Radius>,
+    /// Not representable in iced 0.13; ignored there.
+    #[cfg_attr(feature = "iced-0-13", allow(dead_code))]
+    pub text_color: Option<Color>,
+}
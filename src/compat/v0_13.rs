@@ -0,0 +1,250 @@
+use iced_core::theme::Palette;
+use iced_core::theme::palette::{self, Extended, Pair};
+use iced_core::border::Radius;
+use iced_core::{Background, Border, Color, Shadow};
+use iced_widget::overlay::menu;
+use iced_widget::{ProgressBar, Rule, button, container, progress_bar, rule, scrollable, toggler};
+
+use super::{Interaction, Scrollbar, TogglerParts};
+use crate::palette::{PaletteGenerator, Shade};
+use crate::style::{ApplyOverrides, MapColors, StatusOverrides, impl_approx_eq};
+
+/// The generator iced itself uses for `Extended::generate`.
+pub(crate) const NATIVE_GENERATOR: PaletteGenerator = PaletteGenerator::Legacy;
+
+/// iced 0.13 has no `warning` slot; `ThemeConfig` keeps the color itself.
+pub(crate) fn palette(
+    background: Color,
+    text: Color,
+    primary: Color,
+    success: Color,
+    _warning: Color,
+    danger: Color,
+) -> Palette {
+    Palette { background, text, primary, success, danger }
+}
+
+/// The warning color of iced's light theme. iced 0.13's has none, so this is
+/// iced 0.14's, and a theme missing `warning` falls back to the same color on
+/// both releases.
+pub(crate) fn light_warning() -> Color {
+    Color::from_rgb8(0xB7, 0x7E, 0x33)
+}
+
+/// The `[palette]` keys `p` has a color for. iced 0.13 has no `warning`.
+pub(crate) fn palette_slots(p: &Palette) -> Vec<(&'static str, Color)> {
+    vec![
+        ("background", p.background),
+        ("text", p.text),
+        ("primary", p.primary),
+        ("success", p.success),
+        ("danger", p.danger),
+    ]
+}
+
+pub(crate) fn map_palette(p: Palette, f: impl Fn(Color) -> Color) -> Palette {
+    Palette {
+        background: f(p.background),
+        text: f(p.text),
+        primary: f(p.primary),
+        success: f(p.success),
+        danger: f(p.danger),
+    }
+}
+
+pub(crate) fn background_shades(bg: &mut palette::Background) -> Vec<(Shade, &mut Pair)> {
+    vec![(Shade::Weak, &mut bg.weak), (Shade::Strong, &mut bg.strong)]
+}
+
+/// `(base color, [base, weak, strong])` for every accent slot.
+pub(crate) fn accent_shades<'a>(ext: &'a mut Extended, p: &Palette) -> Vec<(Color, [&'a mut Pair; 3])> {
+    vec![
+        (p.primary, [&mut ext.primary.base, &mut ext.primary.weak, &mut ext.primary.strong]),
+        (p.success, [&mut ext.success.base, &mut ext.success.weak, &mut ext.success.strong]),
+        (p.danger, [&mut ext.danger.base, &mut ext.danger.weak, &mut ext.danger.strong]),
+    ]
+}
+
+/// `(name, [base, weak, strong])` for every non-background group.
+pub(crate) fn accent_pairs(ext: &Extended) -> Vec<(&'static str, [Pair; 3])> {
+    vec![
+        ("primary", [ext.primary.base, ext.primary.weak, ext.primary.strong]),
+        ("secondary", [ext.secondary.base, ext.secondary.weak, ext.secondary.strong]),
+        ("success", [ext.success.base, ext.success.weak, ext.success.strong]),
+        ("danger", [ext.danger.base, ext.danger.weak, ext.danger.strong]),
+    ]
+}
+
+/// iced 0.13 buttons aren't snapped to the pixel grid; `snap` is dropped.
+pub(crate) fn button_style(
+    background: Option<Background>,
+    text_color: Color,
+    border: Border,
+    shadow: Shadow,
+    _snap: bool,
+) -> button::Style {
+    button::Style { background, text_color, border, shadow }
+}
+
+/// iced 0.13 containers aren't snapped to the pixel grid; `snap` is dropped.
+pub(crate) fn container_style(
+    background: Option<Background>,
+    text_color: Option<Color>,
+    border: Border,
+    shadow: Shadow,
+    _snap: bool,
+) -> container::Style {
+    container::Style { background, text_color, border, shadow }
+}
+
+/// iced 0.13 menus have no shadow; it is dropped.
+pub(crate) fn menu_style(
+    background: Background,
+    border: Border,
+    text_color: Color,
+    selected_text_color: Color,
+    selected_background: Background,
+    _shadow: Shadow,
+) -> menu::Style {
+    menu::Style { background, border, text_color, selected_text_color, selected_background }
+}
+
+pub(crate) fn rule_style(color: Color, width: u16, radius: Radius, fill_mode: rule::FillMode) -> rule::Style {
+    rule::Style { color, width, radius, fill_mode }
+}
+
+pub(crate) fn horizontal_rule<'a, Theme: rule::Catalog>(width: u16) -> Rule<'a, Theme> {
+    Rule::horizontal(width)
+}
+
+pub(crate) fn vertical_rule<'a, Theme: rule::Catalog>(width: u16) -> Rule<'a, Theme> {
+    Rule::vertical(width)
+}
+
+/// iced 0.13 progress bars are always horizontal, so `vertical` is ignored.
+pub(crate) fn progress_bar_layout<'a, Theme: progress_bar::Catalog>(
+    bar: ProgressBar<'a, Theme>,
+    girth: Option<f32>,
+    _vertical: bool,
+) -> ProgressBar<'a, Theme> {
+    match girth {
+        Some(girth) => bar.height(girth),
+        None => bar,
+    }
+}
+
+/// iced 0.13 togglers only take solid colors and have no radius or label
+/// color; gradients collapse to their first stop.
+pub(crate) fn toggler_style(t: TogglerParts) -> toggler::Style {
+    toggler::Style {
+        background: solid(t.background),
+        foreground: solid(t.foreground),
+        background_border_width: t.background_border_width,
+        background_border_color: t.background_border_color,
+        foreground_border_width: t.foreground_border_width,
+        foreground_border_color: t.foreground_border_color,
+    }
+}
+
+/// iced 0.13 scrollers only take a solid color; gradients collapse to their
+/// first stop.
+pub(crate) fn scroller(background: Background, border: Border) -> scrollable::Scroller {
+    scrollable::Scroller { color: solid(background), border }
+}
+
+pub(crate) fn map_scroller(s: scrollable::Scroller, f: &impl Fn(Color) -> Color) -> scrollable::Scroller {
+    scrollable::Scroller { color: f(s.color), border: s.border.map_colors(f) }
+}
+
+/// The statuses of the vertical and horizontal scrollbars.
+pub(crate) fn scrollbar_status(status: scrollable::Status) -> (Scrollbar, Scrollbar) {
+    let pick = |on: bool, status: Scrollbar| if on { status } else { Scrollbar::Active };
+    match status {
+        scrollable::Status::Active => (Scrollbar::Active, Scrollbar::Active),
+        scrollable::Status::Hovered { is_vertical_scrollbar_hovered: v, is_horizontal_scrollbar_hovered: h } => {
+            (pick(v, Scrollbar::Hovered), pick(h, Scrollbar::Hovered))
+        }
+        scrollable::Status::Dragged { is_vertical_scrollbar_dragged: v, is_horizontal_scrollbar_dragged: h } => {
+            (pick(v, Scrollbar::Dragged), pick(h, Scrollbar::Dragged))
+        }
+    }
+}
+
+/// iced 0.13 does not report the toggled state of a disabled toggler; it is
+/// treated as untoggled.
+pub(crate) fn toggler_status(status: toggler::Status) -> (Interaction, bool) {
+    match status {
+        toggler::Status::Active { is_toggled } => (Interaction::Active, is_toggled),
+        toggler::Status::Hovered { is_toggled } => (Interaction::Hovered, is_toggled),
+        toggler::Status::Disabled => (Interaction::Disabled, false),
+    }
+}
+
+fn solid(background: Background) -> Color {
+    match background {
+        Background::Color(c) => c,
+        Background::Gradient(iced_core::Gradient::Linear(linear)) => linear
+            .stops
+            .iter()
+            .flatten()
+            .next()
+            .map(|stop| stop.color)
+            .unwrap_or(Color::TRANSPARENT),
+    }
+}
+
+impl MapColors for menu::Style {
+    fn map_colors(self, f: &impl Fn(Color) -> Color) -> Self {
+        menu::Style {
+            background: self.background.map_colors(f),
+            border: self.border.map_colors(f),
+            text_color: f(self.text_color),
+            selected_text_color: f(self.selected_text_color),
+            selected_background: self.selected_background.map_colors(f),
+        }
+    }
+}
+
+impl ApplyOverrides for menu::Style {
+    fn apply_overrides(self, o: &StatusOverrides) -> Self {
+        menu::Style {
+            background: o.background.unwrap_or(self.background),
+            border: o.border(self.border),
+            text_color: o.text_color.unwrap_or(self.text_color),
+            ..self
+        }
+    }
+}
+
+impl MapColors for toggler::Style {
+    fn map_colors(self, f: &impl Fn(Color) -> Color) -> Self {
+        toggler::Style {
+            background: f(self.background),
+            foreground: f(self.foreground),
+            background_border_color: f(self.background_border_color),
+            foreground_border_color: f(self.foreground_border_color),
+            ..self
+        }
+    }
+}
+
+impl ApplyOverrides for toggler::Style {
+    fn apply_overrides(self, o: &StatusOverrides) -> Self {
+        toggler::Style {
+            background: o.background_color().unwrap_or(self.background),
+            background_border_width: o.border_width.unwrap_or(self.background_border_width),
+            background_border_color: o.border_color.unwrap_or(self.background_border_color),
+            ..self
+        }
+    }
+}
+
+impl_approx_eq!(button::Style { background, text_color, border, shadow });
+impl_approx_eq!(container::Style { text_color, background, border, shadow });
+impl_approx_eq!(rule::Style { color, width, radius, fill_mode });
+impl_approx_eq!(menu::Style { background, border, text_color, selected_text_color, selected_background });
+impl_approx_eq!(scrollable::Scroller { color, border });
+impl_approx_eq!(toggler::Style {
+    background, background_border_width, background_border_color,
+    foreground, foreground_border_width, foreground_border_color,
+});
@@ -0,0 +1,248 @@
+use iced_core::theme::{Palette, Theme};
+use iced_core::theme::palette::{self, Extended, Pair};
+use iced_core::border::Radius;
+use iced_core::{Background, Border, Color, Shadow};
+use iced_widget::overlay::menu;
+use iced_widget::{ProgressBar, Rule, button, container, progress_bar, rule, scrollable, toggler};
+
+use super::{Interaction, Scrollbar, TogglerParts};
+use crate::palette::{PaletteGenerator, Shade};
+use crate::style::{ApplyOverrides, MapColors, StatusOverrides, impl_approx_eq};
+
+/// The generator iced itself uses for `Extended::generate`.
+pub(crate) const NATIVE_GENERATOR: PaletteGenerator = PaletteGenerator::Oklch;
+
+pub(crate) fn palette(
+    background: Color,
+    text: Color,
+    primary: Color,
+    success: Color,
+    warning: Color,
+    danger: Color,
+) -> Palette {
+    Palette { background, text, primary, success, warning, danger }
+}
+
+/// The warning color of iced's light theme.
+pub(crate) fn light_warning() -> Color {
+    Theme::Light.palette().warning
+}
+
+/// The `[palette]` keys `p` has a color for.
+pub(crate) fn palette_slots(p: &Palette) -> Vec<(&'static str, Color)> {
+    vec![
+        ("background", p.background),
+        ("text", p.text),
+        ("primary", p.primary),
+        ("success", p.success),
+        ("warning", p.warning),
+        ("danger", p.danger),
+    ]
+}
+
+pub(crate) fn map_palette(p: Palette, f: impl Fn(Color) -> Color) -> Palette {
+    Palette {
+        background: f(p.background),
+        text: f(p.text),
+        primary: f(p.primary),
+        success: f(p.success),
+        warning: f(p.warning),
+        danger: f(p.danger),
+    }
+}
+
+pub(crate) fn background_shades(bg: &mut palette::Background) -> Vec<(Shade, &mut Pair)> {
+    vec![
+        (Shade::Weakest, &mut bg.weakest),
+        (Shade::Weaker, &mut bg.weaker),
+        (Shade::Weak, &mut bg.weak),
+        (Shade::Neutral, &mut bg.neutral),
+        (Shade::Strong, &mut bg.strong),
+        (Shade::Stronger, &mut bg.stronger),
+        (Shade::Strongest, &mut bg.strongest),
+    ]
+}
+
+/// `(base color, [base, weak, strong])` for every accent slot.
+pub(crate) fn accent_shades<'a>(ext: &'a mut Extended, p: &Palette) -> Vec<(Color, [&'a mut Pair; 3])> {
+    vec![
+        (p.primary, [&mut ext.primary.base, &mut ext.primary.weak, &mut ext.primary.strong]),
+        (p.success, [&mut ext.success.base, &mut ext.success.weak, &mut ext.success.strong]),
+        (p.warning, [&mut ext.warning.base, &mut ext.warning.weak, &mut ext.warning.strong]),
+        (p.danger, [&mut ext.danger.base, &mut ext.danger.weak, &mut ext.danger.strong]),
+    ]
+}
+
+/// `(name, [base, weak, strong])` for every non-background group.
+pub(crate) fn accent_pairs(ext: &Extended) -> Vec<(&'static str, [Pair; 3])> {
+    vec![
+        ("primary", [ext.primary.base, ext.primary.weak, ext.primary.strong]),
+        ("secondary", [ext.secondary.base, ext.secondary.weak, ext.secondary.strong]),
+        ("success", [ext.success.base, ext.success.weak, ext.success.strong]),
+        ("warning", [ext.warning.base, ext.warning.weak, ext.warning.strong]),
+        ("danger", [ext.danger.base, ext.danger.weak, ext.danger.strong]),
+    ]
+}
+
+pub(crate) fn button_style(
+    background: Option<Background>,
+    text_color: Color,
+    border: Border,
+    shadow: Shadow,
+    snap: bool,
+) -> button::Style {
+    button::Style { background, text_color, border, shadow, snap }
+}
+
+pub(crate) fn container_style(
+    background: Option<Background>,
+    text_color: Option<Color>,
+    border: Border,
+    shadow: Shadow,
+    snap: bool,
+) -> container::Style {
+    container::Style { background, text_color, border, shadow, snap }
+}
+
+pub(crate) fn menu_style(
+    background: Background,
+    border: Border,
+    text_color: Color,
+    selected_text_color: Color,
+    selected_background: Background,
+    shadow: Shadow,
+) -> menu::Style {
+    menu::Style { background, border, text_color, selected_text_color, selected_background, shadow }
+}
+
+/// iced 0.14 rules take their thickness from the widget, not the style, so
+/// `width` is only used by [`horizontal_rule`] and [`vertical_rule`].
+pub(crate) fn rule_style(color: Color, _width: u16, radius: Radius, fill_mode: rule::FillMode) -> rule::Style {
+    rule::Style { color, radius, fill_mode, snap: true }
+}
+
+pub(crate) fn horizontal_rule<'a, Theme: rule::Catalog>(width: u16) -> Rule<'a, Theme> {
+    rule::horizontal(f32::from(width))
+}
+
+pub(crate) fn vertical_rule<'a, Theme: rule::Catalog>(width: u16) -> Rule<'a, Theme> {
+    rule::vertical(f32::from(width))
+}
+
+pub(crate) fn progress_bar_layout<'a, Theme: progress_bar::Catalog>(
+    bar: ProgressBar<'a, Theme>,
+    girth: Option<f32>,
+    vertical: bool,
+) -> ProgressBar<'a, Theme> {
+    let bar = if vertical { bar.vertical() } else { bar };
+    match girth {
+        Some(girth) => bar.girth(girth),
+        None => bar,
+    }
+}
+
+pub(crate) fn toggler_style(t: TogglerParts) -> toggler::Style {
+    toggler::Style {
+        background: t.background,
+        foreground: t.foreground,
+        background_border_width: t.background_border_width,
+        background_border_color: t.background_border_color,
+        foreground_border_width: t.foreground_border_width,
+        foreground_border_color: t.foreground_border_color,
+        border_radius: t.border_radius,
+        text_color: t.text_color,
+        padding_ratio: 0.36,
+    }
+}
+
+pub(crate) fn scroller(background: Background, border: Border) -> scrollable::Scroller {
+    scrollable::Scroller { background, border }
+}
+
+pub(crate) fn map_scroller(s: scrollable::Scroller, f: &impl Fn(Color) -> Color) -> scrollable::Scroller {
+    scrollable::Scroller { background: s.background.map_colors(f), border: s.border.map_colors(f) }
+}
+
+/// The statuses of the vertical and horizontal scrollbars.
+pub(crate) fn scrollbar_status(status: scrollable::Status) -> (Scrollbar, Scrollbar) {
+    let pick = |on: bool, status: Scrollbar| if on { status } else { Scrollbar::Active };
+    match status {
+        scrollable::Status::Active { .. } => (Scrollbar::Active, Scrollbar::Active),
+        scrollable::Status::Hovered { is_vertical_scrollbar_hovered: v, is_horizontal_scrollbar_hovered: h, .. } => {
+            (pick(v, Scrollbar::Hovered), pick(h, Scrollbar::Hovered))
+        }
+        scrollable::Status::Dragged { is_vertical_scrollbar_dragged: v, is_horizontal_scrollbar_dragged: h, .. } => {
+            (pick(v, Scrollbar::Dragged), pick(h, Scrollbar::Dragged))
+        }
+    }
+}
+
+pub(crate) fn toggler_status(status: toggler::Status) -> (Interaction, bool) {
+    match status {
+        toggler::Status::Active { is_toggled } => (Interaction::Active, is_toggled),
+        toggler::Status::Hovered { is_toggled } => (Interaction::Hovered, is_toggled),
+        toggler::Status::Disabled { is_toggled } => (Interaction::Disabled, is_toggled),
+    }
+}
+
+impl MapColors for menu::Style {
+    fn map_colors(self, f: &impl Fn(Color) -> Color) -> Self {
+        menu::Style {
+            background: self.background.map_colors(f),
+            border: self.border.map_colors(f),
+            text_color: f(self.text_color),
+            selected_text_color: f(self.selected_text_color),
+            selected_background: self.selected_background.map_colors(f),
+            shadow: self.shadow.map_colors(f),
+        }
+    }
+}
+
+impl ApplyOverrides for menu::Style {
+    fn apply_overrides(self, o: &StatusOverrides) -> Self {
+        menu::Style {
+            background: o.background.unwrap_or(self.background),
+            border: o.border(self.border),
+            text_color: o.text_color.unwrap_or(self.text_color),
+            shadow: o.shadow.unwrap_or(self.shadow),
+            ..self
+        }
+    }
+}
+
+impl MapColors for toggler::Style {
+    fn map_colors(self, f: &impl Fn(Color) -> Color) -> Self {
+        toggler::Style {
+            background: self.background.map_colors(f),
+            foreground: self.foreground.map_colors(f),
+            background_border_color: f(self.background_border_color),
+            foreground_border_color: f(self.foreground_border_color),
+            text_color: self.text_color.map_colors(f),
+            ..self
+        }
+    }
+}
+
+impl ApplyOverrides for toggler::Style {
+    fn apply_overrides(self, o: &StatusOverrides) -> Self {
+        toggler::Style {
+            background: o.background.unwrap_or(self.background),
+            background_border_width: o.border_width.unwrap_or(self.background_border_width),
+            background_border_color: o.border_color.unwrap_or(self.background_border_color),
+            text_color: o.text_color.or(self.text_color),
+            border_radius: o.border_radius.or(self.border_radius),
+            ..self
+        }
+    }
+}
+
+impl_approx_eq!(button::Style { background, text_color, border, shadow, snap });
+impl_approx_eq!(container::Style { text_color, background, border, shadow, snap });
+impl_approx_eq!(rule::Style { color, radius, fill_mode, snap });
+impl_approx_eq!(menu::Style { background, border, text_color, selected_text_color, selected_background, shadow });
+impl_approx_eq!(scrollable::Scroller { background, border });
+impl_approx_eq!(toggler::Style {
+    background, background_border_width, background_border_color,
+    foreground, foreground_border_width, foreground_border_color,
+    text_color, border_radius, padding_ratio,
+});
@@ -1,33 +1,179 @@
+use iced_core::Color;
 use iced_core::font::{self, Font};
 use iced_core::theme::{Palette, Theme};
-use serde::Deserialize;
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer};
+use serde_path_to_error::Segment;
 
-use crate::color::HexColor;
-use crate::error::Error;
+use crate::ansi::AnsiRaw;
+use crate::color::{HexColor, parse_color};
+use crate::compat;
+use crate::error::{Error, Reason};
+use crate::icons::IconsRaw;
+use crate::metadata::ThemeMetadata;
+use crate::palette::PaletteGenerator;
 use crate::style::{
-    ButtonSection, CheckboxSection, ContainerSection, ProgressBarSection,
-    RadioSection, SliderSection, TextInputSection, TogglerSection,
+    ButtonSection, CheckboxSection, ComboBoxSection, ContainerSection, DisabledDimming, MenuSection, PaneGridSection,
+    PickListSection, ProgressBarSection, RadioSection, RuleSection, ScrollableSection, SliderSection, SvgSection,
+    TextInputSection, TextSection, TogglerSection, Variants, widget_sections,
 };
+#[cfg(feature = "table")]
+use crate::style::TableSection;
+use crate::syntax::SyntaxRaw;
+use crate::transform::merge;
 use crate::ThemeConfig;
 
-/// Raw top-level TOML structure, before conversion to iced types.
-#[derive(Deserialize)]
-#[serde(rename_all = "kebab-case")]
-pub(crate) struct ThemeRaw {
-    pub name: Option<String>,
-    pub palette: PaletteRaw,
-    pub font: Option<FontRaw>,
-    pub button: Option<ButtonSection>,
-    pub container: Option<ContainerSection>,
-    pub text_input: Option<TextInputSection>,
-    pub checkbox: Option<CheckboxSection>,
-    pub toggler: Option<TogglerSection>,
-    pub slider: Option<SliderSection>,
-    pub progress_bar: Option<ProgressBarSection>,
-    pub radio: Option<RadioSection>,
-}
-
-/// The 6 semantic colors that make up an iced palette.
+macro_rules! theme_raw {
+    ($($(#[$attr:meta])* $field:ident $name:literal $style:ident $raw:ident $resolve:ident $catalog:ident $statuses:tt;)*) => {
+        /// Raw top-level TOML structure, before conversion to iced types.
+        #[derive(Deserialize)]
+        #[serde(rename_all = "kebab-case")]
+        pub(crate) struct ThemeRaw {
+            pub name: Option<String>,
+            pub metadata: Option<ThemeMetadata>,
+            pub palette: PaletteRaw,
+            pub font: Option<FontRaw>,
+            pub defaults: Option<DefaultsRaw>,
+            pub root: Option<ContainerSection>,
+            $($(#[$attr])* pub $field: Option<$raw>,)*
+            pub ansi: Option<AnsiRaw>,
+            pub syntax: Option<SyntaxRaw>,
+            pub icons: Option<IconsRaw>,
+        }
+    };
+}
+widget_sections!(theme_raw);
+
+/// Resolves `$section` with the theme's disabled dimming `$dim` if its
+/// `widget_sections!` entry says it takes one.
+macro_rules! resolve_section {
+    (dim, $section:expr, $dim:expr) => {
+        $section.resolve($dim)
+    };
+    (plain, $section:expr, $dim:expr) => {
+        $section.resolve()
+    };
+}
+
+/// Deserializes `value` (found at key path `prefix`, empty for the document
+/// root), reporting failures with the offending key path and value.
+pub(crate) fn deserialize<T: DeserializeOwned>(value: &toml::Value, prefix: &str) -> Result<T, Error> {
+    let attempt = |value: toml::Value| {
+        NESTED_PATH.take();
+        serde_path_to_error::deserialize::<_, T>(value).map_err(|err| {
+            let nested = NESTED_PATH.take().unwrap_or_default();
+            (err.inner().message().to_string(), path_keys(err.path()), nested)
+        })
+    };
+    let (message, mut keys, nested) = match attempt(value.clone()) {
+        Ok(parsed) => return Ok(parsed),
+        Err(err) => err,
+    };
+
+    // Widget sections `#[serde(flatten)]` their own fields, and serde
+    // buffers flattened fields without tracking their keys, so the path can
+    // stop at the section table. Find the key by elimination: the one whose
+    // removal changes the error. Sections flattened into another section
+    // (like `[combo-box]`'s input fields) stop short once per level, so keep
+    // descending while the culprit is itself a table, unless the error
+    // already says where inside it the problem is (see below).
+    while let Some(toml::Value::Table(table)) = lookup(value, &keys) {
+        let culprit = table.keys().find(|key| {
+            let mut probe = value.clone();
+            if let Some(toml::Value::Table(t)) = lookup_mut(&mut probe, &keys) {
+                t.remove(*key);
+            }
+            match attempt(probe) {
+                Ok(_) => true,
+                Err((probed, _, probed_nested)) => probed != message || probed_nested != nested,
+            }
+        });
+        let Some(key) = culprit else { break };
+        keys.push(key.clone());
+        if !nested.is_empty() {
+            break;
+        }
+    }
+
+    // A value deserialized on its own (see `deserialize_nested`) reports
+    // where inside it the error is.
+    keys.extend(nested);
+
+    let field = prefix.split('.').filter(|p| !p.is_empty()).map(str::to_string).chain(keys.iter().cloned());
+    let field = field.collect::<Vec<_>>().join(".");
+
+    // A string rejected by `HexColor` is a bad color; report why it didn't parse.
+    let offending = lookup(value, &keys);
+    let color_error = offending.and_then(toml::Value::as_str).and_then(|s| parse_color(s).err());
+    Err(match color_error {
+        Some(reason) if message == reason.to_string() => Error::InvalidColor {
+            field,
+            value: offending.and_then(toml::Value::as_str).unwrap_or_default().to_string(),
+            reason,
+        },
+        _ => Error::InvalidValue {
+            field,
+            value: offending.map(toml::Value::to_string).unwrap_or_default(),
+            reason: Reason::Other(message),
+        },
+    })
+}
+
+thread_local! {
+    /// The key path inside the value that last failed in
+    /// [`deserialize_nested`], for [`deserialize`] to append to the field.
+    /// Serde errors only carry a message, so the path travels beside it.
+    static NESTED_PATH: Cell<Option<Vec<String>>> = const { Cell::new(None) };
+}
+
+/// Deserializes `value` as part of a larger value, for custom `Deserialize`
+/// impls that have to buffer their input. The key path within `value` is
+/// recorded in [`NESTED_PATH`], which [`deserialize`] appends to the field.
+pub(crate) fn deserialize_nested<T: DeserializeOwned, E: serde::de::Error>(value: toml::Value) -> Result<T, E> {
+    NESTED_PATH.take();
+    serde_path_to_error::deserialize(value).map_err(|err| {
+        let mut keys = path_keys(err.path());
+        keys.extend(NESTED_PATH.take().unwrap_or_default());
+        NESTED_PATH.set(Some(keys));
+        E::custom(err.inner().message())
+    })
+}
+
+fn path_keys(path: &serde_path_to_error::Path) -> Vec<String> {
+    let mut keys = Vec::new();
+    for segment in path.iter() {
+        match segment {
+            Segment::Map { key } => keys.push(key.clone()),
+            Segment::Seq { index } => keys.push(index.to_string()),
+            _ => break,
+        }
+    }
+    keys
+}
+
+fn lookup<'a>(value: &'a toml::Value, keys: &[String]) -> Option<&'a toml::Value> {
+    keys.iter().try_fold(value, |v, key| match v {
+        toml::Value::Array(items) => items.get(key.parse::<usize>().ok()?),
+        _ => v.get(key),
+    })
+}
+
+fn lookup_mut<'a>(value: &'a mut toml::Value, keys: &[String]) -> Option<&'a mut toml::Value> {
+    keys.iter().try_fold(value, |v, key| match v {
+        toml::Value::Array(items) => items.get_mut(key.parse::<usize>().ok()?),
+        _ => v.get_mut(key),
+    })
+}
+
+/// The `[palette]` keys that aren't custom slots.
+pub(crate) const PALETTE_KEYS: [&str; 7] =
+    ["background", "text", "primary", "success", "warning", "danger", "generator"];
+
+/// The 6 semantic colors that make up an iced palette, plus the algorithm
+/// used to derive the extended palette from them. Any other key is a custom
+/// slot, like `info` or `accent-2`.
 #[derive(Deserialize)]
 pub(crate) struct PaletteRaw {
     pub background: HexColor,
@@ -36,6 +182,118 @@ pub(crate) struct PaletteRaw {
     pub success: HexColor,
     pub warning: HexColor,
     pub danger: HexColor,
+    pub generator: Option<PaletteGenerator>,
+    // Parsed by `custom_slots`, so a bad color is reported at its own key.
+    #[serde(flatten)]
+    pub custom: BTreeMap<String, toml::Value>,
+}
+
+impl PaletteRaw {
+    pub fn palette(&self) -> Palette {
+        compat::palette(
+            self.background.0,
+            self.text.0,
+            self.primary.0,
+            self.success.0,
+            self.warning.0,
+            self.danger.0,
+        )
+    }
+
+    pub fn generator(&self) -> PaletteGenerator {
+        self.generator.unwrap_or_default()
+    }
+
+    pub fn custom_slots(&self) -> Result<BTreeMap<String, Color>, Error> {
+        self.custom
+            .iter()
+            .map(|(slot, value)| Ok((slot.clone(), deserialize::<HexColor>(value, &format!("palette.{slot}"))?.0)))
+            .collect()
+    }
+}
+
+/// Theme-wide knobs that apply across widget sections.
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "kebab-case")]
+pub(crate) struct DefaultsRaw {
+    pub disabled_opacity: Option<f32>,
+    pub disabled_desaturate: Option<f32>,
+}
+
+impl DefaultsRaw {
+    fn dimming(&self) -> Result<DisabledDimming, Error> {
+        let identity = DisabledDimming::default();
+        Ok(DisabledDimming {
+            opacity: unit_factor("defaults.disabled-opacity", self.disabled_opacity)?
+                .unwrap_or(identity.opacity),
+            desaturate: unit_factor("defaults.disabled-desaturate", self.disabled_desaturate)?
+                .unwrap_or(identity.desaturate),
+        })
+    }
+}
+
+macro_rules! variants {
+    ($($(#[$attr:meta])* $field:ident $name:literal $style:ident $raw:ident $resolve:ident $catalog:ident $statuses:tt;)*) => {
+        /// Resolves every `[<widget>.variants.<name>]` table in a
+        /// variable-resolved document. A variant inherits its widget section,
+        /// including status sub-tables, with its own keys merged over it.
+        pub(crate) fn variants(root: &toml::Value) -> Result<Variants, Error> {
+            let dim = match root.get("defaults") {
+                Some(defaults) => deserialize::<DefaultsRaw>(defaults, "defaults")?.dimming()?,
+                None => DisabledDimming::default(),
+            };
+
+            Ok(Variants {
+                $($(#[$attr])* $field: section_variants(root, $name, |s: $raw| resolve_section!($resolve, s, dim))?,)*
+            })
+        }
+    };
+}
+widget_sections!(variants);
+
+fn section_variants<S: DeserializeOwned, T>(
+    root: &toml::Value,
+    section: &str,
+    resolve: impl Fn(S) -> T,
+) -> Result<BTreeMap<String, T>, Error> {
+    let Some(toml::Value::Table(table)) = root.get(section) else {
+        return Ok(BTreeMap::new());
+    };
+    let Some(variants) = table.get("variants") else {
+        return Ok(BTreeMap::new());
+    };
+    let not_a_table = |field: String, value: &toml::Value| Error::InvalidValue {
+        field,
+        value: value.to_string(),
+        reason: Reason::NotATable,
+    };
+    let variants = variants.as_table().ok_or_else(|| not_a_table(format!("{section}.variants"), variants))?;
+
+    let mut base = table.clone();
+    base.remove("variants");
+
+    let mut resolved = BTreeMap::new();
+    for (name, patch) in variants {
+        let field = format!("{section}.variants.{name}");
+        if !patch.is_table() {
+            return Err(not_a_table(field, patch));
+        }
+        let mut merged = toml::Value::Table(base.clone());
+        merge(&mut merged, patch.clone());
+        resolved.insert(name.clone(), resolve(deserialize(&merged, &field)?));
+    }
+    Ok(resolved)
+}
+
+fn unit_factor(field: &str, value: Option<f32>) -> Result<Option<f32>, Error> {
+    match value {
+        Some(v) if !(0.0..=1.0).contains(&v) => Err(Error::InvalidValue {
+            field: field.to_string(),
+            value: v.to_string(),
+            reason: Reason::OutOfRange { min: 0.0, max: 1.0 },
+        }),
+        _ => Ok(value),
+    }
 }
 
 /// Optional font configuration. All fields default to iced's defaults when absent.
@@ -47,6 +305,27 @@ pub(crate) struct FontRaw {
     pub stretch: Option<FontStretch>,
 }
 
+/// A glyph: the character itself, like `"✓"` or `"\uF00C"`, or its code
+/// point written `"U+F00C"`.
+pub(crate) struct CodePoint(pub char);
+
+impl<'de> Deserialize<'de> for CodePoint {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        let mut chars = s.chars();
+        let parsed = match (chars.next(), chars.next()) {
+            (Some(c), None) => Some(c),
+            _ => s
+                .strip_prefix("U+")
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .and_then(char::from_u32),
+        };
+        parsed
+            .map(CodePoint)
+            .ok_or_else(|| serde::de::Error::custom("expected a single character or a code point like `U+F00C`"))
+    }
+}
+
 // Mirror enums for serde -- iced_core's enums don't derive Deserialize.
 
 #[derive(Deserialize, Clone, Copy)]
@@ -127,51 +406,50 @@ impl From<FontStretch> for font::Stretch {
     }
 }
 
-impl TryFrom<ThemeRaw> for ThemeConfig {
-    type Error = Error;
+macro_rules! try_from_raw {
+    ($($(#[$attr:meta])* $field:ident $name:literal $style:ident $raw:ident $resolve:ident $catalog:ident $statuses:tt;)*) => {
+        impl TryFrom<ThemeRaw> for ThemeConfig {
+            type Error = Error;
 
-    fn try_from(raw: ThemeRaw) -> Result<Self, Self::Error> {
-        let name = raw.name.unwrap_or_else(|| "Custom".to_string());
+            fn try_from(raw: ThemeRaw) -> Result<Self, Self::Error> {
+                let name = raw.name.unwrap_or_else(|| "Custom".to_string());
 
-        let palette = Palette {
-            background: raw.palette.background.0,
-            text: raw.palette.text.0,
-            primary: raw.palette.primary.0,
-            success: raw.palette.success.0,
-            warning: raw.palette.warning.0,
-            danger: raw.palette.danger.0,
-        };
+                let generator = raw.palette.generator();
+                let palette = raw.palette.palette();
+                let theme = Theme::custom_with_fn(name.clone(), palette, |p| generator.generate(p));
+                let custom_slots = raw.palette.custom_slots()?;
+                let warning = raw.palette.warning.0;
 
-        let theme = Theme::custom(name.clone(), palette);
-
-        let font = raw.font.map(build_font);
-
-        let button = raw.button.map(|s| s.resolve());
-        let container = raw.container.map(|s| s.resolve());
-        let text_input = raw.text_input.map(|s| s.resolve());
-        let checkbox = raw.checkbox.map(|s| s.resolve());
-        let toggler = raw.toggler.map(|s| s.resolve());
-        let slider = raw.slider.map(|s| s.resolve());
-        let progress_bar = raw.progress_bar.map(|s| s.resolve());
-        let radio = raw.radio.map(|s| s.resolve());
-
-        Ok(ThemeConfig {
-            name,
-            theme,
-            font,
-            button,
-            container,
-            text_input,
-            checkbox,
-            toggler,
-            slider,
-            progress_bar,
-            radio,
-        })
-    }
+                let font = raw.font.map(build_font);
+
+                let dim = raw.defaults.unwrap_or_default().dimming()?;
+
+                Ok(ThemeConfig {
+                    name,
+                    metadata: raw.metadata.unwrap_or_default(),
+                    theme,
+                    generator,
+                    custom_slots,
+                    warning,
+                    font,
+                    root: raw.root.map(|s| s.resolve()),
+                    $($(#[$attr])* $field: raw.$field.map(|s| resolve_section!($resolve, s, dim)),)*
+                    ansi: raw.ansi.unwrap_or_default().resolve(&palette, warning),
+                    syntax: raw.syntax.unwrap_or_default().resolve(&palette, warning),
+                    icons: raw.icons.unwrap_or_default().resolve(font),
+                    source: toml::Value::Table(toml::Table::new()),
+                    resolved: toml::Value::Table(toml::Table::new()),
+                    variables: Default::default(),
+                    variants: Variants::default(),
+                    derivations: Vec::new(),
+                })
+            }
+        }
+    };
 }
+widget_sections!(try_from_raw);
 
-fn build_font(raw: FontRaw) -> Font {
+pub(crate) fn build_font(raw: FontRaw) -> Font {
     let family = match raw.family.as_deref() {
         None | Some("sans-serif") => font::Family::SansSerif,
         Some("serif") => font::Family::Serif,
@@ -191,3 +469,791 @@ fn build_font(raw: FontRaw) -> Font {
         style: raw.style.map(Into::into).unwrap_or(font::Style::Normal),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use iced_widget::button;
+
+    use crate::ThemeConfig;
+    use crate::testing::PALETTE;
+
+    fn button_style(toml: &str, status: button::Status) -> button::Style {
+        let config: ThemeConfig = format!("{PALETTE}{toml}").parse().unwrap();
+        config.button().unwrap().style_fn()(&config.theme(), status)
+    }
+
+    #[test]
+    fn border_table_sets_the_border_fields() {
+        let toml = "[button]\nborder = { width = 2, color = \"@primary\", radius = 4 }\n";
+        let style = button_style(toml, button::Status::Pressed);
+        assert_eq!((style.border.width, style.border.radius.top_left), (2.0, 4.0));
+        assert_eq!(style.border.color, iced_core::Color::from_rgb8(0x1E, 0x88, 0xE5));
+    }
+
+    #[test]
+    fn shadow_offset_takes_a_pair() {
+        let toml = "[variables]\nlift = [0, 2]\n\n\
+                    [button]\nshadow-color = \"#00000040\"\nshadow-offset = \"$lift\"\n\n\
+                    [button.hovered]\nshadow = { offset = [1, 4.5], blur = 6 }\n\n\
+                    [button.pressed]\nshadow-offset-y = 1.0\n";
+        let offset = |status| button_style(toml, status).shadow.offset;
+        assert_eq!(offset(button::Status::Active), iced_core::Vector::new(0.0, 2.0));
+        assert_eq!(offset(button::Status::Hovered), iced_core::Vector::new(1.0, 4.5));
+        assert_eq!(offset(button::Status::Pressed), iced_core::Vector::new(0.0, 1.0));
+        assert_eq!(button_style(toml, button::Status::Hovered).shadow.blur_radius, 6.0);
+
+        let err = format!("{PALETTE}[container]\nshadow-offset = 2.0\n").parse::<ThemeConfig>().err().unwrap();
+        assert_eq!(err.field(), Some("container.shadow-offset"), "got: {err}");
+    }
+
+    #[test]
+    fn enabled_flags_switch_off_borders_and_shadows() {
+        let toml = "[button]\nborder-width = 2.0\nborder-color = \"@primary\"\nborder-radius = 6.0\n\
+                    shadow-color = \"#00000040\"\nshadow-blur-radius = 8.0\n\n\
+                    [button.pressed]\nshadow-enabled = false\n\n\
+                    [button.disabled]\nborder-enabled = false\n";
+        let active = button_style(toml, button::Status::Active);
+        assert_eq!((active.border.width, active.shadow.blur_radius), (2.0, 8.0));
+
+        let pressed = button_style(toml, button::Status::Pressed);
+        assert_eq!(pressed.shadow, iced_core::Shadow::default());
+        assert_eq!(pressed.border, active.border);
+
+        let disabled = button_style(toml, button::Status::Disabled);
+        assert_eq!((disabled.border.width, disabled.border.radius.top_left), (0.0, 6.0));
+        assert_eq!(disabled.shadow.blur_radius, 8.0);
+
+        let radio: ThemeConfig = format!("{PALETTE}[radio]\nborder-enabled = false\n").parse().unwrap();
+        let status = iced_widget::radio::Status::Active { is_selected: true };
+        assert_eq!(radio.radio().unwrap().style_fn()(&radio.theme(), status).border_width, 0.0);
+    }
+
+    #[test]
+    fn border_enabled_switches_off_the_slider_handle_border() {
+        use iced_widget::slider::Status;
+
+        let toml = "[slider]\nhandle-border-width = 2.0\nhandle-border-color = \"@primary\"\n\n\
+                    [slider.dragged]\nborder-enabled = false\n";
+        let config: ThemeConfig = format!("{PALETTE}{toml}").parse().unwrap();
+        let style = config.slider().unwrap().style_fn();
+        assert_eq!(style(&config.theme(), Status::Hovered).handle.border_width, 2.0);
+        let dragged = style(&config.theme(), Status::Dragged).handle;
+        assert_eq!((dragged.border_width, dragged.border_color), (0.0, iced_core::Color::TRANSPARENT));
+    }
+
+    #[test]
+    fn border_enabled_switches_off_both_toggler_borders() {
+        use iced_widget::toggler::Status;
+
+        let toml = "[toggler]\nbackground-border-width = 1.0\nforeground-border-width = 2.0\n\n\
+                    [toggler.hovered]\nborder-enabled = false\n";
+        let config: ThemeConfig = format!("{PALETTE}{toml}").parse().unwrap();
+        let style = config.toggler().unwrap().style_fn();
+        let active = style(&config.theme(), Status::Active { is_toggled: false });
+        assert_eq!((active.background_border_width, active.foreground_border_width), (1.0, 2.0));
+        let hovered = style(&config.theme(), Status::Hovered { is_toggled: true });
+        assert_eq!((hovered.background_border_width, hovered.foreground_border_width), (0.0, 0.0));
+    }
+
+    #[test]
+    fn border_enabled_hides_the_default_pane_grid_region_border() {
+        let toml = "[pane-grid.hovered-region]\nborder-enabled = false\nborder-radius = 4.0\n";
+        let config: ThemeConfig = format!("{PALETTE}{toml}").parse().unwrap();
+        let border = config.pane_grid().unwrap().style_fn()(&config.theme()).hovered_region.border;
+        assert_eq!((border.width, border.radius), (0.0, 4.0.into()));
+    }
+
+    #[cfg(feature = "iced-0-14")]
+    #[test]
+    fn border_enabled_switches_off_scrollbar_borders() {
+        use iced_widget::scrollable::Status;
+
+        let toml = "[scrollable]\nrail-border-width = 1.0\nscroller-border-width = 1.0\n\n\
+                    [scrollable.dragged]\nborder-enabled = false\n";
+        let config: ThemeConfig = format!("{PALETTE}{toml}").parse().unwrap();
+        let style = config.scrollable().unwrap().style_fn();
+        let status = |dragged| Status::Dragged {
+            is_horizontal_scrollbar_dragged: false,
+            is_vertical_scrollbar_dragged: dragged,
+            is_horizontal_scrollbar_disabled: false,
+            is_vertical_scrollbar_disabled: false,
+        };
+        let rail = style(&config.theme(), status(false)).vertical_rail;
+        assert_eq!((rail.border.width, rail.scroller.border.width), (1.0, 1.0));
+        let rail = style(&config.theme(), status(true)).vertical_rail;
+        assert_eq!((rail.border.width, rail.scroller.border.width), (0.0, 0.0));
+        assert_eq!(rail.scroller.border.radius.top_left, 2.0);
+    }
+
+    #[test]
+    fn disabled_dimming_applies_to_derived_disabled_style() {
+        let style = button_style(
+            r##"
+[defaults]
+disabled-opacity = 0.5
+
+[button]
+text-color = "#FFFFFF"
+"##,
+            button::Status::Disabled,
+        );
+        assert!((style.text_color.a - 0.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn explicit_disabled_section_is_not_dimmed() {
+        let style = button_style(
+            r##"
+[defaults]
+disabled-opacity = 0.5
+
+[button]
+text-color = "#FFFFFF"
+
+[button.disabled]
+text-color = "#808080"
+"##,
+            button::Status::Disabled,
+        );
+        assert!((style.text_color.a - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn background_accepts_numeric_color_forms() {
+        let style = button_style("[button]\nbackground = [1.0, 0.0, 0.0]\ntext-color = 0x00FF00\n", button::Status::Active);
+        assert_eq!(style.background, Some(iced_core::Color::from_rgb(1.0, 0.0, 0.0).into()));
+        assert_eq!(style.text_color, iced_core::Color::from_rgb(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    #[cfg(feature = "table")]
+    fn table_rows_stripe_and_inherit_status_styles() {
+        use crate::style::TableRowStatus;
+
+        let toml = "[table]\nbackground = \"#FFFFFF\"\nstripe-background = \"#EEEEEE\"\ntext-color = \"#202020\"\n\n\
+                    [table.selected]\nbackground = \"#1E88E5\"\nstripe-background = \"#1E88E5\"\n";
+        let config: ThemeConfig = format!("{PALETTE}{toml}").parse().unwrap();
+        let table = config.table().unwrap();
+        let grey = iced_core::Color::from_rgb8(0xEE, 0xEE, 0xEE);
+        assert_eq!(table.row(3, TableRowStatus::Active).background, Some(grey.into()));
+        assert_eq!(table.row(3, TableRowStatus::Hovered).background, Some(grey.into()));
+        let selected = table.row(3, TableRowStatus::Selected);
+        assert_eq!(selected.background, Some(iced_core::Color::from_rgb8(0x1E, 0x88, 0xE5).into()));
+        assert_eq!(selected.text_color, Some(iced_core::Color::from_rgb8(0x20, 0x20, 0x20)));
+        assert_eq!(table.header().background, None);
+    }
+
+    #[test]
+    #[cfg(feature = "table")]
+    fn table_status_background_outranks_the_inherited_stripe() {
+        use crate::style::TableRowStatus;
+
+        let toml = "[table]\nstripe-background = \"#EEEEEE\"\n\n[table.hovered]\nbackground = \"#1E88E5\"\n";
+        let config: ThemeConfig = format!("{PALETTE}{toml}").parse().unwrap();
+        let hovered = config.table().unwrap().row(1, TableRowStatus::Hovered);
+        assert_eq!(hovered.background, Some(iced_core::Color::from_rgb8(0x1E, 0x88, 0xE5).into()));
+
+        let toml = "[table]\n\n[table.selected]\ndivider-color = \"#FF0000\"\n";
+        let err = format!("{PALETTE}{toml}").parse::<ThemeConfig>().err().unwrap();
+        assert_eq!(err.field(), Some("table.selected.divider-color"), "got: {err}");
+    }
+
+    #[cfg(feature = "iced-0-14")]
+    #[test]
+    fn snap_cascades_into_button_statuses_and_containers() {
+        use iced_widget::button::Status;
+
+        let toml = "[button]\nsnap = true\n\n[button.pressed]\nsnap = false\n\n[container]\nsnap = true\n";
+        let config: ThemeConfig = format!("{PALETTE}{toml}").parse().unwrap();
+        let theme = config.theme();
+        let button = config.button().unwrap().style_fn();
+        assert!(button(&theme, Status::Active).snap && button(&theme, Status::Hovered).snap);
+        assert!(!button(&theme, Status::Pressed).snap);
+        assert!(config.container().unwrap().style_fn()(&theme).snap);
+
+        let plain: ThemeConfig = format!("{PALETTE}[button]\nborder-width = 1.0\n").parse().unwrap();
+        assert!(!plain.button().unwrap().style_fn()(&theme, Status::Active).snap);
+    }
+
+    #[cfg(feature = "iced-0-14")]
+    #[test]
+    fn scrollable_status_restyles_only_the_scrollbar_under_the_pointer() {
+        use iced_widget::scrollable;
+
+        let toml = "[scrollable]\nscroller-background = \"#9E9E9E\"\n\n\
+                    [scrollable.hovered]\nscroller-background = \"#1E88E5\"\n";
+        let config: ThemeConfig = format!("{PALETTE}{toml}").parse().unwrap();
+        let status = scrollable::Status::Hovered {
+            is_horizontal_scrollbar_hovered: false,
+            is_vertical_scrollbar_hovered: true,
+            is_horizontal_scrollbar_disabled: false,
+            is_vertical_scrollbar_disabled: false,
+        };
+        let style = config.scrollable().unwrap().style_fn()(&config.theme(), status);
+        let blue = iced_core::Color::from_rgb8(0x1E, 0x88, 0xE5);
+        assert_eq!(style.vertical_rail.scroller.background, blue.into());
+        assert_eq!(style.horizontal_rail.scroller.background, iced_core::Color::from_rgb8(0x9E, 0x9E, 0x9E).into());
+        assert_eq!(style.vertical_rail.border.radius.top_left, 2.0);
+    }
+
+    fn slider_handles(toml: &str) -> [iced_widget::slider::HandleShape; 3] {
+        use iced_widget::slider::Status;
+
+        let config: ThemeConfig = format!("{PALETTE}{toml}").parse().unwrap();
+        let style = config.slider().unwrap().style_fn();
+        [Status::Active, Status::Hovered, Status::Dragged].map(|status| style(&config.theme(), status).handle.shape)
+    }
+
+    #[test]
+    fn slider_statuses_switch_handle_shape() {
+        use iced_widget::slider::HandleShape;
+
+        let toml = "[slider]\nhandle-radius = 9.0\nhandle-width = 12.0\n\n\
+                    [slider.dragged]\nhandle-shape = \"rectangle\"\nhandle-border-radius = 0.0\n";
+        let [active, hovered, dragged] = slider_handles(toml);
+        assert!(matches!(active, HandleShape::Circle { radius: 9.0 }));
+        assert!(matches!(hovered, HandleShape::Circle { radius: 9.0 }));
+        let HandleShape::Rectangle { width, border_radius } = dragged else { panic!("got {dragged:?}") };
+        assert_eq!((width, border_radius.top_left), (12, 0.0));
+
+        let toml = "[slider]\nhandle-shape = \"rectangle\"\n\n[slider.hovered]\nhandle-shape = \"circle\"\n";
+        let [active, hovered, _] = slider_handles(toml);
+        assert!(matches!(active, HandleShape::Rectangle { width: 8, .. }));
+        assert!(matches!(hovered, HandleShape::Circle { radius: 7.0 }));
+    }
+
+    #[test]
+    fn slider_shape_parameters_imply_their_shape() {
+        use iced_widget::slider::HandleShape;
+
+        let toml = "[slider]\nhandle-width = 6.0\n\n[slider.hovered]\nhandle-radius = 10.0\n";
+        let [active, hovered, dragged] = slider_handles(toml);
+        assert!(matches!(active, HandleShape::Rectangle { width: 6, .. }));
+        assert!(matches!(hovered, HandleShape::Circle { radius: 10.0 }));
+        assert!(matches!(dragged, HandleShape::Rectangle { width: 6, .. }));
+    }
+
+    #[test]
+    fn custom_palette_slots_are_colors_widgets_can_reference() {
+        use iced_core::Color;
+
+        let toml = "info = \"#0288D1\"\naccent-2 = \"lighten(#8E24AA, 10%)\"\n\n\
+                    [button]\nbackground = \"$palette.info\"\nborder-color = \"@accent-2\"\n";
+        let config: ThemeConfig = format!("{PALETTE}{toml}").parse().unwrap();
+        let info = Color::from_rgb8(0x02, 0x88, 0xD1);
+        assert_eq!(config.palette_color("info"), Some(info));
+        assert_eq!(config.palette_color("primary"), Some(config.theme().palette().primary));
+        assert_eq!(config.palette_color("warning"), Some(Color::from_rgb8(0xFB, 0x8C, 0x00)));
+        assert_eq!(config.palette_color("missing"), None);
+        assert_eq!(config.custom_palette().keys().collect::<Vec<_>>(), ["accent-2", "info"]);
+        let style = config.button().unwrap().style_fn()(&config.theme(), iced_widget::button::Status::Active);
+        assert_eq!(style.background, Some(info.into()));
+        assert_eq!(Some(style.border.color), config.palette_color("accent-2"));
+        assert_ne!(config.derive_dark().palette_color("info"), Some(info));
+
+        let err = format!("{PALETTE}info = \"blue-ish\"\n").parse::<ThemeConfig>().err().unwrap();
+        assert_eq!(err.field(), Some("palette.info"), "got: {err}");
+    }
+
+    #[test]
+    fn slider_rails_take_gradients() {
+        use iced_core::{Background, Gradient};
+        use iced_widget::slider::Status;
+
+        let toml = "[gradients.fill]\nangle = 90.0\n\
+                    stops = [{ offset = 0.0, color = \"@primary\" }, { offset = 1.0, color = \"@success\" }]\n\n\
+                    [slider]\nrail-background-1 = \"$gradients.fill\"\nrail-background-2 = \"#E0E0E0\"\n";
+        let config: ThemeConfig = format!("{PALETTE}{toml}").parse().unwrap();
+        let theme = config.theme();
+        let rail = config.slider().unwrap().style_fn()(&theme, Status::Hovered).rail;
+        let Background::Gradient(Gradient::Linear(linear)) = rail.backgrounds.0 else {
+            panic!("expected a gradient rail, got {:?}", rail.backgrounds.0);
+        };
+        assert_eq!(linear.stops[1].unwrap().color, theme.palette().success);
+        assert_eq!(rail.backgrounds.1, iced_core::Color::from_rgb8(0xE0, 0xE0, 0xE0).into());
+    }
+
+    #[test]
+    fn pick_list_statuses_inherit_the_base_style() {
+        use iced_widget::pick_list;
+
+        let toml = "[pick-list]\ntext-color = \"#202020\"\nborder-width = 1.0\n\n\
+                    [pick-list.opened]\nborder-color = \"#1E88E5\"\n";
+        let config: ThemeConfig = format!("{PALETTE}{toml}").parse().unwrap();
+        let style = config.pick_list().unwrap().style_fn();
+        let hovered = style(&config.theme(), pick_list::Status::Hovered);
+        assert_eq!(hovered.text_color, iced_core::Color::from_rgb8(0x20, 0x20, 0x20));
+        assert_eq!(hovered.border.color, iced_core::Color::TRANSPARENT);
+
+        #[cfg(feature = "iced-0-14")]
+        let opened = style(&config.theme(), pick_list::Status::Opened { is_hovered: true });
+        #[cfg(feature = "iced-0-13")]
+        let opened = style(&config.theme(), pick_list::Status::Opened);
+        assert_eq!(opened.border.color, iced_core::Color::from_rgb8(0x1E, 0x88, 0xE5));
+        assert_eq!(opened.border.width, 1.0);
+    }
+
+    #[test]
+    fn combo_box_section_styles_input_and_menu() {
+        use iced_widget::text_input;
+
+        let toml = "[combo-box]\nvalue-color = \"#202020\"\n\n\
+                    [combo-box.menu]\nselected-background = \"#1E88E5\"\n\n\
+                    [combo-box.variants.plain]\nvalue-color = \"#000000\"\n";
+        let config: ThemeConfig = format!("{PALETTE}{toml}").parse().unwrap();
+        let combo_box = config.combo_box().unwrap();
+        let input = combo_box.input().style_fn()(&config.theme(), text_input::Status::Active);
+        assert_eq!(input.value, iced_core::Color::from_rgb8(0x20, 0x20, 0x20));
+        let menu = combo_box.menu().unwrap().style_fn()(&config.theme());
+        assert_eq!(menu.selected_background, iced_core::Color::from_rgb8(0x1E, 0x88, 0xE5).into());
+
+        let plain = config.combo_box_variant("plain").unwrap();
+        assert_eq!(plain.input().style_fn()(&config.theme(), text_input::Status::Active).value, iced_core::Color::BLACK);
+        assert!(plain.menu().is_some());
+
+        let config: ThemeConfig = format!("{PALETTE}[combo-box]\nvalue-color = \"#202020\"\n").parse().unwrap();
+        assert!(config.combo_box().unwrap().menu().is_none());
+
+        let err = format!("{PALETTE}[combo-box.focused]\nvalue-color = \"blue-ish\"\n").parse::<ThemeConfig>().err().unwrap();
+        assert_eq!(err.field(), Some("combo-box.focused.value-color"), "got: {err}");
+    }
+
+    #[test]
+    fn rule_fill_parameters_imply_their_mode() {
+        use iced_widget::rule;
+
+        let rule = |toml: &str| {
+            let config: ThemeConfig = format!("{PALETTE}[rule]\ncolor = \"#1E88E5\"\n{toml}").parse().unwrap();
+            let style = *config.rule().unwrap();
+            (style.style_fn()(&config.theme()), style.width())
+        };
+        let (style, width) = rule("");
+        assert_eq!(style.color, iced_core::Color::from_rgb8(0x1E, 0x88, 0xE5));
+        assert!(matches!(style.fill_mode, rule::FillMode::Full));
+        assert_eq!(width, 1);
+
+        assert!(matches!(rule("fill-percent = 80.0\n").0.fill_mode, rule::FillMode::Percent(80.0)));
+        assert!(matches!(rule("fill-padding = 8\n").0.fill_mode, rule::FillMode::Padded(8)));
+        assert!(matches!(rule("fill-padding = [4, 12]\n").0.fill_mode, rule::FillMode::AsymmetricPadding(4, 12)));
+        assert!(matches!(rule("fill-mode = \"full\"\nfill-padding = 8\n").0.fill_mode, rule::FillMode::Full));
+
+        let err = format!("{PALETTE}[rule]\nfill-padding = \"wide\"\n").parse::<ThemeConfig>().err().unwrap();
+        assert_eq!(err.field(), Some("rule.fill-padding"), "got: {err}");
+    }
+
+    #[test]
+    fn checkbox_and_radio_labels_take_spacing_size_and_font() {
+        let toml = "[checkbox]\nicon-color = \"#1E88E5\"\nlabel-spacing = 12.0\nlabel-size = 15.0\n\n\
+                    [checkbox.label-font]\nfamily = \"monospace\"\nweight = \"bold\"\n\n\
+                    [radio]\nlabel-size = 13.0\n";
+        let config: ThemeConfig = format!("{PALETTE}{toml}").parse().unwrap();
+        let checkbox = config.checkbox().unwrap();
+        assert_eq!(checkbox.label_spacing(), Some(12.0));
+        assert_eq!(checkbox.label_size(), Some(15.0));
+        let font = checkbox.label_font().unwrap();
+        assert_eq!((font.family, font.weight), (iced_core::font::Family::Monospace, iced_core::font::Weight::Bold));
+
+        let radio = config.radio().unwrap();
+        assert_eq!((radio.label_spacing(), radio.label_size(), radio.label_font()), (None, Some(13.0), None));
+
+        let err = format!("{PALETTE}[radio.label-font]\nweight = \"heavy\"\n").parse::<ThemeConfig>().err().unwrap();
+        assert_eq!(err.field(), Some("radio.label-font.weight"), "got: {err}");
+    }
+
+    #[test]
+    fn checkbox_takes_gradient_backgrounds() {
+        use iced_core::{Background, Gradient};
+        use iced_widget::checkbox::Status;
+
+        let toml = "[checkbox]\nbackground = \"@background\"\n\n[checkbox.checked.background]\nangle = 135.0\n\
+                    stops = [{ offset = 0.0, color = \"@primary\" }, { offset = 1.0, color = \"@success\" }]\n";
+        let config: ThemeConfig = format!("{PALETTE}{toml}").parse().unwrap();
+        let style = config.checkbox().unwrap().style_fn();
+        let checked = style(&config.theme(), Status::Hovered { is_checked: true }).background;
+        let Background::Gradient(Gradient::Linear(linear)) = checked else { panic!("got {checked:?}") };
+        assert_eq!(linear.stops[1].unwrap().color, config.theme().palette().success);
+        let unchecked = style(&config.theme(), Status::Active { is_checked: false }).background;
+        assert_eq!(unchecked, config.theme().palette().background.into());
+    }
+
+    #[test]
+    fn checkbox_icon_takes_font_glyph_and_size() {
+        let toml = "[checkbox.icon]\ncode-point = \"U+E5CA\"\nsize = 12.0\nline-height = 1.2\n\n\
+                    [checkbox.icon.font]\nfamily = \"Material Icons\"\n";
+        let config: ThemeConfig = format!("{PALETTE}{toml}").parse().unwrap();
+        let icon = config.checkbox().unwrap().icon().unwrap();
+        assert_eq!(icon.code_point(), Some('\u{E5CA}'));
+        assert_eq!(icon.font().unwrap().family, iced_core::font::Family::Name("Material Icons"));
+
+        let native = icon.to_icon(iced_core::Font::DEFAULT, '✓');
+        assert_eq!(native.code_point, '\u{E5CA}');
+        assert_eq!(native.size, Some(iced_core::Pixels(12.0)));
+        assert_eq!(native.line_height, iced_core::text::LineHeight::Relative(1.2));
+
+        let glyph: ThemeConfig = format!("{PALETTE}[checkbox.icon]\ncode-point = \"✔\"\n").parse().unwrap();
+        let icon = glyph.checkbox().unwrap().icon().unwrap();
+        assert_eq!(icon.to_icon(iced_core::Font::MONOSPACE, '✓').font, iced_core::Font::MONOSPACE);
+        assert_eq!(icon.code_point(), Some('✔'));
+
+        let err = format!("{PALETTE}[checkbox.icon]\ncode-point = \"ok\"\n").parse::<ThemeConfig>().err().unwrap();
+        assert_eq!(err.field(), Some("checkbox.icon.code-point"), "got: {err}");
+    }
+
+    #[test]
+    fn button_and_text_input_carry_layout_hints() {
+        let toml = "[button]\npadding = [4.0, 12.0]\ntext-size = 14.0\n\n\
+                    [button.variants.compact]\npadding = 2.0\n\n\
+                    [combo-box]\npadding = [1.0, 2.0, 3.0, 4.0]\n";
+        let config: ThemeConfig = format!("{PALETTE}{toml}").parse().unwrap();
+        let button = config.button().unwrap();
+        assert_eq!(button.padding(), Some(iced_core::Padding::from([4.0, 12.0])));
+        assert_eq!(button.text_size(), Some(14.0));
+        assert_eq!(config.button_variant("compact").unwrap().padding(), Some(iced_core::Padding::new(2.0)));
+
+        let input = config.combo_box().unwrap().input();
+        let padding = input.padding().unwrap();
+        assert_eq!((padding.top, padding.right, padding.bottom, padding.left), (1.0, 2.0, 3.0, 4.0));
+        assert_eq!(input.text_size(), None);
+
+        let err = format!("{PALETTE}[text-input]\npadding = \"wide\"\n").parse::<ThemeConfig>().err().unwrap();
+        assert_eq!(err.field(), Some("text-input.padding"), "got: {err}");
+    }
+
+    #[test]
+    fn progress_bar_fills_take_gradients() {
+        use iced_core::{Background, Gradient};
+
+        let toml = "[progress-bar]\nbar = \"$gradients.sheen\"\n\n[progress-bar.background]\nangle = 0.0\n\
+                    stops = [{ offset = 0.0, color = \"#EEEEEE\" }, { offset = 1.0, color = \"#E0E0E0\" }]\n\n\
+                    [gradients.sheen]\nangle = 90.0\n\
+                    stops = [{ offset = 0.0, color = \"@primary\" }, { offset = 1.0, color = \"@success\" }]\n";
+        let config: ThemeConfig = format!("{PALETTE}{toml}").parse().unwrap();
+        let style = config.progress_bar().unwrap().style_fn()(&config.theme());
+        let Background::Gradient(Gradient::Linear(bar)) = style.bar else { panic!("got {:?}", style.bar) };
+        assert_eq!(bar.stops[0].unwrap().color, config.theme().palette().primary);
+        assert!(matches!(style.background, Background::Gradient(_)), "got {:?}", style.background);
+    }
+
+    #[test]
+    fn progress_bar_carries_girth_and_orientation() {
+        let config: ThemeConfig = format!("{PALETTE}[progress-bar]\ngirth = 6.0\nvertical = true\n").parse().unwrap();
+        let bar = config.progress_bar().unwrap();
+        assert_eq!((bar.girth(), bar.vertical()), (Some(6.0), true));
+
+        let plain: ThemeConfig = format!("{PALETTE}[progress-bar]\nbar = \"@primary\"\n").parse().unwrap();
+        assert_eq!((plain.progress_bar().unwrap().girth(), plain.progress_bar().unwrap().vertical()), (None, false));
+
+        let err = format!("{PALETTE}[progress-bar]\nvertical = \"yes\"\n").parse::<ThemeConfig>().err().unwrap();
+        assert_eq!(err.field(), Some("progress-bar.vertical"), "got: {err}");
+    }
+
+    #[test]
+    fn text_input_hovered_cascades_from_the_base() {
+        use iced_widget::text_input::Status;
+
+        let toml = "[text-input]\nborder-width = 1.0\nborder-color = \"#808080\"\n\n\
+                    [text-input.hovered]\nborder-color = \"@primary\"\n";
+        let config: ThemeConfig = format!("{PALETTE}{toml}").parse().unwrap();
+        let style = config.text_input().unwrap().style_fn();
+        let theme = config.theme();
+        let hovered = style(&theme, Status::Hovered).border;
+        assert_eq!((hovered.width, hovered.color), (1.0, theme.palette().primary));
+        assert_eq!(style(&theme, Status::Active).border.color, iced_core::Color::from_rgb8(0x80, 0x80, 0x80));
+    }
+
+    #[test]
+    fn text_input_takes_gradient_backgrounds() {
+        use iced_core::{Background, Gradient};
+        use iced_widget::text_input::Status;
+
+        let toml = "[gradients.sheen]\nangle = 180.0\n\
+                    stops = [{ offset = 0.0, color = \"#FFFFFF\" }, { offset = 1.0, color = \"#F0F0F0\" }]\n\n\
+                    [text-input]\nbackground = \"$gradients.sheen\"\n\n[combo-box]\nbackground = \"$gradients.sheen\"\n";
+        let config: ThemeConfig = format!("{PALETTE}{toml}").parse().unwrap();
+        let hovered = config.text_input().unwrap().style_fn()(&config.theme(), Status::Hovered);
+        let Background::Gradient(Gradient::Linear(linear)) = hovered.background else {
+            panic!("got {:?}", hovered.background)
+        };
+        assert_eq!(linear.stops[1].unwrap().color, iced_core::Color::from_rgb8(0xF0, 0xF0, 0xF0));
+        let combo = config.combo_box().unwrap().input().style_fn()(&config.theme(), Status::Active);
+        assert_eq!(combo.background, hovered.background);
+    }
+
+    #[test]
+    fn svg_hovered_tint_falls_back_to_the_idle_one() {
+        let toml = "[svg]\ncolor = \"#202020\"\n\n[svg.hovered]\ncolor = \"@primary\"\n";
+        let config: ThemeConfig = format!("{PALETTE}{toml}").parse().unwrap();
+        let svg = config.svg().unwrap();
+        assert_eq!(svg.idle(), Some(iced_core::Color::from_rgb8(0x20, 0x20, 0x20)));
+        assert_eq!(svg.hovered(), Some(iced_core::Color::from_rgb8(0x1E, 0x88, 0xE5)));
+
+        let config: ThemeConfig = format!("{PALETTE}[svg]\ncolor = \"#202020\"\n").parse().unwrap();
+        assert_eq!(config.svg().unwrap().hovered(), config.svg().unwrap().idle());
+    }
+
+    #[test]
+    fn pane_grid_parts_fall_back_to_iced_defaults() {
+        let toml = "[pane-grid.hovered-region]\nborder-color = \"@primary\"\nborder-radius = 4.0\n\n\
+                    [pane-grid.picked-split]\ncolor = \"#FF0000\"\n\n[pane-grid.hovered-split]\nwidth = 1.0\n";
+        let config: ThemeConfig = format!("{PALETTE}{toml}").parse().unwrap();
+        let theme = config.theme();
+        let style = config.pane_grid().unwrap().style_fn()(&theme);
+        let default = iced_widget::pane_grid::default(&theme);
+
+        assert_eq!(style.hovered_region.background, default.hovered_region.background);
+        assert_eq!(style.hovered_region.border.color, iced_core::Color::from_rgb8(0x1E, 0x88, 0xE5));
+        assert_eq!(style.hovered_region.border.width, default.hovered_region.border.width);
+        assert_eq!(style.hovered_region.border.radius, 4.0.into());
+        assert_eq!(style.picked_split.color, iced_core::Color::from_rgb8(0xFF, 0x00, 0x00));
+        assert_eq!(style.picked_split.width, default.picked_split.width);
+        assert_eq!(style.hovered_split.color, default.hovered_split.color);
+        assert_eq!(style.hovered_split.width, 1.0);
+    }
+
+    #[test]
+    fn pill_radius_rounds_every_corner() {
+        let style = button_style("[button]\nborder-radius = \"pill\"\n", button::Status::Active);
+        assert!(style.border.radius.top_left >= 1000.0 && style.border.radius.bottom_right >= 1000.0);
+
+        let err = format!("{PALETTE}[button]\nborder-radius = \"round\"\n").parse::<ThemeConfig>().err().unwrap();
+        assert_eq!(err.field(), Some("button.border-radius"), "got: {err}");
+    }
+
+    #[test]
+    fn status_overrides_apply_over_every_status() {
+        use crate::style::StatusOverrides;
+        use iced_core::{Background, Color};
+
+        let toml = "[button]\nbackground = \"@primary\"\nborder-width = 1.0\nborder-radius = 4.0\n\n\
+                    [button.hovered]\nborder-width = 2.0\n\n[radio]\nborder-width = 1.0\n";
+        let config: ThemeConfig = format!("{PALETTE}{toml}").parse().unwrap();
+        let danger = config.palette_color("danger").unwrap();
+        let overrides = StatusOverrides::new().background(danger).border_radius(0.0);
+        let style = config.button().unwrap().style_fn_with(overrides);
+        for status in [button::Status::Active, button::Status::Hovered, button::Status::Disabled] {
+            let style = style(&config.theme(), status);
+            assert_eq!(style.background, Some(Background::Color(danger)));
+            assert_eq!(style.border.radius, 0.0.into());
+        }
+        assert_eq!(style(&config.theme(), button::Status::Hovered).border.width, 2.0);
+
+        let radio = config.radio().unwrap().style_fn_with(StatusOverrides::new().text_color(Color::WHITE));
+        let radio = radio(&config.theme(), iced_widget::radio::Status::Active { is_selected: false });
+        assert_eq!((radio.text_color, radio.border_width), (Some(Color::WHITE), 1.0));
+    }
+
+    #[test]
+    fn status_overrides_cover_every_style() {
+        use crate::style::StatusOverrides;
+        use iced_core::Background;
+        use iced_widget::slider;
+
+        let toml = "[slider]
+handle-background = \"@primary\"\n\n[progress-bar]\nbar = \"@primary\"\n\n\
+                    [text]\ncolor = \"@text\"\n\n[pane-grid.hovered-region]\nborder-width = 1.0\n";
+        let config: ThemeConfig = format!("{PALETTE}{toml}").parse().unwrap();
+        let (theme, danger) = (config.theme(), config.palette_color("danger").unwrap());
+        let overrides = StatusOverrides::new().background(danger).text_color(danger).border_color(danger);
+
+        let slider = config.slider().unwrap().style_fn_with(overrides);
+        assert_eq!(slider(&theme, slider::Status::Dragged).handle.background, Background::Color(danger));
+        let bar = config.progress_bar().unwrap().style_fn_with(overrides)(&theme);
+        assert_eq!((bar.background, bar.bar), (Background::Color(danger), theme.palette().primary.into()));
+        assert_eq!(config.text().unwrap().style_fn_with(overrides)(&theme).color, Some(danger));
+        let region = config.pane_grid().unwrap().style_fn_with(overrides)(&theme).hovered_region;
+        assert_eq!((region.border.color, region.border.width), (danger, 1.0));
+    }
+
+    #[test]
+    fn radius_takes_css_shorthand() {
+        let radius = |value: &str| {
+            button_style(&format!("[button]\nborder-radius = \"{value}\"\n"), button::Status::Active).border.radius
+        };
+        assert_eq!(radius("6"), 6.0.into());
+        let two = radius("4px 8px");
+        assert_eq!((two.top_left, two.top_right, two.bottom_right, two.bottom_left), (4.0, 8.0, 4.0, 8.0));
+        let four = radius(" 1 2  3 4 ");
+        assert_eq!((four.top_left, four.top_right, four.bottom_right, four.bottom_left), (1.0, 2.0, 3.0, 4.0));
+
+        for bad in ["", "1 2 3", "4 wide", "1 2 3 4 5"] {
+            let err = format!("{PALETTE}[button]\nborder-radius = \"{bad}\"\n").parse::<ThemeConfig>().err().unwrap();
+            assert_eq!(err.field(), Some("button.border-radius"), "`{bad}` got: {err}");
+        }
+    }
+
+    #[cfg(feature = "iced-0-14")]
+    #[test]
+    fn toggler_takes_per_corner_radius() {
+        use iced_widget::toggler;
+
+        let config: ThemeConfig = format!("{PALETTE}[toggler]\nborder-radius = [8.0, 0.0, 0.0, 8.0]\n").parse().unwrap();
+        let style = config.toggler().unwrap().style_fn()(&config.theme(), toggler::Status::Active { is_toggled: false });
+        let radius = style.border_radius.unwrap();
+        assert_eq!((radius.top_left, radius.top_right, radius.bottom_left), (8.0, 0.0, 8.0));
+    }
+
+    #[test]
+    fn toggler_takes_gradient_backgrounds() {
+        #[cfg(feature = "iced-0-14")]
+        use iced_core::{Background, Gradient};
+        use iced_widget::toggler;
+
+        let toml = "[toggler.toggled.background]\nangle = 90.0\n\
+                    stops = [{ offset = 0.0, color = \"@primary\" }, { offset = 1.0, color = \"#90CAF9\" }]\n";
+        let config: ThemeConfig = format!("{PALETTE}{toml}").parse().unwrap();
+        let style = config.toggler().unwrap().style_fn();
+        let toggled = style(&config.theme(), toggler::Status::Hovered { is_toggled: true });
+        let primary = config.theme().palette().primary;
+        // iced 0.13 togglers take one color, the gradient's first stop.
+        #[cfg(feature = "iced-0-14")]
+        assert!(matches!(
+            toggled.background,
+            Background::Gradient(Gradient::Linear(l)) if l.stops[0].unwrap().color == primary
+        ));
+        #[cfg(feature = "iced-0-13")]
+        assert_eq!(toggled.background, primary);
+        let untoggled = style(&config.theme(), toggler::Status::Active { is_toggled: false });
+        assert_ne!(untoggled.background, toggled.background);
+    }
+
+    #[test]
+    fn disabled_opacity_out_of_range_is_an_error() {
+        let result = format!("{PALETTE}[defaults]\ndisabled-opacity = 1.5\n").parse::<ThemeConfig>();
+        assert!(matches!(result, Err(crate::Error::InvalidValue { .. })));
+    }
+
+    #[test]
+    fn invalid_widget_color_reports_key_path_and_reason() {
+        for (toml, field) in [
+            ("[button]\nbackground = \"#GG0000\"\n", "button.background"),
+            ("[button.hovered]\ntext-color = \"#12345\"\n", "button.hovered.text-color"),
+        ] {
+            let err = format!("{PALETTE}{toml}").parse::<ThemeConfig>().err().unwrap();
+            assert_eq!(err.field(), Some(field), "got: {err}");
+            assert_eq!(err.section(), Some("button"));
+            assert!(matches!(err, crate::Error::InvalidColor { .. }), "got: {err}");
+        }
+    }
+
+    #[test]
+    fn wrong_type_reports_key_path() {
+        let err = format!("{PALETTE}[button]\nborder-width = \"thick\"\n").parse::<ThemeConfig>().err().unwrap();
+        assert_eq!(err.field(), Some("button.border-width"), "got: {err}");
+        assert_eq!(err.value(), Some("\"thick\""));
+        assert!(matches!(err.reason(), Some(crate::Reason::Other(_))));
+    }
+
+    #[test]
+    fn gradient_errors_report_the_field_inside_it() {
+        let gradient = "[button.background]\nangle = 90.0\nstops = [{ offset = 0.0, color = \"#000000\" }, ";
+        let err = format!("{PALETTE}{gradient}{{ offest = 1.0, color = \"#FFFFFF\" }}]\n").parse::<ThemeConfig>();
+        let err = err.err().unwrap();
+        assert_eq!(err.field(), Some("button.background.stops.1.offest"), "got: {err}");
+        assert!(err.to_string().contains("unknown field `offest`"), "got: {err}");
+
+        let err = format!("{PALETTE}{gradient}{{ offset = 1.0, color = \"#FFFFF\" }}]\n").parse::<ThemeConfig>();
+        let err = err.err().unwrap();
+        assert_eq!(err.field(), Some("button.background.stops.1.color"), "got: {err}");
+        assert!(matches!(err, crate::Error::InvalidColor { .. }), "got: {err}");
+
+        let err = format!("{PALETTE}[button]\nbackground = true\n").parse::<ThemeConfig>().err().unwrap();
+        assert!(err.to_string().contains("expected a color or a gradient table"), "got: {err}");
+    }
+
+    #[test]
+    fn container_and_root_take_gradient_backgrounds() {
+        use iced_core::{Background, Gradient};
+
+        let gradient = "angle = 180.0\n\
+                        stops = [{ offset = 0.0, color = \"@primary\" }, { offset = 1.0, color = \"#000000\" }]\n";
+        let toml = format!("{PALETTE}[container.background]\n{gradient}\n[root.background]\n{gradient}");
+        let config: ThemeConfig = toml.parse().unwrap();
+        let theme = config.theme();
+        let container = config.container().unwrap().style_fn()(&theme);
+        let Some(Background::Gradient(Gradient::Linear(linear))) = container.background else {
+            panic!("expected a gradient background");
+        };
+        assert_eq!(linear.stops[0].unwrap().color, theme.palette().primary);
+        let root = crate::style::root_style_fn(config.root().copied())(&theme);
+        assert!(matches!(root.background, Some(Background::Gradient(_))));
+    }
+
+    #[test]
+    fn root_fills_unset_background_from_palette() {
+        let config: ThemeConfig = format!("{PALETTE}[root]\ntext-color = \"#FF0000\"\n").parse().unwrap();
+        let style = crate::style::root_style_fn(config.root().copied())(&config.theme());
+        assert_eq!(style.background, Some(iced_core::Color::from_rgb8(0xFA, 0xFA, 0xFA).into()));
+        assert_eq!(style.text_color, Some(iced_core::Color::from_rgb8(0xFF, 0x00, 0x00)));
+    }
+
+    #[test]
+    fn variant_inherits_section_and_status_tables() {
+        let config: ThemeConfig = format!(
+            r##"{PALETTE}
+[button]
+background = "#1E88E5"
+text-color = "#FFFFFF"
+
+[button.hovered]
+border-width = 2.0
+
+[button.variants.danger]
+background = "#E53935"
+"##
+        )
+        .parse()
+        .unwrap();
+        let style = config.button_variant("danger").unwrap().style_fn();
+        let hovered = style(&config.theme(), button::Status::Hovered);
+        assert_eq!(hovered.background, Some(iced_core::Color::from_rgb8(0xE5, 0x39, 0x35).into()));
+        assert_eq!(hovered.text_color, iced_core::Color::WHITE);
+        assert_eq!(hovered.border.width, 2.0);
+        assert!(config.button_variant("missing").is_none());
+    }
+
+    #[test]
+    fn button_variants_are_full_sections() {
+        let config: ThemeConfig = format!(
+            r##"{PALETTE}
+[button]
+background = "@primary"
+text-color = "#FFFFFF"
+padding    = 8.0
+
+[button.variants.primary]
+border-radius = 6.0
+
+[button.variants.ghost]
+background = "transparent"
+text-color = "@primary"
+padding    = [4.0, 8.0]
+
+[button.variants.ghost.hovered]
+background = "#1E88E51A"
+"##
+        )
+        .parse()
+        .unwrap();
+        let ghost = config.button_variant("ghost").unwrap();
+        let active = ghost.style_fn()(&config.theme(), button::Status::Active);
+        let hovered = ghost.style_fn()(&config.theme(), button::Status::Hovered);
+        assert_eq!(active.background, Some(iced_core::Color::TRANSPARENT.into()));
+        assert_eq!(hovered.text_color, iced_core::Color::from_rgb8(0x1E, 0x88, 0xE5));
+        assert_ne!(hovered.background, active.background);
+        assert_eq!(ghost.padding(), Some(iced_core::Padding::from([4.0, 8.0])));
+
+        let primary = config.button_variant("primary").unwrap();
+        let pressed = primary.style_fn()(&config.theme(), button::Status::Pressed);
+        assert_eq!((pressed.border.radius.top_left, pressed.text_color), (6.0, iced_core::Color::WHITE));
+        assert_eq!(primary.padding(), Some(iced_core::Padding::from(8.0)));
+    }
+
+    #[test]
+    fn invalid_variant_reports_variant_path() {
+        let err = format!("{PALETTE}[button.variants.danger]\nbackground = \"#GG0000\"\n")
+            .parse::<ThemeConfig>()
+            .err()
+            .unwrap();
+        assert_eq!(err.field(), Some("button.variants.danger.background"), "got: {err}");
+    }
+}
@@ -0,0 +1,238 @@
+//! Which widget sections and statuses a theme styles itself, and whether it
+//! has the sections a component needs.
+
+use std::collections::BTreeMap;
+
+use crate::ThemeConfig;
+use crate::style::widget_sections;
+
+macro_rules! statuses {
+    ($($(#[$attr:meta])* $field:ident $name:literal $style:ident $raw:ident $resolve:ident $catalog:ident [$($status:literal),*];)*) => {
+        /// Each widget section and the status sub-tables iced can use.
+        const STATUSES: &[(&str, &[&str])] = &[("root", &[]), $($(#[$attr])* ($name, &[$($status),*])),*];
+    };
+}
+widget_sections!(statuses);
+
+/// Where the style for a widget section or status comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Coverage {
+    /// The theme defines it.
+    Explicit,
+    /// The theme defines the section but not this status, which inherits the
+    /// section's base style (dimmed by `[defaults]` when disabled).
+    Derived,
+    /// The theme doesn't define the section, so iced's default style applies.
+    Default,
+}
+
+/// Which widget sections a theme defines, and for each, which statuses it
+/// specifies and which it leaves to be derived.
+///
+/// Returned by [`ThemeConfig::coverage`]. Useful for checking that every
+/// theme in a pack styles the same widgets:
+///
+/// ```
+/// # use iced_themer::{Coverage, ThemeConfig};
+/// let toml = r##"
+/// [palette]
+/// background = "#FAFAFA"
+/// text       = "#202020"
+/// primary    = "#1E88E5"
+/// success    = "#43A047"
+/// warning    = "#FB8C00"
+/// danger     = "#E53935"
+///
+/// [button]
+/// background = "#1E88E5"
+///
+/// [button.hovered]
+/// background = "#1565C0"
+/// "##;
+/// let coverage = toml.parse::<ThemeConfig>().unwrap().coverage();
+/// assert_eq!(coverage.status("button", "hovered"), Some(Coverage::Explicit));
+/// assert_eq!(coverage.status("button", "pressed"), Some(Coverage::Derived));
+/// assert_eq!(coverage.section("slider"), Some(Coverage::Default));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoverageReport {
+    sections: BTreeMap<&'static str, SectionCoverage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SectionCoverage {
+    coverage: Coverage,
+    statuses: Vec<(&'static str, Coverage)>,
+}
+
+impl CoverageReport {
+    /// Whether the widget section `section` (a TOML key like `"text-input"`)
+    /// is [`Explicit`](Coverage::Explicit) or falls back to the
+    /// [`Default`](Coverage::Default). `None` if there's no such section.
+    pub fn section(&self, section: &str) -> Option<Coverage> {
+        self.sections.get(section).map(|s| s.coverage)
+    }
+
+    /// Where the style for `status` of `section` comes from. `None` if the
+    /// section has no such status sub-table.
+    pub fn status(&self, section: &str, status: &str) -> Option<Coverage> {
+        let statuses = &self.sections.get(section)?.statuses;
+        statuses.iter().find(|(name, _)| *name == status).map(|(_, coverage)| *coverage)
+    }
+
+    /// The statuses of `section`, in a fixed order. Empty for
+    /// sections without status sub-tables, like `container`.
+    pub fn statuses(&self, section: &str) -> impl Iterator<Item = (&str, Coverage)> {
+        self.sections.get(section).into_iter().flat_map(|s| s.statuses.iter().copied())
+    }
+
+    /// The widget sections the theme defines, in alphabetical order.
+    pub fn defined(&self) -> impl Iterator<Item = &str> {
+        self.sections.iter().filter(|(_, s)| s.coverage == Coverage::Explicit).map(|(name, _)| *name)
+    }
+
+    /// The widget sections left to iced's defaults, in alphabetical order.
+    pub fn defaults(&self) -> impl Iterator<Item = &str> {
+        self.sections.iter().filter(|(_, s)| s.coverage == Coverage::Default).map(|(name, _)| *name)
+    }
+
+    /// Returns `true` if the theme defines every widget section and every
+    /// status explicitly.
+    pub fn is_complete(&self) -> bool {
+        self.sections.values().all(|s| {
+            s.coverage == Coverage::Explicit && s.statuses.iter().all(|(_, c)| *c == Coverage::Explicit)
+        })
+    }
+}
+
+/// Which of the sections a component asked for a theme provides, returned
+/// by [`ThemeConfig::supports`].
+///
+/// Sections are reported in the order they were asked for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SupportReport {
+    provided: Vec<String>,
+    missing:  Vec<String>,
+}
+
+impl SupportReport {
+    /// Returns `true` if the theme provides every section asked for.
+    pub fn is_supported(&self) -> bool {
+        self.missing.is_empty()
+    }
+
+    /// The sections the theme defines.
+    pub fn provided(&self) -> impl Iterator<Item = &str> {
+        self.provided.iter().map(String::as_str)
+    }
+
+    /// The sections the theme leaves out, which the component has to style
+    /// some other way, say with iced's defaults or its own fallback.
+    pub fn missing(&self) -> impl Iterator<Item = &str> {
+        self.missing.iter().map(String::as_str)
+    }
+}
+
+impl ThemeConfig {
+    /// Checks whether this theme has the top-level sections a component
+    /// draws with, so plugins can degrade gracefully instead of assuming:
+    ///
+    /// ```
+    /// # use iced_themer::ThemeConfig;
+    /// # let config: ThemeConfig = "[palette]\nbackground = \"#FAFAFA\"\ntext = \"#202020\"\n\
+    /// #     primary = \"#1E88E5\"\nsuccess = \"#43A047\"\nwarning = \"#FB8C00\"\ndanger = \"#E53935\"\n\
+    /// #     [slider]\nrail-width = 6\n".parse().unwrap();
+    /// let report = config.supports(&["slider", "menu"]);
+    /// if !report.is_supported() {
+    ///     assert_eq!(report.missing().collect::<Vec<_>>(), ["menu"]);
+    /// }
+    /// ```
+    ///
+    /// Any top-level table counts, like `"icons"` or `"syntax"`, and, as
+    /// for [`coverage`](Self::coverage), however it got into the theme.
+    /// A section named twice is reported once.
+    pub fn supports(&self, sections: &[&str]) -> SupportReport {
+        let mut report = SupportReport { provided: Vec::new(), missing: Vec::new() };
+        for &section in sections {
+            if report.provided.iter().chain(&report.missing).any(|s| s == section) {
+                continue;
+            }
+            match self.resolved.get(section) {
+                Some(toml::Value::Table(_)) => report.provided.push(section.to_string()),
+                _ => report.missing.push(section.to_string()),
+            }
+        }
+        report
+    }
+
+    /// Reports which widget sections and statuses this theme defines, which
+    /// it leaves to be derived, and which fall back to iced's defaults.
+    ///
+    /// Sections count as defined however they got into the theme, including
+    /// through named definitions; variants aren't counted.
+    pub fn coverage(&self) -> CoverageReport {
+        let sections = STATUSES
+            .iter()
+            .copied()
+            .map(|(name, statuses)| {
+                let table = self.resolved.get(name).and_then(toml::Value::as_table);
+                let status = |status: &'static str| {
+                    let coverage = match table {
+                        Some(table) if table.get(status).is_some_and(toml::Value::is_table) => Coverage::Explicit,
+                        Some(_) => Coverage::Derived,
+                        None => Coverage::Default,
+                    };
+                    (status, coverage)
+                };
+                let section = SectionCoverage {
+                    coverage: if table.is_some() { Coverage::Explicit } else { Coverage::Default },
+                    statuses: statuses.iter().copied().map(status).collect(),
+                };
+                (name, section)
+            })
+            .collect();
+        CoverageReport { sections }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::PALETTE;
+
+    fn coverage(toml: &str) -> CoverageReport {
+        format!("{PALETTE}{toml}").parse::<ThemeConfig>().unwrap().coverage()
+    }
+
+    #[test]
+    fn statuses_are_explicit_derived_or_default() {
+        let report = coverage("[text-input]\nborder-width = 1.0\n\n[text-input.focused]\nborder-width = 2.0\n");
+        assert_eq!(
+            report.statuses("text-input").collect::<Vec<_>>(),
+            [("hovered", Coverage::Derived), ("focused", Coverage::Explicit), ("disabled", Coverage::Derived)]
+        );
+        assert!(report.statuses("toggler").all(|(_, c)| c == Coverage::Default));
+        assert_eq!(report.defined().collect::<Vec<_>>(), ["text-input"]);
+        assert_eq!(report.status("text-input", "pressed"), None);
+        assert!(!report.is_complete());
+    }
+
+    #[test]
+    fn supports_reports_missing_sections_in_order() {
+        let toml = "[menu]\nborder-width = 1.0\n\n[icons]\nclose = \"x\"\n";
+        let config: ThemeConfig = format!("{PALETTE}{toml}").parse().unwrap();
+        let report = config.supports(&["table", "menu", "icons", "pane-grid", "menu"]);
+        assert!(!report.is_supported());
+        assert_eq!(report.provided().collect::<Vec<_>>(), ["menu", "icons"]);
+        assert_eq!(report.missing().collect::<Vec<_>>(), ["table", "pane-grid"]);
+        assert!(config.supports(&["menu", "palette"]).is_supported());
+        assert!(config.supports(&[]).is_supported());
+    }
+
+    #[test]
+    fn sections_from_named_definitions_count() {
+        let report = coverage("[borders.card]\nwidth = 1.0\n\n[container]\nborder = \"card\"\n");
+        assert_eq!(report.section("container"), Some(Coverage::Explicit));
+        assert_eq!(report.section("root"), Some(Coverage::Default));
+    }
+}
@@ -0,0 +1,277 @@
+//! One shape for everything a theme check reports.
+
+use std::fmt;
+use std::ops::Range;
+
+use toml::Spanned;
+use toml::de::{DeTable, DeValue};
+
+use crate::ThemeConfig;
+use crate::color::contrast_ratio;
+use crate::config::PALETTE_KEYS;
+use crate::error::{Error, Reason};
+
+/// The WCAG AA minimum contrast ratio for body text.
+const MIN_TEXT_CONTRAST: f32 = 4.5;
+
+/// How serious a [`Diagnostic`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// The theme loads and looks as intended, but something deserves a look,
+    /// like a font that isn't installed.
+    Warning,
+    /// A value the theme can't use. A lenient load drops it; a strict one
+    /// fails.
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Warning => f.write_str("warning"),
+            Severity::Error => f.write_str("error"),
+        }
+    }
+}
+
+/// A problem found in a theme, in one shape for editors, linters, and CI
+/// scripts to consume.
+///
+/// Returned by [`ThemeConfig::check`], [`ThemeConfig::lint`], and
+/// [`ThemeConfig::from_str_lenient`], and built from any [`Error`] with
+/// `From`. Displays as `error: button.background: invalid color ...`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// The dotted key path of the offending value, e.g.
+    /// `"button.hovered.background"`; `None` for problems with the file as a
+    /// whole, like TOML syntax errors.
+    pub path: Option<String>,
+    /// What's wrong, without the key path.
+    pub message: String,
+    /// The byte range in the theme source that the diagnostic points at, when
+    /// it is known.
+    pub span: Option<Range<usize>>,
+}
+
+impl Diagnostic {
+    /// Points the diagnostic at its key path in `source`, the TOML it was
+    /// found in. A path that isn't in `source`, like a missing key, points at
+    /// the header of the nearest table that is. Syntax errors already carry a
+    /// span.
+    pub fn locate(mut self, source: &str) -> Self {
+        if self.span.is_none() {
+            self.span = self.path.as_deref().and_then(|path| locate(source, path));
+        }
+        self
+    }
+}
+
+impl From<&Error> for Diagnostic {
+    fn from(err: &Error) -> Self {
+        let severity = match err.reason() {
+            Some(Reason::UnavailableFont { .. }) => Severity::Warning,
+            _ => Severity::Error,
+        };
+        let message = match err {
+            Error::InvalidColor { value, reason, .. } => format!("invalid color \"{value}\" ({reason})"),
+            Error::InvalidValue { value, reason, .. } => format!("invalid value {value} ({reason})"),
+            Error::Io(_) | Error::Parse(_) => err.to_string(),
+        };
+        let span = match err {
+            Error::Parse(err) => err.span(),
+            _ => None,
+        };
+        Diagnostic { severity, path: err.field().map(str::to_string), message, span }
+    }
+}
+
+impl From<Error> for Diagnostic {
+    fn from(err: Error) -> Self {
+        Diagnostic::from(&err)
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.path {
+            Some(path) => write!(f, "{}: {path}: {}", self.severity, self.message),
+            None => write!(f, "{}: {}", self.severity, self.message),
+        }
+    }
+}
+
+impl ThemeConfig {
+    /// Checks the theme TOML in `source` and reports every problem found,
+    /// each pointed at its place in `source`.
+    ///
+    /// The theme is loaded like [`from_str_lenient`](Self::from_str_lenient),
+    /// so every value it would drop is reported, not just the first, followed
+    /// by what [`lint`](Self::lint) finds in the result; a problem that stops
+    /// the load entirely, like a TOML syntax error, is reported on its own.
+    /// An empty result means the theme loads cleanly.
+    ///
+    /// ```
+    /// # use iced_themer::{Severity, ThemeConfig};
+    /// let toml = "[palette]\nbackground = \"#1B2838\"\ntext = \"#C7D5E0\"\nprimary = \"#66C0F4\"\n\
+    ///             success = \"#4CAF50\"\nwarning = \"#FFC107\"\ndanger = \"#F44336\"\n\n\
+    ///             [button]\nbackground = \"#66C0F\"\n";
+    /// let diagnostics = ThemeConfig::check(toml);
+    /// assert_eq!(diagnostics[0].severity, Severity::Error);
+    /// assert_eq!(diagnostics[0].path.as_deref(), Some("button.background"));
+    /// assert_eq!(&toml[diagnostics[0].span.clone().unwrap()], "\"#66C0F\"");
+    /// ```
+    pub fn check(source: &str) -> Vec<Diagnostic> {
+        match ThemeConfig::from_str_lenient(source) {
+            Ok((config, mut diagnostics)) => {
+                diagnostics.extend(config.lint().into_iter().map(|lint| lint.locate(source)));
+                diagnostics
+            }
+            Err(err) => vec![Diagnostic::from(err).locate(source)],
+        }
+    }
+
+    /// Reports what deserves a look in a theme that loaded cleanly, as
+    /// warnings: palette text that is hard to read on the palette background
+    /// (below WCAG AA's 4.5:1), a custom palette slot whose name is one typo
+    /// away from one of iced's, like `sucess`, and with the `font-check`
+    /// feature, a font family that isn't installed (see
+    /// [`check_font`](Self::check_font)).
+    ///
+    /// The diagnostics carry no span; [`Diagnostic::locate`] points them at
+    /// the theme's source.
+    pub fn lint(&self) -> Vec<Diagnostic> {
+        let mut warnings = Vec::new();
+        let palette = self.theme().palette();
+        let ratio = contrast_ratio(palette.text, palette.background);
+        if ratio < MIN_TEXT_CONTRAST {
+            warnings.push(Diagnostic {
+                severity: Severity::Warning,
+                path:     Some("palette.text".to_string()),
+                message:  format!("contrast with the background is {ratio:.1}:1, below {MIN_TEXT_CONTRAST}:1"),
+                span:     None,
+            });
+        }
+        for slot in self.custom_slots.keys() {
+            if let Some(key) = PALETTE_KEYS.into_iter().find(|key| is_typo(slot, key)) {
+                warnings.push(Diagnostic {
+                    severity: Severity::Warning,
+                    path:     Some(format!("palette.{slot}")),
+                    message:  format!("custom slot `{slot}` looks like a misspelling of `{key}`"),
+                    span:     None,
+                });
+            }
+        }
+        #[cfg(feature = "font-check")]
+        warnings.extend(self.check_font().err().map(Diagnostic::from));
+        warnings
+    }
+}
+
+/// Whether `word` is one typo away from `key`: one character added,
+/// dropped, changed, or swapped with its neighbor, or two of those for keys
+/// of seven or more characters.
+fn is_typo(word: &str, key: &str) -> bool {
+    let (a, b): (Vec<char>, Vec<char>) = (word.chars().collect(), key.chars().collect());
+    // Optimal string alignment distance, one row per character of `a`.
+    let mut rows = vec![(0..=b.len()).collect::<Vec<_>>()];
+    for i in 1..=a.len() {
+        let mut row = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            row[j] = (rows[i - 1][j] + 1).min(row[j - 1] + 1).min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                row[j] = row[j].min(rows[i - 2][j - 2] + 1);
+            }
+        }
+        rows.push(row);
+    }
+    let distance = rows[a.len()][b.len()];
+    distance > 0 && distance <= if b.len() >= 7 { 2 } else { 1 }
+}
+
+/// The span of the value at the dotted key `path` in `source`, or of the
+/// deepest table or array on the path that exists.
+fn locate(source: &str, path: &str) -> Option<Range<usize>> {
+    let root = DeTable::parse(source).ok()?;
+    let mut found: Option<&Spanned<DeValue<'_>>> = None;
+    for key in path.split('.') {
+        let next = match found.map(Spanned::get_ref) {
+            None => root.get_ref().get(key),
+            Some(DeValue::Table(table)) => table.get(key),
+            Some(DeValue::Array(items)) => key.parse::<usize>().ok().and_then(|i| items.get(i)),
+            Some(_) => None,
+        };
+        match next {
+            Some(value) => found = Some(value),
+            None => break,
+        }
+    }
+    found.map(Spanned::span)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::PALETTE;
+
+    #[test]
+    fn check_reports_every_dropped_value_with_its_span() {
+        let toml = format!("{PALETTE}\n[button]\nbackground = \"#12\"\nborder-width = \"wide\"\n");
+        let diagnostics = ThemeConfig::check(&toml);
+        assert_eq!(diagnostics.len(), 2);
+        let paths: Vec<_> = diagnostics.iter().filter_map(|d| d.path.as_deref()).collect();
+        assert!(paths.contains(&"button.background") && paths.contains(&"button.border-width"), "got {paths:?}");
+        for diagnostic in &diagnostics {
+            assert_eq!(diagnostic.severity, Severity::Error);
+            let span = diagnostic.span.clone().unwrap();
+            assert!(matches!(&toml[span], "\"#12\"" | "\"wide\""));
+        }
+        assert!(ThemeConfig::check(PALETTE).is_empty());
+    }
+
+    #[test]
+    fn lint_warns_about_unreadable_palette_text() {
+        let toml = PALETTE.replace("#202020", "#DDDDDD");
+        let diagnostics = ThemeConfig::check(&toml);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert_eq!(diagnostics[0].path.as_deref(), Some("palette.text"));
+        assert_eq!(&toml[diagnostics[0].span.clone().unwrap()], "\"#DDDDDD\"");
+        assert!(PALETTE.parse::<ThemeConfig>().unwrap().lint().is_empty());
+    }
+
+    #[test]
+    fn lint_warns_about_custom_slots_that_look_like_typos() {
+        let toml = format!("{PALETTE}sucess = \"#00FF00\"\nprimray = \"#0000FF\"\ninfo = \"#0288D1\"\n");
+        let diagnostics = ThemeConfig::check(&toml);
+        let paths: Vec<_> = diagnostics.iter().filter_map(|d| d.path.as_deref()).collect();
+        assert_eq!(paths, ["palette.primray", "palette.sucess"]);
+        assert!(diagnostics[1].message.contains("`success`"), "got: {}", diagnostics[1].message);
+        assert_eq!(&toml[diagnostics[1].span.clone().unwrap()], "\"#00FF00\"");
+    }
+
+    #[test]
+    fn syntax_errors_are_fatal_and_spanned() {
+        let diagnostics = ThemeConfig::check("[button\n");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].path, None);
+        assert!(diagnostics[0].span.is_some());
+    }
+
+    #[test]
+    fn missing_keys_point_at_their_table() {
+        let toml = format!("{PALETTE}\n[button]\nborder-width = 2.0\n");
+        let span = locate(&toml, "button.hovered.background").unwrap();
+        assert_eq!(&toml[span], "[button]");
+        assert_eq!(locate(&toml, "slider.rail"), None);
+    }
+
+    #[test]
+    fn displays_severity_and_path() {
+        let err = format!("{PALETTE}\n[button]\nbackground = \"#12\"\n").parse::<ThemeConfig>().err().unwrap();
+        let diagnostic = Diagnostic::from(err);
+        let message = "invalid color \"#12\" (expected 3, 6, or 8 hex digits after '#', got 2)";
+        assert_eq!(diagnostic.to_string(), format!("error: button.background: {message}"));
+    }
+}
@@ -0,0 +1,318 @@
+//! Section-level comparison of two theme configs, fingerprints for telling
+//! whether anything changed at all, and equality up to float rounding.
+
+use std::collections::BTreeSet;
+
+use iced_core::Color;
+
+use crate::ThemeConfig;
+use crate::compat;
+use crate::metadata::ThemeMetadata;
+use crate::palette::PaletteGenerator;
+use crate::style::{ApproxEq, approx_eq_exactly, impl_approx_eq, widget_sections};
+use crate::transform::Derivation;
+
+/// The top-level TOML sections that differ between two versions of a theme.
+///
+/// Sections are compared after variable resolution, so editing a variable
+/// reports every section that uses it, and `[variables]` itself never
+/// appears. Section names are the TOML keys: `"name"`, `"palette"`, `"font"`,
+/// `"defaults"`, `"button"`, `"text-input"`, and so on.
+///
+/// ```no_run
+/// # use iced_themer::{ThemeConfig, ThemeDiff};
+/// # let (old, new): (ThemeConfig, ThemeConfig) = todo!();
+/// let diff = ThemeDiff::between(&old, &new);
+/// if diff.changed("font") {
+///     // reload fonts
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ThemeDiff {
+    sections: BTreeSet<String>,
+}
+
+impl ThemeDiff {
+    /// Compare `old` against `new`.
+    ///
+    /// If the two were derived differently (say, one went through
+    /// [`ThemeConfig::derive_dark`]), every section present in either is
+    /// reported as changed.
+    pub fn between(old: &ThemeConfig, new: &ThemeConfig) -> ThemeDiff {
+        let empty = toml::Table::new();
+        let old_doc = old.resolved.as_table().unwrap_or(&empty);
+        let new_doc = new.resolved.as_table().unwrap_or(&empty);
+        let derived_differently = old.derivations != new.derivations;
+
+        let sections = old_doc
+            .keys()
+            .chain(new_doc.keys())
+            .filter(|key| derived_differently || old_doc.get(*key) != new_doc.get(*key))
+            .cloned()
+            .collect();
+
+        ThemeDiff { sections }
+    }
+
+    /// Returns `true` if nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.sections.is_empty()
+    }
+
+    /// Returns `true` if the top-level section `section` changed, was added,
+    /// or was removed.
+    pub fn changed(&self, section: &str) -> bool {
+        self.sections.contains(section)
+    }
+
+    /// The changed sections, in alphabetical order.
+    pub fn sections(&self) -> impl Iterator<Item = &str> {
+        self.sections.iter().map(String::as_str)
+    }
+}
+
+impl ThemeConfig {
+    /// A hash of everything that determines how this theme looks: its name,
+    /// palette, derivations, and every section after variable resolution.
+    ///
+    /// Equal configs have equal fingerprints, in any process and on any
+    /// platform, so a fingerprint can be stored alongside cached renders and
+    /// compared after a reload to skip redoing them:
+    ///
+    /// ```no_run
+    /// # use iced_themer::ThemeConfig;
+    /// # let (cached, new): (u64, ThemeConfig) = todo!();
+    /// if new.fingerprint() != cached {
+    ///     // re-render
+    /// }
+    /// ```
+    ///
+    /// Reformatting the TOML, reordering keys, or renaming a variable without
+    /// changing its value leaves the fingerprint unchanged.
+    pub fn fingerprint(&self) -> u64 {
+        let mut hash = Fnv::default();
+        hash.bytes(self.name.as_bytes());
+        let palette = self.theme.palette();
+        let slots = [palette.background, palette.text, palette.primary, palette.success, self.warning, palette.danger];
+        for color in slots {
+            hash.color(color);
+        }
+        for derivation in &self.derivations {
+            match derivation {
+                Derivation::Dark => hash.bytes(b"dark"),
+                Derivation::Quantize(depth) => hash.bytes(format!("quantize {depth:?}").as_bytes()),
+            }
+        }
+        hash.value(&self.resolved);
+        hash.0
+    }
+
+    /// Returns `true` if the two themes resolve to the same styles, with
+    /// every color channel, width, radius, and other number within `epsilon`
+    /// of its counterpart.
+    ///
+    /// Meant for tests of conversions, where a theme that went through an
+    /// export and back, or a derivation and its inverse, should come out the
+    /// same but can differ in the last bits of a float. Names, fonts, and
+    /// everything else that isn't a number must match exactly. Only resolved
+    /// values are compared, so how the TOML spelled them doesn't matter.
+    ///
+    /// ```
+    /// # use iced_themer::ThemeConfig;
+    /// # let palette = "[palette]\nbackground = \"#FAFAFA\"\ntext = \"#202020\"\nprimary = \"#1E88E5\"\n\
+    /// #     success = \"#43A047\"\nwarning = \"#FB8C00\"\ndanger = \"#E53935\"\n";
+    /// let a: ThemeConfig = format!("{palette}[button]\nborder-width = 1.0\n").parse().unwrap();
+    /// let b: ThemeConfig = format!("{palette}[button]\nborder-width = 1.00001\n").parse().unwrap();
+    /// assert!(a.approx_eq(&b, 1e-4));
+    /// assert!(!a.approx_eq(&b, 1e-6));
+    /// ```
+    pub fn approx_eq(&self, other: &ThemeConfig, epsilon: f32) -> bool {
+        // The extended palette is generated from the palette by `generator`,
+        // so comparing those covers it.
+        let slots = |config: &ThemeConfig| {
+            compat::palette_slots(&config.theme.palette()).into_iter().map(|(_, color)| color).collect::<Vec<_>>()
+        };
+        slots(self).approx_eq(&slots(other), epsilon)
+            && ApproxEq::approx_eq(self, other, epsilon)
+    }
+}
+
+approx_eq_exactly!(ThemeMetadata, PaletteGenerator);
+
+macro_rules! approx_eq_config {
+    ($($(#[$attr:meta])* $field:ident $name:literal $style:ident $raw:ident $resolve:ident $catalog:ident $statuses:tt;)*) => {
+        impl_approx_eq!(ThemeConfig {
+            name, metadata, generator, custom_slots, warning, font, root, $($(#[$attr])* $field,)* ansi, syntax, icons, variants,
+        });
+    };
+}
+widget_sections!(approx_eq_config);
+
+/// 64-bit FNV-1a, whose output, unlike std's hashers, is specified and so
+/// stable across Rust versions.
+struct Fnv(u64);
+
+impl Default for Fnv {
+    fn default() -> Self {
+        Fnv(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Fnv {
+    fn bytes(&mut self, bytes: &[u8]) {
+        for &b in (bytes.len() as u64).to_le_bytes().iter().chain(bytes) {
+            self.0 = (self.0 ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn color(&mut self, color: Color) {
+        for c in [color.r, color.g, color.b, color.a] {
+            self.bytes(&c.to_bits().to_le_bytes());
+        }
+    }
+
+    /// Hashes `value` with each type tagged and table keys in sorted order.
+    fn value(&mut self, value: &toml::Value) {
+        match value {
+            toml::Value::String(s) => {
+                self.bytes(b"s");
+                self.bytes(s.as_bytes());
+            }
+            toml::Value::Integer(i) => {
+                self.bytes(b"i");
+                self.bytes(&i.to_le_bytes());
+            }
+            toml::Value::Float(f) => {
+                self.bytes(b"f");
+                self.bytes(&f.to_bits().to_le_bytes());
+            }
+            toml::Value::Boolean(b) => {
+                self.bytes(b"b");
+                self.bytes(&[u8::from(*b)]);
+            }
+            toml::Value::Datetime(d) => {
+                self.bytes(b"d");
+                self.bytes(d.to_string().as_bytes());
+            }
+            toml::Value::Array(items) => {
+                self.bytes(b"a");
+                self.bytes(&(items.len() as u64).to_le_bytes());
+                items.iter().for_each(|item| self.value(item));
+            }
+            toml::Value::Table(table) => {
+                self.bytes(b"t");
+                let mut keys: Vec<_> = table.keys().collect();
+                keys.sort();
+                self.bytes(&(keys.len() as u64).to_le_bytes());
+                for key in keys {
+                    self.bytes(key.as_bytes());
+                    self.value(&table[key]);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: &str = r##"
+name = "Base"
+
+[variables]
+accent = "#1E88E5"
+
+[palette]
+background = "#FAFAFA"
+text       = "#202020"
+primary    = "$accent"
+success    = "#43A047"
+warning    = "#FB8C00"
+danger     = "#E53935"
+
+[font]
+family = "serif"
+
+[button]
+background = "$accent"
+"##;
+
+    fn config(toml: &str) -> ThemeConfig {
+        toml.parse().unwrap()
+    }
+
+    #[test]
+    fn identical_configs_have_empty_diff() {
+        assert!(ThemeDiff::between(&config(BASE), &config(BASE)).is_empty());
+    }
+
+    #[test]
+    fn font_change_is_reported_alone() {
+        let new = BASE.replace("\"serif\"", "\"monospace\"");
+        let diff = ThemeDiff::between(&config(BASE), &config(&new));
+        assert_eq!(diff.sections().collect::<Vec<_>>(), ["font"]);
+    }
+
+    #[test]
+    fn variable_change_reports_dependent_sections() {
+        let new = BASE.replace("#1E88E5", "#8E24AA");
+        let diff = ThemeDiff::between(&config(BASE), &config(&new));
+        assert_eq!(diff.sections().collect::<Vec<_>>(), ["button", "palette"]);
+    }
+
+    #[test]
+    fn removed_section_is_reported() {
+        let new = BASE.replace("[button]\nbackground = \"$accent\"\n", "");
+        let diff = ThemeDiff::between(&config(BASE), &config(&new));
+        assert!(diff.changed("button"));
+        assert!(!diff.changed("palette"));
+    }
+
+    #[test]
+    fn derived_config_differs_everywhere() {
+        let base = config(BASE);
+        let diff = ThemeDiff::between(&base, &base.derive_dark());
+        assert!(diff.changed("palette"));
+        assert!(diff.changed("button"));
+    }
+
+    #[test]
+    fn fingerprint_follows_resolved_values() {
+        let base = config(BASE);
+        assert_eq!(base.fingerprint(), config(BASE).fingerprint());
+
+        let renamed = BASE.replace("accent", "brand");
+        assert_eq!(base.fingerprint(), config(&renamed).fingerprint());
+
+        let recolored = BASE.replace("#1E88E5", "#8E24AA");
+        assert_ne!(base.fingerprint(), config(&recolored).fingerprint());
+        assert_ne!(base.fingerprint(), base.derive_dark().fingerprint());
+    }
+
+    #[test]
+    fn approx_eq_tolerates_float_rounding_only() {
+        let base = config(BASE);
+        let nudged = config(&BASE.replace("background = \"$accent\"", "background = \"$accent\"\nborder-width = 1e-7"));
+        assert!(base.approx_eq(&config(BASE), 0.0));
+        assert!(base.approx_eq(&nudged, 1e-6));
+        assert!(!base.approx_eq(&nudged, 0.0));
+        assert!(!base.approx_eq(&config(&BASE.replace("#1E88E5", "#1E88E6")), 1e-3));
+        assert!(!base.approx_eq(&config(&BASE.replace("\"Base\"", "\"Other\"")), 1.0));
+    }
+
+    #[test]
+    fn approx_eq_compares_gradients_and_variants() {
+        let gradient = |offset: &str| {
+            let stops = format!("[{{ offset = 0.0, color = \"#000\" }}, {{ offset = {offset}, color = \"$accent\" }}]");
+            config(&format!("{BASE}\n[container.background]\nangle = 90.0\nstops = {stops}\n"))
+        };
+        assert!(gradient("1.0").approx_eq(&gradient("0.99999"), 1e-4));
+        assert!(!gradient("1.0").approx_eq(&gradient("0.5"), 1e-4));
+
+        let variant = |width: &str| config(&format!("{BASE}\n[button.variants.outline]\nborder-width = {width}\n"));
+        assert!(variant("1.0").approx_eq(&variant("1.0"), 0.0));
+        assert!(!variant("1.0").approx_eq(&variant("2.0"), 0.5));
+        assert!(!variant("1.0").approx_eq(&config(BASE), 1.0));
+    }
+}
@@ -0,0 +1,246 @@
+//! Editing theme files in place, keeping their comments and layout.
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use iced_core::Color;
+use toml_edit::{DocumentMut, InlineTable, Item, Table, TableLike, Value};
+
+use crate::ThemeConfig;
+use crate::color::HexColor;
+use crate::error::{Error, Reason};
+
+/// A theme file opened for editing, for theme-editor apps that change
+/// values programmatically and write the file back.
+///
+/// Only the edited values change: comments, key order, blank lines, and
+/// the formatting of everything else survive the round trip, and a
+/// replaced value keeps the comment after it.
+///
+/// ```
+/// # use iced_themer::ThemeDocument;
+/// let mut doc: ThemeDocument = "[button]\n# brand color\nbackground = \"#1E88E5\"  # keep in sync\n"
+///     .parse()
+///     .unwrap();
+/// doc.set("button.background", "#8E24AA").unwrap();
+/// doc.set("button.hovered.border-width", 2.0).unwrap();
+/// assert_eq!(
+///     doc.to_string(),
+///     "[button]\n# brand color\nbackground = \"#8E24AA\"  # keep in sync\n\n\
+///      [button.hovered]\nborder-width = 2.0\n"
+/// );
+/// ```
+///
+/// Paths are dotted keys as they appear in the file, like
+/// `"button.hovered.background"`; variables are edited at their definition,
+/// `"variables.accent"`.
+#[derive(Debug, Clone, Default)]
+pub struct ThemeDocument {
+    doc: DocumentMut,
+}
+
+impl ThemeDocument {
+    /// Opens the theme file at `path`.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, Error> {
+        fs::read_to_string(path)?.parse()
+    }
+
+    /// Writes the document to `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        Ok(fs::write(path, self.doc.to_string())?)
+    }
+
+    /// The value at `path`, as written: a color is still its string, and a
+    /// `$variable` reference isn't resolved.
+    pub fn get(&self, path: &str) -> Option<&Value> {
+        let mut item = self.doc.as_item();
+        for key in path.split('.') {
+            item = item.as_table_like()?.get(key)?;
+        }
+        item.as_value()
+    }
+
+    /// Sets the value at `path`, adding it, and any tables on the way, if
+    /// the file doesn't have it yet. A replaced value keeps its comment.
+    ///
+    /// Fails if a key on the way to `path` holds something other than a
+    /// table.
+    pub fn set(&mut self, path: &str, value: impl Into<Value>) -> Result<(), Error> {
+        let mut value = value.into();
+        let (parents, leaf) = path.rsplit_once('.').map_or((None, path), |(p, l)| (Some(p), l));
+        let (table, inline) = self.table_mut(parents, path)?;
+        // Replacing in place, rather than inserting, keeps the key's own
+        // formatting and the comments above it.
+        match table.get_mut(leaf) {
+            Some(old) => {
+                if let Some(decor) = old.as_value().map(|v| v.decor().clone()) {
+                    *value.decor_mut() = decor;
+                }
+                *old = Item::Value(value);
+            }
+            None => {
+                table.insert(leaf, Item::Value(value));
+                // Re-spaces `{ a = 1 , b = 2 }` left by appending to an
+                // inline table.
+                if inline {
+                    table.fmt();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Sets the color at `path`, written as `"#RRGGBB"`, or `"#RRGGBBAA"`
+    /// when it is translucent.
+    pub fn set_color(&mut self, path: &str, color: Color) -> Result<(), Error> {
+        self.set(path, HexColor(color).to_string())
+    }
+
+    /// Removes the value or table at `path`, returning whether there was one.
+    pub fn remove(&mut self, path: &str) -> bool {
+        let (parents, leaf) = path.rsplit_once('.').map_or((None, path), |(p, l)| (Some(p), l));
+        let mut item = self.doc.as_item_mut();
+        for key in parents.into_iter().flat_map(|p| p.split('.')) {
+            match item.as_table_like_mut().and_then(|t| t.get_mut(key)) {
+                Some(next) => item = next,
+                None => return false,
+            }
+        }
+        item.as_table_like_mut().and_then(|t| t.remove(leaf)).is_some()
+    }
+
+    /// Loads the theme as it stands, edits included.
+    ///
+    /// Like [`ThemeConfig::from_str`], this can't follow `extends`; save the
+    /// document and load it with [`ThemeConfig::from_file`] for that.
+    pub fn config(&self) -> Result<ThemeConfig, Error> {
+        self.doc.to_string().parse()
+    }
+
+    /// The table at the dotted path `parents` (the document root for
+    /// `None`), created where missing, and whether it is an inline table.
+    /// `path` is the full path being set, for errors.
+    fn table_mut(&mut self, parents: Option<&str>, path: &str) -> Result<(&mut dyn TableLike, bool), Error> {
+        let mut item = self.doc.as_item_mut();
+        let mut walked = String::new();
+        for key in parents.into_iter().flat_map(|p| p.split('.')) {
+            if !walked.is_empty() {
+                walked.push('.');
+            }
+            walked.push_str(key);
+            let inline = item.is_inline_table();
+            let Some(table) = item.as_table_like_mut() else {
+                return Err(not_a_table(path, &walked));
+            };
+            item = table.entry(key).or_insert_with(|| {
+                if inline {
+                    return Item::Value(Value::InlineTable(InlineTable::new()));
+                }
+                let mut table = Table::new();
+                table.set_implicit(true);
+                Item::Table(table)
+            });
+        }
+        let inline = item.is_inline_table();
+        item.as_table_like_mut().map(|table| (table, inline)).ok_or_else(|| not_a_table(path, &walked))
+    }
+}
+
+fn not_a_table(path: &str, key: &str) -> Error {
+    Error::InvalidValue {
+        field: path.to_string(),
+        value: key.to_string(),
+        reason: Reason::Other(format!("`{key}` is not a table")),
+    }
+}
+
+impl FromStr for ThemeDocument {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        // Parsing with `toml` first reports syntax errors the way loading
+        // the theme does.
+        toml::from_str::<toml::Table>(s)?;
+        let doc = s.parse::<DocumentMut>().map_err(|err| Error::InvalidValue {
+            field: String::new(),
+            value: String::new(),
+            reason: Reason::Other(err.to_string()),
+        })?;
+        Ok(ThemeDocument { doc })
+    }
+}
+
+impl fmt::Display for ThemeDocument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.doc.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const THEME: &str = r##"# Steam-ish
+name     = "Steam"
+metadata = { author = "Valve" }
+
+[variables]
+accent = "#66C0F4"  # brand blue
+
+[palette]
+background = "#1B2838"
+text       = "#C7D5E0"
+primary    = "$accent"
+success    = "#4CAF50"
+warning    = "#FFC107"
+danger     = "#F44336"
+
+[button]
+background = "$accent"
+"##;
+
+    #[test]
+    fn edits_keep_comments_and_alignment() {
+        let mut doc: ThemeDocument = THEME.parse().unwrap();
+        doc.set_color("variables.accent", Color::from_rgb8(0x8E, 0x24, 0xAA)).unwrap();
+        doc.set("name", "Steam Purple").unwrap();
+        let text = doc.to_string();
+        assert!(text.starts_with("# Steam-ish\nname     = \"Steam Purple\"\n"));
+        assert!(text.contains("accent = \"#8E24AA\"  # brand blue\n"));
+        assert!(text.contains("text       = \"#C7D5E0\"\n"));
+
+        let config = doc.config().unwrap();
+        assert_eq!(config.name(), "Steam Purple");
+        assert_eq!(config.theme().palette().primary, Color::from_rgb8(0x8E, 0x24, 0xAA));
+    }
+
+    #[test]
+    fn set_creates_tables_and_follows_inline_ones() {
+        let mut doc: ThemeDocument = THEME.parse().unwrap();
+        doc.set("metadata.description", "Dark blue").unwrap();
+        doc.set("slider.rail.width", 4.0).unwrap();
+        assert_eq!(doc.get("metadata.description").and_then(Value::as_str), Some("Dark blue"));
+        assert!(doc.to_string().contains("metadata = { author = \"Valve\", description = \"Dark blue\" }"));
+        assert!(doc.to_string().ends_with("\n[slider.rail]\nwidth = 4.0\n"));
+
+        let err = doc.set("name.first", "x").err().unwrap();
+        assert_eq!(err.field(), Some("name.first"), "got: {err}");
+    }
+
+    #[test]
+    fn remove_reports_whether_anything_was_there() {
+        let mut doc: ThemeDocument = THEME.parse().unwrap();
+        assert!(doc.remove("button"));
+        assert!(!doc.remove("button.background"));
+        assert!(!doc.remove("missing.key"));
+        assert!(doc.get("button.background").is_none());
+        assert!(doc.config().unwrap().button().is_none());
+    }
+
+    #[test]
+    fn syntax_errors_are_parse_errors() {
+        assert!(matches!("[button\n".parse::<ThemeDocument>(), Err(Error::Parse(_))));
+    }
+}
@@ -1,6 +1,19 @@
+use std::fmt;
 use std::io;
 
 /// Errors that can occur when loading or parsing a theme.
+///
+/// Errors about a specific value carry its dotted key path (e.g.
+/// `"button.hovered.background"`), the offending value as written, and a typed
+/// [`Reason`], so applications can build their own diagnostics:
+///
+/// ```
+/// # use iced_themer::{Error, Reason, ThemeConfig};
+/// let err = "[palette]\nbackground = \"#12\"".parse::<ThemeConfig>().err().unwrap();
+/// assert_eq!(err.section(), Some("palette"));
+/// assert_eq!(err.field(), Some("palette.background"));
+/// assert!(matches!(err.reason(), Some(Reason::HexDigitCount(2))));
+/// ```
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     /// Failed to read the theme file.
@@ -16,6 +29,141 @@ pub enum Error {
     InvalidColor {
         field: String,
         value: String,
-        reason: String,
+        reason: Reason,
     },
+
+    /// A non-color value was out of range or otherwise invalid.
+    #[error("invalid value for `{field}`: {value} ({reason})")]
+    InvalidValue {
+        field: String,
+        value: String,
+        reason: Reason,
+    },
+}
+
+impl Error {
+    /// The dotted key path of the offending value, e.g. `"button.hovered.background"`.
+    pub fn field(&self) -> Option<&str> {
+        match self {
+            Error::InvalidColor { field, .. } | Error::InvalidValue { field, .. } => Some(field),
+            Error::Io(_) | Error::Parse(_) => None,
+        }
+    }
+
+    /// The top-level section of [`field()`](Self::field), e.g. `"button"`.
+    pub fn section(&self) -> Option<&str> {
+        self.field().and_then(|field| field.split('.').next())
+    }
+
+    /// The offending value as written in the theme.
+    pub fn value(&self) -> Option<&str> {
+        match self {
+            Error::InvalidColor { value, .. } | Error::InvalidValue { value, .. } => Some(value),
+            Error::Io(_) | Error::Parse(_) => None,
+        }
+    }
+
+    /// Why the value was rejected.
+    pub fn reason(&self) -> Option<&Reason> {
+        match self {
+            Error::InvalidColor { reason, .. } | Error::InvalidValue { reason, .. } => Some(reason),
+            Error::Io(_) | Error::Parse(_) => None,
+        }
+    }
+}
+
+/// Why a value in a theme was rejected.
+///
+/// The [`Display`](fmt::Display) impl gives an English message; match on the
+/// variants to produce your own.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum Reason {
+    /// A color without a `#` prefix that isn't a named color either.
+    MissingHashPrefix,
+    /// A hex color with a digit count other than 3, 6, or 8.
+    HexDigitCount(usize),
+    /// A non-hex character at this position after the `#`.
+    InvalidHexDigit(usize),
+    /// A `$variable` or `@slot` reference with no definition.
+    UndefinedVariable(String),
+    /// Variables that reference each other in a loop.
+    CyclicVariables(Vec<String>),
+    /// A value that must be a string, like a `[variables]` entry.
+    NotAString,
+    /// A value that must be a table.
+    NotATable,
+    /// A string that looks like an expression but isn't a well-formed call.
+    MalformedExpression,
+    /// An expression calling a function that doesn't exist.
+    UnknownFunction(String),
+    /// An expression calling a function with the wrong number of arguments.
+    ArgumentCount {
+        function: String,
+        expected: usize,
+        got: usize,
+    },
+    /// An expression argument that should be a percentage like `20%`.
+    InvalidPercentage(String),
+    /// An expression argument that should be an angle like `180deg`.
+    InvalidAngle(String),
+    /// An expression argument that should be a number like `0.4`.
+    InvalidNumber(String),
+    /// A number outside its allowed range.
+    OutOfRange { min: f32, max: f32 },
+    /// A reference to a named definition (gradient, shadow, border) that
+    /// doesn't exist.
+    UndefinedPreset { kind: String, name: String },
+    /// A key that isn't one of the expected fields.
+    UnknownField { expected: Vec<String> },
+    /// An `extended.<group>.<shade>` reference to a shade iced doesn't generate.
+    UnknownExtendedColor,
+    /// A `[font] family` that isn't installed, so iced uses `fallback`.
+    UnavailableFont { fallback: String },
+    /// A `[script]` that failed to compile or run, with the engine's message.
+    Script(String),
+    /// An `extends` path that is absolute, contains `..`, or leads outside
+    /// the theme's root directory.
+    PathOutsideRoot,
+    /// Theme files that extend each other in a loop.
+    CyclicExtends(Vec<String>),
+    /// Anything else, such as a value of the wrong type, described by the
+    /// underlying parser.
+    Other(String),
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Reason::MissingHashPrefix => write!(f, "expected '#' prefix or a named color"),
+            Reason::HexDigitCount(n) => write!(f, "expected 3, 6, or 8 hex digits after '#', got {n}"),
+            Reason::InvalidHexDigit(pos) => write!(f, "invalid hex digit at position {pos}"),
+            Reason::UndefinedVariable(name) => write!(f, "undefined variable `{name}`"),
+            Reason::CyclicVariables(names) => {
+                let names: Vec<String> = names.iter().map(|n| format!("`${n}`")).collect();
+                write!(f, "cyclic variable references: {}", names.join(", "))
+            }
+            Reason::NotAString => write!(f, "must be a string value"),
+            Reason::NotATable => write!(f, "must be a table"),
+            Reason::MalformedExpression => write!(f, "expected a function call like `darken($primary, 20%)`"),
+            Reason::UnknownFunction(name) => write!(f, "unknown color function `{name}`"),
+            Reason::ArgumentCount { function, expected, got } => {
+                write!(f, "`{function}` expects {expected} argument(s), got {got}")
+            }
+            Reason::InvalidPercentage(s) => write!(f, "expected a percentage 0–100 like `20%`, got `{s}`"),
+            Reason::InvalidAngle(s) => write!(f, "expected an angle like `180deg`, got `{s}`"),
+            Reason::InvalidNumber(s) => write!(f, "expected a number like `0.4`, got `{s}`"),
+            Reason::OutOfRange { min, max } => write!(f, "must be in {min:?}..={max:?}"),
+            Reason::UndefinedPreset { kind, name } => write!(f, "undefined {kind} `{name}`"),
+            Reason::UnknownField { expected } => {
+                write!(f, "unknown field; expected one of: {}", expected.join(", "))
+            }
+            Reason::UnknownExtendedColor => write!(f, "no such extended palette color"),
+            Reason::UnavailableFont { fallback } => write!(f, "font family is not installed, falling back to `{fallback}`"),
+            Reason::Script(message) => write!(f, "script error: {message}"),
+            Reason::PathOutsideRoot => write!(f, "must be a relative path inside the theme directory, without `..`"),
+            Reason::CyclicExtends(files) => write!(f, "theme files extend each other: {}", files.join(" -> ")),
+            Reason::Other(message) => f.write_str(message),
+        }
+    }
 }
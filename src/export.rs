@@ -0,0 +1,196 @@
+//! Writing a loaded theme back out as TOML, normalized so that exporting the
+//! same theme twice gives the same text.
+
+use iced_core::Color;
+use toml::{Table, Value};
+
+use crate::ThemeConfig;
+use crate::color::HexColor;
+use crate::compat;
+
+/// Sections written first, in this order; the others follow alphabetically.
+const LEADING_SECTIONS: &[&str] = &["metadata", "palette", "font"];
+
+/// Sections whose every key is a color.
+const COLOR_SECTIONS: &[&str] = &["palette", "ansi", "syntax"];
+
+impl ThemeConfig {
+    /// The theme as a self-contained TOML document, for saving a theme built
+    /// or changed in code, like one from [`with_accent`](Self::with_accent)
+    /// or [`derive_dark`](Self::derive_dark):
+    ///
+    /// ```
+    /// # use iced_themer::ThemeConfig;
+    /// let config: ThemeConfig = "[variables]\naccent = \"#1e88e5\"\n\n[palette]\nbackground = \"#fafafa\"\n\
+    ///     text = \"#222\"\nprimary = \"$accent\"\nsuccess = \"#43A047\"\nwarning = \"#FB8C00\"\n\
+    ///     danger = \"#E53935\"\n\n[button]\nbackground = \"$accent\"\n".parse().unwrap();
+    /// let exported = config.to_toml();
+    /// assert!(exported.starts_with("name = \"Custom\"\n\n[palette]\nbackground = \"#FAFAFA\"\n"));
+    /// assert!(exported.ends_with("[button]\nbackground = \"#1E88E5\"\n"));
+    /// ```
+    ///
+    /// The document is flattened: variables, presets, and palette references
+    /// are written as the values they resolve to, and derivations are applied
+    /// to the colors. It is also normalized, so exports of similar themes diff
+    /// cleanly: top-level keys come first, then `[metadata]`, `[palette]`,
+    /// and `[font]`, then the other sections alphabetically; keys within a
+    /// table are sorted; and hex colors are written as uppercase `#RRGGBB`,
+    /// or `#RRGGBBAA` when translucent.
+    pub fn to_toml(&self) -> String {
+        let mut root = self.resolved.as_table().cloned().unwrap_or_default();
+        let derive = |color| self.derivations.iter().fold(color, |c, d| d.map(c));
+        for (key, value) in root.iter_mut() {
+            normalize(key, value, COLOR_SECTIONS.contains(&key.as_str()), &derive);
+        }
+
+        // The palette comes from the theme itself, so a config built from an
+        // iced theme, with no TOML behind it, exports its colors too.
+        let palette = root.entry("palette").or_insert_with(|| Value::Table(Table::new()));
+        if let Some(palette) = palette.as_table_mut() {
+            for (slot, color) in compat::palette_slots(&self.theme.palette()) {
+                palette.insert(slot.to_string(), Value::String(HexColor(color).to_string()));
+            }
+            for (slot, color) in &self.custom_slots {
+                palette.insert(slot.clone(), Value::String(HexColor(*color).to_string()));
+            }
+        }
+        root.insert("name".to_string(), Value::String(self.name.clone()));
+        write(root)
+    }
+}
+
+/// Rewrites the color field `key` under `value`, and every color field
+/// nested in it, as uppercase `#RRGGBB` or `#RRGGBBAA` after passing it
+/// through `map`. A color field holds any color form the theme accepts: a
+/// string like `"#abc"` or `"transparent"`, a `0xRRGGBB` integer, or an
+/// `[r, g, b, a]` array. Every key of a section in `colors` is one.
+fn normalize(key: &str, value: &mut Value, colors: bool, map: &impl Fn(Color) -> Color) {
+    if let Value::Table(table) = value {
+        for (key, item) in table.iter_mut() {
+            normalize(key, item, colors, map);
+        }
+    } else if colors || is_color_field(key) {
+        if let Some(color) = color(value) {
+            *value = Value::String(HexColor(map(color)).to_string());
+        }
+    } else if let Value::Array(items) = value {
+        for item in items {
+            normalize(key, item, colors, map);
+        }
+    }
+}
+
+/// Whether `key` names a color in a widget section, like `background`,
+/// `color`, or `border-color`.
+fn is_color_field(key: &str) -> bool {
+    key == "background" || key == "color" || key.ends_with("-color")
+}
+
+/// The color a color field's value holds, read as [`HexColor`] reads it.
+fn color(value: &Value) -> Option<Color> {
+    value.clone().try_into::<HexColor>().ok().map(|color| color.0)
+}
+
+/// Serializes `root`: its plain values, then its sections in
+/// [`LEADING_SECTIONS`] order.
+fn write(root: Table) -> String {
+    let (mut sections, values): (Vec<_>, Vec<_>) = root.into_iter().partition(|(_, value)| value.is_table());
+    sections.sort_by_key(|(key, _)| {
+        let leading = LEADING_SECTIONS.iter().position(|s| s == key).unwrap_or(LEADING_SECTIONS.len());
+        (leading, key.clone())
+    });
+    let mut out = to_string(values.into_iter().collect());
+    for section in sections {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(&to_string(Table::from_iter([section])));
+    }
+    out
+}
+
+fn to_string(table: Table) -> String {
+    toml::to_string(&table).expect("a TOML table always serializes")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iced_widget::button::Status;
+
+    const THEME: &str = r##"
+name = "Paper"
+
+[variables]
+accent = "#1e88e5"
+
+[button]
+background = "$accent"
+shadow     = { color = "#00000088", blur = 4 }
+
+[palette]
+background = "#fafafa"
+text       = "#222"
+primary    = "$accent"
+success    = "#43A047"
+warning    = "#FB8C00"
+danger     = "#E53935"
+info       = "#0288d1"
+
+[metadata]
+author = "Someone"
+
+[button.hovered]
+border-color = "extended.primary.strong"
+"##;
+
+    #[test]
+    fn exports_are_ordered_and_canonical() {
+        let config: ThemeConfig = THEME.parse().unwrap();
+        let exported = config.to_toml();
+        let headers: Vec<&str> = exported.lines().filter(|line| line.starts_with('[')).collect();
+        assert_eq!(headers, ["[metadata]", "[palette]", "[button]", "[button.hovered]"]);
+        assert!(exported.starts_with("name = \"Paper\"\n\n[metadata]\n"));
+        assert!(exported.contains("text = \"#222222\"\n"));
+        assert!(exported.contains("info = \"#0288D1\"\n"));
+        assert!(exported.contains("shadow-color = \"#00000088\"\n"));
+        assert!(!exported.contains('$') && !exported.contains("extended."));
+
+        let reloaded: ThemeConfig = exported.parse().unwrap();
+        assert_eq!(reloaded.to_toml(), exported);
+        assert_eq!(reloaded.theme().palette(), config.theme().palette());
+    }
+
+    #[test]
+    fn every_color_form_is_normalized_and_derived() {
+        let theme = format!(
+            "{THEME}\n[container]\nborder-color = 0x1e88e5\nshadow-color = [0.0, 0.0, 0.0, 0.5]\n\
+             text-color = \"transparent\"\nshadow-offset = [0, 2]\n"
+        );
+        let config: ThemeConfig = theme.parse().unwrap();
+        let exported = config.to_toml();
+        assert!(exported.contains("border-color = \"#1E88E5\"\n"));
+        assert!(exported.contains("shadow-color = \"#00000080\"\n"));
+        assert!(exported.contains("text-color = \"#00000000\"\n"));
+        assert!(exported.parse::<ThemeConfig>().is_ok());
+
+        let dark = config.derive_dark().to_toml();
+        let inverted = HexColor(crate::color::invert_lightness(Color::from_rgb8(0x1e, 0x88, 0xe5)));
+        assert!(dark.contains(&format!("border-color = \"{inverted}\"\n")));
+    }
+
+    #[test]
+    fn exports_carry_derivations() {
+        let dark = THEME.parse::<ThemeConfig>().unwrap().derive_dark();
+        let reloaded: ThemeConfig = dark.to_toml().parse().unwrap();
+        assert_eq!(reloaded.name(), "Paper Dark");
+        assert!(reloaded.is_dark());
+        let button = |config: &ThemeConfig| config.button().unwrap().style_fn()(&config.theme(), Status::Active);
+        let (expected, got) = (button(&dark).background, button(&reloaded).background);
+        let hex = |background| match background {
+            Some(iced_core::Background::Color(color)) => HexColor(color).to_string(),
+            other => panic!("expected a color, got {other:?}"),
+        };
+        assert_eq!(hex(got), hex(expected));
+    }
+}
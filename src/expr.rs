@@ -2,60 +2,204 @@
 //!
 //! Turns strings like `"darken($primary, 20%)"` into resolved hex color strings.
 //! All `$variable` arguments must already be resolved to hex strings in `vars`
-//! before calling [`evaluate`].
+//! before evaluating.
+//!
+//! Channel functions like `hue($primary)` return numbers instead, formatted so
+//! they can be passed straight to other functions: `hsl(hue($primary), 60%, 40%)`.
+//! So do the arithmetic functions, for numeric fields like gradient stop
+//! offsets: `add($fade-point, 0.2)`.
+//!
+//! `blend` takes a bracketed list of colors, each with an optional weight:
+//! `blend([$brand 2, $accent 1, #808080 1])`.
+//!
+//! With the `expr` feature, [`evaluate_many`] exposes the evaluator to apps.
 
 use std::collections::HashMap;
 
 use farver::{Color as _, deg, percent, rgb};
 use iced_core::Color;
 
-/// Evaluates a color transformation expression and returns a hex color string.
+use crate::error::Reason;
+
+/// Evaluates a color transformation expression and returns a hex color string,
+/// or a number for the channel functions.
 ///
 /// `vars` must contain fully-resolved hex strings (no remaining `$refs`).
 /// Supports: `darken`, `lighten`, `saturate`, `desaturate`, `tint`, `shade`,
-/// `greyscale` / `grayscale`, `spin`, `mix`.
-pub(crate) fn evaluate(s: &str, vars: &HashMap<String, String>) -> Result<String, String> {
-    let s = s.trim();
-    let (fn_name, args_str) = parse_call(s)?;
-    let args: Vec<&str> = args_str.split(',').map(str::trim).collect();
-    apply(fn_name, &args, vars)
+/// `greyscale` / `grayscale`, `spin`, `mix`, `mix-linear`, `blend`, `multiply`, `screen`,
+/// `overlay`, `best-contrast`, `hsl`, the
+/// channel functions `red`, `green`, `blue`, `alpha`, `hue`, `saturation`,
+/// `lightness`, and the arithmetic functions `add`, `sub`, `mul`, `div`, `min`,
+/// `max`. Arguments may themselves be function calls.
+pub(crate) fn evaluate(s: &str, vars: &HashMap<String, String>) -> Result<String, Reason> {
+    Evaluator::new(vars).evaluate(s)
+}
+
+/// Evaluates a batch of expressions against the same variables, for theme
+/// editors offering an expression playground.
+///
+/// ```
+/// use std::collections::HashMap;
+/// use iced_themer::expr::evaluate_many;
+///
+/// let vars = HashMap::from([
+///     ("brand".to_string(), "#1E88E5".to_string()),
+///     ("palette.danger".to_string(), "#E53935".to_string()),
+/// ]);
+/// let results = evaluate_many(&["darken($brand, 10%)", "red(@danger)", "bake($brand)"], &vars);
+/// assert_eq!(results[1], Ok("229".to_string()));
+/// assert!(results[0].is_ok() && results[2].is_err());
+/// ```
+///
+/// Results come back in the order of `exprs`, one per expression, so a bad
+/// one doesn't hide the others. `vars` maps names to resolved values, as
+/// [`ThemeConfig::variables`](crate::ThemeConfig::variables) returns them;
+/// `@slot` references look up `palette.<slot>`. Colors and calls are parsed
+/// once for the whole batch, so expressions sharing a subexpression like
+/// `lighten($brand, 10%)` only work it out once.
+#[cfg(feature = "expr")]
+pub fn evaluate_many(exprs: &[&str], vars: &HashMap<String, String>) -> Vec<Result<String, Reason>> {
+    let mut evaluator = Evaluator::new(vars);
+    exprs.iter().map(|s| evaluator.evaluate(s)).collect()
+}
+
+/// Evaluates expressions against one set of variables, remembering every
+/// color it parses and every call it works out.
+struct Evaluator<'a> {
+    vars:   &'a HashMap<String, String>,
+    colors: HashMap<String, Color>,
+    calls:  HashMap<String, Result<String, Reason>>,
+}
+
+impl<'a> Evaluator<'a> {
+    fn new(vars: &'a HashMap<String, String>) -> Self {
+        Evaluator { vars, colors: HashMap::new(), calls: HashMap::new() }
+    }
+
+    fn evaluate(&mut self, s: &str) -> Result<String, Reason> {
+        let s = s.trim();
+        if let Some(result) = self.calls.get(s) {
+            return result.clone();
+        }
+        let result = self.evaluate_call(s);
+        self.calls.insert(s.to_string(), result.clone());
+        result
+    }
+
+    fn evaluate_call(&mut self, s: &str) -> Result<String, Reason> {
+        let (fn_name, args_str) = parse_call(s)?;
+        let args = split_args(args_str)?
+            .into_iter()
+            .map(|arg| if is_call(arg) { self.evaluate(arg) } else { Ok(arg.to_string()) })
+            .collect::<Result<Vec<_>, _>>()?;
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        apply(s, fn_name, &args, self)
+    }
+
+    /// A color argument, parsed once per evaluator.
+    fn color(&mut self, s: &str) -> Result<Color, Reason> {
+        if let Some(color) = self.colors.get(s) {
+            return Ok(*color);
+        }
+        let color = resolve_color(s, self.vars)?;
+        self.colors.insert(s.to_string(), color);
+        Ok(color)
+    }
 }
 
 // ── Parsing helpers ──────────────────────────────────────────────────────────
 
-fn parse_call(s: &str) -> Result<(&str, &str), String> {
-    let (name, rest) = s
-        .split_once('(')
-        .ok_or_else(|| format!("expected a function call, got `{s}`"))?;
-    let args = rest
-        .strip_suffix(')')
-        .ok_or_else(|| format!("missing closing `)` in `{s}`"))?;
+fn parse_call(s: &str) -> Result<(&str, &str), Reason> {
+    let (name, rest) = s.split_once('(').ok_or(Reason::MalformedExpression)?;
+    let args = rest.strip_suffix(')').ok_or(Reason::MalformedExpression)?;
     Ok((name.trim(), args))
 }
 
-fn expect_args<'a>(fn_name: &str, args: &'a [&'a str], n: usize) -> Result<&'a [&'a str], String> {
+/// Splits a call's arguments on the commas that aren't inside a nested call
+/// or a `[...]` list.
+fn split_args(s: &str) -> Result<Vec<&str>, Reason> {
+    let mut args = Vec::new();
+    let (mut depth, mut start) = (0usize, 0);
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth = depth.checked_sub(1).ok_or(Reason::MalformedExpression)?,
+            ',' if depth == 0 => {
+                args.push(s[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if depth != 0 {
+        return Err(Reason::MalformedExpression);
+    }
+    args.push(s[start..].trim());
+    Ok(args)
+}
+
+fn is_call(s: &str) -> bool {
+    s.ends_with(')') && s.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+}
+
+fn expect_args<'a>(fn_name: &str, args: &'a [&'a str], n: usize) -> Result<&'a [&'a str], Reason> {
     if args.len() == n {
         Ok(args)
     } else {
-        Err(format!(
-            "`{fn_name}` expects {n} argument(s), got {}",
-            args.len()
-        ))
+        Err(Reason::ArgumentCount {
+            function: fn_name.to_string(),
+            expected: n,
+            got: args.len(),
+        })
+    }
+}
+
+fn expect_min_args<'a>(fn_name: &str, args: &'a [&'a str], n: usize) -> Result<&'a [&'a str], Reason> {
+    if args.len() >= n {
+        Ok(args)
+    } else {
+        expect_args(fn_name, args, n)
     }
 }
 
 // ── Color argument resolution ────────────────────────────────────────────────
 
-/// Resolves a color argument: either a `$variable` reference or a literal color string.
-fn resolve_color(s: &str, vars: &HashMap<String, String>) -> Result<Color, String> {
-    let literal = if let Some(name) = s.strip_prefix('$') {
-        vars.get(name)
-            .ok_or_else(|| format!("undefined variable `${name}`"))?
-            .as_str()
-    } else {
-        s
+/// Resolves a color argument: a `$variable` or `@slot` reference, or a literal color string.
+/// A variable holding an integer is read as a `0xRRGGBB` color literal.
+fn resolve_color(s: &str, vars: &HashMap<String, String>) -> Result<Color, Reason> {
+    let Some(name) = crate::variables::reference(s) else {
+        return crate::color::parse_color(s);
+    };
+    let value = vars.get(&name).ok_or_else(|| Reason::UndefinedVariable(s.to_string()))?;
+    match value.parse().ok().and_then(crate::color::integer_color) {
+        Some(hex) => crate::color::parse_color(&hex),
+        None => crate::color::parse_color(value),
+    }
+}
+
+/// Resolves a number argument: a `$variable` reference, or a literal like
+/// `0.4`. A `deg` or `%` suffix, as the channel functions write, is returned
+/// as the number's unit.
+fn resolve_number(s: &str, vars: &HashMap<String, String>) -> Result<(f32, &'static str), Reason> {
+    let literal = resolve_literal(s, vars)?.trim();
+    let (digits, unit) = match (literal.strip_suffix("deg"), literal.strip_suffix('%')) {
+        (Some(digits), _) => (digits, "deg"),
+        (_, Some(digits)) => (digits, "%"),
+        _ => (literal, ""),
     };
-    crate::color::parse_color(literal).map_err(|e| format!("invalid color `{literal}`: {e}"))
+    let n = digits.trim().parse().map_err(|_| Reason::InvalidNumber(s.to_string()))?;
+    Ok((n, unit))
+}
+
+/// The value of a `$variable` or `@slot` argument, or the argument itself.
+fn resolve_literal<'v>(s: &'v str, vars: &'v HashMap<String, String>) -> Result<&'v str, Reason> {
+    match crate::variables::reference(s) {
+        Some(name) => vars
+            .get(&name)
+            .map(String::as_str)
+            .ok_or_else(|| Reason::UndefinedVariable(s.to_string())),
+        None => Ok(s),
+    }
 }
 
 fn to_farver(c: Color) -> farver::RGB {
@@ -68,88 +212,248 @@ fn to_farver(c: Color) -> farver::RGB {
 
 // ── Parameter parsing ────────────────────────────────────────────────────────
 
-fn parse_percent(s: &str) -> Result<farver::Ratio, String> {
-    let digits = s
+// Each parser also takes a `$variable` holding the value, like `$h` for
+// `h = "hue(@primary)"`.
+
+fn parse_percent(s: &str, vars: &HashMap<String, String>) -> Result<farver::Ratio, Reason> {
+    Ok(percent((parse_fraction(s, vars)? * 100.0).round() as u8))
+}
+
+fn parse_angle(s: &str, vars: &HashMap<String, String>) -> Result<farver::Angle, Reason> {
+    Ok(deg(parse_degrees(s, vars)?))
+}
+
+fn parse_degrees(s: &str, vars: &HashMap<String, String>) -> Result<i32, Reason> {
+    resolve_literal(s, vars)?
+        .strip_suffix("deg")
+        .and_then(|digits| digits.trim().parse::<f32>().ok())
+        .filter(|n| n.is_finite())
+        .map(|n| n.round() as i32)
+        .ok_or_else(|| Reason::InvalidAngle(s.to_string()))
+}
+
+/// A percentage as a fraction in `0.0..=1.0`.
+fn parse_fraction(s: &str, vars: &HashMap<String, String>) -> Result<f32, Reason> {
+    resolve_literal(s, vars)?
         .strip_suffix('%')
-        .ok_or_else(|| format!("expected a percentage like `20%`, got `{s}`"))?
-        .trim();
-    let n: u8 = digits
-        .parse()
-        .map_err(|_| format!("invalid percentage value `{digits}`"))?;
-    if n > 100 {
-        return Err(format!("percentage must be 0–100, got `{n}`"));
+        .and_then(|digits| digits.trim().parse::<f32>().ok())
+        .filter(|n| (0.0..=100.0).contains(n))
+        .map(|n| n / 100.0)
+        .ok_or_else(|| Reason::InvalidPercentage(s.to_string()))
+}
+
+// ── Weighted blend ───────────────────────────────────────────────────────────
+
+/// Averages the colors of a `[color weight, ...]` list in linear RGB. A
+/// missing weight counts as `1`; the colors may be calls themselves.
+fn weighted_blend(list: &str, ev: &mut Evaluator) -> Result<Color, Reason> {
+    let list = list
+        .strip_prefix('[')
+        .and_then(|l| l.strip_suffix(']'))
+        .ok_or(Reason::MalformedExpression)?;
+    let (mut sum, mut total) = ([0.0; 4], 0.0);
+    for entry in split_args(list)? {
+        let (color, weight) = match entry.rsplit_once(char::is_whitespace) {
+            Some((color, weight)) if !weight.ends_with(')') => (color.trim(), resolve_number(weight, ev.vars)?.0),
+            _ => (entry, 1.0),
+        };
+        if weight < 0.0 {
+            return Err(Reason::InvalidNumber(entry.to_string()));
+        }
+        let color = if is_call(color) { ev.evaluate(color)? } else { color.to_string() };
+        for (sum, channel) in sum.iter_mut().zip(ev.color(&color)?.into_linear()) {
+            *sum += channel * weight;
+        }
+        total += weight;
     }
-    Ok(percent(n))
+    if total <= 0.0 {
+        return Err(Reason::InvalidNumber(list.to_string()));
+    }
+    let [r, g, b, a] = sum.map(|channel| channel / total);
+    Ok(Color::from_linear_rgba(r, g, b, a))
 }
 
-fn parse_angle(s: &str) -> Result<farver::Angle, String> {
-    let digits = s
-        .strip_suffix("deg")
-        .ok_or_else(|| format!("expected an angle like `180deg`, got `{s}`"))?
-        .trim();
-    let n: i32 = digits
-        .parse()
-        .map_err(|_| format!("invalid angle value `{digits}`"))?;
-    Ok(deg(n))
+// ── Blend modes ──────────────────────────────────────────────────────────────
+
+/// Blends `top` over `base` channel by channel, as design tools' blend modes
+/// do, keeping `base`'s alpha.
+fn blend(base: Color, top: Color, mode: fn(f32, f32) -> f32) -> Color {
+    Color::from_rgba(mode(base.r, top.r), mode(base.g, top.g), mode(base.b, top.b), base.a)
+}
+
+fn multiply(base: f32, top: f32) -> f32 {
+    base * top
+}
+
+fn screen(base: f32, top: f32) -> f32 {
+    1.0 - (1.0 - base) * (1.0 - top)
+}
+
+fn overlay(base: f32, top: f32) -> f32 {
+    if base < 0.5 {
+        2.0 * base * top
+    } else {
+        1.0 - 2.0 * (1.0 - base) * (1.0 - top)
+    }
+}
+
+// ── Channel formatting ───────────────────────────────────────────────────────
+
+/// A number without a trailing `.0`, or with at most three decimals.
+fn number(n: f32) -> String {
+    let rounded = (n * 1000.0).round() / 1000.0;
+    rounded.to_string()
+}
+
+fn channel(fn_name: &str, args: &[&str], ev: &mut Evaluator) -> Result<String, Reason> {
+    let a = expect_args(fn_name, args, 1)?;
+    let color = ev.color(a[0])?;
+    let hsl = crate::color::to_hsl(color);
+    let byte = |v: f32| number((v * 255.0).round());
+    Ok(match fn_name {
+        "red" => byte(color.r),
+        "green" => byte(color.g),
+        "blue" => byte(color.b),
+        "alpha" => number(color.a),
+        "hue" => format!("{}deg", hsl.h.round()),
+        "saturation" => format!("{}%", (hsl.s * 100.0).round()),
+        _ => format!("{}%", (hsl.l * 100.0).round()),
+    })
+}
+
+// ── Arithmetic ───────────────────────────────────────────────────────────────
+
+/// Applies an arithmetic function; a result that isn't finite, like a
+/// division by zero, is reported as the whole `expr`, as are operands in
+/// different units. The result keeps its operands' unit, so
+/// `add(hue($brand), 30)` is still an angle.
+fn arithmetic(expr: &str, fn_name: &str, args: &[&str], vars: &HashMap<String, String>) -> Result<String, Reason> {
+    let a = expect_args(fn_name, args, 2)?;
+    let ((x, x_unit), (y, y_unit)) = (resolve_number(a[0], vars)?, resolve_number(a[1], vars)?);
+    let unit = match (x_unit, y_unit) {
+        (unit, "") | ("", unit) => unit,
+        (x_unit, y_unit) if x_unit == y_unit => x_unit,
+        _ => return Err(Reason::InvalidNumber(expr.to_string())),
+    };
+    let result = match fn_name {
+        "add" => x + y,
+        "sub" => x - y,
+        "mul" => x * y,
+        "div" => x / y,
+        "min" => x.min(y),
+        _ => x.max(y),
+    };
+    if result.is_finite() {
+        Ok(format!("{}{unit}", number(result)))
+    } else {
+        Err(Reason::InvalidNumber(expr.to_string()))
+    }
 }
 
 // ── Dispatch ─────────────────────────────────────────────────────────────────
 
-fn apply(fn_name: &str, args: &[&str], vars: &HashMap<String, String>) -> Result<String, String> {
+/// Applies `fn_name` to its evaluated `args`; `expr` is the call as written.
+fn apply(expr: &str, fn_name: &str, args: &[&str], ev: &mut Evaluator) -> Result<String, Reason> {
     match fn_name {
         "darken" => {
             let a = expect_args(fn_name, args, 2)?;
-            Ok(to_farver(resolve_color(a[0], vars)?)
-                .darken(parse_percent(a[1])?)
+            Ok(to_farver(ev.color(a[0])?)
+                .darken(parse_percent(a[1], ev.vars)?)
                 .to_hex())
         }
         "lighten" => {
             let a = expect_args(fn_name, args, 2)?;
-            Ok(to_farver(resolve_color(a[0], vars)?)
-                .lighten(parse_percent(a[1])?)
+            Ok(to_farver(ev.color(a[0])?)
+                .lighten(parse_percent(a[1], ev.vars)?)
                 .to_hex())
         }
         "saturate" => {
             let a = expect_args(fn_name, args, 2)?;
-            Ok(to_farver(resolve_color(a[0], vars)?)
-                .saturate(parse_percent(a[1])?)
+            Ok(to_farver(ev.color(a[0])?)
+                .saturate(parse_percent(a[1], ev.vars)?)
                 .to_hex())
         }
         "desaturate" => {
             let a = expect_args(fn_name, args, 2)?;
-            Ok(to_farver(resolve_color(a[0], vars)?)
-                .desaturate(parse_percent(a[1])?)
+            Ok(to_farver(ev.color(a[0])?)
+                .desaturate(parse_percent(a[1], ev.vars)?)
                 .to_hex())
         }
         "tint" => {
             let a = expect_args(fn_name, args, 2)?;
-            Ok(to_farver(resolve_color(a[0], vars)?)
-                .tint(parse_percent(a[1])?)
+            Ok(to_farver(ev.color(a[0])?)
+                .tint(parse_percent(a[1], ev.vars)?)
                 .to_hex())
         }
         "shade" => {
             let a = expect_args(fn_name, args, 2)?;
-            Ok(to_farver(resolve_color(a[0], vars)?)
-                .shade(parse_percent(a[1])?)
+            Ok(to_farver(ev.color(a[0])?)
+                .shade(parse_percent(a[1], ev.vars)?)
                 .to_hex())
         }
         "greyscale" | "grayscale" => {
             let a = expect_args(fn_name, args, 1)?;
-            Ok(to_farver(resolve_color(a[0], vars)?).greyscale().to_hex())
+            Ok(to_farver(ev.color(a[0])?).greyscale().to_hex())
         }
         "spin" => {
             let a = expect_args(fn_name, args, 2)?;
-            Ok(to_farver(resolve_color(a[0], vars)?)
-                .spin(parse_angle(a[1])?)
+            Ok(to_farver(ev.color(a[0])?)
+                .spin(parse_angle(a[1], ev.vars)?)
                 .to_hex())
         }
         "mix" => {
             let a = expect_args(fn_name, args, 3)?;
-            let c1 = to_farver(resolve_color(a[0], vars)?);
-            let c2 = to_farver(resolve_color(a[1], vars)?);
-            Ok(c1.mix(c2, parse_percent(a[2])?).to_hex())
+            let c1 = to_farver(ev.color(a[0])?);
+            let c2 = to_farver(ev.color(a[1])?);
+            Ok(c1.mix(c2, parse_percent(a[2], ev.vars)?).to_hex())
+        }
+        "mix-linear" => {
+            // Like `mix`, the percentage is the weight of the first color.
+            let a = expect_args(fn_name, args, 3)?;
+            let weight = parse_fraction(a[2], ev.vars)?;
+            let color = crate::color::mix(ev.color(a[1])?, ev.color(a[0])?, weight);
+            Ok(crate::color::HexColor(color).to_string())
+        }
+        "blend" => {
+            let a = expect_args(fn_name, args, 1)?;
+            Ok(crate::color::HexColor(weighted_blend(a[0], ev)?).to_string())
+        }
+        "multiply" | "screen" | "overlay" => {
+            let a = expect_args(fn_name, args, 2)?;
+            let mode = match fn_name {
+                "multiply" => multiply,
+                "screen" => screen,
+                _ => overlay,
+            };
+            let color = blend(ev.color(a[0])?, ev.color(a[1])?, mode);
+            Ok(crate::color::HexColor(color).to_string())
+        }
+        "best-contrast" => {
+            let a = expect_min_args(fn_name, args, 2)?;
+            let background = ev.color(a[0])?;
+            let mut best = (ev.color(a[1])?, 0.0);
+            for arg in &a[1..] {
+                let candidate = ev.color(arg)?;
+                let ratio = crate::color::contrast_ratio(background, candidate);
+                if ratio > best.1 {
+                    best = (candidate, ratio);
+                }
+            }
+            Ok(crate::color::HexColor(best.0).to_string())
+        }
+        "hsl" => {
+            let a = expect_args(fn_name, args, 3)?;
+            let hsl = crate::color::Hsl {
+                h: parse_degrees(a[0], ev.vars)? as f32,
+                s: parse_fraction(a[1], ev.vars)?,
+                l: parse_fraction(a[2], ev.vars)?,
+                a: 1.0,
+            };
+            Ok(crate::color::HexColor(crate::color::from_hsl(hsl)).to_string())
         }
-        _ => Err(format!("unknown color function `{fn_name}`")),
+        "red" | "green" | "blue" | "alpha" | "hue" | "saturation" | "lightness" => channel(fn_name, args, ev),
+        "add" | "sub" | "mul" | "div" | "min" | "max" => arithmetic(expr, fn_name, args, ev.vars),
+        _ => Err(Reason::UnknownFunction(fn_name.to_string())),
     }
 }
 
@@ -205,27 +509,150 @@ mod tests {
         assert!(result.len() == 7 || result.len() == 9, "got `{result}`");
     }
 
+    #[test]
+    fn best_contrast_picks_most_readable_candidate() {
+        let vars = [("bg".to_string(), "#1B2838".to_string())].into();
+        assert_eq!(evaluate("best-contrast($bg, #000000, #FFFFFF)", &vars).unwrap(), "#FFFFFF");
+        assert_eq!(evaluate("best-contrast(#FAFAFA, #000000, #FFFFFF)", &vars).unwrap(), "#000000");
+        assert_eq!(evaluate("best-contrast($bg, #66C0F4)", &vars).unwrap(), "#66C0F4");
+    }
+
+    #[test]
+    fn best_contrast_needs_a_candidate() {
+        let err = evaluate("best-contrast($primary)", &vars()).unwrap_err();
+        assert!(matches!(err, Reason::ArgumentCount { expected: 2, got: 1, .. }), "got: {err}");
+    }
+
+    #[test]
+    fn mix_linear_keeps_midpoints_bright() {
+        assert_eq!(evaluate("mix-linear(#FF0000, #00FF00, 50%)", &vars()).unwrap(), "#BCBC00");
+        let weighted = evaluate("mix-linear(#FFFFFF, #000000, 100%)", &vars()).unwrap();
+        assert_eq!(weighted, "#FFFFFF");
+    }
+
+    #[test]
+    fn blend_averages_weighted_colors() {
+        assert_eq!(evaluate("blend([#FF0000, #00FF00])", &vars()).unwrap(), "#BCBC00");
+        assert_eq!(evaluate("blend([#FFFFFF 3, #000000 0])", &vars()).unwrap(), "#FFFFFF");
+        let nested = evaluate("blend([$primary 2, darken($danger, 10%) 1, #808080])", &vars()).unwrap();
+        assert!(nested.starts_with('#') && nested.len() == 7, "got `{nested}`");
+    }
+
+    #[test]
+    fn blend_rejects_bad_lists() {
+        assert!(matches!(evaluate("blend($primary, $danger)", &vars()), Err(Reason::ArgumentCount { .. })));
+        assert!(matches!(evaluate("blend([$primary 0])", &vars()), Err(Reason::InvalidNumber(_))));
+        assert!(matches!(evaluate("blend([$primary -1])", &vars()), Err(Reason::InvalidNumber(_))));
+        assert!(matches!(evaluate("blend([$primary heavy])", &vars()), Err(Reason::InvalidNumber(_))));
+        assert!(matches!(evaluate("blend($primary)", &vars()), Err(Reason::MalformedExpression)));
+    }
+
+    #[test]
+    fn blend_modes() {
+        assert_eq!(evaluate("multiply(#FF8000, #808080)", &vars()).unwrap(), "#804000");
+        assert_eq!(evaluate("screen(#000000, $danger)", &vars()).unwrap(), "#F44336");
+        assert_eq!(evaluate("screen(#FFFFFF, $danger)", &vars()).unwrap(), "#FFFFFF");
+        assert_eq!(evaluate("overlay(#404040, #808080)", &vars()).unwrap(), "#404040");
+        assert_eq!(evaluate("overlay(#C0C0C0, #FFFFFF)", &vars()).unwrap(), "#FFFFFF");
+    }
+
+    #[test]
+    fn channels_extract_numbers() {
+        assert_eq!(evaluate("red($primary)", &vars()).unwrap(), "102");
+        assert_eq!(evaluate("green($primary)", &vars()).unwrap(), "192");
+        assert_eq!(evaluate("blue(#66C0F4)", &vars()).unwrap(), "244");
+        assert_eq!(evaluate("alpha(#66C0F480)", &vars()).unwrap(), "0.502");
+        assert_eq!(evaluate("hue(#FF0000)", &vars()).unwrap(), "0deg");
+        assert_eq!(evaluate("saturation(#FF0000)", &vars()).unwrap(), "100%");
+        assert_eq!(evaluate("lightness(#808080)", &vars()).unwrap(), "50%");
+    }
+
+    #[test]
+    fn channels_feed_other_functions() {
+        let same_hue = evaluate("hsl(hue($danger), 100%, 50%)", &vars()).unwrap();
+        assert_eq!(same_hue, evaluate("hsl(4deg, 100%, 50%)", &vars()).unwrap());
+        let result = evaluate("darken($primary, lightness(#333333))", &vars()).unwrap();
+        assert!(result.starts_with('#'));
+    }
+
+    #[test]
+    fn channels_work_as_numbers() {
+        assert_eq!(evaluate("add(hue(#FF0000), 30)", &vars()).unwrap(), "30deg");
+        assert_eq!(evaluate("mul(lightness(#808080), 0.5)", &vars()).unwrap(), "25%");
+        assert_eq!(evaluate("add(0.5, alpha(#00000080))", &vars()).unwrap(), "1.002");
+        let shifted = evaluate("hsl(add(hue($danger), 180), saturation($danger), 50%)", &vars()).unwrap();
+        assert_eq!(shifted, evaluate("hsl(184deg, 90%, 50%)", &vars()).unwrap());
+    }
+
+    #[test]
+    fn parameters_take_variables() {
+        let vars: HashMap<String, String> = [
+            ("primary".to_string(), "#66C0F4".to_string()),
+            ("h".to_string(), "200deg".to_string()),
+            ("amount".to_string(), "20%".to_string()),
+        ]
+        .into();
+        assert_eq!(evaluate("darken($primary, $amount)", &vars), evaluate("darken($primary, 20%)", &vars));
+        assert_eq!(evaluate("spin($primary, $h)", &vars), evaluate("spin($primary, 200deg)", &vars));
+        assert_eq!(evaluate("hsl($h, $amount, 50%)", &vars), evaluate("hsl(200deg, 20%, 50%)", &vars));
+        assert_eq!(evaluate("spin($primary, $amount)", &vars).unwrap_err(), Reason::InvalidAngle("$amount".to_string()));
+        let err = evaluate("darken($primary, $missing)", &vars).unwrap_err();
+        assert_eq!(err, Reason::UndefinedVariable("$missing".to_string()));
+    }
+
+    #[test]
+    fn unbalanced_nested_call_is_malformed() {
+        let err = evaluate("hsl(hue($danger, 100%, 50%)", &vars()).unwrap_err();
+        assert_eq!(err, Reason::MalformedExpression);
+    }
+
     #[test]
     fn unknown_function_returns_error() {
         let err = evaluate("bake($primary, 10%)", &vars()).unwrap_err();
-        assert!(err.contains("unknown color function"), "got: {err}");
+        assert_eq!(err, Reason::UnknownFunction("bake".to_string()));
     }
 
     #[test]
     fn undefined_variable_returns_error() {
         let err = evaluate("darken($missing, 10%)", &vars()).unwrap_err();
-        assert!(err.contains("undefined variable"), "got: {err}");
+        assert_eq!(err, Reason::UndefinedVariable("$missing".to_string()));
     }
 
     #[test]
     fn wrong_arg_count_returns_error() {
         let err = evaluate("darken($primary)", &vars()).unwrap_err();
-        assert!(err.contains("expects"), "got: {err}");
+        assert!(matches!(err, Reason::ArgumentCount { expected: 2, got: 1, .. }), "got: {err}");
     }
 
     #[test]
     fn percent_out_of_range_returns_error() {
         let err = evaluate("darken($primary, 150%)", &vars()).unwrap_err();
-        assert!(err.contains("percentage"), "got: {err}");
+        assert_eq!(err, Reason::InvalidPercentage("150%".to_string()));
+    }
+
+    #[cfg(feature = "expr")]
+    #[test]
+    fn evaluate_many_reuses_shared_calls() {
+        let exprs = ["darken($primary, 20%)", "mix(darken($primary, 20%), $danger, 50%)", "darken($primary)"];
+        let results = evaluate_many(&exprs, &vars());
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0], evaluate(exprs[0], &vars()));
+        assert_eq!(results[1], evaluate(exprs[1], &vars()));
+        assert!(matches!(results[2], Err(Reason::ArgumentCount { .. })));
+    }
+
+    #[test]
+    fn arithmetic_takes_numbers_and_variables() {
+        let vars: HashMap<String, String> = [("fade-point".to_string(), "0.4".to_string())].into();
+        assert_eq!(evaluate("add($fade-point, 0.2)", &vars).unwrap(), "0.6");
+        assert_eq!(evaluate("sub(1, $fade-point)", &vars).unwrap(), "0.6");
+        assert_eq!(evaluate("min(mul($fade-point, 3), 1)", &vars).unwrap(), "1");
+        assert_eq!(evaluate("div(alpha(#00000080), 2)", &vars).unwrap(), "0.251");
+        let err = evaluate("add(hue(#FF0000), 10%)", &vars).unwrap_err();
+        assert_eq!(err, Reason::InvalidNumber("add(hue(#FF0000), 10%)".to_string()));
+        let err = evaluate("div($fade-point, 0)", &vars).unwrap_err();
+        assert_eq!(err, Reason::InvalidNumber("div($fade-point, 0)".to_string()));
+        let err = evaluate("add(1, div(1, sub($fade-point, 0.4)))", &vars).unwrap_err();
+        assert_eq!(err, Reason::InvalidNumber("div(1, sub($fade-point, 0.4))".to_string()));
     }
 }
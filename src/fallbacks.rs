@@ -0,0 +1,150 @@
+//! The `[fallbacks]` table: widget sections a theme borrows from another
+//! section when it doesn't define them itself.
+//!
+//! ```toml
+//! [fallbacks]
+//! toggler = "checkbox"
+//! radio   = ["checkbox", "button"]
+//! ```
+//!
+//! A section with a list takes the first one the theme defines, and a
+//! fallback that is missing too is followed through its own fallbacks.
+//! Borrowing happens before presets and variables are expanded, so the
+//! borrowed table resolves like one written in place.
+
+use std::collections::BTreeMap;
+
+use toml::{Table, Value};
+
+use crate::error::{Error, Reason};
+use crate::style::WIDGET_SECTIONS;
+
+/// Removes `[fallbacks]` from `root` and fills in every widget section it
+/// names that `root` doesn't define from the first section in its chain
+/// that `root` does.
+pub(crate) fn apply(root: &mut Value) -> Result<(), Error> {
+    let Some(table) = root.as_table_mut() else {
+        return Ok(());
+    };
+    let Some(fallbacks) = table.remove("fallbacks") else {
+        return Ok(());
+    };
+    let Value::Table(fallbacks) = &fallbacks else {
+        return Err(invalid("fallbacks", &fallbacks, Reason::NotATable));
+    };
+
+    let mut chains = BTreeMap::new();
+    for (section, targets) in fallbacks {
+        let path = format!("fallbacks.{section}");
+        let targets = match targets {
+            Value::String(target) => vec![target.as_str()],
+            Value::Array(items) => items
+                .iter()
+                .map(|item| item.as_str().ok_or_else(|| invalid(&path, item, Reason::NotAString)))
+                .collect::<Result<_, _>>()?,
+            other => return Err(invalid(&path, other, Reason::NotAString)),
+        };
+        for name in targets.iter().copied().chain([section.as_str()]) {
+            if !WIDGET_SECTIONS.contains(&name) {
+                let expected = WIDGET_SECTIONS.iter().map(|s| s.to_string()).collect();
+                return Err(invalid(&path, &Value::String(name.to_string()), Reason::UnknownField { expected }));
+            }
+        }
+        chains.insert(section.as_str(), targets);
+    }
+
+    let borrowed: Vec<(String, Value)> = chains
+        .keys()
+        .filter(|section| !table.contains_key(**section))
+        .filter_map(|section| Some((section.to_string(), borrow(table, &chains, section, &mut Vec::new())?.clone())))
+        .collect();
+    table.extend(borrowed);
+    Ok(())
+}
+
+/// The table `section` borrows: the first of its fallbacks that `table`
+/// defines, trying each missing one's own fallbacks before the next. `seen`
+/// stops fallbacks that loop.
+fn borrow<'a>(
+    table: &'a Table,
+    chains: &BTreeMap<&str, Vec<&str>>,
+    section: &str,
+    seen: &mut Vec<String>,
+) -> Option<&'a Value> {
+    if seen.iter().any(|s| s == section) {
+        return None;
+    }
+    seen.push(section.to_string());
+    for target in chains.get(section)? {
+        if let Some(value) = table.get(*target) {
+            return Some(value);
+        }
+        if let Some(value) = borrow(table, chains, target, seen) {
+            return Some(value);
+        }
+    }
+    None
+}
+
+fn invalid(field: &str, value: &Value, reason: Reason) -> Error {
+    Error::InvalidValue {
+        field: field.to_string(),
+        value: value.to_string(),
+        reason,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::PALETTE;
+
+    fn parse(s: &str) -> Value {
+        toml::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn missing_sections_borrow_along_the_chain() {
+        let mut v = parse(
+            r##"
+[fallbacks]
+toggler = "checkbox"
+checkbox = ["radio", "button"]
+radio = "toggler"
+slider = "button"
+
+[button]
+background = "$accent"
+
+[slider]
+rail-width = 4.0
+"##,
+        );
+        apply(&mut v).unwrap();
+        assert!(v.get("fallbacks").is_none());
+        assert_eq!(v["toggler"]["background"].as_str(), Some("$accent"));
+        assert_eq!(v["checkbox"], v["button"]);
+        assert_eq!(v["radio"], v["button"]);
+        assert!(v["slider"].get("background").is_none());
+    }
+
+    #[test]
+    fn borrowed_sections_ignore_keys_the_widget_lacks() {
+        let toml = "[fallbacks]\ntoggler = \"checkbox\"\n\n\
+                    [checkbox]\nbackground = \"@primary\"\nicon-color = \"#FFFFFF\"\n\n\
+                    [checkbox.checked]\nborder-width = 2.0\n";
+        let config: crate::ThemeConfig = format!("{PALETTE}{toml}").parse().unwrap();
+        assert!(config.toggler().is_some());
+        assert!(config.supports(&["toggler"]).is_supported());
+    }
+
+    #[test]
+    fn fallbacks_must_name_widget_sections() {
+        let mut v = parse("[fallbacks]\ntoggler = \"switch\"\n");
+        let err = apply(&mut v).unwrap_err();
+        assert_eq!(err.field(), Some("fallbacks.toggler"), "got: {err}");
+
+        let mut v = parse("[fallbacks]\ntoggler = 1\n");
+        assert!(matches!(apply(&mut v), Err(Error::InvalidValue { reason: Reason::NotAString, .. })));
+    }
+}
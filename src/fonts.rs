@@ -0,0 +1,74 @@
+//! Checking that a theme's font family is installed.
+
+use std::sync::OnceLock;
+
+use fontdb::{Database, Family};
+use iced_core::font;
+
+use crate::ThemeConfig;
+use crate::error::{Error, Reason};
+
+/// The system fonts, loaded once: scanning them takes a while.
+fn system_fonts() -> &'static Database {
+    static FONTS: OnceLock<Database> = OnceLock::new();
+    FONTS.get_or_init(|| {
+        let mut db = Database::new();
+        db.load_system_fonts();
+        db
+    })
+}
+
+impl ThemeConfig {
+    /// Check that the `[font] family` is installed on this system.
+    ///
+    /// iced silently falls back to another font when a family doesn't exist,
+    /// so a misspelled name otherwise goes unnoticed. The error's
+    /// [`Reason::UnavailableFont`] names the fallback that will be used.
+    /// Generic families like `"monospace"` always pass.
+    ///
+    /// Only installed fonts are checked: a family your app bundles and loads
+    /// itself is reported as missing. [`lint`](Self::lint) includes this
+    /// check in its warnings.
+    ///
+    /// Requires the `font-check` feature.
+    pub fn check_font(&self) -> Result<(), Error> {
+        let Some(font::Family::Name(family)) = self.font.map(|font| font.family) else {
+            return Ok(());
+        };
+        let db = system_fonts();
+        let installed = db.faces().any(|face| face.families.iter().any(|(name, _)| name.eq_ignore_ascii_case(family)));
+        if installed {
+            return Ok(());
+        }
+        Err(Error::InvalidValue {
+            field: "font.family".to_string(),
+            value: format!("\"{family}\""),
+            reason: Reason::UnavailableFont { fallback: db.family_name(&Family::SansSerif).to_string() },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::PALETTE;
+
+    #[test]
+    fn missing_family_names_fallback() {
+        let config: ThemeConfig = format!("{PALETTE}[font]\nfamily = \"Definitely Not A Font 1734\"\n").parse().unwrap();
+        let err = config.check_font().unwrap_err();
+        assert_eq!(err.field(), Some("font.family"));
+        assert!(matches!(err.reason(), Some(Reason::UnavailableFont { .. })));
+
+        let warnings = config.lint();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].severity, crate::Severity::Warning);
+    }
+
+    #[test]
+    fn generic_families_pass() {
+        let config: ThemeConfig = format!("{PALETTE}[font]\nfamily = \"monospace\"\n").parse().unwrap();
+        assert!(config.check_font().is_ok());
+        assert!(PALETTE.parse::<ThemeConfig>().unwrap().check_font().is_ok());
+    }
+}
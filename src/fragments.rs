@@ -0,0 +1,209 @@
+//! The `[fragments]` table: named groups of fields that sections pull in with
+//! `use`, so a look shared by several widgets is written once.
+//!
+//! ```toml
+//! [fragments.card]
+//! border = "hairline"
+//! shadow = "soft"
+//!
+//! [container]
+//! use        = "card"
+//! background = "@background"
+//!
+//! [button]
+//! use = ["card", "compact"]
+//! ```
+//!
+//! Fields a table sets itself win over its fragments, and a later fragment in
+//! the list wins over an earlier one. Fragments may `use` other fragments,
+//! and may hold status sub-tables like `hovered`, which merge with the
+//! section's own. Like fallbacks, fragments are applied before presets and
+//! variables are expanded, so `use` works anywhere a field would, including
+//! status tables and variants, and the status cascade then runs on the
+//! merged section.
+
+use toml::{Table, Value};
+
+use crate::error::{Error, Reason};
+use crate::presets;
+
+/// Removes `[fragments]` from `root` and replaces every `use` under the
+/// widget sections with the fields of the fragments it names.
+pub(crate) fn apply(root: &mut Value) -> Result<(), Error> {
+    let Some(table) = root.as_table_mut() else {
+        return Ok(());
+    };
+    let fragments = match table.remove("fragments") {
+        Some(Value::Table(fragments)) => fragments,
+        Some(other) => return Err(invalid("fragments", &other, Reason::NotATable)),
+        None => Table::new(),
+    };
+    for (name, fragment) in &fragments {
+        if !fragment.is_table() {
+            return Err(invalid(&format!("fragments.{name}"), fragment, Reason::NotATable));
+        }
+    }
+
+    for (key, value) in presets::widget_sections(table) {
+        expand(value, key, &fragments)?;
+    }
+    Ok(())
+}
+
+/// Expands `use` in the table `value`, found at `path`, and in every table
+/// under it.
+fn expand(value: &mut Value, path: &str, fragments: &Table) -> Result<(), Error> {
+    let Value::Table(table) = value else {
+        return Ok(());
+    };
+    if let Some(names) = table.remove("use") {
+        for name in names_in(&names, &format!("{path}.use"))?.into_iter().rev() {
+            let fields = resolve(name, &format!("{path}.use"), fragments, &mut Vec::new())?;
+            merge_under(table, fields);
+        }
+    }
+    for (key, item) in table.iter_mut() {
+        expand(item, &format!("{path}.{key}"), fragments)?;
+    }
+    Ok(())
+}
+
+/// The fields of the fragment `name`, with the fragments it uses merged in.
+/// `seen` is the chain of fragments being resolved, to catch loops.
+fn resolve(name: &str, path: &str, fragments: &Table, seen: &mut Vec<String>) -> Result<Table, Error> {
+    if seen.iter().any(|s| s == name) {
+        seen.push(name.to_string());
+        let reason = Reason::Other(format!("fragments use each other in a loop: {}", seen.join(" -> ")));
+        return Err(invalid(path, &Value::String(name.to_string()), reason));
+    }
+    let Some(Value::Table(fragment)) = fragments.get(name) else {
+        return Err(Error::InvalidValue {
+            field: path.to_string(),
+            value: format!("\"{name}\""),
+            reason: Reason::UndefinedPreset { kind: "fragment".to_string(), name: name.to_string() },
+        });
+    };
+
+    let mut fields = fragment.clone();
+    if let Some(names) = fields.remove("use") {
+        let path = format!("fragments.{name}.use");
+        seen.push(name.to_string());
+        for used in names_in(&names, &path)?.into_iter().rev() {
+            let used = resolve(used, &path, fragments, seen)?;
+            merge_under(&mut fields, used);
+        }
+        seen.pop();
+    }
+    Ok(fields)
+}
+
+/// The fragment names in a `use` value: one name or a list of them.
+fn names_in<'a>(names: &'a Value, path: &str) -> Result<Vec<&'a str>, Error> {
+    match names {
+        Value::String(name) => Ok(vec![name.as_str()]),
+        Value::Array(items) => {
+            items.iter().map(|item| item.as_str().ok_or_else(|| invalid(path, item, Reason::NotAString))).collect()
+        }
+        other => Err(invalid(path, other, Reason::NotAString)),
+    }
+}
+
+/// Adds the `fields` that `table` doesn't set, merging sub-tables both have.
+fn merge_under(table: &mut Table, fields: Table) {
+    for (key, value) in fields {
+        match (table.get_mut(&key), value) {
+            (Some(Value::Table(own)), Value::Table(inner)) => merge_under(own, inner),
+            (Some(_), _) => {}
+            (None, value) => {
+                table.insert(key, value);
+            }
+        }
+    }
+}
+
+fn invalid(field: &str, value: &Value, reason: Reason) -> Error {
+    Error::InvalidValue {
+        field: field.to_string(),
+        value: value.to_string(),
+        reason,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::PALETTE;
+
+    fn parse(s: &str) -> Value {
+        toml::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn sections_take_fragment_fields_they_lack() {
+        let mut v = parse(
+            r##"
+[fragments.card]
+border-width = 1.0
+shadow       = "soft"
+hovered      = { border-width = 2.0, background = "$raised" }
+
+[fragments.compact]
+use          = "card"
+padding      = 4
+border-width = 0.5
+
+[container]
+use        = "card"
+background = "$surface"
+
+[button]
+use          = ["compact", "card"]
+border-width = 3.0
+
+[button.hovered]
+background = "$accent"
+"##,
+        );
+        apply(&mut v).unwrap();
+        assert!(v.get("fragments").is_none());
+        assert_eq!(v["container"]["border-width"].as_float(), Some(1.0));
+        assert_eq!(v["container"]["shadow"].as_str(), Some("soft"));
+        assert!(v["container"].get("use").is_none());
+
+        let button = &v["button"];
+        assert_eq!(button["border-width"].as_float(), Some(3.0));
+        assert_eq!(button["padding"].as_integer(), Some(4));
+        assert_eq!(button["hovered"]["background"].as_str(), Some("$accent"));
+        assert_eq!(button["hovered"]["border-width"].as_float(), Some(2.0));
+    }
+
+    #[test]
+    fn later_fragments_win_and_the_status_cascade_runs_after() {
+        let toml = "[fragments.outlined]\nborder-width = 1.0\nborder-color = \"@primary\"\n\n\
+                    [fragments.thick]\nborder-width = 2.0\n\n\
+                    [button]\nuse = [\"outlined\", \"thick\"]\n\n\
+                    [button.pressed]\nuse = \"outlined\"\n";
+        let config: crate::ThemeConfig = format!("{PALETTE}{toml}").parse().unwrap();
+        let style = |status| config.button().unwrap().style_fn()(&config.theme(), status);
+        let active = style(iced_widget::button::Status::Active);
+        assert_eq!(active.border.width, 2.0);
+        assert_eq!(active.border.color, config.theme().palette().primary);
+        assert_eq!(style(iced_widget::button::Status::Hovered).border.width, 2.0);
+        assert_eq!(style(iced_widget::button::Status::Pressed).border.width, 1.0);
+    }
+
+    #[test]
+    fn missing_and_looping_fragments_are_errors() {
+        let mut v = parse("[button]\nuse = \"card\"\n");
+        let err = apply(&mut v).unwrap_err();
+        assert_eq!(err.field(), Some("button.use"), "got: {err}");
+        assert!(matches!(err, Error::InvalidValue { reason: Reason::UndefinedPreset { .. }, .. }));
+
+        let mut v = parse("[fragments.a]\nuse = \"b\"\n\n[fragments.b]\nuse = \"a\"\n\n[button]\nuse = \"a\"\n");
+        let err = apply(&mut v).unwrap_err();
+        assert!(err.to_string().contains("a -> b -> a"), "got: {err}");
+
+        let mut v = parse("[button]\nuse = 1\n");
+        assert!(matches!(apply(&mut v), Err(Error::InvalidValue { reason: Reason::NotAString, .. })));
+    }
+}
@@ -0,0 +1,164 @@
+//! Logical icon names mapped to glyphs of an icon font, from the theme's
+//! optional `[icons]` section.
+
+use std::collections::BTreeMap;
+
+use iced_core::{Color, Font};
+use serde::Deserialize;
+
+use crate::ThemeConfig;
+use crate::color::HexColor;
+use crate::config::{self, CodePoint, FontRaw};
+use crate::style::{MapColors, impl_approx_eq};
+
+/// Raw `[icons]` section: the icon font and color, and every other key an
+/// icon name mapped to its glyph.
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub(crate) struct IconsRaw {
+    font:   Option<FontRaw>,
+    color:  Option<HexColor>,
+    #[serde(flatten)]
+    glyphs: BTreeMap<String, CodePoint>,
+}
+
+impl IconsRaw {
+    /// The icon set, in `fallback` (the theme's `[font]`) unless the section
+    /// names its own font.
+    pub(crate) fn resolve(self, fallback: Option<Font>) -> IconSet {
+        IconSet {
+            font:   self.font.map(config::build_font).or(fallback).unwrap_or_default(),
+            color:  self.color.map(|c| c.0),
+            glyphs: self.glyphs.into_iter().map(|(name, glyph)| (name, glyph.0)).collect(),
+        }
+    }
+}
+
+/// A theme's icons: logical names like `"close"` or `"settings"`, each
+/// mapped to a glyph of one icon font.
+///
+/// Apps ask for icons by name, so switching themes can switch icon sets,
+/// say from outlined to filled, along with the colors:
+///
+/// ```toml
+/// [icons]
+/// close    = "U+E5CD"
+/// settings = "U+E8B8"
+/// color    = "@primary"
+///
+/// [icons.font]
+/// family = "Material Symbols Outlined"
+/// ```
+///
+/// `font` and `color` are reserved and can't name icons. Without a `font`
+/// table, icons use the theme's `[font]`, or iced's default font.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IconSet {
+    font:   Font,
+    color:  Option<Color>,
+    glyphs: BTreeMap<String, char>,
+}
+
+impl IconSet {
+    /// The font the glyphs are drawn from.
+    pub fn font(&self) -> Font {
+        self.font
+    }
+
+    /// `color`: the color to draw icons in, if the theme sets one.
+    pub fn color(&self) -> Option<Color> {
+        self.color
+    }
+
+    /// The glyph for the icon `name`.
+    pub fn glyph(&self, name: &str) -> Option<char> {
+        self.glyphs.get(name).copied()
+    }
+
+    /// The icon names, in alphabetical order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.glyphs.keys().map(String::as_str)
+    }
+}
+
+impl MapColors for IconSet {
+    fn map_colors(self, f: &impl Fn(Color) -> Color) -> Self {
+        IconSet { color: self.color.map(f), ..self }
+    }
+}
+
+impl_approx_eq!(IconSet { font, color, glyphs });
+
+impl ThemeConfig {
+    /// The theme's `[icons]` section; empty if it has none.
+    pub fn icons(&self) -> &IconSet {
+        &self.icons
+    }
+
+    /// The font and glyph of the icon `name`, ready for a `text` widget:
+    ///
+    /// ```no_run
+    /// # use iced::widget::text;
+    /// # use iced_themer::ThemeConfig;
+    /// # let config: ThemeConfig = todo!();
+    /// if let Some((font, glyph)) = config.icon("close") {
+    ///     let icon: iced::Element<'_, ()> = text(glyph).font(font).into();
+    /// }
+    /// ```
+    ///
+    /// `None` if the theme doesn't map `name`.
+    pub fn icon(&self, name: &str) -> Option<(Font, char)> {
+        self.icons.glyph(name).map(|glyph| (self.icons.font, glyph))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::PALETTE;
+
+    #[test]
+    fn icons_map_names_to_glyphs_of_one_font() {
+        let toml = "[icons]\nclose = \"U+E5CD\"\ncheck = \"✓\"\ncolor = \"@primary\"\n\n\
+                    [icons.font]\nfamily = \"Material Icons\"\n";
+        let config: ThemeConfig = format!("{PALETTE}{toml}").parse().unwrap();
+        let (font, glyph) = config.icon("close").unwrap();
+        assert_eq!(font.family, iced_core::font::Family::Name("Material Icons"));
+        assert_eq!(glyph, '\u{E5CD}');
+        assert_eq!(config.icon("check").map(|(_, glyph)| glyph), Some('✓'));
+        assert_eq!(config.icon("settings"), None);
+        assert_eq!(config.icons().names().collect::<Vec<_>>(), ["check", "close"]);
+        assert_eq!(config.icons().color(), Some(Color::from_rgb8(0x1E, 0x88, 0xE5)));
+        assert_ne!(config.derive_dark().icons().color(), config.icons().color());
+    }
+
+    #[test]
+    fn icons_default_to_the_theme_font() {
+        let toml = "[font]\nfamily = \"monospace\"\n\n[icons]\nclose = \"x\"\n";
+        let config: ThemeConfig = format!("{PALETTE}{toml}").parse().unwrap();
+        assert_eq!(config.icon("close"), Some((Font::MONOSPACE, 'x')));
+
+        let bare: ThemeConfig = PALETTE.parse().unwrap();
+        assert_eq!(bare.icons(), &IconSet::default());
+    }
+
+    #[test]
+    fn icon_names_can_match_preset_keys() {
+        let toml = "[shadows.soft]\nblur = 4.0\n\n[fragments.card]\nshadow = \"soft\"\n\n\
+                    [metadata]\nshadow = \"soft look\"\n\n\
+                    [icons]\nborder = \"U+2500\"\nshadow = \"U+2591\"\nuse = \"U+2713\"\n\n\
+                    [button]\nuse = \"card\"\n";
+        let config: ThemeConfig = format!("{PALETTE}{toml}").parse().unwrap();
+        assert_eq!(config.icons().glyph("border"), Some('─'));
+        assert_eq!(config.icons().glyph("shadow"), Some('░'));
+        assert_eq!(config.icons().glyph("use"), Some('✓'));
+        let style = config.button().unwrap().style_fn()(&iced_core::Theme::Light, iced_widget::button::Status::Active);
+        assert_eq!(style.shadow.blur_radius, 4.0);
+    }
+
+    #[test]
+    fn bad_glyph_is_reported_at_its_name() {
+        let err = format!("{PALETTE}[icons]\nclose = \"xx\"\n").parse::<ThemeConfig>().err().unwrap();
+        assert_eq!(err.field(), Some("icons.close"), "got: {err}");
+    }
+}
@@ -0,0 +1,198 @@
+//! Theme files that build on other theme files through `extends`.
+//!
+//! `extends` names one file, or a list of files merged in order, that the
+//! theme is deep-merged over. Paths are relative to a root directory, the
+//! theme's own directory unless the application picks another, and can't
+//! leave it: absolute paths and `..` are rejected, as are symlinks that point
+//! outside the root.
+
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use toml::Value;
+
+use crate::ThemeConfig;
+use crate::error::{Error, Reason};
+use crate::transform;
+
+impl ThemeConfig {
+    /// Read and parse a TOML theme file whose `extends` paths resolve against
+    /// `root` instead of the theme's own directory.
+    ///
+    /// Use this to load a theme from a bundle that shares base themes
+    /// between subdirectories. Paths still can't be absolute, contain `..`,
+    /// or lead outside `root`.
+    pub fn from_file_in(path: impl AsRef<Path>, root: impl AsRef<Path>) -> Result<Self, Error> {
+        ThemeConfig::from_value(read_in(path.as_ref(), root.as_ref())?)
+    }
+}
+
+/// Reads the theme file at `path` with everything it extends merged in,
+/// resolving `extends` against the file's own directory.
+pub(crate) fn read(path: &Path) -> Result<Value, Error> {
+    read_in(path, root_of(path))
+}
+
+/// Reads the theme file at `path` with everything it extends merged in,
+/// resolving `extends` against `root`.
+pub(crate) fn read_in(path: &Path, root: &Path) -> Result<Value, Error> {
+    Ok(read_tracked(path, root)?.0)
+}
+
+/// Like [`read_in`], also returning every file that was read: `path` first,
+/// then the files it extends, directly or not.
+pub(crate) fn read_tracked(path: &Path, root: &Path) -> Result<(Value, Vec<PathBuf>), Error> {
+    let root = root.canonicalize()?;
+    let mut chain = vec![path.canonicalize()?];
+    let mut files = chain.clone();
+    let value = load(path, &root, &mut chain, &mut files)?;
+    Ok((value, files))
+}
+
+/// The directory `extends` resolves against when loading `path` by itself.
+pub(crate) fn root_of(path: &Path) -> &Path {
+    match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    }
+}
+
+/// Loads `path`, the last file in `chain`, merged over the files it extends,
+/// adding each of those to `files`.
+fn load(path: &Path, root: &Path, chain: &mut Vec<PathBuf>, files: &mut Vec<PathBuf>) -> Result<Value, Error> {
+    let mut value: Value = toml::from_str(&fs::read_to_string(path)?)?;
+    let Some(extends) = value.as_table_mut().and_then(|table| table.remove("extends")) else {
+        return Ok(value);
+    };
+    let names = match extends {
+        Value::String(name) => vec![name],
+        Value::Array(items) => items
+            .into_iter()
+            .enumerate()
+            .map(|(i, item)| match item {
+                Value::String(name) => Ok(name),
+                other => Err(invalid(&format!("extends.{i}"), other.to_string(), Reason::NotAString)),
+            })
+            .collect::<Result<_, _>>()?,
+        other => return Err(invalid("extends", other.to_string(), Reason::NotAString)),
+    };
+
+    let mut merged = Value::Table(Default::default());
+    for name in names {
+        let base = resolve(root, &name)?;
+        if let Some(start) = chain.iter().position(|p| *p == base) {
+            let cycle = chain[start..].iter().chain([&base]).map(|p| display(root, p)).collect();
+            return Err(invalid("extends", name, Reason::CyclicExtends(cycle)));
+        }
+        if !files.contains(&base) {
+            files.push(base.clone());
+        }
+        chain.push(base.clone());
+        let parent = load(&base, root, chain, files)?;
+        chain.pop();
+        transform::merge(&mut merged, parent);
+    }
+    transform::merge(&mut merged, value);
+    Ok(merged)
+}
+
+/// The canonical path of `name` inside `root`.
+fn resolve(root: &Path, name: &str) -> Result<PathBuf, Error> {
+    let relative = Path::new(name);
+    let plain = relative.components().all(|c| matches!(c, Component::Normal(_) | Component::CurDir));
+    if relative.is_absolute() || !plain {
+        return Err(invalid("extends", name.to_string(), Reason::PathOutsideRoot));
+    }
+    let path = root.join(relative).canonicalize()?;
+    // A symlink inside the root can still point anywhere.
+    if !path.starts_with(root) {
+        return Err(invalid("extends", name.to_string(), Reason::PathOutsideRoot));
+    }
+    Ok(path)
+}
+
+fn display(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root).unwrap_or(path).display().to_string()
+}
+
+fn invalid(field: &str, value: String, reason: Reason) -> Error {
+    Error::InvalidValue { field: field.to_string(), value, reason }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{PALETTE, temp_path};
+
+    fn bundle(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir = temp_path(name);
+        let _ = fs::remove_dir_all(&dir);
+        for (file, contents) in files {
+            let path = dir.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+        dir
+    }
+
+    fn reason(err: Error) -> Reason {
+        err.reason().cloned().unwrap_or_else(|| panic!("unexpected error: {err}"))
+    }
+
+    #[test]
+    fn extends_merges_in_order() {
+        let dir = bundle(
+            "extends",
+            &[
+                ("base.toml", &format!("name = \"Base\"\n{PALETTE}\n[button]\nbackground = \"#111111\"\n")),
+                ("accent.toml", "[palette]\nprimary = \"#FF0000\"\n"),
+                ("theme.toml", "extends = [\"base.toml\", \"accent.toml\"]\nname = \"Child\"\n"),
+            ],
+        );
+        let config = ThemeConfig::from_file(dir.join("theme.toml")).unwrap();
+        assert_eq!(config.name(), "Child");
+        assert_eq!(config.theme().palette().primary, iced_core::Color::from_rgb8(0xFF, 0, 0));
+        assert!(config.button().is_some());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn paths_cannot_leave_the_root() {
+        let dir = bundle("sandbox", &[("themes/theme.toml", "extends = \"../base.toml\"\n"), ("base.toml", PALETTE)]);
+        let err = ThemeConfig::from_file(dir.join("themes/theme.toml")).err().unwrap();
+        assert_eq!(err.field(), Some("extends"));
+        assert_eq!(reason(err), Reason::PathOutsideRoot);
+
+        let absolute = format!("extends = {:?}\n", dir.join("base.toml").display().to_string());
+        fs::write(dir.join("themes/theme.toml"), absolute).unwrap();
+        let err = ThemeConfig::from_file(dir.join("themes/theme.toml")).err().unwrap();
+        assert_eq!(reason(err), Reason::PathOutsideRoot);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn root_is_configurable() {
+        let dir = bundle(
+            "root",
+            &[("shared/base.toml", PALETTE), ("themes/theme.toml", "extends = \"shared/base.toml\"\nname = \"T\"\n")],
+        );
+        assert!(ThemeConfig::from_file(dir.join("themes/theme.toml")).is_err());
+        let config = ThemeConfig::from_file_in(dir.join("themes/theme.toml"), &dir).unwrap();
+        assert_eq!(config.name(), "T");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn cycles_are_reported() {
+        let dir = bundle("cycle", &[("a.toml", "extends = \"b.toml\"\n"), ("b.toml", "extends = \"a.toml\"\n")]);
+        let err = ThemeConfig::from_file(dir.join("a.toml")).err().unwrap();
+        assert_eq!(reason(err), Reason::CyclicExtends(vec!["a.toml".into(), "b.toml".into(), "a.toml".into()]));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn extends_needs_a_file() {
+        let err = format!("extends = \"base.toml\"\n{PALETTE}").parse::<ThemeConfig>().err().unwrap();
+        assert_eq!(err.field(), Some("extends"));
+    }
+}
@@ -0,0 +1,266 @@
+//! Blending between two resolved styles, for animating status changes.
+
+use iced_core::border::Radius;
+use iced_core::gradient::{ColorStop, Gradient, Linear};
+use iced_core::{Background, Border, Color, Radians, Shadow, Vector};
+use iced_widget::{button, checkbox, container, pick_list, radio, text_input};
+
+use crate::color;
+
+/// A style that can be blended with another of its kind.
+///
+/// iced swaps a widget's style the moment its status changes. To fade a
+/// button's hover instead, keep an animation progress `t` in your state and
+/// style the button with the blend of its two appearances:
+///
+/// ```no_run
+/// use iced::widget::button;
+/// use iced_themer::{Interpolate, ThemeConfig};
+///
+/// # let config: ThemeConfig = todo!();
+/// # let theme = config.theme();
+/// # let t = 0.5;
+/// let style = config.button().unwrap().style_fn();
+/// let active = style(&theme, button::Status::Active);
+/// let hovered = style(&theme, button::Status::Hovered);
+/// let frame = active.interpolate(&hovered, t);
+/// ```
+///
+/// `t = 0.0` gives `self` and `1.0` gives `other`; values outside are
+/// clamped. Colors blend in linear RGB, and numbers, offsets, and radii
+/// linearly. A missing background or text color fades from or to a
+/// transparent copy of the other, so a text-only button can fade in a
+/// background. What can't be blended, like gradients with different stops
+/// or a color against a gradient, switches over at `t = 0.5`.
+pub trait Interpolate {
+    fn interpolate(&self, other: &Self, t: f32) -> Self;
+}
+
+/// `a` up to `t = 0.5`, then `b`.
+fn step<T: Clone>(a: &T, b: &T, t: f32) -> T {
+    if t < 0.5 { a.clone() } else { b.clone() }
+}
+
+impl Interpolate for f32 {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        self + (other - self) * t.clamp(0.0, 1.0)
+    }
+}
+
+impl Interpolate for Color {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        color::mix(*self, *other, t)
+    }
+}
+
+impl Interpolate for Option<Color> {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        match (self, other) {
+            (Some(a), Some(b)) => Some(a.interpolate(b, t)),
+            (Some(a), None) => Some(a.interpolate(&Color { a: 0.0, ..*a }, t)),
+            (None, Some(b)) => Some(Color { a: 0.0, ..*b }.interpolate(b, t)),
+            (None, None) => None,
+        }
+    }
+}
+
+impl Interpolate for Vector {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        Vector::new(self.x.interpolate(&other.x, t), self.y.interpolate(&other.y, t))
+    }
+}
+
+impl Interpolate for Radius {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        Radius {
+            top_left: self.top_left.interpolate(&other.top_left, t),
+            top_right: self.top_right.interpolate(&other.top_right, t),
+            bottom_right: self.bottom_right.interpolate(&other.bottom_right, t),
+            bottom_left: self.bottom_left.interpolate(&other.bottom_left, t),
+        }
+    }
+}
+
+impl Interpolate for Border {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        Border {
+            color: self.color.interpolate(&other.color, t),
+            width: self.width.interpolate(&other.width, t),
+            radius: self.radius.interpolate(&other.radius, t),
+        }
+    }
+}
+
+impl Interpolate for Shadow {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        Shadow {
+            color: self.color.interpolate(&other.color, t),
+            offset: self.offset.interpolate(&other.offset, t),
+            blur_radius: self.blur_radius.interpolate(&other.blur_radius, t),
+        }
+    }
+}
+
+impl Interpolate for Background {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        match (self, other) {
+            (Background::Color(a), Background::Color(b)) => Background::Color(a.interpolate(b, t)),
+            (Background::Gradient(Gradient::Linear(a)), Background::Gradient(Gradient::Linear(b))) => {
+                match interpolate_linear(a, b, t) {
+                    Some(linear) => Background::Gradient(Gradient::Linear(linear)),
+                    None => step(self, other, t),
+                }
+            }
+            _ => step(self, other, t),
+        }
+    }
+}
+
+/// Blends two gradients stop by stop; `None` unless they have the same
+/// number of stops.
+fn interpolate_linear(a: &Linear, b: &Linear, t: f32) -> Option<Linear> {
+    let mut stops = [None; 8];
+    for (stop, (a, b)) in stops.iter_mut().zip(a.stops.iter().zip(&b.stops)) {
+        *stop = match (a, b) {
+            (Some(a), Some(b)) => Some(ColorStop {
+                offset: a.offset.interpolate(&b.offset, t),
+                color: a.color.interpolate(&b.color, t),
+            }),
+            (None, None) => None,
+            _ => return None,
+        };
+    }
+    Some(Linear { angle: Radians(a.angle.0.interpolate(&b.angle.0, t)), stops })
+}
+
+impl Interpolate for Option<Background> {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        let transparent = |background: &Background| match background {
+            Background::Color(c) => Some(Background::Color(Color { a: 0.0, ..*c })),
+            Background::Gradient(_) => None,
+        };
+        match (self, other) {
+            (Some(a), Some(b)) => Some(a.interpolate(b, t)),
+            (Some(a), None) => transparent(a).map(|b| a.interpolate(&b, t)).or_else(|| step(self, other, t)),
+            (None, Some(b)) => transparent(b).map(|a| a.interpolate(b, t)).or_else(|| step(self, other, t)),
+            (None, None) => None,
+        }
+    }
+}
+
+// On iced 0.14 the struct update carries `snap`; on 0.13 nothing is left for it.
+#[allow(clippy::needless_update)]
+impl Interpolate for button::Style {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        button::Style {
+            background: self.background.interpolate(&other.background, t),
+            text_color: self.text_color.interpolate(&other.text_color, t),
+            border: self.border.interpolate(&other.border, t),
+            shadow: self.shadow.interpolate(&other.shadow, t),
+            ..step(self, other, t)
+        }
+    }
+}
+
+// On iced 0.14 the struct update carries `snap`; on 0.13 nothing is left for it.
+#[allow(clippy::needless_update)]
+impl Interpolate for container::Style {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        container::Style {
+            text_color: self.text_color.interpolate(&other.text_color, t),
+            background: self.background.interpolate(&other.background, t),
+            border: self.border.interpolate(&other.border, t),
+            shadow: self.shadow.interpolate(&other.shadow, t),
+            ..step(self, other, t)
+        }
+    }
+}
+
+impl Interpolate for checkbox::Style {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        checkbox::Style {
+            background: self.background.interpolate(&other.background, t),
+            icon_color: self.icon_color.interpolate(&other.icon_color, t),
+            border: self.border.interpolate(&other.border, t),
+            text_color: self.text_color.interpolate(&other.text_color, t),
+        }
+    }
+}
+
+impl Interpolate for radio::Style {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        radio::Style {
+            background: self.background.interpolate(&other.background, t),
+            dot_color: self.dot_color.interpolate(&other.dot_color, t),
+            border_width: self.border_width.interpolate(&other.border_width, t),
+            border_color: self.border_color.interpolate(&other.border_color, t),
+            text_color: self.text_color.interpolate(&other.text_color, t),
+        }
+    }
+}
+
+impl Interpolate for text_input::Style {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        text_input::Style {
+            background: self.background.interpolate(&other.background, t),
+            border: self.border.interpolate(&other.border, t),
+            icon: self.icon.interpolate(&other.icon, t),
+            placeholder: self.placeholder.interpolate(&other.placeholder, t),
+            value: self.value.interpolate(&other.value, t),
+            selection: self.selection.interpolate(&other.selection, t),
+        }
+    }
+}
+
+impl Interpolate for pick_list::Style {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        pick_list::Style {
+            text_color: self.text_color.interpolate(&other.text_color, t),
+            placeholder_color: self.placeholder_color.interpolate(&other.placeholder_color, t),
+            handle_color: self.handle_color.interpolate(&other.handle_color, t),
+            background: self.background.interpolate(&other.background, t),
+            border: self.border.interpolate(&other.border, t),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shadows_blend_every_part() {
+        let rest = Shadow { color: Color::TRANSPARENT, offset: Vector::new(0.0, 1.0), blur_radius: 2.0 };
+        let lifted = Shadow { color: Color::BLACK, offset: Vector::new(0.0, 5.0), blur_radius: 10.0 };
+        let half = rest.interpolate(&lifted, 0.5);
+        assert_eq!((half.offset, half.blur_radius), (Vector::new(0.0, 3.0), 6.0));
+        assert!((half.color.a - 0.5).abs() < 1e-6);
+        assert_eq!(rest.interpolate(&lifted, 2.0), lifted);
+        assert_eq!(rest.interpolate(&lifted, -1.0), rest);
+    }
+
+    #[test]
+    fn missing_backgrounds_fade_through_transparent() {
+        let red = Some(Background::Color(Color::from_rgb(1.0, 0.0, 0.0)));
+        let Some(Background::Color(faded)) = None.interpolate(&red, 0.25) else {
+            panic!("expected a color");
+        };
+        assert!((faded.r - 1.0).abs() < 1e-5 && (faded.a - 0.25).abs() < 1e-6, "got {faded:?}");
+        assert_eq!(None::<Background>.interpolate(&None, 0.5), None);
+    }
+
+    #[test]
+    fn mismatched_gradients_switch_halfway() {
+        let two = Linear::new(0.0).add_stop(0.0, Color::BLACK).add_stop(0.5, Color::WHITE);
+        let three = two.add_stop(1.0, Color::BLACK);
+        let (a, b) = (Background::from(Gradient::Linear(two)), Background::from(Gradient::Linear(three)));
+        assert_eq!(a.interpolate(&b, 0.4), a);
+        assert_eq!(a.interpolate(&b, 0.6), b);
+
+        let dark = Linear::new(0.0).add_stop(0.0, Color::BLACK).add_stop(1.0, Color::BLACK);
+        let Background::Gradient(Gradient::Linear(blend)) = a.interpolate(&Gradient::Linear(dark).into(), 1.0) else {
+            panic!("expected a gradient");
+        };
+        assert_eq!(blend.stops[1].unwrap().color, Color::BLACK);
+    }
+}
@@ -0,0 +1,202 @@
+//! Loading themes that contain mistakes.
+//!
+//! [`ThemeConfig::from_str_lenient`] drops each invalid value so it falls back
+//! to its default, and reports it as a [`Diagnostic`] instead of failing the
+//! load.
+
+use std::path::Path;
+
+use iced_core::theme::Theme;
+use toml::Value;
+
+use crate::ThemeConfig;
+use crate::color::HexColor;
+use crate::compat;
+use crate::diagnostic::Diagnostic;
+use crate::error::Error;
+use crate::include;
+
+const PALETTE_SLOTS: [&str; 6] = ["background", "text", "primary", "success", "warning", "danger"];
+
+impl ThemeConfig {
+    /// Parse a theme, recovering from invalid values instead of failing.
+    ///
+    /// Each value that would make [`from_str`](std::str::FromStr::from_str)
+    /// fail is removed, so it takes its default, and reported as a
+    /// [`Diagnostic`] pointing at the value in `s`.
+    /// Invalid or missing palette colors are replaced with those of iced's
+    /// light theme:
+    ///
+    /// ```
+    /// # use iced_themer::ThemeConfig;
+    /// let toml = r##"
+    /// [palette]
+    /// background = "#1B2838"
+    /// text       = "#C7D5E0"
+    /// primary    = "#66C0F4"
+    /// success    = "#4CAF50"
+    /// warning    = "#FFC107"
+    /// danger     = "#F44336"
+    ///
+    /// [button]
+    /// background = "#66C0F"
+    /// text-color = "#FFFFFF"
+    /// "##;
+    /// let (config, warnings) = ThemeConfig::from_str_lenient(toml).unwrap();
+    /// assert!(config.button().is_some());
+    /// assert_eq!(warnings[0].path.as_deref(), Some("button.background"));
+    /// ```
+    ///
+    /// Only errors that can't be pinned to a single value, like TOML syntax
+    /// errors, still fail the load. Problems with a theme that loads cleanly,
+    /// like a font that isn't installed, are left to [`lint`](Self::lint).
+    pub fn from_str_lenient(s: &str) -> Result<(ThemeConfig, Vec<Diagnostic>), Error> {
+        let (config, warnings) = ThemeConfig::from_value_lenient(toml::from_str(s)?)?;
+        Ok((config, warnings.into_iter().map(|warning| warning.locate(s)).collect()))
+    }
+
+    /// Read a theme file with [`from_str_lenient`](Self::from_str_lenient).
+    ///
+    /// Errors in resolving `extends` still fail the load. The warnings carry
+    /// no span, as they may come from any file in the `extends` chain.
+    pub fn from_file_lenient(path: impl AsRef<Path>) -> Result<(ThemeConfig, Vec<Diagnostic>), Error> {
+        ThemeConfig::from_value_lenient(include::read(path.as_ref())?)
+    }
+
+    fn from_value_lenient(mut source: Value) -> Result<(ThemeConfig, Vec<Diagnostic>), Error> {
+        let mut warnings = Vec::new();
+        loop {
+            let err = match ThemeConfig::from_value(source.clone()) {
+                Ok(config) => return Ok((config, warnings)),
+                Err(err) => err,
+            };
+            if !repair(&mut source, &err) {
+                return Err(err);
+            }
+            warnings.push(Diagnostic::from(err));
+        }
+    }
+}
+
+/// Changes `source` so that `err` can't occur again. Returns `false` if the
+/// error can't be pinned to a value in `source`.
+fn repair(source: &mut Value, err: &Error) -> bool {
+    let Some(field) = err.field() else {
+        return false;
+    };
+    if (field.is_empty() && source.get("palette").is_none()) || field == "palette" {
+        return fill_palette(source);
+    }
+    if let Some(slot) = field.strip_prefix("palette.").filter(|slot| PALETTE_SLOTS.contains(slot)) {
+        let Some(Value::Table(palette)) = source.get_mut("palette") else {
+            return false;
+        };
+        palette.insert(slot.to_string(), Value::String(default_slot(slot)));
+        return true;
+    }
+    remove(source, field)
+}
+
+/// Adds every missing palette color, returning whether any was missing.
+fn fill_palette(source: &mut Value) -> bool {
+    let Some(root) = source.as_table_mut() else {
+        return false;
+    };
+    let Value::Table(palette) = root.entry("palette").or_insert_with(|| Value::Table(Default::default())) else {
+        return false;
+    };
+    let mut filled = false;
+    for slot in PALETTE_SLOTS {
+        if !palette.contains_key(slot) {
+            palette.insert(slot.to_string(), Value::String(default_slot(slot)));
+            filled = true;
+        }
+    }
+    filled
+}
+
+/// iced's light-theme color for the palette `slot`, as a hex string.
+fn default_slot(slot: &str) -> String {
+    let palette = Theme::Light.palette();
+    let color = match slot {
+        "background" => palette.background,
+        "text" => palette.text,
+        "primary" => palette.primary,
+        "success" => palette.success,
+        "warning" => compat::light_warning(),
+        _ => palette.danger,
+    };
+    HexColor(color).to_string()
+}
+
+/// Removes the value at the dotted key path `field`, returning whether it
+/// existed.
+fn remove(source: &mut Value, field: &str) -> bool {
+    let mut keys: Vec<&str> = field.split('.').collect();
+    let Some(last) = keys.pop() else {
+        return false;
+    };
+    let parent = keys.iter().try_fold(source, |v, key| match v {
+        Value::Array(items) => items.get_mut(key.parse::<usize>().ok()?),
+        _ => v.get_mut(*key),
+    });
+    match parent {
+        Some(Value::Table(table)) => table.remove(last).is_some(),
+        Some(Value::Array(items)) => match last.parse::<usize>() {
+            Ok(index) if index < items.len() => {
+                items.remove(index);
+                true
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::PALETTE;
+
+    #[test]
+    fn invalid_values_become_warnings() {
+        let toml = format!(
+            "{PALETTE}[defaults]\ndisabled-opacity = 1.5\n\n[button]\nbackground = \"#GG0000\"\ntext-color = \"#FFFFFF\"\n"
+        );
+        let (config, warnings) = ThemeConfig::from_str_lenient(&toml).unwrap();
+        let fields: Vec<_> = warnings.iter().filter_map(|w| w.path.as_deref()).collect();
+        assert_eq!(fields.len(), 2, "got: {fields:?}");
+        assert!(fields.contains(&"button.background"));
+        assert!(fields.contains(&"defaults.disabled-opacity"));
+        assert!(config.button().is_some());
+        let span = warnings.iter().find(|w| w.path.as_deref() == Some("button.background")).unwrap().span.clone();
+        assert_eq!(&toml[span.unwrap()], "\"#GG0000\"");
+    }
+
+    #[test]
+    fn invalid_palette_color_takes_default() {
+        let toml = PALETTE.replace("#1E88E5", "#1E88E");
+        let (config, warnings) = ThemeConfig::from_str_lenient(&toml).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(config.theme().palette().primary, Theme::Light.palette().primary);
+    }
+
+    #[test]
+    fn missing_palette_is_filled_in() {
+        let (config, warnings) = ThemeConfig::from_str_lenient("name = \"Bare\"\n").unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(config.theme().palette().background, Theme::Light.palette().background);
+        assert_eq!(config.ansi().yellow, compat::light_warning());
+    }
+
+    #[test]
+    fn syntax_errors_still_fail() {
+        assert!(matches!(ThemeConfig::from_str_lenient("[palette"), Err(Error::Parse(_))));
+    }
+
+    #[test]
+    fn valid_theme_has_no_warnings() {
+        let (_, warnings) = ThemeConfig::from_str_lenient(PALETTE).unwrap();
+        assert!(warnings.is_empty());
+    }
+}
@@ -50,6 +50,11 @@
 //! # ;
 //! ```
 //!
+//! The optional `generator` key in `[palette]` picks how iced's extended
+//! palette (weak/strong shades, secondary colors) is derived from these six
+//! colors: `"oklch"` (iced 0.14's), `"hsl"`, or `"legacy"` (iced 0.13's). See
+//! [`PaletteGenerator`].
+//!
 //! With just a `[palette]`, buttons will use the primary color, text will use
 //! the text color, backgrounds will use the background color, and so on. This
 //! is iced's built-in theming at work — the palette flows through every widget
@@ -103,26 +108,119 @@
 //! |-------------------|-------------------------------------|
 //! | `[button]`        | [`ButtonStyle`](style::ButtonStyle) |
 //! | `[checkbox]`      | [`CheckboxStyle`](style::CheckboxStyle) |
+//! | `[combo-box]`     | [`ComboBoxStyle`](style::ComboBoxStyle), input field and drop-down menu |
 //! | `[container]`     | [`ContainerStyle`](style::ContainerStyle) |
+//! | `[menu]`          | [`MenuStyle`](style::MenuStyle), for pick list and combo box drop-downs |
+//! | `[pane-grid]`     | [`PaneGridStyle`](style::PaneGridStyle), drop regions and split lines |
+//! | `[pick-list]`     | [`PickListStyle`](style::PickListStyle) |
 //! | `[progress-bar]`  | [`ProgressBarStyle`](style::ProgressBarStyle) |
 //! | `[radio]`         | [`RadioStyle`](style::RadioStyle) |
+//! | `[root]`          | [`ContainerStyle`](style::ContainerStyle), via [`ThemeConfig::themed_root`] |
+//! | `[rule]`          | [`RuleStyle`](style::RuleStyle) |
+//! | `[scrollable]`    | [`ScrollableStyle`](style::ScrollableStyle) |
 //! | `[slider]`        | [`SliderStyle`](style::SliderStyle) |
+//! | `[svg]`           | [`SvgStyle`](style::SvgStyle), icon tints; the `svg` feature styles `Svg` widgets |
+//! | `[table]`         | `TableStyle`, with the `table` feature |
+//! | `[text]`          | [`TextStyle`](style::TextStyle), with classes like `muted` as variants |
 //! | `[text-input]`    | [`TextInputStyle`](style::TextInputStyle) |
 //! | `[toggler]`       | [`TogglerStyle`](style::TogglerStyle) |
 
+#[cfg(all(feature = "iced-0-13", feature = "iced-0-14"))]
+compile_error!("the `iced-0-13` and `iced-0-14` features are mutually exclusive");
+
+#[cfg(not(any(feature = "iced-0-13", feature = "iced-0-14")))]
+compile_error!("enable one of the `iced-0-13` or `iced-0-14` features");
+
+// With `iced-0-13`, the 0.13 crates take the `iced_core`/`iced_widget` names,
+// so every `iced_core::...` path in the crate resolves against that release.
+// The few APIs that differ between releases go through `compat`.
+#[cfg(feature = "iced-0-13")]
+extern crate iced_core_0_13 as iced_core;
+#[cfg(feature = "iced-0-13")]
+extern crate iced_widget_0_13 as iced_widget;
+
+mod ansi;
+#[cfg(feature = "application")]
+mod app;
+#[cfg(feature = "iced-0-14")]
+mod catalog;
+#[cfg(feature = "iced-0-14")]
+mod codegen;
 mod color;
+mod compat;
 mod config;
+mod coverage;
+mod diagnostic;
+mod diff;
+#[cfg(feature = "edit")]
+mod document;
 mod error;
+#[cfg(feature = "expr")]
+pub mod expr;
+#[cfg(not(feature = "expr"))]
 mod expr;
+mod export;
+mod fallbacks;
+#[cfg(feature = "font-check")]
+mod fonts;
+mod fragments;
+mod icons;
+mod include;
+mod interpolate;
+mod lenient;
+mod library;
+mod macros;
+mod metadata;
+mod palette;
+mod presets;
+mod quantize;
+mod reload;
+#[cfg(feature = "schedule")]
+mod schedule;
+mod script;
 pub mod style;
+mod summary;
+mod syntax;
+#[cfg(feature = "terminal")]
+mod terminal;
+#[cfg(test)]
+mod testing;
 pub mod themed;
+mod transform;
 mod variables;
+mod watch;
 
-pub use error::Error;
-pub use themed::Themed;
+pub use ansi::AnsiPalette;
+#[cfg(feature = "application")]
+pub use app::{application, reloads};
+#[cfg(feature = "iced-0-14")]
+pub use catalog::{ThemerClass, ThemerTheme};
+pub use color::HexColor;
+pub use coverage::{Coverage, CoverageReport, SupportReport};
+pub use diagnostic::{Diagnostic, Severity};
+pub use diff::ThemeDiff;
+#[cfg(feature = "edit")]
+pub use document::ThemeDocument;
+pub use error::{Error, Reason};
+pub use icons::IconSet;
+pub use interpolate::Interpolate;
+pub use library::{SortBy, ThemeLibrary, ThemeSummary};
+pub use metadata::ThemeMetadata;
+pub use palette::PaletteGenerator;
+pub use quantize::ColorDepth;
+#[cfg(feature = "schedule")]
+pub use schedule::{Schedule, ScheduledTheme};
+pub use summary::ConfigSummary;
+pub use syntax::{SyntaxTheme, SyntaxToken};
+#[cfg(feature = "terminal")]
+pub use terminal::TerminalPalette;
+pub use themed::{Themed, ThemedLive};
+pub use transform::ResolutionContext;
+pub use watch::{DirectoryWatcher, LibraryEvent, LibraryHandle, ThemeHandle, ThemeWatcher};
 
 use iced_core::font::Font;
 use iced_core::theme::Theme;
+use std::collections::BTreeMap;
 use std::path::Path;
 use std::str::FromStr;
 
@@ -134,25 +232,60 @@ use style::*;
 /// converts the theme data into iced types. Accessor methods are cheap: `theme()`
 /// clones an `Arc`, and `font()` copies a `Copy` type. Widget style accessors
 /// return `Option<&Style>` — `None` when the TOML omits that widget's section.
+#[derive(Clone)]
 pub struct ThemeConfig {
     pub(crate) name: String,
+    pub(crate) metadata: ThemeMetadata,
     pub(crate) theme: Theme,
+    pub(crate) generator: PaletteGenerator,
+    /// `[palette]` keys beyond the six iced slots.
+    pub(crate) custom_slots: BTreeMap<String, iced_core::Color>,
+    /// `[palette] warning`, kept here as iced 0.13's `Palette` has no slot for it.
+    pub(crate) warning: iced_core::Color,
     pub(crate) font: Option<Font>,
     pub(crate) button: Option<ButtonStyle>,
     pub(crate) container: Option<ContainerStyle>,
+    pub(crate) root: Option<ContainerStyle>,
     pub(crate) text_input: Option<TextInputStyle>,
     pub(crate) checkbox: Option<CheckboxStyle>,
     pub(crate) toggler: Option<TogglerStyle>,
     pub(crate) slider: Option<SliderStyle>,
     pub(crate) progress_bar: Option<ProgressBarStyle>,
     pub(crate) radio: Option<RadioStyle>,
+    #[cfg(feature = "table")]
+    pub(crate) table: Option<TableStyle>,
+    pub(crate) scrollable: Option<ScrollableStyle>,
+    pub(crate) pick_list: Option<PickListStyle>,
+    pub(crate) menu: Option<MenuStyle>,
+    pub(crate) combo_box: Option<ComboBoxStyle>,
+    pub(crate) rule: Option<RuleStyle>,
+    pub(crate) text: Option<TextStyle>,
+    pub(crate) svg: Option<SvgStyle>,
+    pub(crate) pane_grid: Option<PaneGridStyle>,
+    pub(crate) ansi: AnsiPalette,
+    pub(crate) syntax: SyntaxTheme,
+    pub(crate) icons: IconSet,
+    /// The TOML document this config was built from, before variable resolution.
+    pub(crate) source: toml::Value,
+    /// `source` after variable resolution; what [`ThemeDiff`] compares.
+    pub(crate) resolved: toml::Value,
+    /// The resolved `[variables]` table.
+    pub(crate) variables: BTreeMap<String, String>,
+    /// Named `[<widget>.variants.<name>]` styles.
+    pub(crate) variants: Variants,
+    /// Whole-theme transforms applied on top of `source`, in order.
+    pub(crate) derivations: Vec<transform::Derivation>,
 }
 
 impl ThemeConfig {
     /// Read and parse a TOML theme file.
+    ///
+    /// A top-level `extends` names a theme file, or a list of them, to merge
+    /// this one over. Its paths are relative to this file's directory and
+    /// can't leave it; see [`from_file_in`](Self::from_file_in) to allow a
+    /// wider directory.
     pub fn from_file(path: impl AsRef<Path>) -> Result<Self, Error> {
-        let contents = std::fs::read_to_string(path)?;
-        contents.parse()
+        ThemeConfig::from_value(include::read(path.as_ref())?)
     }
 
     /// The theme name. Defaults to `"Custom"` if not specified in the TOML.
@@ -160,11 +293,74 @@ impl ThemeConfig {
         &self.name
     }
 
+    /// The `[metadata]` table: author and description.
+    pub fn metadata(&self) -> &ThemeMetadata {
+        &self.metadata
+    }
+
     /// Returns an iced [`Theme`]. This is a cheap `Arc` clone.
     pub fn theme(&self) -> Theme {
         self.theme.clone()
     }
 
+    /// The relative luminance of the palette background, from `0.0` (black)
+    /// to `1.0` (white).
+    pub fn brightness(&self) -> f32 {
+        color::luminance(self.theme.palette().background)
+    }
+
+    /// Whether the palette background is dark, i.e. light text reads better
+    /// on it than dark text.
+    pub fn is_dark(&self) -> bool {
+        color::is_dark(self.theme.palette().background)
+    }
+
+    /// The algorithm used to derive the extended palette, set by
+    /// `[palette] generator`. Defaults to the selected iced release's own generator.
+    pub fn palette_generator(&self) -> PaletteGenerator {
+        self.generator
+    }
+
+    /// The `[palette]` color named `slot`: one of iced's slots, like
+    /// `"primary"`, or a custom one the theme adds, like `"info"`.
+    ///
+    /// ```toml
+    /// [palette]
+    /// # ...the six iced slots, then:
+    /// info     = "#0288D1"
+    /// accent-2 = "#8E24AA"
+    ///
+    /// [text-input.focused]
+    /// border-color = "$palette.info"
+    /// ```
+    pub fn palette_color(&self, slot: &str) -> Option<iced_core::Color> {
+        // iced 0.13 palettes have no warning color, so it's kept beside them.
+        if slot == "warning" {
+            return Some(self.warning);
+        }
+        compat::palette_slots(&self.theme.palette())
+            .into_iter()
+            .find_map(|(name, color)| (name == slot).then_some(color))
+            .or_else(|| self.custom_slots.get(slot).copied())
+    }
+
+    /// The custom `[palette]` slots, by name.
+    pub fn custom_palette(&self) -> &BTreeMap<String, iced_core::Color> {
+        &self.custom_slots
+    }
+
+    /// The `[variables]` table with every reference and expression resolved,
+    /// e.g. `"primary-h" → "#7ACBF6"`. Handy for a debug palette panel.
+    pub fn variables(&self) -> &BTreeMap<String, String> {
+        &self.variables
+    }
+
+    /// A resolved variable parsed as a [`Color`](iced_core::Color). Returns
+    /// `None` if the variable is undefined or isn't a color.
+    pub fn variable(&self, name: &str) -> Option<iced_core::Color> {
+        self.variables.get(name).and_then(|value| color::parse_color(value).ok())
+    }
+
     /// Returns the configured [`Font`], if one was specified in the TOML.
     pub fn font(&self) -> Option<Font> {
         self.font
@@ -178,6 +374,12 @@ impl ThemeConfig {
         self.container.as_ref()
     }
 
+    /// The `[root]` section: a container style for the window-filling root
+    /// container of [`themed_root()`](Self::themed_root).
+    pub fn root(&self) -> Option<&ContainerStyle> {
+        self.root.as_ref()
+    }
+
     pub fn text_input(&self) -> Option<&TextInputStyle> {
         self.text_input.as_ref()
     }
@@ -201,19 +403,220 @@ impl ThemeConfig {
     pub fn radio(&self) -> Option<&RadioStyle> {
         self.radio.as_ref()
     }
+
+    #[cfg(feature = "table")]
+    pub fn table(&self) -> Option<&TableStyle> {
+        self.table.as_ref()
+    }
+
+    pub fn scrollable(&self) -> Option<&ScrollableStyle> {
+        self.scrollable.as_ref()
+    }
+
+    pub fn pick_list(&self) -> Option<&PickListStyle> {
+        self.pick_list.as_ref()
+    }
+
+    pub fn menu(&self) -> Option<&MenuStyle> {
+        self.menu.as_ref()
+    }
+
+    pub fn combo_box(&self) -> Option<&ComboBoxStyle> {
+        self.combo_box.as_ref()
+    }
+
+    pub fn rule(&self) -> Option<&RuleStyle> {
+        self.rule.as_ref()
+    }
+
+    pub fn text(&self) -> Option<&TextStyle> {
+        self.text.as_ref()
+    }
+
+    pub fn svg(&self) -> Option<&SvgStyle> {
+        self.svg.as_ref()
+    }
+
+    pub fn pane_grid(&self) -> Option<&PaneGridStyle> {
+        self.pane_grid.as_ref()
+    }
+
+    /// The `[button.variants.<name>]` style: `[button]` with the variant's
+    /// keys merged over it. The other `*_variant` accessors work the same way
+    /// for their sections.
+    pub fn button_variant(&self, name: &str) -> Option<&ButtonStyle> {
+        self.variants.button.get(name)
+    }
+
+    pub fn container_variant(&self, name: &str) -> Option<&ContainerStyle> {
+        self.variants.container.get(name)
+    }
+
+    pub fn text_input_variant(&self, name: &str) -> Option<&TextInputStyle> {
+        self.variants.text_input.get(name)
+    }
+
+    pub fn checkbox_variant(&self, name: &str) -> Option<&CheckboxStyle> {
+        self.variants.checkbox.get(name)
+    }
+
+    pub fn toggler_variant(&self, name: &str) -> Option<&TogglerStyle> {
+        self.variants.toggler.get(name)
+    }
+
+    pub fn slider_variant(&self, name: &str) -> Option<&SliderStyle> {
+        self.variants.slider.get(name)
+    }
+
+    pub fn progress_bar_variant(&self, name: &str) -> Option<&ProgressBarStyle> {
+        self.variants.progress_bar.get(name)
+    }
+
+    pub fn radio_variant(&self, name: &str) -> Option<&RadioStyle> {
+        self.variants.radio.get(name)
+    }
+
+    #[cfg(feature = "table")]
+    pub fn table_variant(&self, name: &str) -> Option<&TableStyle> {
+        self.variants.table.get(name)
+    }
+
+    pub fn scrollable_variant(&self, name: &str) -> Option<&ScrollableStyle> {
+        self.variants.scrollable.get(name)
+    }
+
+    pub fn pick_list_variant(&self, name: &str) -> Option<&PickListStyle> {
+        self.variants.pick_list.get(name)
+    }
+
+    pub fn menu_variant(&self, name: &str) -> Option<&MenuStyle> {
+        self.variants.menu.get(name)
+    }
+
+    pub fn combo_box_variant(&self, name: &str) -> Option<&ComboBoxStyle> {
+        self.variants.combo_box.get(name)
+    }
+
+    pub fn rule_variant(&self, name: &str) -> Option<&RuleStyle> {
+        self.variants.rule.get(name)
+    }
+
+    pub fn text_variant(&self, name: &str) -> Option<&TextStyle> {
+        self.variants.text.get(name)
+    }
+
+    pub fn svg_variant(&self, name: &str) -> Option<&SvgStyle> {
+        self.variants.svg.get(name)
+    }
+
+    pub fn pane_grid_variant(&self, name: &str) -> Option<&PaneGridStyle> {
+        self.variants.pane_grid.get(name)
+    }
 }
 
+/// Parses TOML theme content, as [`ThemeConfig::from_file`] does.
+///
+/// There's no file to resolve paths against, so a top-level `extends` is an
+/// error; load such themes with [`from_file`](ThemeConfig::from_file) or
+/// [`from_file_in`](ThemeConfig::from_file_in).
 impl FromStr for ThemeConfig {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut value: toml::Value = toml::from_str(s)?;
-        variables::resolve(&mut value).map_err(|reason| Error::InvalidColor {
-            field: "variables".to_string(),
-            value: String::new(),
-            reason,
-        })?;
-        let raw: config::ThemeRaw = serde::Deserialize::deserialize(value)?;
-        raw.try_into()
+        let source: toml::Value = toml::from_str(s)?;
+        ThemeConfig::from_value(source)
+    }
+}
+
+impl ThemeConfig {
+    /// Resolve variables in an unresolved TOML document and build a config from it.
+    pub(crate) fn from_value(source: toml::Value) -> Result<Self, Error> {
+        let mut value = source.clone();
+        script::run(&mut value)?;
+        // Override tables only apply through `resolve_in()`. `[platform]`
+        // and `[scope]` hold one table per name.
+        for key in ["platform", "rtl", "scope"] {
+            let Some(overrides) = value.as_table_mut().and_then(|root| root.remove(key)) else { continue };
+            let invalid = match overrides.as_table() {
+                None => Some((key.to_string(), &overrides)),
+                Some(_) if key == "rtl" => None,
+                Some(named) => named
+                    .iter()
+                    .find(|(_, table)| !table.is_table())
+                    .map(|(name, table)| (format!("{key}.{name}"), table)),
+            };
+            if let Some((field, table)) = invalid {
+                return Err(Error::InvalidValue { field, value: table.to_string(), reason: Reason::NotATable });
+            }
+        }
+        // File loading merges and removes `extends`; anywhere else it can't resolve.
+        if let Some(extends) = value.get("extends") {
+            return Err(Error::InvalidValue {
+                field: "extends".to_string(),
+                value: extends.to_string(),
+                reason: Reason::Other("`extends` is only supported when loading a theme file".to_string()),
+            });
+        }
+        fallbacks::apply(&mut value)?;
+        fragments::apply(&mut value)?;
+        presets::expand(&mut value)?;
+        let variables = variables::resolve(&mut value)?;
+        presets::split_pairs(&mut value)?;
+        if let Some(palette) = value.get("palette") {
+            let palette: config::PaletteRaw = config::deserialize(palette, "palette")?;
+            let extended = palette.generator().generate(palette.palette());
+            palette::substitute_extended(&mut value, &extended)?;
+        }
+        let raw: config::ThemeRaw = config::deserialize(&value, "")?;
+        let mut config: ThemeConfig = raw.try_into()?;
+        config.variants = config::variants(&value)?;
+        config.source = source;
+        config.resolved = value;
+        config.variables = variables;
+        Ok(config)
+    }
+
+    /// A config with `theme`'s palette and no widget sections.
+    #[cfg(feature = "iced-0-14")]
+    pub(crate) fn from_theme(theme: Theme) -> Self {
+        let palette = theme.palette();
+        let ansi = ansi::AnsiRaw::default().resolve(&palette, palette.warning);
+        let syntax = syntax::SyntaxRaw::default().resolve(&palette, palette.warning);
+        ThemeConfig {
+            name: theme.to_string(),
+            metadata: ThemeMetadata::default(),
+            theme,
+            generator: PaletteGenerator::default(),
+            custom_slots: BTreeMap::new(),
+            warning: palette.warning,
+            font: None,
+            button: None,
+            container: None,
+            root: None,
+            text_input: None,
+            checkbox: None,
+            toggler: None,
+            slider: None,
+            progress_bar: None,
+            radio: None,
+            #[cfg(feature = "table")]
+            table: None,
+            scrollable: None,
+            pick_list: None,
+            menu: None,
+            combo_box: None,
+            rule: None,
+            text: None,
+            svg: None,
+            pane_grid: None,
+            ansi,
+            syntax,
+            icons: IconSet::default(),
+            source: toml::Value::Table(toml::Table::new()),
+            resolved: toml::Value::Table(toml::Table::new()),
+            variables: BTreeMap::new(),
+            variants: Variants::default(),
+            derivations: Vec::new(),
+        }
     }
 }
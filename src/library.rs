@@ -0,0 +1,368 @@
+//! A collection of loaded themes, for theme pickers and galleries.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use iced_core::theme::Palette;
+use serde::Deserialize;
+
+use crate::ThemeConfig;
+use crate::color;
+use crate::compat;
+use crate::config::{self, PaletteRaw};
+use crate::error::Error;
+use crate::include;
+use crate::metadata::ThemeMetadata;
+use crate::script;
+use crate::variables;
+use crate::watch::DirectoryWatcher;
+
+/// A set of themes keyed by the file they were loaded from.
+///
+/// Cloning is cheap: configs are shared behind `Arc`s. To keep a library in
+/// sync with a directory as files come and go, use a [`DirectoryWatcher`].
+#[derive(Clone, Default)]
+pub struct ThemeLibrary {
+    themes: BTreeMap<PathBuf, Arc<ThemeConfig>>,
+}
+
+impl ThemeLibrary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load every `*.toml` file directly inside `dir`.
+    ///
+    /// Files that fail to load are skipped; a [`DirectoryWatcher`] reports
+    /// them as [`LibraryEvent::Failed`](crate::LibraryEvent::Failed).
+    pub fn from_dir(dir: impl AsRef<Path>) -> Result<Self, Error> {
+        let mut watcher = DirectoryWatcher::new(dir);
+        watcher.poll()?;
+        Ok(watcher.library().clone())
+    }
+
+    /// Like [`from_dir`](Self::from_dir), but parses the files in parallel on
+    /// rayon's global thread pool. Worth it for galleries of hundreds of
+    /// themes.
+    ///
+    /// Requires the `rayon` feature.
+    #[cfg(feature = "rayon")]
+    pub fn scan_parallel(dir: impl AsRef<Path>) -> Result<Self, Error> {
+        use rayon::prelude::*;
+
+        let themes = theme_files(dir.as_ref())?
+            .into_par_iter()
+            .filter_map(|path| {
+                let config = ThemeConfig::from_file(&path).ok()?;
+                Some((path, Arc::new(config)))
+            })
+            .collect();
+        Ok(ThemeLibrary { themes })
+    }
+
+    /// Read just the name, metadata, and palette of every `*.toml` file
+    /// directly inside `dir`, ordered by path.
+    ///
+    /// Widget sections aren't resolved, so this is much cheaper than
+    /// [`from_dir`](Self::from_dir): enough for a theme picker to show
+    /// swatches, loading the full theme with [`ThemeConfig::from_file`] once
+    /// one is picked. Files whose palette fails to load are skipped.
+    pub fn index(dir: impl AsRef<Path>) -> Result<Vec<ThemeSummary>, Error> {
+        let mut summaries: Vec<ThemeSummary> =
+            theme_files(dir.as_ref())?.into_iter().filter_map(|path| ThemeSummary::load(path).ok()).collect();
+        summaries.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(summaries)
+    }
+
+    /// The theme loaded from `path`.
+    pub fn get(&self, path: impl AsRef<Path>) -> Option<&Arc<ThemeConfig>> {
+        self.themes.get(path.as_ref())
+    }
+
+    /// The first theme (in path order) whose `name` is `name`.
+    pub fn by_name(&self, name: &str) -> Option<&Arc<ThemeConfig>> {
+        self.themes.values().find(|config| config.name() == name)
+    }
+
+    /// All themes, ordered by path.
+    pub fn iter(&self) -> impl Iterator<Item = (&Path, &Arc<ThemeConfig>)> {
+        self.themes.iter().map(|(path, config)| (path.as_path(), config))
+    }
+
+    /// The themes with a dark background (see [`ThemeConfig::is_dark`]),
+    /// ordered by path.
+    pub fn dark(&self) -> impl Iterator<Item = (&Path, &Arc<ThemeConfig>)> {
+        self.iter().filter(|(_, config)| config.is_dark())
+    }
+
+    /// The themes with a light background, ordered by path.
+    pub fn light(&self) -> impl Iterator<Item = (&Path, &Arc<ThemeConfig>)> {
+        self.iter().filter(|(_, config)| !config.is_dark())
+    }
+
+    /// The `n` themes whose palettes look most like `config`'s, closest
+    /// first, for "more like this" suggestions in a gallery. Themes are
+    /// ranked by [`ThemeConfig::palette_distance`], and ties keep path order.
+    ///
+    /// When `config` is one of the library's own themes, as when it was
+    /// picked from the gallery, it is left out.
+    pub fn nearest(&self, config: &ThemeConfig, n: usize) -> Vec<(&Path, &Arc<ThemeConfig>)> {
+        let mut themes: Vec<_> = self
+            .iter()
+            .filter(|(_, other)| !std::ptr::eq(other.as_ref(), config))
+            .map(|(path, other)| (path, other, config.palette_distance(other)))
+            .collect();
+        themes.sort_by(|(_, _, a), (_, _, b)| a.total_cmp(b));
+        themes.into_iter().take(n).map(|(path, other, _)| (path, other)).collect()
+    }
+
+    /// All themes in the given order. Ties keep path order.
+    pub fn sorted(&self, by: SortBy) -> Vec<(&Path, &Arc<ThemeConfig>)> {
+        let mut themes: Vec<_> = self.iter().collect();
+        themes.sort_by(|(_, a), (_, b)| by.compare(&SortKey::config(a), &SortKey::config(b)));
+        themes
+    }
+
+    pub fn len(&self) -> usize {
+        self.themes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.themes.is_empty()
+    }
+
+    /// Add or replace the theme for `path`, returning the previous one.
+    pub fn insert(&mut self, path: impl Into<PathBuf>, config: ThemeConfig) -> Option<Arc<ThemeConfig>> {
+        self.themes.insert(path.into(), Arc::new(config))
+    }
+
+    /// Remove the theme for `path`, returning it.
+    pub fn remove(&mut self, path: impl AsRef<Path>) -> Option<Arc<ThemeConfig>> {
+        self.themes.remove(path.as_ref())
+    }
+}
+
+impl ThemeConfig {
+    /// How different this theme's palette looks from `other`'s: the
+    /// perceptual color difference between each pair of core palette colors,
+    /// averaged. `0.0` means the same palette, and the further apart two
+    /// palettes look, the higher it gets, up to about `1.0`.
+    ///
+    /// Custom palette slots and widget sections aren't compared.
+    pub fn palette_distance(&self, other: &ThemeConfig) -> f32 {
+        let ours = compat::palette_slots(&self.theme.palette());
+        let theirs = compat::palette_slots(&other.theme.palette());
+        let total: f32 = ours.iter().zip(&theirs).map(|((_, a), (_, b))| color::distance(*a, *b)).sum();
+        total / ours.len() as f32
+    }
+}
+
+/// What [`ThemeLibrary::index`] reads from a theme file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThemeSummary {
+    pub path: PathBuf,
+    /// The theme name, `"Custom"` if not specified.
+    pub name: String,
+    pub metadata: ThemeMetadata,
+    /// The six palette colors, with variables resolved.
+    pub palette: Palette,
+}
+
+impl ThemeSummary {
+    /// Reads `path`, resolving only the top-level keys, `[metadata]`, and
+    /// `[palette]`.
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self, Error> {
+        let path = path.into();
+        let mut root = include::read(&path)?;
+
+        // Only the tables a summary reads; widget sections can be large.
+        if let Some(table) = root.as_table_mut() {
+            table.retain(|key, value| !value.is_table() || ["variables", "palette", "metadata", "script"].contains(&key));
+        }
+        script::run(&mut root)?;
+        variables::resolve(&mut root)?;
+
+        let raw: SummaryRaw = config::deserialize(&root, "")?;
+        Ok(ThemeSummary {
+            path,
+            name: raw.name.unwrap_or_else(|| "Custom".to_string()),
+            metadata: raw.metadata.unwrap_or_default(),
+            palette: raw.palette.palette(),
+        })
+    }
+
+    /// The relative luminance of the background; see [`ThemeConfig::brightness`].
+    pub fn brightness(&self) -> f32 {
+        color::luminance(self.palette.background)
+    }
+
+    /// Whether the background is dark; see [`ThemeConfig::is_dark`].
+    pub fn is_dark(&self) -> bool {
+        color::is_dark(self.palette.background)
+    }
+}
+
+/// An order for theme lists, used by [`ThemeLibrary::sorted`] and
+/// [`SortBy::sort_summaries`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortBy {
+    /// Alphabetically by name, ignoring case.
+    Name,
+    /// Alphabetically by `[metadata] author`, then by name. Themes without an
+    /// author come last.
+    Author,
+    /// From darkest to lightest background.
+    Brightness,
+}
+
+impl SortBy {
+    /// Sort the entries of a [`ThemeLibrary::index`] in this order.
+    pub fn sort_summaries(self, summaries: &mut [ThemeSummary]) {
+        summaries.sort_by(|a, b| self.compare(&SortKey::summary(a), &SortKey::summary(b)));
+    }
+
+    fn compare(self, a: &SortKey<'_>, b: &SortKey<'_>) -> Ordering {
+        let by_name = || a.name.to_lowercase().cmp(&b.name.to_lowercase());
+        match self {
+            SortBy::Name => by_name(),
+            SortBy::Author => {
+                let author = |key: &SortKey<'_>| key.metadata.author.as_ref().map(|a| a.to_lowercase());
+                match (author(a), author(b)) {
+                    (Some(a), Some(b)) => a.cmp(&b),
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (None, None) => Ordering::Equal,
+                }
+                .then_with(by_name)
+            }
+            SortBy::Brightness => a.brightness.total_cmp(&b.brightness),
+        }
+    }
+}
+
+/// What [`SortBy`] compares, from either a config or a summary.
+struct SortKey<'a> {
+    name: &'a str,
+    metadata: &'a ThemeMetadata,
+    brightness: f32,
+}
+
+impl<'a> SortKey<'a> {
+    fn config(config: &'a ThemeConfig) -> Self {
+        SortKey { name: config.name(), metadata: config.metadata(), brightness: config.brightness() }
+    }
+
+    fn summary(summary: &'a ThemeSummary) -> Self {
+        SortKey { name: &summary.name, metadata: &summary.metadata, brightness: summary.brightness() }
+    }
+}
+
+#[derive(Deserialize)]
+struct SummaryRaw {
+    name: Option<String>,
+    metadata: Option<ThemeMetadata>,
+    palette: PaletteRaw,
+}
+
+/// The `*.toml` files directly inside `dir`.
+pub(crate) fn theme_files(dir: &Path) -> Result<Vec<PathBuf>, Error> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_file() && path.extension().is_some_and(|ext| ext == "toml") {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::testing::{PALETTE, temp_path, write};
+
+    #[test]
+    fn index_reads_name_metadata_and_palette() {
+        let dir = temp_path("index");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir(&dir).unwrap();
+        let theme = PALETTE.replace("\"#1E88E5\"", "\"$accent\"");
+        write(
+            &dir.join("a.toml"),
+            &format!("name = \"A\"\n[variables]\naccent = \"#FF0000\"\n[metadata]\nauthor = \"Ann\"\n{theme}[button]\nbackground = \"$missing\"\n"),
+            60,
+        );
+        write(&dir.join("b.toml"), "[palette]\nbackground = \"#FFF\"\n", 60);
+
+        let index = ThemeLibrary::index(&dir).unwrap();
+        assert_eq!(index.len(), 1);
+        assert_eq!(index[0].name, "A");
+        assert_eq!(index[0].metadata.author.as_deref(), Some("Ann"));
+        assert_eq!(index[0].palette.primary, iced_core::Color::from_rgb8(0xFF, 0x00, 0x00));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn library_classifies_and_sorts() {
+        let mut library = ThemeLibrary::new();
+        let dark = PALETTE.replace("#FAFAFA", "#1B2838").replace("#202020", "#C7D5E0");
+        library.insert("a.toml", format!("name = \"zebra\"\n[metadata]\nauthor = \"Bo\"\n{PALETTE}").parse().unwrap());
+        library.insert("b.toml", format!("name = \"Night\"\n{dark}").parse().unwrap());
+        library.insert("c.toml", format!("name = \"apple\"\n[metadata]\nauthor = \"Al\"\n{PALETTE}").parse().unwrap());
+
+        let names = |themes: Vec<(&Path, &Arc<ThemeConfig>)>| themes.iter().map(|(_, c)| c.name().to_string()).collect::<Vec<_>>();
+        assert_eq!(names(library.dark().collect()), ["Night"]);
+        assert_eq!(names(library.light().collect()), ["zebra", "apple"]);
+        assert_eq!(names(library.sorted(SortBy::Name)), ["apple", "Night", "zebra"]);
+        assert_eq!(names(library.sorted(SortBy::Author)), ["apple", "zebra", "Night"]);
+        assert_eq!(names(library.sorted(SortBy::Brightness))[0], "Night");
+    }
+
+    #[test]
+    fn nearest_ranks_by_palette_distance() {
+        let mut library = ThemeLibrary::new();
+        let dark = PALETTE.replace("#FAFAFA", "#1B2838").replace("#202020", "#C7D5E0");
+        library.insert("a.toml", format!("name = \"Paper\"\n{PALETTE}").parse().unwrap());
+        library.insert("b.toml", format!("name = \"Night\"\n{dark}").parse().unwrap());
+        let teal = PALETTE.replace("#1E88E5", "#00897B");
+        library.insert("c.toml", format!("name = \"Teal\"\n{teal}").parse().unwrap());
+        library.insert("d.toml", format!("name = \"Copy\"\n{PALETTE}").parse().unwrap());
+
+        let names = |themes: Vec<(&Path, &Arc<ThemeConfig>)>| {
+            themes.iter().map(|(_, c)| c.name().to_string()).collect::<Vec<_>>()
+        };
+        let paper = library.get("a.toml").unwrap().clone();
+        assert_eq!(names(library.nearest(&paper, 5)), ["Copy", "Teal", "Night"]);
+        assert_eq!(names(library.nearest(&paper, 1)), ["Copy"]);
+        assert_eq!(paper.palette_distance(library.by_name("Copy").unwrap()), 0.0);
+
+        let outside: ThemeConfig = format!("name = \"Outside\"\n{dark}").parse().unwrap();
+        assert_eq!(names(library.nearest(&outside, 2)), ["Night", "Paper"]);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn scan_parallel_matches_from_dir() {
+        let dir = temp_path("parallel");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir(&dir).unwrap();
+        for i in 0..8 {
+            write(&dir.join(format!("{i}.toml")), &format!("name = \"T{i}\"\n{PALETTE}"), 60);
+        }
+        write(&dir.join("broken.toml"), "broken", 60);
+
+        let parallel = ThemeLibrary::scan_parallel(&dir).unwrap();
+        let serial = ThemeLibrary::from_dir(&dir).unwrap();
+        let names = |library: &ThemeLibrary| library.iter().map(|(_, c)| c.name().to_string()).collect::<Vec<_>>();
+        assert_eq!(parallel.len(), 8);
+        assert_eq!(names(&parallel), names(&serial));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,112 @@
+//! Macros for wiring theme styles into app state.
+
+/// Declares a struct holding the styles an app uses, read from a
+/// [`ThemeConfig`](crate::ThemeConfig) in one go and re-read after a reload.
+///
+/// List each field with its style type, and `=> "name"` after it for a
+/// `[<widget>.variants.<name>]` style; the macro wraps each in an `Option`,
+/// as the config's own accessors return them:
+///
+/// ```
+/// use iced_themer::style::{ButtonStyle, SliderStyle, TextInputStyle};
+/// use iced_themer::{ThemeConfig, watch_styles};
+///
+/// watch_styles! {
+///     /// The styles the settings screen draws with.
+///     pub struct Styles {
+///         button: ButtonStyle,
+///         search: TextInputStyle => "search",
+///         volume: SliderStyle,
+///     }
+/// }
+///
+/// # let config: ThemeConfig = "[palette]\nbackground = \"#FAFAFA\"\ntext = \"#202020\"\n\
+/// #     primary = \"#1E88E5\"\nsuccess = \"#43A047\"\nwarning = \"#FB8C00\"\ndanger = \"#E53935\"\n\
+/// #     [button]\nbackground = \"@primary\"\n\
+/// #     [text-input.variants.search]\nborder-width = 2.0\n".parse().unwrap();
+/// let mut styles = Styles::from_config(&config);
+/// assert!(styles.button.is_some() && styles.search.is_some() && styles.volume.is_none());
+///
+/// // After a reload, e.g. in a `ThemeHandle::on_reload` hook or on the next
+/// // update:
+/// styles.refresh(&config);
+/// ```
+///
+/// Widgets then take a field with `.themed(styles.button.as_ref())`. The
+/// struct derives `Debug`, `Clone`, and `Default`, its fields are public, and
+/// field names are free: a field's type, not its name, picks the section,
+/// through [`ThemeConfig::style`](crate::ThemeConfig::style), or
+/// [`ThemeConfig::style_variant`](crate::ThemeConfig::style_variant) for a
+/// variant.
+#[macro_export]
+macro_rules! watch_styles {
+    (@read $config:ident, $ty:ty) => {
+        $config.style::<$ty>().cloned()
+    };
+    (@read $config:ident, $ty:ty, $variant:literal) => {
+        $config.style_variant::<$ty>($variant).cloned()
+    };
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $($field:ident: $ty:ty $(=> $variant:literal)?),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Default)]
+        $vis struct $name {
+            $(pub $field: ::std::option::Option<$ty>,)*
+        }
+
+        impl $name {
+            /// Reads every style from `config`.
+            $vis fn from_config(config: &$crate::ThemeConfig) -> Self {
+                $name {
+                    $($field: $crate::watch_styles!(@read config, $ty $(, $variant)?),)*
+                }
+            }
+
+            /// Re-reads every style from `config`, after the theme changes.
+            $vis fn refresh(&mut self, config: &$crate::ThemeConfig) {
+                *self = Self::from_config(config);
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ThemeConfig;
+    use crate::style::{ButtonStyle, ContainerStyle};
+    use crate::testing::PALETTE;
+
+    crate::watch_styles! {
+        struct Styles {
+            primary: ButtonStyle,
+            card: ContainerStyle,
+            danger: ButtonStyle => "danger",
+        }
+    }
+
+    #[test]
+    fn refresh_follows_the_new_config() {
+        let config: ThemeConfig = format!("{PALETTE}[button]\nborder-width = 1.0\n").parse().unwrap();
+        let mut styles = Styles::from_config(&config);
+        assert!(styles.primary.is_some() && styles.card.is_none());
+
+        let reloaded = config.apply_patch("[container]\nborder-width = 2.0\n").unwrap();
+        styles.refresh(&reloaded);
+        assert!(styles.primary.is_some() && styles.card.is_some());
+        assert!(Styles::default().primary.is_none());
+    }
+
+    #[test]
+    fn variant_fields_read_the_named_variant() {
+        let toml = "[button]\nborder-width = 1.0\n\n[button.variants.danger]\nbackground = \"@danger\"\n";
+        let config: ThemeConfig = format!("{PALETTE}{toml}").parse().unwrap();
+        let styles = Styles::from_config(&config);
+        let style = styles.danger.unwrap().style_fn()(&config.theme(), iced_widget::button::Status::Active);
+        assert_eq!(style.background, Some(config.theme().palette().danger.into()));
+        assert_eq!(style.border.width, 1.0);
+    }
+}
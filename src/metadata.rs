@@ -0,0 +1,18 @@
+//! Descriptive information about a theme, from the optional `[metadata]` table.
+
+use serde::Deserialize;
+
+/// The `[metadata]` table: who made a theme and what it's for. Every field is
+/// optional and none of them affect styling.
+///
+/// ```toml
+/// [metadata]
+/// author      = "Jane Doe"
+/// description = "A calm blue theme for long sessions"
+/// ```
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct ThemeMetadata {
+    pub author: Option<String>,
+    pub description: Option<String>,
+}
@@ -0,0 +1,327 @@
+//! Extended-palette generation strategies.
+//!
+//! iced derives an [`Extended`] palette (weak/strong shades, secondary colors,
+//! text pairs) from the six core colors. The algorithm is selectable from TOML
+//! with `[palette] generator = "..."`.
+//!
+//! Widget fields can reuse the generated shades by name, e.g.
+//! `"extended.primary.weak"`; see [`substitute_extended`].
+
+use iced_core::Color;
+use iced_core::theme::Palette;
+use iced_core::theme::palette::{Extended, Pair};
+use serde::Deserialize;
+use toml::Value;
+
+use crate::color::{HexColor, Oklch, from_hsl, from_oklch, mix, to_hsl, to_oklch};
+use crate::compat;
+use crate::error::{Error, Reason};
+
+/// The algorithm used to derive iced's [`Extended`] palette.
+///
+/// - `oklch`: iced 0.14's generator, which shifts lightness in the
+///   perceptual Oklch space.
+/// - `hsl`: the same shade structure as `oklch`, but shifting HSL lightness.
+///   Keeps saturated hues saturated where Oklch can wash them out.
+/// - `legacy`: the generator used by iced 0.13 and earlier, where background
+///   shades are mixed towards the text color.
+///
+/// The default is the generator of the selected iced release, which is
+/// delegated to iced's own `Extended::generate`.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum PaletteGenerator {
+    Oklch,
+    Hsl,
+    Legacy,
+}
+
+impl Default for PaletteGenerator {
+    fn default() -> Self {
+        compat::NATIVE_GENERATOR
+    }
+}
+
+impl PaletteGenerator {
+    /// Derive the [`Extended`] palette for `palette` using this strategy.
+    pub fn generate(self, palette: Palette) -> Extended {
+        if self == compat::NATIVE_GENERATOR {
+            return Extended::generate(palette);
+        }
+        match self {
+            PaletteGenerator::Oklch => shaded(palette, oklch_deviate),
+            PaletteGenerator::Hsl => shaded(palette, hsl_deviate),
+            PaletteGenerator::Legacy => legacy(palette),
+        }
+    }
+}
+
+/// The background shade levels of an extended palette. iced 0.13 only has
+/// `Weak` and `Strong`.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "iced-0-13", allow(dead_code))]
+pub(crate) enum Shade {
+    Weakest,
+    Weaker,
+    Weak,
+    Neutral,
+    Strong,
+    Stronger,
+    Strongest,
+}
+
+impl Shade {
+    /// The shade's field name in iced's `palette::Background`.
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Shade::Weakest => "weakest",
+            Shade::Weaker => "weaker",
+            Shade::Weak => "weak",
+            Shade::Neutral => "neutral",
+            Shade::Strong => "strong",
+            Shade::Stronger => "stronger",
+            Shade::Strongest => "strongest",
+        }
+    }
+
+    /// How far iced 0.14 deviates this shade from the base background.
+    fn deviation(self) -> f32 {
+        match self {
+            Shade::Weakest => 0.03,
+            Shade::Weaker => 0.07,
+            Shade::Weak => 0.1,
+            Shade::Neutral => 0.125,
+            Shade::Strong => 0.15,
+            Shade::Stronger => 0.175,
+            Shade::Strongest => 0.2,
+        }
+    }
+
+    /// How far the legacy generator mixes this shade towards the text color.
+    /// iced 0.13 defined `Weak` and `Strong`; the others are interpolated.
+    fn legacy_mix(self) -> f32 {
+        match self {
+            Shade::Weakest => 0.05,
+            Shade::Weaker => 0.1,
+            Shade::Weak => 0.15,
+            Shade::Neutral => 0.275,
+            Shade::Strong => 0.4,
+            Shade::Stronger => 0.5,
+            Shade::Strongest => 0.6,
+        }
+    }
+}
+
+/// iced 0.14's shade structure, with lightness shifted by `deviate`.
+fn shaded(p: Palette, deviate: fn(Color, f32) -> Color) -> Extended {
+    let mut ext = Extended::generate(p);
+
+    for (shade, pair) in compat::background_shades(&mut ext.background) {
+        *pair = Pair::new(deviate(p.background, shade.deviation()), p.text);
+    }
+
+    let factor = if ext.is_dark { 0.2 } else { 0.4 };
+    ext.secondary.weak = Pair::new(mix(deviate(p.background, 0.1), p.text, factor), p.text);
+    ext.secondary.base = Pair::new(mix(deviate(p.background, 0.2), p.text, factor), p.text);
+    ext.secondary.strong = Pair::new(mix(deviate(p.background, 0.3), p.text, factor), p.text);
+
+    for (base, [b, weak, strong]) in compat::accent_shades(&mut ext, &p) {
+        *b = Pair::new(base, p.text);
+        *weak = Pair::new(mix(base, p.background, 0.4), p.text);
+        *strong = Pair::new(deviate(base, 0.1), p.text);
+    }
+
+    ext
+}
+
+/// iced 0.13's generator: HSL accents, background shades mixed towards text.
+fn legacy(p: Palette) -> Extended {
+    let mut ext = shaded(p, hsl_deviate);
+
+    for (shade, pair) in compat::background_shades(&mut ext.background) {
+        *pair = Pair::new(mix(p.background, p.text, shade.legacy_mix()), p.text);
+    }
+
+    let secondary = mix(p.background, p.text, 0.2);
+    ext.secondary.base = Pair::new(secondary, p.text);
+    ext.secondary.weak = Pair::new(mix(secondary, p.text, 0.1), p.text);
+    ext.secondary.strong = Pair::new(mix(secondary, p.text, 0.3), p.text);
+
+    ext
+}
+
+/// Shift HSL lightness away from the color's own end of the scale.
+fn hsl_deviate(color: Color, amount: f32) -> Color {
+    let mut hsl = to_hsl(color);
+    hsl.l = if hsl.l < 0.6 {
+        (hsl.l + amount).min(1.0)
+    } else {
+        (hsl.l - amount).max(0.0)
+    };
+    from_hsl(hsl)
+}
+
+/// Shift Oklch lightness away from the color's own end of the scale, bumping
+/// chroma slightly as iced 0.14 does.
+fn oklch_deviate(color: Color, amount: f32) -> Color {
+    let Oklch { l, c, h, a } = to_oklch(color);
+    let (l, c) = if l < 0.6 {
+        ((l + amount).min(1.0), c * (1.0 + 2.0 * amount / l.max(0.05)))
+    } else if c > 0.0 && c < (1.0 - l) / 2.0 {
+        ((l - amount).max(0.0), c * (1.0 + (0.2 / c).min(100.0) * amount))
+    } else {
+        ((l - amount).max(0.0), c)
+    };
+    from_oklch(Oklch { l, c, h, a })
+}
+
+/// Replaces every `"extended.<group>.<shade>"` string outside `[palette]` with
+/// that pair's color from `ext`, or with its text color for
+/// `"extended.<group>.<shade>.text"`.
+///
+/// Groups are `background`, `primary`, `secondary`, `success`, `warning`
+/// (iced 0.14), and `danger`; shades are `base`, `weak`, and `strong`, plus
+/// `weakest`, `weaker`, `neutral`, `stronger`, and `strongest` for
+/// `background` on iced 0.14.
+pub(crate) fn substitute_extended(root: &mut Value, ext: &Extended) -> Result<(), Error> {
+    let Some(table) = root.as_table_mut() else {
+        return Ok(());
+    };
+
+    let mut pairs = vec![("background.base".to_string(), ext.background.base)];
+    let mut background = ext.background;
+    for (shade, pair) in compat::background_shades(&mut background) {
+        pairs.push((format!("background.{}", shade.name()), *pair));
+    }
+    for (group, [base, weak, strong]) in compat::accent_pairs(ext) {
+        pairs.push((format!("{group}.base"), base));
+        pairs.push((format!("{group}.weak"), weak));
+        pairs.push((format!("{group}.strong"), strong));
+    }
+
+    for (key, value) in table.iter_mut().filter(|(key, _)| *key != "palette") {
+        substitute_pairs(value, key, &pairs)?;
+    }
+    Ok(())
+}
+
+fn substitute_pairs(value: &mut Value, path: &str, pairs: &[(String, Pair)]) -> Result<(), Error> {
+    match value {
+        Value::String(s) => {
+            if let Some(reference) = s.strip_prefix("extended.") {
+                let (name, text) = match reference.strip_suffix(".text") {
+                    Some(name) => (name, true),
+                    None => (reference, false),
+                };
+                let pair = pairs.iter().find(|(n, _)| n == name).map(|(_, pair)| pair).ok_or_else(|| {
+                    Error::InvalidColor {
+                        field: path.to_string(),
+                        value: s.clone(),
+                        reason: Reason::UnknownExtendedColor,
+                    }
+                })?;
+                let color = if text { pair.text } else { pair.color };
+                *s = HexColor(color).to_string();
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                substitute_pairs(item, path, pairs)?;
+            }
+        }
+        Value::Table(table) => {
+            for (key, item) in table.iter_mut() {
+                substitute_pairs(item, &format!("{path}.{key}"), pairs)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn palette() -> Palette {
+        compat::palette(
+            Color::from_rgb8(0x1B, 0x28, 0x38),
+            Color::from_rgb8(0xC7, 0xD5, 0xE0),
+            Color::from_rgb8(0x66, 0xC0, 0xF4),
+            Color::from_rgb8(0x4C, 0xAF, 0x50),
+            Color::from_rgb8(0xFF, 0xC1, 0x07),
+            Color::from_rgb8(0xF4, 0x43, 0x36),
+        )
+    }
+
+    #[test]
+    fn native_generator_matches_iced_default() {
+        assert_eq!(compat::NATIVE_GENERATOR.generate(palette()), Extended::generate(palette()));
+    }
+
+    #[test]
+    fn hsl_differs_from_oklch() {
+        let hsl = PaletteGenerator::Hsl.generate(palette());
+        let oklch = PaletteGenerator::Oklch.generate(palette());
+        assert_ne!(hsl.primary.strong, oklch.primary.strong);
+    }
+
+    #[test]
+    fn hsl_strong_keeps_hue() {
+        let p = palette();
+        let strong = PaletteGenerator::Hsl.generate(p).primary.strong.color;
+        assert!((to_hsl(strong).h - to_hsl(p.primary).h).abs() < 1.0);
+    }
+
+    // On iced 0.13, `legacy` is iced's own generator, which
+    // `native_generator_matches_iced_default` covers.
+    #[cfg(feature = "iced-0-14")]
+    #[test]
+    fn legacy_mixes_background_towards_text() {
+        let p = palette();
+        let ext = PaletteGenerator::Legacy.generate(p);
+        assert_eq!(ext.background.weak.color, mix(p.background, p.text, 0.15));
+        assert_eq!(ext.background.strong.color, mix(p.background, p.text, 0.4));
+    }
+
+    #[test]
+    fn base_colors_are_preserved() {
+        let p = palette();
+        for generator in [PaletteGenerator::Oklch, PaletteGenerator::Hsl, PaletteGenerator::Legacy] {
+            let ext = generator.generate(p);
+            assert_eq!(ext.primary.base.color, p.primary);
+            assert_eq!(ext.background.base.color, p.background);
+        }
+    }
+
+    #[test]
+    fn extended_reference_resolves_to_generated_shade() {
+        let ext = PaletteGenerator::default().generate(palette());
+        let mut v: Value = toml::from_str(
+            r##"
+[button]
+background = "extended.primary.weak"
+text-color = "extended.primary.weak.text"
+
+[button.hovered]
+background = "extended.background.strong"
+"##,
+        )
+        .unwrap();
+        substitute_extended(&mut v, &ext).unwrap();
+        assert_eq!(v["button"]["background"].as_str(), Some(HexColor(ext.primary.weak.color).to_string().as_str()));
+        assert_eq!(v["button"]["text-color"].as_str(), Some(HexColor(ext.primary.weak.text).to_string().as_str()));
+        assert_eq!(
+            v["button"]["hovered"]["background"].as_str(),
+            Some(HexColor(ext.background.strong.color).to_string().as_str())
+        );
+    }
+
+    #[test]
+    fn unknown_extended_reference_returns_error() {
+        let ext = PaletteGenerator::default().generate(palette());
+        let mut v: Value = toml::from_str("[button]\nbackground = \"extended.primary.medium\"\n").unwrap();
+        let err = substitute_extended(&mut v, &ext).unwrap_err();
+        assert!(matches!(err, Error::InvalidColor { ref field, .. } if field == "button.background"), "got: {err}");
+    }
+}
@@ -0,0 +1,511 @@
+//! Named, reusable definitions expanded before variable resolution.
+//!
+//! - `[gradients.<name>]` tables are substituted wherever a value is the
+//!   string `"$gradients.<name>"`.
+//! - `[shadows.<name>]` tables expand `shadow = "<name>"` in a widget section
+//!   into its `shadow-*` fields. Fields the section sets itself win.
+//! - `[borders.<name>]` tables expand `border = "<name>"` the same way.
+//! - `[elevation]` holds a numbered scale of shadows, `[elevation.2]` and so
+//!   on, that `shadow = "@elevation.2"` expands like a named shadow.
+//! - `border = { width = 1.0, ... }` and `shadow = { ... }` tables in place
+//!   of a name expand the same way, without a definition.
+//! - Shadows, named or inline, may also give `blur` for `blur-radius`, and
+//!   `offset = [x, y]`, CSS style.
+//!
+//! Pairs like `shadow-offset = [x, y]` are split into their `-x`/`-y` fields
+//! by [`split_pairs`], after variables are resolved, so a variable can hold
+//! the whole pair.
+//!
+//! The definition tables are removed from the document, and since expansion
+//! runs before variables are resolved, definitions may use `$variables` too.
+
+use toml::{Table, Value};
+
+use crate::error::{Error, Reason};
+use crate::style::WIDGET_SECTIONS;
+
+/// A preset kind that expands one string-valued key into flat, prefixed
+/// fields: `shadow = "name"` → `shadow-color`, `shadow-offset-x`, ...
+struct FieldPreset {
+    table: &'static str,
+    key: &'static str,
+    kind: &'static str,
+    fields: &'static [&'static str],
+    /// Shorthand fields and the field they stand for.
+    aliases: &'static [(&'static str, &'static str)],
+    /// Fields taking an `[x, y]` pair, and the two fields it fills.
+    pairs: &'static [(&'static str, [&'static str; 2])],
+    /// A table of numbered levels, referenced as `"@<scale>.<level>"`.
+    scale: Option<&'static str>,
+}
+
+const SHADOWS: FieldPreset = FieldPreset {
+    table: "shadows",
+    key: "shadow",
+    kind: "shadow preset",
+    fields: &["color", "offset", "offset-x", "offset-y", "blur-radius", "enabled"],
+    aliases: &[("blur", "blur-radius")],
+    pairs: &[("offset", ["offset-x", "offset-y"])],
+    scale: Some("elevation"),
+};
+
+const BORDERS: FieldPreset = FieldPreset {
+    table: "borders",
+    key: "border",
+    kind: "border preset",
+    fields: &["width", "color", "radius", "enabled"],
+    aliases: &[],
+    pairs: &[],
+    scale: None,
+};
+
+/// Expands every preset reference in the widget sections of `root` and
+/// removes the definitions.
+pub(crate) fn expand(root: &mut Value) -> Result<(), Error> {
+    let Some(table) = root.as_table_mut() else {
+        return Ok(());
+    };
+
+    let gradients = definitions(table, "gradients")?;
+    let mut presets = Vec::new();
+    for preset in [&SHADOWS, &BORDERS] {
+        let mut defs = definitions(table, preset.table)?;
+        for (name, def) in defs.iter_mut().filter_map(|(n, d)| Some((n, d.as_table_mut()?))) {
+            normalize_fields(preset, def, &format!("{}.{name}", preset.table))?;
+        }
+        let mut levels = match preset.scale {
+            Some(scale) => levels(table, scale)?,
+            None => Table::new(),
+        };
+        for (level, def) in levels.iter_mut().filter_map(|(n, d)| Some((n, d.as_table_mut()?))) {
+            normalize_fields(preset, def, &format!("{}.{level}", preset.scale.unwrap_or_default()))?;
+        }
+        presets.push((preset, defs, levels));
+    }
+
+    for (key, value) in widget_sections(table) {
+        if !gradients.is_empty() {
+            substitute_gradients(value, key, &gradients)?;
+        }
+        for (preset, defs, levels) in &presets {
+            expand_fields(value, key, preset, defs, levels)?;
+        }
+    }
+    Ok(())
+}
+
+/// Removes the `[<kind>]` table from `root`, checking that every entry is itself a table.
+fn definitions(root: &mut Table, kind: &str) -> Result<Table, Error> {
+    let Some(value) = root.remove(kind) else {
+        return Ok(Table::new());
+    };
+    let Value::Table(defs) = value else {
+        return Err(invalid(kind, &value, Reason::NotATable));
+    };
+    for (name, def) in &defs {
+        if !def.is_table() {
+            return Err(invalid(&format!("{kind}.{name}"), def, Reason::NotATable));
+        }
+    }
+    Ok(defs)
+}
+
+/// Removes the `[<scale>]` table of numbered levels from `root`, checking
+/// that every level is a table with a number for a name.
+fn levels(root: &mut Table, scale: &str) -> Result<Table, Error> {
+    let levels = definitions(root, scale)?;
+    match levels.iter().find(|(level, _)| level.parse::<u32>().is_err()) {
+        Some((level, def)) => {
+            let reason = Reason::Other(format!("`[{scale}]` levels are numbered, like `{scale}.2`"));
+            Err(invalid(&format!("{scale}.{level}"), def, reason))
+        }
+        None => Ok(levels),
+    }
+}
+
+fn substitute_gradients(value: &mut Value, path: &str, gradients: &Table) -> Result<(), Error> {
+    match value {
+        Value::String(s) => {
+            if let Some(name) = s.strip_prefix("$gradients.") {
+                let gradient = gradients.get(name).ok_or_else(|| Error::InvalidValue {
+                    field: path.to_string(),
+                    value: format!("\"{s}\""),
+                    reason: Reason::UndefinedPreset { kind: "gradient".to_string(), name: name.to_string() },
+                })?;
+                *value = gradient.clone();
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                substitute_gradients(item, path, gradients)?;
+            }
+        }
+        Value::Table(table) => {
+            for (key, item) in table.iter_mut() {
+                substitute_gradients(item, &format!("{path}.{key}"), gradients)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Rewrites `preset`'s aliases in `def`, found at `path`, to the fields they
+/// stand for, and fails on a field `preset` doesn't know or a pair that isn't
+/// one. A field given both ways keeps its long form.
+fn normalize_fields(preset: &FieldPreset, def: &mut Table, path: &str) -> Result<(), Error> {
+    for (alias, field) in preset.aliases {
+        if let Some(v) = def.remove(*alias) {
+            def.entry(*field).or_insert(v);
+        }
+    }
+    // A string is a variable, checked by `split_pairs` once it's resolved.
+    for (pair, _) in preset.pairs {
+        if let Some(v) = def.get(*pair).filter(|v| !v.is_str()) {
+            split_pair(v, &format!("{path}.{pair}"))?;
+        }
+    }
+    match def.iter().find(|(f, _)| !preset.fields.contains(&f.as_str())) {
+        Some((field, v)) => Err(invalid(
+            &format!("{path}.{field}"),
+            v,
+            Reason::UnknownField { expected: preset.fields.iter().map(|f| f.to_string()).collect() },
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Replaces `<key> = "<name>"` in every table under `value` with the named
+/// definition's fields, prefixed with `<key>-`, `<key> = "@<scale>.<level>"`
+/// with the level's, and `<key> = { ... }` with its own fields.
+fn expand_fields(
+    value: &mut Value,
+    path: &str,
+    preset: &FieldPreset,
+    defs: &Table,
+    levels: &Table,
+) -> Result<(), Error> {
+    let Value::Table(table) = value else {
+        return Ok(());
+    };
+
+    let def = match table.get(preset.key) {
+        Some(Value::String(reference)) => {
+            let (defs, name, kind) = match level(preset, reference) {
+                Some((scale, level)) => (levels, level, format!("{scale} level")),
+                None => (defs, reference.as_str(), preset.kind.to_string()),
+            };
+            Some(defs.get(name).and_then(Value::as_table).cloned().ok_or_else(|| Error::InvalidValue {
+                field: format!("{path}.{}", preset.key),
+                value: format!("\"{reference}\""),
+                reason: Reason::UndefinedPreset { kind, name: name.to_string() },
+            })?)
+        }
+        Some(Value::Table(inline)) => {
+            let mut inline = inline.clone();
+            normalize_fields(preset, &mut inline, &format!("{path}.{}", preset.key))?;
+            Some(inline)
+        }
+        _ => None,
+    };
+    if let Some(def) = def {
+        table.remove(preset.key);
+        for (field, v) in def {
+            table.entry(format!("{}-{field}", preset.key)).or_insert(v);
+        }
+    }
+
+    for (key, item) in table.iter_mut() {
+        expand_fields(item, &format!("{path}.{key}"), preset, defs, levels)?;
+    }
+    Ok(())
+}
+
+/// The scale and level named by `reference`, when it's a
+/// `"@<scale>.<level>"` reference to `preset`'s scale.
+fn level<'a>(preset: &FieldPreset, reference: &'a str) -> Option<(&'static str, &'a str)> {
+    let scale = preset.scale?;
+    Some((scale, reference.strip_prefix('@')?.strip_prefix(scale)?.strip_prefix('.')?))
+}
+
+/// Splits every `[x, y]` pair in the widget sections of `root`, like
+/// `shadow-offset = [0, 2]`, into its two fields. Fields the table sets itself win.
+pub(crate) fn split_pairs(root: &mut Value) -> Result<(), Error> {
+    let Some(table) = root.as_table_mut() else {
+        return Ok(());
+    };
+    for (key, value) in widget_sections(table) {
+        split_pairs_in(value, key)?;
+    }
+    Ok(())
+}
+
+/// The widget sections of `root`. Only they take presets and pairs: in
+/// `[icons]` or `[metadata]`, a key like `border` or `shadow` is just a name.
+pub(crate) fn widget_sections(root: &mut Table) -> impl Iterator<Item = (&String, &mut Value)> {
+    root.iter_mut().filter(|(key, _)| WIDGET_SECTIONS.contains(&key.as_str()))
+}
+
+fn split_pairs_in(value: &mut Value, path: &str) -> Result<(), Error> {
+    let Value::Table(table) = value else {
+        return Ok(());
+    };
+    for preset in [&SHADOWS, &BORDERS] {
+        for (pair, [x, y]) in preset.pairs {
+            let key = format!("{}-{pair}", preset.key);
+            let Some(v) = table.remove(&key) else {
+                continue;
+            };
+            let (vx, vy) = split_pair(&v, &format!("{path}.{key}"))?;
+            table.entry(format!("{}-{x}", preset.key)).or_insert(vx);
+            table.entry(format!("{}-{y}", preset.key)).or_insert(vy);
+        }
+    }
+    for (key, item) in table.iter_mut() {
+        split_pairs_in(item, &format!("{path}.{key}"))?;
+    }
+    Ok(())
+}
+
+/// The halves of `v`, found at `field`, if it's an `[x, y]` pair.
+fn split_pair(v: &Value, field: &str) -> Result<(Value, Value), Error> {
+    match v.as_array().map(Vec::as_slice) {
+        Some([x, y]) => Ok((x.clone(), y.clone())),
+        _ => Err(invalid(field, v, Reason::Other("expected an `[x, y]` pair".to_string()))),
+    }
+}
+
+/// Whether `key` is a field [`split_pairs`] splits, like `shadow-offset`.
+pub(crate) fn is_pair_field(key: &str) -> bool {
+    [&SHADOWS, &BORDERS].into_iter().any(|preset| {
+        key.strip_prefix(preset.key)
+            .and_then(|rest| rest.strip_prefix('-'))
+            .is_some_and(|pair| preset.pairs.iter().any(|(p, _)| *p == pair))
+    })
+}
+
+fn invalid(field: &str, value: &Value, reason: Reason) -> Error {
+    Error::InvalidValue {
+        field: field.to_string(),
+        value: value.to_string(),
+        reason,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Value {
+        toml::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn gradient_reference_is_replaced_by_definition() {
+        let mut v = parse(
+            r##"
+[gradients.ocean]
+angle = 90
+stops = [
+  { offset = 0.0, color = "#1B2838" },
+  { offset = 1.0, color = "$deep" },
+]
+
+[button]
+background = "$gradients.ocean"
+
+[button.hovered]
+background = "$gradients.ocean"
+"##,
+        );
+        expand(&mut v).unwrap();
+        assert!(v.get("gradients").is_none());
+        assert_eq!(v["button"]["background"]["angle"].as_integer(), Some(90));
+        assert_eq!(v["button"]["hovered"]["background"]["stops"][1]["color"].as_str(), Some("$deep"));
+    }
+
+    #[test]
+    fn undefined_gradient_returns_error() {
+        let mut v = parse(
+            r##"
+[gradients.ocean]
+angle = 0
+stops = []
+
+[container]
+background = "$gradients.sunset"
+"##,
+        );
+        let err = expand(&mut v).unwrap_err();
+        assert!(matches!(err, Error::InvalidValue { ref field, .. } if field == "container.background"), "got: {err}");
+    }
+
+    #[test]
+    fn gradient_definition_must_be_a_table() {
+        let mut v = parse("[gradients]\nocean = \"#000000\"\n");
+        assert!(expand(&mut v).is_err());
+    }
+
+    #[test]
+    fn shadow_preset_expands_into_fields() {
+        let mut v = parse(
+            r##"
+[shadows.elevation-2]
+color       = "#00000040"
+offset-y    = 2.0
+blur-radius = 6.0
+
+[button]
+shadow = "elevation-2"
+
+[button.hovered]
+shadow          = "elevation-2"
+shadow-offset-y = 4.0
+"##,
+        );
+        expand(&mut v).unwrap();
+        assert!(v.get("shadows").is_none());
+        assert!(v["button"].get("shadow").is_none());
+        assert_eq!(v["button"]["shadow-color"].as_str(), Some("#00000040"));
+        assert_eq!(v["button"]["shadow-blur-radius"].as_float(), Some(6.0));
+        assert_eq!(v["button"]["hovered"]["shadow-offset-y"].as_float(), Some(4.0));
+    }
+
+    #[test]
+    fn undefined_shadow_preset_returns_error() {
+        let mut v = parse("[shadows.soft]
+blur-radius = 2.0
+
+[container]
+shadow = \"hard\"
+");
+        let err = expand(&mut v).unwrap_err();
+        assert!(err.to_string().contains("undefined shadow preset `hard`"), "got: {err}");
+    }
+
+    #[test]
+    fn unknown_shadow_field_returns_error() {
+        let mut v = parse("[shadows.soft]
+spread = 2.0
+");
+        let err = expand(&mut v).unwrap_err();
+        assert!(matches!(err, Error::InvalidValue { ref field, .. } if field == "shadows.soft.spread"), "got: {err}");
+    }
+
+    #[test]
+    fn shadow_table_takes_css_style_fields() {
+        let mut v = parse(
+            r##"
+[shadows.lifted]
+offset = [0, 4]
+blur   = 12
+
+[button]
+shadow = { color = "#00000080", offset = [1, 2], blur = 8 }
+
+[button.hovered]
+shadow = "lifted"
+shadow-offset-x = 2
+"##,
+        );
+        expand(&mut v).unwrap();
+        split_pairs(&mut v).unwrap();
+        assert_eq!(v["button"]["shadow-color"].as_str(), Some("#00000080"));
+        assert_eq!(v["button"]["shadow-offset-x"].as_integer(), Some(1));
+        assert_eq!(v["button"]["shadow-offset-y"].as_integer(), Some(2));
+        assert_eq!(v["button"]["shadow-blur-radius"].as_integer(), Some(8));
+        assert_eq!(v["button"]["hovered"]["shadow-offset-x"].as_integer(), Some(2));
+        assert_eq!(v["button"]["hovered"]["shadow-offset-y"].as_integer(), Some(4));
+        assert_eq!(v["button"]["hovered"]["shadow-blur-radius"].as_integer(), Some(12));
+
+        let mut v = parse("[container]\nshadow = { offset = [1, 2, 3] }\n");
+        let err = expand(&mut v).unwrap_err();
+        assert_eq!(err.field(), Some("container.shadow.offset"), "got: {err}");
+    }
+
+    #[test]
+    fn elevation_levels_expand_like_shadow_presets() {
+        let mut v = parse(
+            r##"
+[elevation.1]
+color  = "#00000033"
+offset = [0, 1]
+blur   = 2
+
+[elevation.2]
+color  = "#00000040"
+offset = [0, 2]
+blur   = 6
+
+[container]
+shadow = "@elevation.1"
+
+[button.hovered]
+shadow             = "@elevation.2"
+shadow-blur-radius = 8
+"##,
+        );
+        expand(&mut v).unwrap();
+        split_pairs(&mut v).unwrap();
+        assert!(v.get("elevation").is_none());
+        assert_eq!(v["container"]["shadow-color"].as_str(), Some("#00000033"));
+        assert_eq!(v["container"]["shadow-offset-y"].as_integer(), Some(1));
+        assert_eq!(v["button"]["hovered"]["shadow-blur-radius"].as_integer(), Some(8));
+        assert_eq!(v["button"]["hovered"]["shadow-offset-y"].as_integer(), Some(2));
+
+        let mut v = parse("[elevation.1]\nblur = 2\n\n[container]\nshadow = \"@elevation.3\"\n");
+        let err = expand(&mut v).unwrap_err();
+        assert!(err.to_string().contains("undefined elevation level `3`"), "got: {err}");
+
+        let mut v = parse("[elevation.high]\nblur = 2\n");
+        assert_eq!(expand(&mut v).unwrap_err().field(), Some("elevation.high"));
+    }
+
+    #[test]
+    fn border_preset_expands_into_fields() {
+        let mut v = parse(
+            r##"
+[borders.input]
+width  = 1.0
+color  = "$outline"
+radius = [4.0, 4.0, 0.0, 0.0]
+
+[text-input]
+border = "input"
+
+[text-input.focused]
+border-color = "#66C0F4"
+"##,
+        );
+        expand(&mut v).unwrap();
+        assert!(v.get("borders").is_none());
+        assert_eq!(v["text-input"]["border-width"].as_float(), Some(1.0));
+        assert_eq!(v["text-input"]["border-color"].as_str(), Some("$outline"));
+        assert_eq!(v["text-input"]["border-radius"].as_array().map(Vec::len), Some(4));
+        assert!(v["text-input"]["focused"].get("border-width").is_none());
+    }
+
+    #[test]
+    fn border_table_expands_in_place() {
+        let mut v = parse(
+            r##"
+[button]
+border       = { width = 2, color = "#FFFFFF", radius = 4 }
+border-width = 1.0
+
+[button.hovered]
+border = { color = "$accent" }
+"##,
+        );
+        expand(&mut v).unwrap();
+        assert!(v["button"].get("border").is_none());
+        assert_eq!(v["button"]["border-width"].as_float(), Some(1.0));
+        assert_eq!(v["button"]["border-color"].as_str(), Some("#FFFFFF"));
+        assert_eq!(v["button"]["border-radius"].as_integer(), Some(4));
+        assert_eq!(v["button"]["hovered"]["border-color"].as_str(), Some("$accent"));
+
+        let mut v = parse("[checkbox]\nborder = { thickness = 2 }\n");
+        let err = expand(&mut v).unwrap_err();
+        assert_eq!(err.field(), Some("checkbox.border.thickness"), "got: {err}");
+    }
+}
@@ -0,0 +1,101 @@
+//! Snapping a theme's colors to what a low-color display can show.
+
+use iced_core::Color;
+
+use crate::color::{self, Oklch};
+
+/// The colors a display can show, for [`ThemeConfig::quantized`](crate::ThemeConfig::quantized).
+///
+/// Every color is replaced by the nearest one the depth allows, judged by
+/// perceptual (Oklab) distance, and keeps its alpha.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColorDepth {
+    /// `n` bits per red, green, and blue channel, from 1 to 8. `Bits(2)` is
+    /// the 64 colors of a 6-bit display.
+    Bits(u8),
+    /// The 240 fixed colors of a 256-color terminal: the 6×6×6 color cube
+    /// and the 24-step gray ramp. The first 16 are left out, since every
+    /// terminal theme redefines them.
+    Ansi256,
+    /// `n` evenly spaced grays from black to white, like `Grays(16)` for a
+    /// 4-bit e-ink panel. At least 2.
+    Grays(u8),
+    /// The colors of a fixed palette, such as a display's hardware palette.
+    /// An empty palette leaves colors unchanged.
+    Palette(&'static [Color]),
+}
+
+impl ColorDepth {
+    /// The color nearest `color` at this depth.
+    pub(crate) fn quantize(self, color: Color) -> Color {
+        let snapped = match self {
+            ColorDepth::Bits(bits) => {
+                let levels = f32::from((1u16 << bits.clamp(1, 8)) - 1);
+                let snap = |v: f32| (v * levels).round() / levels;
+                Color::from_rgb(snap(color.r), snap(color.g), snap(color.b))
+            }
+            ColorDepth::Ansi256 => nearest(color, ansi256()),
+            ColorDepth::Grays(n) => {
+                let levels = f32::from(n.max(2) - 1);
+                let gray = color::from_oklch(Oklch { l: color::to_oklch(color).l, c: 0.0, h: 0.0, a: 1.0 });
+                let v = (gray.r * levels).round() / levels;
+                Color::from_rgb(v, v, v)
+            }
+            ColorDepth::Palette([]) => color,
+            ColorDepth::Palette(palette) => nearest(color, palette.iter().copied()),
+        };
+        Color { a: color.a, ..snapped }
+    }
+}
+
+/// The candidate perceptually closest to `color`.
+fn nearest(color: Color, candidates: impl Iterator<Item = Color>) -> Color {
+    candidates
+        .map(|candidate| (candidate, color::distance(color, candidate)))
+        .min_by(|x, y| x.1.total_cmp(&y.1))
+        .map_or(color, |(candidate, _)| candidate)
+}
+
+/// Colors 16 to 255 of the xterm 256-color palette.
+fn ansi256() -> impl Iterator<Item = Color> {
+    const CUBE: [u8; 6] = [0x00, 0x5F, 0x87, 0xAF, 0xD7, 0xFF];
+    let cube = (0..216).map(|i| Color::from_rgb8(CUBE[i / 36], CUBE[i / 6 % 6], CUBE[i % 6]));
+    let grays = (0..24).map(|i| {
+        let v = 8 + 10 * i;
+        Color::from_rgb8(v, v, v)
+    });
+    cube.chain(grays)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bits_snap_each_channel() {
+        let color = Color::from_rgba8(0x40, 0x80, 0xC0, 0.5);
+        assert_eq!(ColorDepth::Bits(1).quantize(color), Color::from_rgba(0.0, 1.0, 1.0, 0.5));
+        assert_eq!(ColorDepth::Bits(8).quantize(Color::from_rgb8(0x12, 0x34, 0x56)), Color::from_rgb8(0x12, 0x34, 0x56));
+    }
+
+    #[test]
+    fn ansi256_picks_cube_and_ramp_colors() {
+        assert_eq!(ColorDepth::Ansi256.quantize(Color::from_rgb8(0xFE, 0x01, 0x02)), Color::from_rgb8(0xFF, 0x00, 0x00));
+        assert_eq!(ColorDepth::Ansi256.quantize(Color::from_rgb8(0x7F, 0x7F, 0x7F)), Color::from_rgb8(0x80, 0x80, 0x80));
+    }
+
+    #[test]
+    fn grays_keep_lightness() {
+        let gray = ColorDepth::Grays(2).quantize(Color::from_rgb8(0xFF, 0xFF, 0x00));
+        assert_eq!(gray, Color::WHITE);
+        assert_eq!(ColorDepth::Grays(2).quantize(Color::from_rgb8(0x10, 0x00, 0x40)), Color::BLACK);
+    }
+
+    #[test]
+    fn palette_picks_the_nearest_entry() {
+        const PALETTE: &[Color] = &[Color::BLACK, Color::WHITE, Color::from_rgb(1.0, 0.0, 0.0)];
+        let red = Color::from_rgb8(0xC0, 0x20, 0x20);
+        assert_eq!(ColorDepth::Palette(PALETTE).quantize(red), PALETTE[2]);
+        assert_eq!(ColorDepth::Palette(&[]).quantize(red), red);
+    }
+}
@@ -0,0 +1,163 @@
+//! Rebuilding a theme after an edit, re-resolving only what changed.
+
+use toml::Value;
+
+use crate::ThemeConfig;
+use crate::error::Error;
+use crate::style::{WIDGET_SECTIONS, widget_sections};
+
+impl ThemeConfig {
+    /// Builds the config for `source`, an edited version of this config's
+    /// TOML, re-resolving only the widget sections that changed.
+    ///
+    /// Widget sections depend on everything else (variables, palette,
+    /// definitions, defaults), so an edit anywhere else rebuilds the whole
+    /// theme, as does reloading a derived config.
+    pub(crate) fn reload(&self, source: Value) -> Result<ThemeConfig, Error> {
+        let (Some(old), Some(new)) = (self.source.as_table(), source.as_table()) else {
+            return ThemeConfig::from_value(source);
+        };
+        let global_changed = old
+            .keys()
+            .chain(new.keys())
+            .filter(|key| !WIDGET_SECTIONS.contains(&key.as_str()))
+            .any(|key| old.get(key) != new.get(key));
+        // With fallbacks, one widget section can be built from another.
+        if global_changed || !self.derivations.is_empty() || new.contains_key("fallbacks") {
+            return ThemeConfig::from_value(source);
+        }
+
+        let changed: Vec<&str> = WIDGET_SECTIONS.iter().copied().filter(|s| old.get(*s) != new.get(*s)).collect();
+        let mut config = self.clone();
+        if !changed.is_empty() {
+            // The unchanged global sections plus the changed widget sections.
+            let partial = new
+                .iter()
+                .filter(|(key, _)| !WIDGET_SECTIONS.contains(&key.as_str()) || changed.contains(&key.as_str()))
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect();
+            let fresh = ThemeConfig::from_value(Value::Table(partial))?;
+
+            for section in changed {
+                config.take_section(&fresh, section);
+                let resolved = fresh.resolved.get(section).cloned();
+                if let Some(table) = config.resolved.as_table_mut() {
+                    match resolved {
+                        Some(value) => table.insert(section.to_string(), value),
+                        None => table.remove(section),
+                    };
+                }
+            }
+        }
+        config.source = source;
+        Ok(config)
+    }
+}
+
+macro_rules! take_section {
+    ($($(#[$attr:meta])* $field:ident $name:literal $style:ident $raw:ident $resolve:ident $catalog:ident $statuses:tt;)*) => {
+        impl ThemeConfig {
+            /// Replaces the styles built from the widget section `section`
+            /// with those of `other`.
+            fn take_section(&mut self, other: &ThemeConfig, section: &str) {
+                match section {
+                    "root" => self.root = other.root,
+                    $($(#[$attr])* $name => {
+                        self.$field = other.$field;
+                        self.variants.$field = other.variants.$field.clone();
+                    })*
+                    _ => {}
+                }
+            }
+        }
+    };
+}
+widget_sections!(take_section);
+
+#[cfg(test)]
+mod tests {
+    use iced_widget::{button, slider};
+
+    use super::*;
+    use crate::ThemeDiff;
+
+    const BASE: &str = r##"
+[variables]
+accent = "#1E88E5"
+
+[palette]
+background = "#FAFAFA"
+text       = "#202020"
+primary    = "$accent"
+success    = "#43A047"
+warning    = "#FB8C00"
+danger     = "#E53935"
+
+[button]
+background = "$accent"
+
+[slider]
+rail-width = 4.0
+"##;
+
+    fn reload(old: &str, new: &str) -> (ThemeConfig, ThemeConfig) {
+        let old: ThemeConfig = old.parse().unwrap();
+        let reloaded = old.reload(toml::from_str(new).unwrap()).unwrap();
+        let full = new.parse().unwrap();
+        (reloaded, full)
+    }
+
+    fn button_active(config: &ThemeConfig) -> button::Style {
+        config.button().unwrap().style_fn()(&config.theme(), button::Status::Active)
+    }
+
+    #[test]
+    fn changed_section_matches_full_rebuild() {
+        let edited = BASE.replace("rail-width = 4.0", "rail-width = 6.0\n\n[button.hovered]\nbackground = \"#FF0000\"");
+        let (reloaded, full) = reload(BASE, &edited);
+        let status = button::Status::Hovered;
+        assert_eq!(
+            reloaded.button().unwrap().style_fn()(&reloaded.theme(), status),
+            full.button().unwrap().style_fn()(&full.theme(), status)
+        );
+        let status = slider::Status::Active;
+        assert_eq!(reloaded.slider().unwrap().style_fn()(&reloaded.theme(), status).rail.width, 6.0);
+        assert_eq!(reloaded.resolved, full.resolved);
+    }
+
+    #[test]
+    fn removed_section_is_dropped() {
+        let edited = BASE.replace("[slider]\nrail-width = 4.0\n", "");
+        let (reloaded, full) = reload(BASE, &edited);
+        assert!(reloaded.slider().is_none());
+        assert_eq!(reloaded.resolved, full.resolved);
+    }
+
+    #[test]
+    fn variable_edit_rebuilds_everything() {
+        let edited = BASE.replace("#1E88E5", "#E53935");
+        let old: ThemeConfig = BASE.parse().unwrap();
+        let (reloaded, full) = reload(BASE, &edited);
+        assert_eq!(button_active(&reloaded), button_active(&full));
+        assert_eq!(reloaded.theme().palette(), full.theme().palette());
+        assert!(ThemeDiff::between(&old, &reloaded).changed("button"));
+    }
+
+    #[test]
+    fn fallbacks_follow_the_section_they_borrow() {
+        let base = format!("{BASE}\n[fallbacks]\ncontainer = \"button\"\n");
+        let edited = base.replace("background = \"$accent\"", "background = \"#E53935\"");
+        let (reloaded, full) = reload(&base, &edited);
+        let background = |config: &ThemeConfig| config.container().unwrap().style_fn()(&config.theme()).background;
+        assert_eq!(background(&reloaded), background(&full));
+        assert_eq!(background(&reloaded), Some(iced_core::Color::from_rgb8(0xE5, 0x39, 0x35).into()));
+    }
+
+    #[test]
+    fn reload_errors_like_a_full_load() {
+        let old: ThemeConfig = BASE.parse().unwrap();
+        let edited = BASE.replace("rail-width = 4.0", "rail-width = \"wide\"");
+        let err = old.reload(toml::from_str(&edited).unwrap()).err().unwrap();
+        assert_eq!(err.field(), Some("slider.rail-width"));
+    }
+}
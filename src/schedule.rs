@@ -0,0 +1,351 @@
+//! Switching between light and dark variants of a theme by time of day.
+
+use std::f64::consts::PI;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::sync::mpsc::{self as std_mpsc, RecvTimeoutError};
+use std::time::Duration;
+
+use chrono::{DateTime, Days, Local, NaiveDate, NaiveTime, TimeZone, Utc};
+use iced::Subscription;
+use iced::futures::StreamExt;
+use iced::futures::channel::mpsc;
+use serde::Deserialize;
+
+use crate::ThemeConfig;
+use crate::error::{Error, Reason};
+
+/// The longest [`ScheduledTheme::transitions`] sleeps before re-checking the
+/// clock, so a suspended machine or a changed clock is noticed quickly.
+const MAX_SLEEP: Duration = Duration::from_secs(60);
+
+/// When to use the dark variant of a theme.
+///
+/// Parsed from TOML, either with fixed local times:
+///
+/// ```toml
+/// light = "07:00"
+/// dark  = "19:30"
+/// ```
+///
+/// or with a location, switching at sunrise and sunset:
+///
+/// ```toml
+/// latitude  = 52.52
+/// longitude = 13.40   # east is positive
+/// ```
+///
+/// Requires the `schedule` feature.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Schedule {
+    /// Light from `light` until `dark`, local time.
+    Fixed { light: NaiveTime, dark: NaiveTime },
+    /// Light from sunrise until sunset at this location. Days without a
+    /// sunrise (polar night) are dark; days without a sunset are light.
+    Solar { latitude: f64, longitude: f64 },
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ScheduleRaw {
+    light: Option<String>,
+    dark: Option<String>,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+}
+
+impl FromStr for Schedule {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let raw: ScheduleRaw = crate::config::deserialize(&toml::from_str(s)?, "")?;
+        match raw {
+            ScheduleRaw { light: Some(light), dark: Some(dark), latitude: None, longitude: None } => {
+                Ok(Schedule::Fixed { light: parse_time("light", &light)?, dark: parse_time("dark", &dark)? })
+            }
+            ScheduleRaw { light: None, dark: None, latitude: Some(latitude), longitude: Some(longitude) } => {
+                check_range("latitude", latitude, 90.0)?;
+                check_range("longitude", longitude, 180.0)?;
+                Ok(Schedule::Solar { latitude, longitude })
+            }
+            _ => Err(Error::InvalidValue {
+                field: String::new(),
+                value: s.trim().to_string(),
+                reason: Reason::Other("expected `light` and `dark` times, or `latitude` and `longitude`".to_string()),
+            }),
+        }
+    }
+}
+
+fn parse_time(field: &str, value: &str) -> Result<NaiveTime, Error> {
+    NaiveTime::parse_from_str(value, "%H:%M").map_err(|_| Error::InvalidValue {
+        field: field.to_string(),
+        value: format!("\"{value}\""),
+        reason: Reason::Other("expected a 24-hour time like \"19:30\"".to_string()),
+    })
+}
+
+fn check_range(field: &str, value: f64, limit: f64) -> Result<(), Error> {
+    if (-limit..=limit).contains(&value) {
+        return Ok(());
+    }
+    Err(Error::InvalidValue {
+        field: field.to_string(),
+        value: value.to_string(),
+        reason: Reason::OutOfRange { min: -limit as f32, max: limit as f32 },
+    })
+}
+
+impl Schedule {
+    /// Read a schedule from a TOML file.
+    pub fn from_file(path: impl AsRef<std::path::Path>) -> Result<Self, Error> {
+        std::fs::read_to_string(path)?.parse()
+    }
+
+    /// Whether the dark variant applies at `time`.
+    pub fn is_dark_at<Tz: TimeZone>(&self, time: &DateTime<Tz>) -> bool {
+        match *self {
+            Schedule::Fixed { light, dark } => {
+                let now = time.time();
+                if light <= dark { now < light || now >= dark } else { now >= dark && now < light }
+            }
+            Schedule::Solar { latitude, longitude } => match sun(time.date_naive(), latitude, longitude) {
+                Sun::Rises { sunrise, sunset } => {
+                    let now = time.with_timezone(&Utc);
+                    now < sunrise || now >= sunset
+                }
+                Sun::AlwaysUp => false,
+                Sun::AlwaysDown => true,
+            },
+        }
+    }
+
+    /// The first time after `time` at which the variant changes, if there is
+    /// one within the next two days.
+    pub fn next_transition<Tz: TimeZone>(&self, time: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let tz = time.timezone();
+        let days = (0..=2).filter_map(|n| time.date_naive().checked_add_days(Days::new(n)));
+        let candidates: Vec<DateTime<Tz>> = match *self {
+            Schedule::Fixed { light, dark } => days
+                .flat_map(|date| [date.and_time(light), date.and_time(dark)])
+                .filter_map(|naive| tz.from_local_datetime(&naive).earliest())
+                .collect(),
+            Schedule::Solar { latitude, longitude } => days
+                .flat_map(|date| match sun(date, latitude, longitude) {
+                    Sun::Rises { sunrise, sunset } => vec![sunrise, sunset],
+                    Sun::AlwaysUp | Sun::AlwaysDown => Vec::new(),
+                })
+                .map(|utc| utc.with_timezone(&tz))
+                .collect(),
+        };
+        candidates.into_iter().filter(|candidate| candidate > time).min()
+    }
+}
+
+/// The sun's course over one day.
+enum Sun {
+    Rises { sunrise: DateTime<Utc>, sunset: DateTime<Utc> },
+    AlwaysUp,
+    AlwaysDown,
+}
+
+/// Sunrise and sunset on `date` with the sunrise equation, accurate to about
+/// a minute.
+fn sun(date: NaiveDate, latitude: f64, longitude: f64) -> Sun {
+    let rad = PI / 180.0;
+    let noon = date.and_time(NaiveTime::from_hms_opt(12, 0, 0).unwrap_or_default()).and_utc();
+    let julian_day = noon.timestamp() as f64 / 86_400.0 + 2_440_587.5;
+
+    let day = (julian_day - 2_451_545.0 + 0.0008).round() - longitude / 360.0;
+    let anomaly = (357.5291 + 0.985_600_28 * day).rem_euclid(360.0);
+    let center = 1.9148 * (anomaly * rad).sin() + 0.02 * (2.0 * anomaly * rad).sin() + 0.0003 * (3.0 * anomaly * rad).sin();
+    let ecliptic = (anomaly + center + 180.0 + 102.9372).rem_euclid(360.0);
+    let transit = 2_451_545.0 + day + 0.0053 * (anomaly * rad).sin() - 0.0069 * (2.0 * ecliptic * rad).sin();
+
+    let declination = ((ecliptic * rad).sin() * (23.4397 * rad).sin()).asin();
+    let cos_hour_angle = ((-0.833 * rad).sin() - (latitude * rad).sin() * declination.sin())
+        / ((latitude * rad).cos() * declination.cos());
+    if cos_hour_angle < -1.0 {
+        return Sun::AlwaysUp;
+    }
+    if cos_hour_angle > 1.0 {
+        return Sun::AlwaysDown;
+    }
+    let hour_angle = cos_hour_angle.acos() / rad;
+
+    let to_utc = |julian: f64| DateTime::from_timestamp(((julian - 2_440_587.5) * 86_400.0) as i64, 0);
+    match (to_utc(transit - hour_angle / 360.0), to_utc(transit + hour_angle / 360.0)) {
+        (Some(sunrise), Some(sunset)) => Sun::Rises { sunrise, sunset },
+        _ => Sun::AlwaysDown,
+    }
+}
+
+/// A light and a dark theme, and the [`Schedule`] that picks between them:
+///
+/// ```no_run
+/// use iced::Subscription;
+/// use iced_themer::{Schedule, ScheduledTheme, ThemeConfig};
+///
+/// # fn main() -> Result<(), iced_themer::Error> {
+/// let light = ThemeConfig::from_file("light.toml")?;
+/// let dark = light.derive_dark();
+/// let scheduled = ScheduledTheme::new(light, dark, Schedule::from_file("schedule.toml")?);
+///
+/// // In the app: start with scheduled.current(), and swap themes on
+/// // scheduled.transitions() in the subscription.
+/// let changes: Subscription<std::sync::Arc<ThemeConfig>> = scheduled.transitions();
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Requires the `schedule` feature.
+#[derive(Clone)]
+pub struct ScheduledTheme {
+    light: Arc<ThemeConfig>,
+    dark: Arc<ThemeConfig>,
+    schedule: Schedule,
+}
+
+impl ScheduledTheme {
+    pub fn new(light: ThemeConfig, dark: ThemeConfig, schedule: Schedule) -> Self {
+        ScheduledTheme { light: Arc::new(light), dark: Arc::new(dark), schedule }
+    }
+
+    pub fn schedule(&self) -> &Schedule {
+        &self.schedule
+    }
+
+    /// The variant for the current local time.
+    pub fn current(&self) -> Arc<ThemeConfig> {
+        self.at(&Local::now())
+    }
+
+    /// The variant for `time`.
+    pub fn at<Tz: TimeZone>(&self, time: &DateTime<Tz>) -> Arc<ThemeConfig> {
+        if self.schedule.is_dark_at(time) { self.dark.clone() } else { self.light.clone() }
+    }
+
+    /// A subscription producing the new variant each time the schedule
+    /// switches, within a few seconds of the transition.
+    pub fn transitions(&self) -> Subscription<Arc<ThemeConfig>> {
+        Subscription::run_with(Transitions(self.clone()), |Transitions(scheduled)| {
+            let (sender, receiver) = mpsc::unbounded();
+            // Dropped with the stream, which wakes the thread and stops it.
+            let (stop, stopped) = std_mpsc::channel();
+            let scheduled = scheduled.clone();
+            std::thread::spawn(move || scheduled.send_transitions(&sender, &stopped));
+            receiver.map(move |theme| {
+                let _ = &stop;
+                theme
+            })
+        })
+    }
+
+    /// Sends the new variant on `sender` at every transition, until `stop` is
+    /// disconnected or `sender`'s receiver is dropped.
+    fn send_transitions(&self, sender: &mpsc::UnboundedSender<Arc<ThemeConfig>>, stop: &std_mpsc::Receiver<()>) {
+        let mut dark = self.schedule.is_dark_at(&Local::now());
+        loop {
+            let now = Local::now();
+            let wait = self
+                .schedule
+                .next_transition(&now)
+                .and_then(|next| (next - now).to_std().ok())
+                .map_or(MAX_SLEEP, |until| until.min(MAX_SLEEP));
+            match stop.recv_timeout(wait + Duration::from_millis(100)) {
+                Err(RecvTimeoutError::Timeout) => {}
+                Ok(()) | Err(RecvTimeoutError::Disconnected) => return,
+            }
+
+            let now_dark = self.schedule.is_dark_at(&Local::now());
+            if now_dark != dark {
+                dark = now_dark;
+                let theme = if dark { self.dark.clone() } else { self.light.clone() };
+                if sender.unbounded_send(theme).is_err() {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// Identifies a [`ScheduledTheme::transitions`] subscription by its themes
+/// and schedule.
+struct Transitions(ScheduledTheme);
+
+impl Hash for Transitions {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let ScheduledTheme { light, dark, schedule } = &self.0;
+        Arc::as_ptr(light).hash(state);
+        Arc::as_ptr(dark).hash(state);
+        match *schedule {
+            Schedule::Fixed { light, dark } => (light, dark).hash(state),
+            Schedule::Solar { latitude, longitude } => (latitude.to_bits(), longitude.to_bits()).hash(state),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::FixedOffset;
+
+    use super::*;
+
+    fn at(offset_hours: i32, date: (i32, u32, u32), time: (u32, u32)) -> DateTime<FixedOffset> {
+        let tz = FixedOffset::east_opt(offset_hours * 3600).unwrap();
+        tz.with_ymd_and_hms(date.0, date.1, date.2, time.0, time.1, 0).unwrap()
+    }
+
+    #[test]
+    fn fixed_schedule() {
+        let schedule: Schedule = "light = \"07:00\"\ndark = \"19:30\"".parse().unwrap();
+        assert!(schedule.is_dark_at(&at(0, (2024, 1, 1), (6, 59))));
+        assert!(!schedule.is_dark_at(&at(0, (2024, 1, 1), (7, 0))));
+        assert!(schedule.is_dark_at(&at(0, (2024, 1, 1), (19, 30))));
+        assert_eq!(schedule.next_transition(&at(0, (2024, 1, 1), (20, 0))), Some(at(0, (2024, 1, 2), (7, 0))));
+    }
+
+    #[test]
+    fn solar_schedule_follows_the_sun() {
+        // Berlin: sunrise about 04:45 and sunset about 21:35 local (UTC+2) in late June.
+        let schedule: Schedule = "latitude = 52.52\nlongitude = 13.40".parse().unwrap();
+        assert!(schedule.is_dark_at(&at(2, (2024, 6, 21), (4, 30))));
+        assert!(!schedule.is_dark_at(&at(2, (2024, 6, 21), (12, 0))));
+        assert!(schedule.is_dark_at(&at(2, (2024, 6, 21), (22, 0))));
+        let sunset = schedule.next_transition(&at(2, (2024, 6, 21), (12, 0))).unwrap();
+        assert!(sunset > at(2, (2024, 6, 21), (21, 20)) && sunset < at(2, (2024, 6, 21), (21, 45)), "{sunset}");
+    }
+
+    #[test]
+    fn polar_days() {
+        let schedule: Schedule = "latitude = 78.22\nlongitude = 15.65".parse().unwrap();
+        assert!(!schedule.is_dark_at(&at(0, (2024, 6, 21), (0, 0))));
+        assert!(schedule.is_dark_at(&at(0, (2024, 12, 21), (12, 0))));
+        assert_eq!(schedule.next_transition(&at(0, (2024, 6, 21), (0, 0))), None);
+    }
+
+    #[test]
+    fn invalid_schedules() {
+        let err = "light = \"7am\"\ndark = \"19:30\"".parse::<Schedule>().unwrap_err();
+        assert_eq!(err.field(), Some("light"));
+        let err = "latitude = 95.0\nlongitude = 0.0".parse::<Schedule>().unwrap_err();
+        assert!(matches!(err.reason(), Some(Reason::OutOfRange { .. })));
+        assert!("light = \"07:00\"".parse::<Schedule>().is_err());
+    }
+
+    #[test]
+    fn transitions_stop_when_signalled() {
+        let light = ThemeConfig::from_theme(iced::Theme::Light);
+        let schedule = "light = \"07:00\"\ndark = \"19:30\"".parse().unwrap();
+        let scheduled = ScheduledTheme::new(light.clone(), light.derive_dark(), schedule);
+        let (sender, _receiver) = mpsc::unbounded();
+        let (stop, stopped) = std_mpsc::channel();
+        let thread = std::thread::spawn(move || scheduled.send_transitions(&sender, &stopped));
+
+        let start = std::time::Instant::now();
+        drop(stop);
+        thread.join().unwrap();
+        assert!(start.elapsed() < MAX_SLEEP);
+    }
+}
@@ -0,0 +1,215 @@
+//! `[script]` blocks: variables computed by an embedded Rhai script.
+//!
+//! The script runs before anything else is resolved. It sees the theme's
+//! `[variables]` and `[palette]` as written, and returns a map of variables
+//! to add to `[variables]`:
+//!
+//! ```toml
+//! [variables]
+//! base = "#1E88E5"
+//!
+//! [script]
+//! source = '''
+//! let out = #{};
+//! for i in 1..=4 {
+//!     out["base-" + i] = `lighten($base, ${i * 10}%)`;
+//! }
+//! out
+//! '''
+//! ```
+
+use toml::Value;
+
+use crate::error::{Error, Reason};
+
+/// Removes `[script]` from `root`, runs it, and merges the variables it
+/// returns into `[variables]`, replacing any with the same name.
+pub(crate) fn run(root: &mut Value) -> Result<(), Error> {
+    let Some(table) = root.as_table_mut() else {
+        return Ok(());
+    };
+    let Some(script) = table.remove("script") else {
+        return Ok(());
+    };
+    let source = match script.get("source") {
+        Some(Value::String(source)) => source.clone(),
+        Some(other) => {
+            return Err(Error::InvalidValue {
+                field: "script.source".to_string(),
+                value: other.to_string(),
+                reason: Reason::NotAString,
+            });
+        }
+        None => {
+            return Err(Error::InvalidValue {
+                field: "script".to_string(),
+                value: script.to_string(),
+                reason: Reason::Other("missing `source`".to_string()),
+            });
+        }
+    };
+
+    let computed = evaluate(&source, table.get("variables"), table.get("palette"))?;
+    let variables = table.entry("variables").or_insert_with(|| Value::Table(toml::Table::new()));
+    let Value::Table(variables) = variables else {
+        return Err(Error::InvalidValue {
+            field: "variables".to_string(),
+            value: variables.to_string(),
+            reason: Reason::NotATable,
+        });
+    };
+    variables.extend(computed);
+    Ok(())
+}
+
+#[cfg(feature = "script")]
+fn evaluate(source: &str, variables: Option<&Value>, palette: Option<&Value>) -> Result<toml::Table, Error> {
+    use rhai::module_resolvers::DummyModuleResolver;
+    use rhai::{Dynamic, Engine, Map, Scope};
+
+    let mut engine = Engine::new();
+    // No imports, no output, and bounded work: a theme file can't touch the
+    // system or hang the app.
+    engine
+        .set_module_resolver(DummyModuleResolver::new())
+        .on_print(|_| {})
+        .on_debug(|_, _, _| {})
+        .disable_symbol("eval")
+        .set_max_operations(1_000_000)
+        .set_max_call_levels(32)
+        .set_max_expr_depths(64, 32)
+        .set_max_string_size(64 * 1024)
+        .set_max_array_size(10_000)
+        .set_max_map_size(10_000);
+
+    let strings = |value: Option<&Value>| -> Map {
+        let table = value.and_then(Value::as_table).into_iter().flatten();
+        table
+            .filter_map(|(key, value)| value.as_str().map(|s| (key.as_str().into(), Dynamic::from(s.to_string()))))
+            .collect()
+    };
+    let mut scope = Scope::new();
+    scope.push_constant("variables", strings(variables));
+    scope.push_constant("palette", strings(palette));
+
+    let failed = |err: &rhai::EvalAltResult| {
+        let line = err.position().line().and_then(|n| source.lines().nth(n - 1)).unwrap_or(source);
+        Error::InvalidValue {
+            field: "script.source".to_string(),
+            value: line.trim().to_string(),
+            reason: Reason::Script(err.to_string()),
+        }
+    };
+    let result: Dynamic = engine.eval_with_scope(&mut scope, source).map_err(|err| failed(&err))?;
+    let map = result.try_cast::<Map>().ok_or_else(|| Error::InvalidValue {
+        field: "script.source".to_string(),
+        value: source.lines().last().unwrap_or_default().trim().to_string(),
+        reason: Reason::Script("the script must end with a map of variables".to_string()),
+    })?;
+
+    map.into_iter()
+        .map(|(name, value)| {
+            let converted = if value.is_string() {
+                Some(Value::String(value.to_string()))
+            } else {
+                value.as_int().ok().map(Value::Integer)
+            };
+            converted.map(|v| (name.to_string(), v)).ok_or_else(|| Error::InvalidValue {
+                field: format!("variables.{name}"),
+                value: value.to_string(),
+                reason: Reason::NotAString,
+            })
+        })
+        .collect()
+}
+
+#[cfg(not(feature = "script"))]
+fn evaluate(_: &str, _: Option<&Value>, _: Option<&Value>) -> Result<toml::Table, Error> {
+    Err(Error::InvalidValue {
+        field: "script".to_string(),
+        value: "[script]".to_string(),
+        reason: Reason::Other("`[script]` requires the `script` feature".to_string()),
+    })
+}
+
+#[cfg(all(test, feature = "script"))]
+mod tests {
+    use super::*;
+
+    fn run_script(toml: &str) -> Result<Value, Error> {
+        let mut root: Value = toml::from_str(toml).unwrap();
+        run(&mut root).map(|()| root)
+    }
+
+    #[test]
+    fn script_adds_variables() {
+        let root = run_script(
+            r##"
+[variables]
+base = "#1E88E5"
+
+[script]
+source = '''
+let out = #{};
+for i in 1..=3 {
+    out["base-" + i] = `lighten($base, ${i * 10}%)`;
+}
+if variables.base == "#1E88E5" { out.accent = 0xFF0000; }
+out
+'''
+"##,
+        )
+        .unwrap();
+        assert!(root.get("script").is_none());
+        assert_eq!(root["variables"]["base-2"].as_str(), Some("lighten($base, 20%)"));
+        assert_eq!(root["variables"]["accent"].as_integer(), Some(0xFF0000));
+        assert_eq!(root["variables"]["base"].as_str(), Some("#1E88E5"));
+    }
+
+    #[test]
+    fn script_variables_reach_the_theme() {
+        let config: crate::ThemeConfig = r##"
+[script]
+source = """
+let dark = true;
+#{ bg: if dark { "#1B2838" } else { "#FAFAFA" } }
+"""
+
+[palette]
+background = "$bg"
+text       = "#C7D5E0"
+primary    = "#66C0F4"
+success    = "#4CAF50"
+warning    = "#FFC107"
+danger     = "#F44336"
+"##
+        .parse()
+        .unwrap();
+        assert!(config.is_dark());
+    }
+
+    #[test]
+    fn script_errors_point_at_the_line() {
+        let err = run_script("[script]\nsource = '''\nlet x = 1;\nlet y = x +;\n#{}\n'''\n").unwrap_err();
+        assert_eq!(err.field(), Some("script.source"));
+        assert_eq!(err.value(), Some("let y = x +;"));
+        assert!(matches!(err.reason(), Some(Reason::Script(_))));
+    }
+
+    #[test]
+    fn runaway_scripts_are_stopped() {
+        let err = run_script("[script]\nsource = 'loop {}'\n").unwrap_err();
+        assert!(matches!(err.reason(), Some(Reason::Script(_))));
+    }
+
+    #[test]
+    fn imports_are_not_resolved() {
+        let err = run_script("[script]\nsource = 'import \"/etc/passwd\" as p; #{}'\n").unwrap_err();
+        assert!(matches!(err.reason(), Some(Reason::Script(_))));
+    }
+
+    #[test]
+    fn non_map_result_is_an_error() {
+        assert!(run_script("[script]\nsource = '42'\n").is_err());
+    }
+}
@@ -0,0 +1,443 @@
+//! Backgrounds as theme files write them: a color, or a linear gradient
+//! table with an `angle` in degrees, up to 8 `stops`, and an optional
+//! [`easing`](Easing) between them.
+//!
+//! Every section reads its background keys into a [`BackgroundRaw`]. The
+//! constructors here build the same values in code, with the same checks, for
+//! themes put together programmatically and for apps' own sections:
+//!
+//! ```
+//! use iced_themer::HexColor;
+//! use iced_themer::style::background::{BackgroundRaw, GradientRaw};
+//!
+//! let color = |hex: &str| hex.parse::<HexColor>().unwrap().0;
+//! let sky = GradientRaw::linear(180.0, [(0.0, color("#87CEEB")), (1.0, color("#1E88E5"))]).unwrap();
+//! let background = BackgroundRaw::gradient(sky).into_background();
+//!
+//! assert!(GradientRaw::linear(0.0, [(1.5, color("#FFFFFF"))]).is_err());
+//! ```
+
+use iced_core::gradient::Linear;
+use iced_core::{Background, Color, Degrees};
+use serde::Deserialize;
+
+use crate::color::{self, HexColor};
+use crate::config;
+use crate::error::{Error, Reason};
+
+/// The most color stops a gradient holds, as many as iced's own.
+const MAX_STOPS: usize = 8;
+
+/// A background that is either a solid color or a linear gradient.
+///
+/// Tables with `angle` and `stops` fields deserialize as gradients; anything
+/// else deserializes as a solid color via `HexColor`. This mirrors
+/// `iced_core::Background` but uses serde-friendly types.
+#[derive(Clone, Copy, Debug)]
+pub enum BackgroundRaw {
+    /// A solid color.
+    Color(HexColor),
+    /// A linear gradient.
+    Gradient(GradientRaw),
+}
+
+impl<'de> Deserialize<'de> for BackgroundRaw {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        // Picking the variant by shape, instead of `#[serde(untagged)]`
+        // trying each in turn, keeps the error from inside a gradient rather
+        // than "data did not match any variant".
+        match toml::Value::deserialize(deserializer)? {
+            value @ toml::Value::Table(_) => config::deserialize_nested(value).map(BackgroundRaw::Gradient),
+            value @ (toml::Value::String(_) | toml::Value::Integer(_) | toml::Value::Array(_)) => {
+                config::deserialize_nested(value).map(BackgroundRaw::Color)
+            }
+            other => Err(serde::de::Error::custom(format!(
+                "invalid type: {}, expected a color or a gradient table",
+                other.type_str()
+            ))),
+        }
+    }
+}
+
+impl BackgroundRaw {
+    /// A solid `color` background.
+    pub fn color(color: Color) -> Self {
+        BackgroundRaw::Color(HexColor(color))
+    }
+
+    /// A `gradient` background.
+    pub fn gradient(gradient: GradientRaw) -> Self {
+        BackgroundRaw::Gradient(gradient)
+    }
+
+    /// The native iced background.
+    pub fn into_background(self) -> Background {
+        match self {
+            BackgroundRaw::Color(c) => Background::Color(c.0),
+            BackgroundRaw::Gradient(g) => g.into_background(),
+        }
+    }
+}
+
+impl From<Color> for BackgroundRaw {
+    fn from(color: Color) -> Self {
+        BackgroundRaw::color(color)
+    }
+}
+
+impl From<GradientRaw> for BackgroundRaw {
+    fn from(gradient: GradientRaw) -> Self {
+        BackgroundRaw::gradient(gradient)
+    }
+}
+
+/// How a gradient's colors change between two stops.
+///
+/// iced blends straight from one stop to the next, so anything but `linear`
+/// is approximated by stops added in between, as many as fit in the 8 a
+/// gradient holds, spread across its segments. The curves are simple
+/// polynomial stand-ins for the CSS keywords of the same names:
+///
+/// - `linear`: an even blend, the default.
+/// - `ease-in`: starts slow and speeds up.
+/// - `ease-out`: starts fast and slows down.
+/// - `ease-in-out`: slow at both stops, fast in the middle.
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Easing {
+    #[default]
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+}
+
+impl Easing {
+    /// How far the blend is at `t`, the position between two stops, both
+    /// in `0.0..=1.0`.
+    pub fn apply(self, t: f32) -> f32 {
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+/// A single color stop in a gradient: an offset in `0.0..=1.0` and a color.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct ColorStopEntry {
+    pub offset: f32,
+    pub color: HexColor,
+}
+
+/// A linear gradient with an angle (in degrees) and up to 8 color stops.
+///
+/// Uses a fixed-size array to preserve `Copy` throughout the style system.
+/// A custom `Deserialize` reads a TOML vec and packs it into the array,
+/// validating the stop count and offset range the way
+/// [`linear`](Self::linear) does.
+#[derive(Clone, Copy, Debug)]
+pub struct GradientRaw {
+    pub(crate) angle: f32,
+    pub(crate) stops: [Option<ColorStopEntry>; MAX_STOPS],
+}
+
+impl GradientRaw {
+    /// A gradient at `angle` degrees through `stops`, `(offset, color)` pairs
+    /// in the order they're drawn.
+    ///
+    /// Fails with the same errors as a gradient table in a theme file: when
+    /// there are more than 8 stops, or an offset is outside `0.0..=1.0`.
+    pub fn linear(angle: f32, stops: impl IntoIterator<Item = (f32, Color)>) -> Result<Self, Error> {
+        let stops: Vec<_> = stops.into_iter().map(|(offset, color)| (offset, HexColor(color))).collect();
+        GradientRaw::checked(angle, stops).map_err(|(value, reason)| Error::InvalidValue {
+            field: "stops".to_string(),
+            value,
+            reason,
+        })
+    }
+
+    /// The angle, in degrees.
+    pub fn angle(&self) -> f32 {
+        self.angle
+    }
+
+    /// The color stops, as `(offset, color)` pairs.
+    pub fn stops(&self) -> impl Iterator<Item = (f32, Color)> + '_ {
+        self.stops.iter().flatten().map(|stop| (stop.offset, stop.color.0))
+    }
+
+    /// The gradient with `easing` between its stops, approximated by filling
+    /// the slots left free up to 8 with stops in between. With 8 stops
+    /// already there is no room and the gradient is unchanged.
+    pub fn with_easing(self, easing: Easing) -> Self {
+        let stops: Vec<ColorStopEntry> = self.stops.into_iter().flatten().collect();
+        if easing == Easing::Linear || stops.len() < 2 {
+            return self;
+        }
+
+        let segments = stops.len() - 1;
+        let spare = MAX_STOPS - stops.len();
+        let mut arr = [None; MAX_STOPS];
+        let mut slots = arr.iter_mut();
+        for (i, pair) in stops.windows(2).enumerate() {
+            let (from, to) = (pair[0], pair[1]);
+            *slots.next().expect("stops fit in a gradient") = Some(from);
+            // Stops that don't divide evenly go to the first segments.
+            let added = spare / segments + usize::from(i < spare % segments);
+            for j in 1..=added {
+                let t = j as f32 / (added + 1) as f32;
+                *slots.next().expect("stops fit in a gradient") = Some(ColorStopEntry {
+                    offset: from.offset + (to.offset - from.offset) * t,
+                    color: HexColor(color::mix(from.color.0, to.color.0, easing.apply(t))),
+                });
+            }
+        }
+        *slots.next().expect("stops fit in a gradient") = stops.last().copied();
+        GradientRaw { angle: self.angle, stops: arr }
+    }
+
+    /// The native iced background.
+    pub fn into_background(self) -> Background {
+        let mut linear = Linear::new(Degrees(self.angle));
+        for stop in self.stops.into_iter().flatten() {
+            linear = linear.add_stop(stop.offset, stop.color.0);
+        }
+        Background::Gradient(iced_core::Gradient::Linear(linear))
+    }
+
+    /// Packs `stops` into a gradient, or returns the offending value and why
+    /// it was rejected.
+    fn checked(angle: f32, stops: Vec<(f32, HexColor)>) -> Result<Self, (String, Reason)> {
+        if stops.len() > MAX_STOPS {
+            let message = format!("gradient supports at most {MAX_STOPS} color stops, got {}", stops.len());
+            return Err((stops.len().to_string(), Reason::Other(message)));
+        }
+
+        let mut arr = [None; MAX_STOPS];
+        for (i, (offset, color)) in stops.into_iter().enumerate() {
+            if !(0.0..=1.0).contains(&offset) {
+                let message = format!("color stop offset must be in 0.0..=1.0, got {offset}");
+                return Err((offset.to_string(), Reason::Other(message)));
+            }
+            arr[i] = Some(ColorStopEntry { offset, color });
+        }
+
+        Ok(GradientRaw { angle, stops: arr })
+    }
+}
+
+impl<'de> Deserialize<'de> for GradientRaw {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct StopHelper {
+            offset: f32,
+            color: HexColor,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct GradientHelper {
+            angle: f32,
+            stops: Vec<StopHelper>,
+            #[serde(default)]
+            easing: Easing,
+        }
+
+        let helper = GradientHelper::deserialize(deserializer)?;
+        let stops = helper.stops.into_iter().map(|s| (s.offset, s.color)).collect();
+        GradientRaw::checked(helper.angle, stops)
+            .map(|gradient| gradient.with_easing(helper.easing))
+            .map_err(|(_, reason)| serde::de::Error::custom(reason))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Helper: wraps a TOML value in a `bg = ...` key so we can deserialize
+    /// `BackgroundRaw` from a standalone value via a wrapper struct.
+    #[derive(Deserialize)]
+    struct Wrapper {
+        bg: BackgroundRaw,
+    }
+
+    fn parse_bg(toml_str: &str) -> Result<BackgroundRaw, toml::de::Error> {
+        toml::from_str::<Wrapper>(toml_str).map(|w| w.bg)
+    }
+
+    #[test]
+    fn background_raw_parses_solid_color() {
+        let raw = parse_bg(r##"bg = "#FF0000""##).unwrap();
+        assert!(matches!(raw, BackgroundRaw::Color(_)));
+    }
+
+    #[test]
+    fn background_raw_parses_gradient() {
+        let raw = parse_bg(r##"
+            [bg]
+            angle = 45.0
+            stops = [
+                { offset = 0.0, color = "#ff0000" },
+                { offset = 1.0, color = "#0000ff" },
+            ]
+        "##).unwrap();
+        match raw {
+            BackgroundRaw::Gradient(g) => {
+                assert!((g.angle - 45.0).abs() < f32::EPSILON);
+                assert!(g.stops[0].is_some());
+                assert!(g.stops[1].is_some());
+                assert!(g.stops[2].is_none());
+            }
+            _ => panic!("expected Gradient variant"),
+        }
+    }
+
+    #[test]
+    fn gradient_rejects_more_than_8_stops() {
+        let result: Result<GradientRaw, _> = toml::from_str(r##"
+            angle = 0.0
+            stops = [
+                { offset = 0.0, color = "#000000" },
+                { offset = 0.1, color = "#111111" },
+                { offset = 0.2, color = "#222222" },
+                { offset = 0.3, color = "#333333" },
+                { offset = 0.4, color = "#444444" },
+                { offset = 0.5, color = "#555555" },
+                { offset = 0.6, color = "#666666" },
+                { offset = 0.7, color = "#777777" },
+                { offset = 0.8, color = "#888888" },
+            ]
+        "##);
+        assert!(result.is_err());
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("at most 8"), "error was: {err}");
+    }
+
+    #[test]
+    fn gradient_rejects_offset_out_of_range() {
+        let result: Result<GradientRaw, _> = toml::from_str(r##"
+            angle = 0.0
+            stops = [
+                { offset = 0.0, color = "#000000" },
+                { offset = 1.5, color = "#ffffff" },
+            ]
+        "##);
+        assert!(result.is_err());
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("0.0..=1.0"), "error was: {err}");
+    }
+
+    #[test]
+    fn gradient_accepts_8_stops() {
+        let raw: GradientRaw = toml::from_str(r##"
+            angle = 90.0
+            stops = [
+                { offset = 0.0,   color = "#000000" },
+                { offset = 0.143, color = "#111111" },
+                { offset = 0.286, color = "#222222" },
+                { offset = 0.429, color = "#333333" },
+                { offset = 0.571, color = "#444444" },
+                { offset = 0.714, color = "#555555" },
+                { offset = 0.857, color = "#666666" },
+                { offset = 1.0,   color = "#777777" },
+            ]
+        "##).unwrap();
+        assert!(raw.stops.iter().all(|s| s.is_some()));
+    }
+
+    #[test]
+    fn solid_color_converts_to_background_color() {
+        let raw = parse_bg(r##"bg = "#ff0000""##).unwrap();
+        let bg = raw.into_background();
+        match bg {
+            Background::Color(c) => {
+                assert!((c.r - 1.0).abs() < 0.01);
+                assert!(c.g.abs() < 0.01);
+                assert!(c.b.abs() < 0.01);
+            }
+            _ => panic!("expected Background::Color"),
+        }
+    }
+
+    #[test]
+    fn gradient_converts_to_background_gradient() {
+        let raw = parse_bg(r##"
+            [bg]
+            angle = 90.0
+            stops = [
+                { offset = 0.0, color = "#ff0000" },
+                { offset = 1.0, color = "#0000ff" },
+            ]
+        "##).unwrap();
+        let bg = raw.into_background();
+        match bg {
+            Background::Gradient(iced_core::Gradient::Linear(linear)) => {
+                assert!(linear.stops[0].is_some());
+                assert!(linear.stops[1].is_some());
+                let s0 = linear.stops[0].unwrap();
+                assert!(s0.offset.abs() < f32::EPSILON);
+                assert!((s0.color.r - 1.0).abs() < 0.01);
+            }
+            _ => panic!("expected Background::Gradient(Linear(..))"),
+        }
+    }
+
+    #[test]
+    fn built_gradients_match_parsed_ones() {
+        let parsed = parse_bg(r##"
+            [bg]
+            angle = 45.0
+            stops = [
+                { offset = 0.0, color = "#ff0000" },
+                { offset = 1.0, color = "#0000ff" },
+            ]
+        "##).unwrap();
+        let red = Color::from_rgb8(0xFF, 0, 0);
+        let built = GradientRaw::linear(45.0, [(0.0, red), (1.0, Color::from_rgb8(0, 0, 0xFF))]).unwrap();
+        assert_eq!(BackgroundRaw::from(built).into_background(), parsed.into_background());
+        assert_eq!(built.stops().next(), Some((0.0, red)));
+
+        let err = GradientRaw::linear(0.0, [(0.0, red), (1.5, red)]).unwrap_err();
+        assert_eq!(err.field(), Some("stops"));
+        assert_eq!(err.value(), Some("1.5"));
+        assert!(GradientRaw::linear(0.0, vec![(0.5, red); 9]).is_err());
+    }
+
+    #[test]
+    fn easing_fills_the_free_stops() {
+        let raw: GradientRaw = toml::from_str(r##"
+            angle  = 90.0
+            easing = "ease-in"
+            stops  = [
+                { offset = 0.0, color = "#000000" },
+                { offset = 0.5, color = "#ffffff" },
+                { offset = 1.0, color = "#000000" },
+            ]
+        "##).unwrap();
+        let stops: Vec<(f32, Color)> = raw.stops().collect();
+        let offsets: Vec<f32> = stops.iter().map(|(offset, _)| *offset).collect();
+        assert_eq!(offsets, [0.0, 0.125, 0.25, 0.375, 0.5, 0.6666667, 0.8333334, 1.0]);
+        // A quarter of the way along an ease-in, the blend is a sixteenth in.
+        let expected = color::mix(Color::BLACK, Color::WHITE, 0.0625);
+        assert!((stops[1].1.r - expected.r).abs() < 0.002, "got {:?}", stops[1].1);
+        assert!(stops[5].1.r > stops[6].1.r);
+
+        let linear = GradientRaw::linear(0.0, [(0.0, Color::BLACK), (1.0, Color::WHITE)]).unwrap();
+        assert_eq!(linear.with_easing(Easing::Linear).stops().count(), 2);
+        assert_eq!(linear.with_easing(Easing::EaseInOut).stops().count(), 8);
+
+        let err = toml::from_str::<GradientRaw>("angle = 0.0\nstops = []\neasing = \"bounce\"\n").unwrap_err();
+        assert!(err.to_string().contains("ease-in-out"), "got: {err}");
+    }
+}
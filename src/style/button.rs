@@ -1,9 +1,13 @@
-use iced_core::{Color, Theme};
+use iced_core::{Color, Padding, Theme};
 use iced_widget::button;
 use serde::Deserialize;
 
 use crate::color::HexColor;
-use super::{BackgroundRaw, RadiusRaw, impl_merge, resolve_border, resolve_shadow};
+use crate::compat;
+use super::{
+    ApplyOverrides, BackgroundRaw, DisabledDimming, Layout, LayoutRaw, MapColors, RadiusRaw, StatusOverrides,
+    impl_approx_eq, impl_merge, resolve_border, resolve_shadow,
+};
 
 // -- Layer 1: Serde raw types --
 
@@ -15,16 +19,20 @@ pub(crate) struct ButtonFieldsRaw {
     border_width:       Option<f32>,
     border_color:       Option<HexColor>,
     border_radius:      Option<RadiusRaw>,
+    border_enabled:     Option<bool>,
     shadow_color:       Option<HexColor>,
     shadow_offset_x:    Option<f32>,
     shadow_offset_y:    Option<f32>,
     shadow_blur_radius: Option<f32>,
+    shadow_enabled:     Option<bool>,
+    snap:               Option<bool>,
 }
 
 impl_merge!(ButtonFieldsRaw {
     background, text_color,
-    border_width, border_color, border_radius,
-    shadow_color, shadow_offset_x, shadow_offset_y, shadow_blur_radius,
+    border_width, border_color, border_radius, border_enabled,
+    shadow_color, shadow_offset_x, shadow_offset_y, shadow_blur_radius, shadow_enabled,
+    snap,
 });
 
 #[derive(Deserialize, Default)]
@@ -32,6 +40,8 @@ impl_merge!(ButtonFieldsRaw {
 pub(crate) struct ButtonSection {
     #[serde(flatten)]
     base: ButtonFieldsRaw,
+    #[serde(flatten)]
+    layout: LayoutRaw,
     hovered:  Option<ButtonFieldsRaw>,
     pressed:  Option<ButtonFieldsRaw>,
     disabled: Option<ButtonFieldsRaw>,
@@ -40,13 +50,16 @@ pub(crate) struct ButtonSection {
 // -- Layer 2: Resolution --
 
 impl ButtonSection {
-    pub fn resolve(self) -> ButtonStyle {
+    pub fn resolve(self, dim: DisabledDimming) -> ButtonStyle {
         let active = into_native(self.base);
         let hovered = resolve_status(self.base, self.hovered.as_ref());
         let pressed = resolve_status(self.base, self.pressed.as_ref());
-        let disabled = resolve_status(self.base, self.disabled.as_ref());
+        let disabled = match self.disabled {
+            Some(over) => into_native(self.base.merge(&over)),
+            None => dim.apply(active),
+        };
 
-        ButtonStyle { active, hovered, pressed, disabled }
+        ButtonStyle { active, hovered, pressed, disabled, layout: self.layout.resolve() }
     }
 }
 
@@ -58,24 +71,27 @@ fn resolve_status(base: ButtonFieldsRaw, status: Option<&ButtonFieldsRaw>) -> bu
 }
 
 fn into_native(f: ButtonFieldsRaw) -> button::Style {
-    button::Style {
-        background: f.background.map(BackgroundRaw::into_background),
-        text_color: f.text_color.map(|c| c.0).unwrap_or(Color::BLACK),
-        border: resolve_border(f.border_width, f.border_color, f.border_radius),
-        shadow: resolve_shadow(f.shadow_color, f.shadow_offset_x, f.shadow_offset_y, f.shadow_blur_radius),
-        snap: false,
-    }
+    compat::button_style(
+        f.background.map(BackgroundRaw::into_background),
+        f.text_color.map(|c| c.0).unwrap_or(Color::BLACK),
+        resolve_border(f.border_width, f.border_color, f.border_radius, f.border_enabled),
+        resolve_shadow(f.shadow_color, f.shadow_offset_x, f.shadow_offset_y, f.shadow_blur_radius, f.shadow_enabled),
+        f.snap.unwrap_or(false),
+    )
 }
 
 // -- Layer 3: Public types --
 
 /// Pre-resolved button style with a native `iced_widget` style for each status variant.
+///
+/// It also carries the section's padding and text size as layout hints.
 #[derive(Debug, Clone, Copy)]
 pub struct ButtonStyle {
     active:   button::Style,
     hovered:  button::Style,
     pressed:  button::Style,
     disabled: button::Style,
+    layout:   Layout,
 }
 
 impl ButtonStyle {
@@ -89,4 +105,73 @@ impl ButtonStyle {
             button::Status::Disabled => s.disabled,
         }
     }
+
+    /// Like [`style_fn`](Self::style_fn), with `overrides` applied over every
+    /// status, for one button that differs from the rest; see
+    /// [`StatusOverrides`].
+    pub fn style_fn_with(
+        &self,
+        overrides: StatusOverrides,
+    ) -> impl Fn(&Theme, button::Status) -> button::Style + Copy + 'static {
+        let s = self.apply_overrides(&overrides);
+        move |theme, status| s.style_fn()(theme, status)
+    }
+
+    /// `padding`: the space around the button's content.
+    pub fn padding(&self) -> Option<Padding> {
+        self.layout.padding
+    }
+
+    /// `text-size`: the size of the button's label. A button's content is
+    /// any element, so this is applied by calling `.themed()` with this
+    /// style on the label's `text` widget.
+    pub fn text_size(&self) -> Option<f32> {
+        self.layout.text_size
+    }
+}
+
+impl MapColors for button::Style {
+    fn map_colors(mut self, f: &impl Fn(Color) -> Color) -> Self {
+        self.background = self.background.map_colors(f);
+        self.text_color = f(self.text_color);
+        self.border = self.border.map_colors(f);
+        self.shadow = self.shadow.map_colors(f);
+        self
+    }
 }
+
+impl ApplyOverrides for button::Style {
+    fn apply_overrides(mut self, o: &StatusOverrides) -> Self {
+        self.background = o.background.or(self.background);
+        self.text_color = o.text_color.unwrap_or(self.text_color);
+        self.border = o.border(self.border);
+        self.shadow = o.shadow.unwrap_or(self.shadow);
+        self
+    }
+}
+
+impl MapColors for ButtonStyle {
+    fn map_colors(self, f: &impl Fn(Color) -> Color) -> Self {
+        ButtonStyle {
+            active:   self.active.map_colors(f),
+            hovered:  self.hovered.map_colors(f),
+            pressed:  self.pressed.map_colors(f),
+            disabled: self.disabled.map_colors(f),
+            layout:   self.layout,
+        }
+    }
+}
+
+impl ApplyOverrides for ButtonStyle {
+    fn apply_overrides(self, o: &StatusOverrides) -> Self {
+        ButtonStyle {
+            active:   self.active.apply_overrides(o),
+            hovered:  self.hovered.apply_overrides(o),
+            pressed:  self.pressed.apply_overrides(o),
+            disabled: self.disabled.apply_overrides(o),
+            layout:   self.layout,
+        }
+    }
+}
+
+impl_approx_eq!(ButtonStyle { active, hovered, pressed, disabled, layout });
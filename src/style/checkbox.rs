@@ -1,34 +1,54 @@
-use iced_core::{Background, Color, Theme};
+use iced_core::text::LineHeight;
+use iced_core::{Background, Color, Font, Pixels, Theme};
 use iced_widget::checkbox;
 use serde::Deserialize;
 
 use crate::color::HexColor;
-use super::{BackgroundRaw, RadiusRaw, impl_merge, resolve_border};
+use crate::config;
+use super::{
+    ApplyOverrides, BackgroundRaw, DisabledDimming, Label, LabelRaw, MapColors, RadiusRaw, StatusOverrides,
+    impl_approx_eq, impl_merge, resolve_border,
+};
 
 // -- Layer 1: Serde raw types --
 
 #[derive(Deserialize, Default, Clone, Copy)]
 #[serde(default, rename_all = "kebab-case")]
 pub(crate) struct CheckboxFieldsRaw {
-    background:    Option<BackgroundRaw>,
-    icon_color:    Option<HexColor>,
-    border_width:  Option<f32>,
-    border_color:  Option<HexColor>,
-    border_radius: Option<RadiusRaw>,
-    text_color:    Option<HexColor>,
+    background:     Option<BackgroundRaw>,
+    icon_color:     Option<HexColor>,
+    border_width:   Option<f32>,
+    border_color:   Option<HexColor>,
+    border_radius:  Option<RadiusRaw>,
+    border_enabled: Option<bool>,
+    text_color:     Option<HexColor>,
 }
 
 impl_merge!(CheckboxFieldsRaw {
     background, icon_color,
-    border_width, border_color, border_radius,
+    border_width, border_color, border_radius, border_enabled,
     text_color,
 });
 
+/// The `[checkbox.icon]` table: the checkmark glyph, for apps that ship
+/// their own icon font.
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "kebab-case")]
+pub(crate) struct IconRaw {
+    font:        Option<config::FontRaw>,
+    code_point:  Option<config::CodePoint>,
+    size:        Option<f32>,
+    line_height: Option<f32>,
+}
+
 #[derive(Deserialize, Default)]
 #[serde(default, rename_all = "kebab-case")]
 pub(crate) struct CheckboxSection {
     #[serde(flatten)]
     base: CheckboxFieldsRaw,
+    #[serde(flatten)]
+    label: LabelRaw,
+    icon: Option<IconRaw>,
     checked: Option<CheckboxFieldsRaw>,
     hovered: Option<CheckboxFieldsRaw>,
     disabled: Option<CheckboxFieldsRaw>,
@@ -59,13 +79,19 @@ fn cascade(
 }
 
 impl CheckboxSection {
-    pub fn resolve(self) -> CheckboxStyle {
+    pub fn resolve(self, dim: DisabledDimming) -> CheckboxStyle {
         let active_unchecked = into_native(self.base);
         let active_checked = cascade(self.base, self.checked.as_ref(), None, None);
         let hovered_unchecked = cascade(self.base, None, self.hovered.as_ref(), None);
         let hovered_checked = cascade(self.base, self.checked.as_ref(), self.hovered.as_ref(), self.hovered_checked.as_ref());
-        let disabled_unchecked = cascade(self.base, None, self.disabled.as_ref(), None);
-        let disabled_checked = cascade(self.base, self.checked.as_ref(), self.disabled.as_ref(), self.disabled_checked.as_ref());
+        let disabled_unchecked = match self.disabled {
+            Some(_) => cascade(self.base, None, self.disabled.as_ref(), None),
+            None => dim.apply(active_unchecked),
+        };
+        let disabled_checked = match (self.disabled, self.disabled_checked) {
+            (None, None) => dim.apply(active_checked),
+            _ => cascade(self.base, self.checked.as_ref(), self.disabled.as_ref(), self.disabled_checked.as_ref()),
+        };
 
         CheckboxStyle {
             active_unchecked,
@@ -74,6 +100,13 @@ impl CheckboxSection {
             hovered_checked,
             disabled_unchecked,
             disabled_checked,
+            label: self.label.resolve(),
+            icon: self.icon.map(|icon| CheckboxIcon {
+                font: icon.font.map(config::build_font),
+                code_point: icon.code_point.map(|c| c.0),
+                size: icon.size,
+                line_height: icon.line_height,
+            }),
         }
     }
 }
@@ -82,14 +115,15 @@ fn into_native(f: CheckboxFieldsRaw) -> checkbox::Style {
     checkbox::Style {
         background: f.background.map(BackgroundRaw::into_background).unwrap_or(Background::Color(Color::TRANSPARENT)),
         icon_color: f.icon_color.map(|c| c.0).unwrap_or(Color::BLACK),
-        border: resolve_border(f.border_width, f.border_color, f.border_radius),
+        border: resolve_border(f.border_width, f.border_color, f.border_radius, f.border_enabled),
         text_color: f.text_color.map(|c| c.0),
     }
 }
 
 // -- Layer 3: Public types --
 
-/// Pre-resolved checkbox style with 6 variants (3 statuses x 2 states).
+/// Pre-resolved checkbox style with 6 variants (3 statuses x 2 states), the
+/// label's spacing, size, and font, and the checkmark icon.
 #[derive(Debug, Clone, Copy)]
 pub struct CheckboxStyle {
     active_unchecked:   checkbox::Style,
@@ -98,6 +132,8 @@ pub struct CheckboxStyle {
     hovered_checked:    checkbox::Style,
     disabled_unchecked: checkbox::Style,
     disabled_checked:   checkbox::Style,
+    label:              Label,
+    icon:               Option<CheckboxIcon>,
 }
 
 impl CheckboxStyle {
@@ -116,4 +152,139 @@ impl CheckboxStyle {
             }
         }
     }
+
+    /// Like [`style_fn`](Self::style_fn), with `overrides` applied over every
+    /// status, checked or not. The text color is the label's.
+    pub fn style_fn_with(
+        &self,
+        overrides: StatusOverrides,
+    ) -> impl Fn(&Theme, checkbox::Status) -> checkbox::Style + Copy + 'static {
+        let s = self.apply_overrides(&overrides);
+        move |theme, status| s.style_fn()(theme, status)
+    }
+
+    /// `label-spacing`: the gap between the box and its label.
+    pub fn label_spacing(&self) -> Option<f32> {
+        self.label.spacing
+    }
+
+    /// `label-size`: the label's text size.
+    pub fn label_size(&self) -> Option<f32> {
+        self.label.size
+    }
+
+    /// The `[checkbox.label-font]` table, the label's font.
+    pub fn label_font(&self) -> Option<Font> {
+        self.label.font
+    }
+
+    /// The `[checkbox.icon]` table, the checkmark glyph.
+    pub fn icon(&self) -> Option<CheckboxIcon> {
+        self.icon
+    }
+}
+
+/// The checkmark of a checkbox, from `[checkbox.icon]`: its font, glyph,
+/// size, and line height. Unset keys keep the glyph iced draws.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CheckboxIcon {
+    font:        Option<Font>,
+    code_point:  Option<char>,
+    size:        Option<f32>,
+    line_height: Option<f32>,
+}
+
+impl CheckboxIcon {
+    /// The `[checkbox.icon.font]` table, the font the glyph is drawn in.
+    pub fn font(&self) -> Option<Font> {
+        self.font
+    }
+
+    /// `code-point`: the glyph.
+    pub fn code_point(&self) -> Option<char> {
+        self.code_point
+    }
+
+    /// `size`: the glyph's text size. iced sizes it to the box by default.
+    pub fn size(&self) -> Option<f32> {
+        self.size
+    }
+
+    /// `line-height`: the glyph's line height, relative to its size.
+    pub fn line_height(&self) -> Option<f32> {
+        self.line_height
+    }
+
+    /// iced's checkbox icon with this table's keys over `font` and
+    /// `code_point`, which are usually the renderer's `ICON_FONT` and
+    /// `CHECKMARK_ICON`. `.themed()` passes those for you.
+    pub fn to_icon<F>(&self, font: F, code_point: char) -> checkbox::Icon<F>
+    where
+        Font: Into<F>,
+    {
+        checkbox::Icon {
+            font: self.font.map_or(font, Into::into),
+            code_point: self.code_point.unwrap_or(code_point),
+            size: self.size.map(Pixels),
+            line_height: self.line_height.map_or(LineHeight::default(), LineHeight::Relative),
+            shaping: iced_core::text::Shaping::Basic,
+        }
+    }
+}
+
+impl MapColors for checkbox::Style {
+    fn map_colors(self, f: &impl Fn(Color) -> Color) -> Self {
+        checkbox::Style {
+            background: self.background.map_colors(f),
+            icon_color: f(self.icon_color),
+            border: self.border.map_colors(f),
+            text_color: self.text_color.map_colors(f),
+        }
+    }
+}
+
+impl ApplyOverrides for checkbox::Style {
+    fn apply_overrides(mut self, o: &StatusOverrides) -> Self {
+        self.background = o.background.unwrap_or(self.background);
+        self.text_color = o.text_color.or(self.text_color);
+        self.border = o.border(self.border);
+        self
+    }
+}
+
+impl MapColors for CheckboxStyle {
+    fn map_colors(self, f: &impl Fn(Color) -> Color) -> Self {
+        CheckboxStyle {
+            active_unchecked:   self.active_unchecked.map_colors(f),
+            active_checked:     self.active_checked.map_colors(f),
+            hovered_unchecked:  self.hovered_unchecked.map_colors(f),
+            hovered_checked:    self.hovered_checked.map_colors(f),
+            disabled_unchecked: self.disabled_unchecked.map_colors(f),
+            disabled_checked:   self.disabled_checked.map_colors(f),
+            label:              self.label,
+            icon:               self.icon,
+        }
+    }
+}
+
+impl ApplyOverrides for CheckboxStyle {
+    fn apply_overrides(self, o: &StatusOverrides) -> Self {
+        CheckboxStyle {
+            active_unchecked:   self.active_unchecked.apply_overrides(o),
+            active_checked:     self.active_checked.apply_overrides(o),
+            hovered_unchecked:  self.hovered_unchecked.apply_overrides(o),
+            hovered_checked:    self.hovered_checked.apply_overrides(o),
+            disabled_unchecked: self.disabled_unchecked.apply_overrides(o),
+            disabled_checked:   self.disabled_checked.apply_overrides(o),
+            label:              self.label,
+            icon:               self.icon,
+        }
+    }
 }
+
+impl_approx_eq!(checkbox::Style { background, icon_color, border, text_color });
+impl_approx_eq!(CheckboxIcon { font, code_point, size, line_height });
+impl_approx_eq!(CheckboxStyle {
+    active_unchecked, active_checked, hovered_unchecked, hovered_checked, disabled_unchecked, disabled_checked,
+    label, icon,
+});
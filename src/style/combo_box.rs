@@ -0,0 +1,56 @@
+use iced_core::Color;
+use serde::Deserialize;
+
+use super::{DisabledDimming, MapColors, MenuSection, MenuStyle, TextInputSection, TextInputStyle, impl_approx_eq};
+
+// -- Layer 1: Serde raw types --
+
+/// Top-level `[combo-box]` section. Its own keys and `hovered`, `focused`, and
+/// `disabled` sub-tables style the input field the way `[text-input]` does;
+/// the `menu` sub-table styles the drop-down the way `[menu]` does.
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "kebab-case")]
+pub(crate) struct ComboBoxSection {
+    #[serde(flatten)]
+    input: TextInputSection,
+    menu:  Option<MenuSection>,
+}
+
+// -- Layer 2: Resolution --
+
+impl ComboBoxSection {
+    pub fn resolve(self, dim: DisabledDimming) -> ComboBoxStyle {
+        ComboBoxStyle { input: self.input.resolve(dim), menu: self.menu.map(MenuSection::resolve) }
+    }
+}
+
+// -- Layer 3: Public types --
+
+/// Pre-resolved combo box style: its input field, and its drop-down menu
+/// when the section has a `menu` table.
+#[derive(Debug, Clone, Copy)]
+pub struct ComboBoxStyle {
+    input: TextInputStyle,
+    menu:  Option<MenuStyle>,
+}
+
+impl ComboBoxStyle {
+    /// The style of the input field, for `.input_style()` on a combo box.
+    pub fn input(&self) -> &TextInputStyle {
+        &self.input
+    }
+
+    /// The style of the drop-down menu, for `.menu_style()` on a combo box.
+    /// `None` when the section has no `menu` table.
+    pub fn menu(&self) -> Option<&MenuStyle> {
+        self.menu.as_ref()
+    }
+}
+
+impl MapColors for ComboBoxStyle {
+    fn map_colors(self, f: &impl Fn(Color) -> Color) -> Self {
+        ComboBoxStyle { input: self.input.map_colors(f), menu: self.menu.map_colors(f) }
+    }
+}
+
+impl_approx_eq!(ComboBoxStyle { input, menu });
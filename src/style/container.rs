@@ -1,9 +1,13 @@
-use iced_core::Theme;
+use iced_core::{Color, Theme};
 use iced_widget::container;
 use serde::Deserialize;
 
 use crate::color::HexColor;
-use super::{BackgroundRaw, RadiusRaw, impl_merge, resolve_border, resolve_shadow};
+use crate::compat;
+use super::{
+    ApplyOverrides, ApproxEq, BackgroundRaw, MapColors, RadiusRaw, StatusOverrides, impl_merge, resolve_border,
+    resolve_shadow,
+};
 
 // -- Layer 1: Serde raw types --
 
@@ -15,16 +19,20 @@ pub(crate) struct ContainerFieldsRaw {
     border_width:       Option<f32>,
     border_color:       Option<HexColor>,
     border_radius:      Option<RadiusRaw>,
+    border_enabled:     Option<bool>,
     shadow_color:       Option<HexColor>,
     shadow_offset_x:    Option<f32>,
     shadow_offset_y:    Option<f32>,
     shadow_blur_radius: Option<f32>,
+    shadow_enabled:     Option<bool>,
+    snap:               Option<bool>,
 }
 
 impl_merge!(ContainerFieldsRaw {
     background, text_color,
-    border_width, border_color, border_radius,
-    shadow_color, shadow_offset_x, shadow_offset_y, shadow_blur_radius,
+    border_width, border_color, border_radius, border_enabled,
+    shadow_color, shadow_offset_x, shadow_offset_y, shadow_blur_radius, shadow_enabled,
+    snap,
 });
 
 /// Top-level `[container]` section. No status sub-tables.
@@ -43,13 +51,13 @@ impl ContainerSection {
 }
 
 fn into_native(f: ContainerFieldsRaw) -> container::Style {
-    container::Style {
-        background: f.background.map(BackgroundRaw::into_background),
-        text_color: f.text_color.map(|c| c.0),
-        border: resolve_border(f.border_width, f.border_color, f.border_radius),
-        shadow: resolve_shadow(f.shadow_color, f.shadow_offset_x, f.shadow_offset_y, f.shadow_blur_radius),
-        snap: false,
-    }
+    compat::container_style(
+        f.background.map(BackgroundRaw::into_background),
+        f.text_color.map(|c| c.0),
+        resolve_border(f.border_width, f.border_color, f.border_radius, f.border_enabled),
+        resolve_shadow(f.shadow_color, f.shadow_offset_x, f.shadow_offset_y, f.shadow_blur_radius, f.shadow_enabled),
+        f.snap.unwrap_or(false),
+    )
 }
 
 // -- Layer 3: Public types --
@@ -64,4 +72,55 @@ impl ContainerStyle {
         let s = self.0;
         move |_theme| s
     }
+
+    /// Like [`style_fn`](Self::style_fn), with `overrides` applied; see [`StatusOverrides`].
+    pub fn style_fn_with(&self, overrides: StatusOverrides) -> impl Fn(&Theme) -> container::Style + Copy + 'static {
+        let s = self.0.apply_overrides(&overrides);
+        move |_theme| s
+    }
+}
+
+/// The root container's style: `style`, with the palette's background and text
+/// colors filling in whatever it leaves unset, so the window is never
+/// transparent.
+pub(crate) fn root_style_fn(style: Option<ContainerStyle>) -> impl Fn(&Theme) -> container::Style + Copy + 'static {
+    move |theme| {
+        let mut s = style.map(|s| s.0).unwrap_or_default();
+        let base = theme.extended_palette().background.base;
+        s.background = s.background.or(Some(base.color.into()));
+        s.text_color = s.text_color.or(Some(base.text));
+        s
+    }
+}
+
+impl MapColors for container::Style {
+    fn map_colors(mut self, f: &impl Fn(Color) -> Color) -> Self {
+        self.background = self.background.map_colors(f);
+        self.text_color = self.text_color.map_colors(f);
+        self.border = self.border.map_colors(f);
+        self.shadow = self.shadow.map_colors(f);
+        self
+    }
+}
+
+impl ApplyOverrides for container::Style {
+    fn apply_overrides(mut self, o: &StatusOverrides) -> Self {
+        self.background = o.background.or(self.background);
+        self.text_color = o.text_color.or(self.text_color);
+        self.border = o.border(self.border);
+        self.shadow = o.shadow.unwrap_or(self.shadow);
+        self
+    }
+}
+
+impl MapColors for ContainerStyle {
+    fn map_colors(self, f: &impl Fn(Color) -> Color) -> Self {
+        ContainerStyle(self.0.map_colors(f))
+    }
+}
+
+impl ApproxEq for ContainerStyle {
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        self.0.approx_eq(&other.0, epsilon)
+    }
 }
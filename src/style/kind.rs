@@ -0,0 +1,91 @@
+use crate::ThemeConfig;
+use super::widget_sections;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// A widget style that [`ThemeConfig::style`] can look up by type.
+///
+/// Implemented for every style type in this module, and sealed: the set of
+/// sections is fixed by the crate. [`ContainerStyle`](super::ContainerStyle)
+/// is the `[container]` section; `[root]` is only reachable through
+/// [`ThemeConfig::root`].
+pub trait StyleKind: sealed::Sealed + Sized {
+    /// The TOML section the style is read from, like `"text-input"`.
+    const SECTION: &'static str;
+
+    #[doc(hidden)]
+    fn from_config(config: &ThemeConfig) -> Option<&Self>;
+
+    #[doc(hidden)]
+    fn variant_from_config<'a>(config: &'a ThemeConfig, name: &str) -> Option<&'a Self>;
+}
+
+macro_rules! style_kind {
+    ($($(#[$attr:meta])* $field:ident $name:literal $style:ident $raw:ident $resolve:ident $catalog:ident $statuses:tt;)*) => {$(
+        $(#[$attr])*
+        impl sealed::Sealed for super::$style {}
+
+        $(#[$attr])*
+        impl StyleKind for super::$style {
+            const SECTION: &'static str = $name;
+
+            fn from_config(config: &ThemeConfig) -> Option<&Self> {
+                config.$field.as_ref()
+            }
+
+            fn variant_from_config<'a>(config: &'a ThemeConfig, name: &str) -> Option<&'a Self> {
+                config.variants.$field.get(name)
+            }
+        }
+    )*};
+}
+widget_sections!(style_kind);
+
+impl ThemeConfig {
+    /// The style of type `T`, for code that is generic over widget kinds:
+    ///
+    /// ```
+    /// # use iced_themer::ThemeConfig;
+    /// use iced_themer::style::{ButtonStyle, StyleKind};
+    ///
+    /// fn has_section<T: StyleKind>(config: &ThemeConfig) -> bool {
+    ///     config.style::<T>().is_some()
+    /// }
+    ///
+    /// # let config: ThemeConfig = "[palette]\nbackground = \"#FAFAFA\"\ntext = \"#202020\"\n\
+    /// #     primary = \"#1E88E5\"\nsuccess = \"#43A047\"\nwarning = \"#FB8C00\"\ndanger = \"#E53935\"\n\
+    /// #     [button]\nbackground = \"@primary\"\n".parse().unwrap();
+    /// assert!(has_section::<ButtonStyle>(&config));
+    /// ```
+    ///
+    /// `config.style::<ButtonStyle>()` is the same as `config.button()`.
+    pub fn style<T: StyleKind>(&self) -> Option<&T> {
+        T::from_config(self)
+    }
+
+    /// The named variant of the style of type `T`; the generic counterpart of
+    /// [`button_variant`](Self::button_variant) and the like.
+    pub fn style_variant<T: StyleKind>(&self, name: &str) -> Option<&T> {
+        T::variant_from_config(self, name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::{ButtonStyle, ProgressBarStyle, TextInputStyle};
+    use crate::testing::PALETTE;
+
+    #[test]
+    fn style_looks_up_the_section_of_its_type() {
+        let toml = "[text-input]\nborder-width = 1.0\n\n[text-input.variants.search]\nborder-width = 2.0\n";
+        let config: ThemeConfig = format!("{PALETTE}{toml}").parse().unwrap();
+        assert!(config.style::<TextInputStyle>().is_some());
+        assert!(config.style_variant::<TextInputStyle>("search").is_some());
+        assert!(config.style::<ButtonStyle>().is_none());
+        assert_eq!(<TextInputStyle as StyleKind>::SECTION, "text-input");
+        assert!(config.coverage().section(ProgressBarStyle::SECTION).is_some());
+    }
+}
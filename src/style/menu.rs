@@ -0,0 +1,101 @@
+use iced_core::{Background, Color, Theme};
+use iced_widget::overlay::menu;
+use serde::Deserialize;
+
+use crate::color::HexColor;
+use crate::compat;
+use super::{
+    ApplyOverrides, ApproxEq, BackgroundRaw, MapColors, RadiusRaw, StatusOverrides, impl_merge, resolve_border,
+    resolve_shadow,
+};
+
+// -- Layer 1: Serde raw types --
+
+#[derive(Deserialize, Default, Clone, Copy)]
+#[serde(default, rename_all = "kebab-case")]
+pub(crate) struct MenuFieldsRaw {
+    background:          Option<BackgroundRaw>,
+    text_color:          Option<HexColor>,
+    selected_background: Option<BackgroundRaw>,
+    selected_text_color: Option<HexColor>,
+    border_width:        Option<f32>,
+    border_color:        Option<HexColor>,
+    border_radius:       Option<RadiusRaw>,
+    border_enabled:      Option<bool>,
+    shadow_color:        Option<HexColor>,
+    shadow_offset_x:     Option<f32>,
+    shadow_offset_y:     Option<f32>,
+    shadow_blur_radius:  Option<f32>,
+    shadow_enabled:      Option<bool>,
+}
+
+impl_merge!(MenuFieldsRaw {
+    background, text_color, selected_background, selected_text_color,
+    border_width, border_color, border_radius, border_enabled,
+    shadow_color, shadow_offset_x, shadow_offset_y, shadow_blur_radius, shadow_enabled,
+});
+
+/// Top-level `[menu]` section, for the drop-down lists of pick lists and
+/// combo boxes. No status sub-tables: the option under the pointer is the
+/// selected one.
+#[derive(Deserialize)]
+pub(crate) struct MenuSection {
+    #[serde(flatten)]
+    base: MenuFieldsRaw,
+}
+
+// -- Layer 2: Resolution --
+
+impl MenuSection {
+    pub fn resolve(self) -> MenuStyle {
+        MenuStyle(into_native(self.base))
+    }
+}
+
+fn into_native(f: MenuFieldsRaw) -> menu::Style {
+    compat::menu_style(
+        f.background.map(BackgroundRaw::into_background).unwrap_or(Background::Color(Color::WHITE)),
+        resolve_border(f.border_width, f.border_color, f.border_radius, f.border_enabled),
+        f.text_color.map(|c| c.0).unwrap_or(Color::BLACK),
+        f.selected_text_color.map(|c| c.0).unwrap_or(Color::WHITE),
+        f.selected_background
+            .map(BackgroundRaw::into_background)
+            .unwrap_or(Background::Color(Color::from_rgb8(0x33, 0x99, 0xFF))),
+        resolve_shadow(f.shadow_color, f.shadow_offset_x, f.shadow_offset_y, f.shadow_blur_radius, f.shadow_enabled),
+    )
+}
+
+// -- Layer 3: Public types --
+
+/// Pre-resolved style for the drop-down menu of a pick list or combo box.
+#[derive(Debug, Clone, Copy)]
+pub struct MenuStyle(menu::Style);
+
+impl MenuStyle {
+    /// Returns a closure suitable for passing to `.menu_style()` on a pick
+    /// list or combo box widget.
+    pub fn style_fn(&self) -> impl Fn(&Theme) -> menu::Style + Copy + 'static {
+        let s = self.0;
+        move |_theme| s
+    }
+
+    /// Like [`style_fn`](Self::style_fn), with `overrides` applied; see
+    /// [`StatusOverrides`]. The selected entry keeps its colors, and iced
+    /// 0.13 menus have no shadow.
+    pub fn style_fn_with(&self, overrides: StatusOverrides) -> impl Fn(&Theme) -> menu::Style + Copy + 'static {
+        let s = self.0.apply_overrides(&overrides);
+        move |_theme| s
+    }
+}
+
+impl MapColors for MenuStyle {
+    fn map_colors(self, f: &impl Fn(Color) -> Color) -> Self {
+        MenuStyle(self.0.map_colors(f))
+    }
+}
+
+impl ApproxEq for MenuStyle {
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        self.0.approx_eq(&other.0, epsilon)
+    }
+}
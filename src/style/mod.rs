@@ -4,46 +4,186 @@
 //! `iced_widget` styles for every status variant. Call `style_fn()` and pass
 //! the result directly to the widget's `.style()` builder method.
 
+pub mod background;
 mod button;
 mod checkbox;
+mod combo_box;
 mod container;
+mod kind;
+mod menu;
+mod overrides;
+mod pane_grid;
+mod pick_list;
 mod progress_bar;
 mod radio;
+mod rule;
+mod scrollable;
 mod slider;
+mod svg;
+#[cfg(feature = "table")]
+mod table;
+mod text;
 mod text_input;
 mod toggler;
 
 pub use button::ButtonStyle;
-pub use checkbox::CheckboxStyle;
+pub use checkbox::{CheckboxIcon, CheckboxStyle};
+pub use combo_box::ComboBoxStyle;
 pub use container::ContainerStyle;
+pub use kind::StyleKind;
+pub use menu::MenuStyle;
+pub use overrides::StatusOverrides;
+pub use pane_grid::PaneGridStyle;
+pub use pick_list::PickListStyle;
 pub use progress_bar::ProgressBarStyle;
 pub use radio::RadioStyle;
+pub use rule::RuleStyle;
+pub use scrollable::ScrollableStyle;
 pub use slider::SliderStyle;
+pub use svg::SvgStyle;
+#[cfg(feature = "table")]
+pub use table::{TableRow, TableRowStatus, TableStyle};
+pub use text::TextStyle;
 pub use text_input::TextInputStyle;
 pub use toggler::TogglerStyle;
 
+pub(crate) use background::BackgroundRaw;
+pub(crate) use overrides::ApplyOverrides;
+
 pub(crate) use button::ButtonSection;
 pub(crate) use checkbox::CheckboxSection;
-pub(crate) use container::ContainerSection;
+pub(crate) use combo_box::ComboBoxSection;
+pub(crate) use container::{ContainerSection, root_style_fn};
+pub(crate) use menu::MenuSection;
+pub(crate) use pane_grid::PaneGridSection;
+pub(crate) use pick_list::PickListSection;
 pub(crate) use progress_bar::ProgressBarSection;
 pub(crate) use radio::RadioSection;
+pub(crate) use rule::RuleSection;
+pub(crate) use scrollable::ScrollableSection;
 pub(crate) use slider::SliderSection;
+pub(crate) use svg::SvgSection;
+#[cfg(feature = "table")]
+pub(crate) use table::TableSection;
+pub(crate) use text::TextSection;
 pub(crate) use text_input::TextInputSection;
 pub(crate) use toggler::TogglerSection;
 
-use iced_core::{Background, Border, Degrees};
-use iced_core::gradient::Linear;
+use iced_core::border::Radius;
+use iced_core::gradient::ColorStop;
+use iced_core::{Background, Border, Color, Font, Padding, Shadow, Vector};
+use std::collections::BTreeMap;
+
 use serde::Deserialize;
 
 use crate::color::HexColor;
+use crate::config;
 
-/// Flexible border-radius: a single `f32` for uniform corners, or `[f32; 4]`
-/// for `[top-left, top-right, bottom-right, bottom-left]`.
-#[derive(Deserialize, Clone, Copy, Debug)]
-#[serde(untagged)]
+/// Calls `$callback!` with every widget section that has its own style type
+/// and variants, in alphabetical order. Each entry is
+///
+/// ```text
+/// field "toml-name" Style Section resolve catalog [statuses];
+/// ```
+///
+/// where `resolve` is `dim` if `Section::resolve` takes the theme's
+/// [`DisabledDimming`] and `plain` if not, `catalog` is `status` or `plain`
+/// for the shape of the widget's iced `Catalog` and `none` if it has none, and
+/// `statuses` are the status sub-tables iced can use (radio's disabled tables
+/// are parsed but never applied, so they aren't listed). `[root]` is a
+/// [`ContainerSection`] without variants, so it isn't listed. A section
+/// behind a feature starts with its `#[cfg(...)]`, which callbacks put on
+/// everything they generate for it.
+macro_rules! widget_sections {
+    ($callback:ident) => {
+        $callback! {
+            button       "button"       ButtonStyle      ButtonSection      dim   status
+                ["hovered", "pressed", "disabled"];
+            checkbox     "checkbox"     CheckboxStyle    CheckboxSection    dim   status
+                ["checked", "hovered", "hovered-checked", "disabled", "disabled-checked"];
+            combo_box    "combo-box"    ComboBoxStyle    ComboBoxSection    dim   none
+                ["hovered", "focused", "disabled"];
+            container    "container"    ContainerStyle   ContainerSection   plain plain  [];
+            menu         "menu"         MenuStyle        MenuSection        plain plain  [];
+            pane_grid    "pane-grid"    PaneGridStyle    PaneGridSection    plain plain  [];
+            pick_list    "pick-list"    PickListStyle    PickListSection    plain status ["hovered", "opened"];
+            progress_bar "progress-bar" ProgressBarStyle ProgressBarSection plain plain  [];
+            radio        "radio"        RadioStyle       RadioSection       plain status
+                ["selected", "hovered", "hovered-selected"];
+            rule         "rule"         RuleStyle        RuleSection        plain plain  [];
+            scrollable   "scrollable"   ScrollableStyle  ScrollableSection  plain status ["hovered", "dragged"];
+            slider       "slider"       SliderStyle      SliderSection      plain status ["hovered", "dragged"];
+            svg          "svg"          SvgStyle         SvgSection         plain status ["hovered"];
+            #[cfg(feature = "table")]
+            table        "table"        TableStyle       TableSection       plain plain  ["hovered", "selected"];
+            text         "text"         TextStyle        TextSection        plain plain  [];
+            text_input   "text-input"   TextInputStyle   TextInputSection   dim   status
+                ["hovered", "focused", "disabled"];
+            toggler      "toggler"      TogglerStyle     TogglerSection     dim   status
+                ["toggled", "hovered", "hovered-toggled", "disabled", "disabled-toggled"];
+        }
+    };
+}
+pub(crate) use widget_sections;
+
+/// The top-level widget sections: those of `widget_sections!` and `root`.
+macro_rules! widget_section_names {
+    ($($(#[$attr:meta])* $field:ident $name:literal $style:ident $raw:ident $resolve:ident $catalog:ident $statuses:tt;)*) => {
+        /// Top-level sections that nothing else in a theme depends on, other
+        /// than through `[fallbacks]`.
+        pub(crate) const WIDGET_SECTIONS: &[&str] = &["root", $($(#[$attr])* $name),*];
+    };
+}
+widget_sections!(widget_section_names);
+
+/// Flexible border-radius: a single `f32` for uniform corners, `[f32; 4]`
+/// for `[top-left, top-right, bottom-right, bottom-left]`, a CSS shorthand
+/// string like `"4 8"`, or `"pill"` for fully rounded ends.
+#[derive(Clone, Copy, Debug)]
 pub(crate) enum RadiusRaw {
     Uniform(f32),
     PerCorner([f32; 4]),
+    Pill,
+}
+
+/// The radius `"pill"` stands for. iced clamps each corner to half the
+/// shorter side, so any radius this large rounds the ends completely.
+const PILL_RADIUS: f32 = 1.0e6;
+
+impl<'de> Deserialize<'de> for RadiusRaw {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        match toml::Value::deserialize(deserializer)? {
+            toml::Value::String(s) if s == "pill" => Ok(RadiusRaw::Pill),
+            value @ (toml::Value::Integer(_) | toml::Value::Float(_)) => {
+                config::deserialize_nested(value).map(RadiusRaw::Uniform)
+            }
+            value @ toml::Value::Array(_) => config::deserialize_nested(value).map(RadiusRaw::PerCorner),
+            other => other.as_str().and_then(css_radius).ok_or_else(|| {
+                serde::de::Error::custom(format!(
+                    "invalid value: {other}, expected a radius, 4 corner radii, shorthand like \"4 8\", or \"pill\""
+                ))
+            }),
+        }
+    }
+}
+
+/// Parses CSS `border-radius` shorthand, with or without `px`: one value
+/// for every corner, two for top-left and bottom-right then top-right and
+/// bottom-left, or four from the top left, clockwise.
+fn css_radius(s: &str) -> Option<RadiusRaw> {
+    let values = s
+        .split_whitespace()
+        .map(|v| v.strip_suffix("px").unwrap_or(v).parse::<f32>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    match values[..] {
+        [all] => Some(RadiusRaw::Uniform(all)),
+        [a, b] => Some(RadiusRaw::PerCorner([a, b, a, b])),
+        [tl, tr, br, bl] => Some(RadiusRaw::PerCorner([tl, tr, br, bl])),
+        _ => None,
+    }
 }
 
 impl RadiusRaw {
@@ -56,130 +196,336 @@ impl RadiusRaw {
                 bottom_right: br,
                 bottom_left: bl,
             },
+            RadiusRaw::Pill => PILL_RADIUS.into(),
         }
     }
 }
 
-/// A background that is either a solid color or a linear gradient.
+/// Resolve border fields from raw Option values, falling back to iced defaults.
+///
+/// `border-enabled = false` drops the stroke but keeps the radius, which
+/// still rounds the widget's background.
+pub(crate) fn resolve_border(
+    width: Option<f32>,
+    color: Option<HexColor>,
+    radius: Option<RadiusRaw>,
+    enabled: Option<bool>,
+) -> Border {
+    let radius = radius.map(RadiusRaw::into_radius).unwrap_or(0.0.into());
+    if enabled == Some(false) {
+        return Border { radius, ..Border::default() };
+    }
+    Border {
+        color: color.map(|c| c.0).unwrap_or(iced_core::Color::TRANSPARENT),
+        width: width.unwrap_or(0.0),
+        radius,
+    }
+}
+
+/// Resolve shadow fields from raw Option values; none at all for
+/// `shadow-enabled = false`.
+pub(crate) fn resolve_shadow(
+    color: Option<HexColor>,
+    offset_x: Option<f32>,
+    offset_y: Option<f32>,
+    blur_radius: Option<f32>,
+    enabled: Option<bool>,
+) -> iced_core::Shadow {
+    if enabled == Some(false) {
+        return iced_core::Shadow::default();
+    }
+    iced_core::Shadow {
+        color: color.map(|c| c.0).unwrap_or(iced_core::Color::TRANSPARENT),
+        offset: iced_core::Vector::new(offset_x.unwrap_or(0.0), offset_y.unwrap_or(0.0)),
+        blur_radius: blur_radius.unwrap_or(0.0),
+    }
+}
+
+/// The `label-*` keys of sections for widgets with a text label. They apply
+/// in every status, so status sub-tables don't take them.
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "kebab-case")]
+pub(crate) struct LabelRaw {
+    label_spacing: Option<f32>,
+    label_size:    Option<f32>,
+    label_font:    Option<config::FontRaw>,
+}
+
+impl LabelRaw {
+    pub fn resolve(self) -> Label {
+        Label { spacing: self.label_spacing, size: self.label_size, font: self.label_font.map(config::build_font) }
+    }
+}
+
+/// Resolved `label-*` keys: the gap between a widget and its label, and the
+/// label's text size and font. Unset ones keep the widget's own.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Label {
+    pub spacing: Option<f32>,
+    pub size:    Option<f32>,
+    pub font:    Option<Font>,
+}
+
+/// The `text-size` and `padding` keys of sections for widgets that size
+/// their own text and content. Like the `label-*` keys, they apply in every
+/// status.
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "kebab-case")]
+pub(crate) struct LayoutRaw {
+    text_size: Option<f32>,
+    padding:   Option<PaddingRaw>,
+}
+
+/// `padding`: the same on every side, `[vertical, horizontal]`, or
+/// `[top, right, bottom, left]`.
+#[derive(Deserialize, Clone, Copy)]
+#[serde(untagged, expecting = "a padding, [vertical, horizontal], or [top, right, bottom, left]")]
+enum PaddingRaw {
+    Uniform(f32),
+    Axes([f32; 2]),
+    Sides([f32; 4]),
+}
+
+impl LayoutRaw {
+    pub fn resolve(self) -> Layout {
+        let padding = self.padding.map(|p| match p {
+            PaddingRaw::Uniform(all) => Padding::from(all),
+            PaddingRaw::Axes(axes) => Padding::from(axes),
+            PaddingRaw::Sides([top, right, bottom, left]) => Padding { top, right, bottom, left },
+        });
+        Layout { text_size: self.text_size, padding }
+    }
+}
+
+/// Resolved `text-size` and `padding` keys, layout hints carried alongside a
+/// style. Unset ones keep the widget's own.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Layout {
+    pub text_size: Option<f32>,
+    pub padding:   Option<Padding>,
+}
+
+/// Applies a color transformation to every color in a resolved style.
 ///
-/// Strings deserialize as solid colors via `HexColor`; tables with `angle` and
-/// `stops` fields deserialize as gradients. This mirrors `iced_core::Background`
-/// but uses serde-friendly types.
-#[derive(Deserialize, Clone, Copy, Debug)]
-#[serde(untagged)]
-pub(crate) enum BackgroundRaw {
-    Color(HexColor),
-    Gradient(GradientRaw),
-}
-
-impl BackgroundRaw {
-    pub fn into_background(self) -> Background {
+/// This is what whole-theme transforms such as
+/// [`ThemeConfig::derive_dark`](crate::ThemeConfig::derive_dark) are built on:
+/// every public style type and the native `iced_widget` styles it wraps
+/// implement it.
+pub(crate) trait MapColors {
+    fn map_colors(self, f: &impl Fn(Color) -> Color) -> Self;
+}
+
+impl MapColors for Color {
+    fn map_colors(self, f: &impl Fn(Color) -> Color) -> Self {
+        f(self)
+    }
+}
+
+impl<T: MapColors> MapColors for Option<T> {
+    fn map_colors(self, f: &impl Fn(Color) -> Color) -> Self {
+        self.map(|v| v.map_colors(f))
+    }
+}
+
+impl<T: MapColors> MapColors for BTreeMap<String, T> {
+    fn map_colors(self, f: &impl Fn(Color) -> Color) -> Self {
+        self.into_iter().map(|(name, v)| (name, v.map_colors(f))).collect()
+    }
+}
+
+impl MapColors for Background {
+    fn map_colors(self, f: &impl Fn(Color) -> Color) -> Self {
         match self {
-            BackgroundRaw::Color(c) => Background::Color(c.0),
-            BackgroundRaw::Gradient(g) => g.into_background(),
+            Background::Color(c) => Background::Color(f(c)),
+            Background::Gradient(iced_core::Gradient::Linear(mut linear)) => {
+                for stop in linear.stops.iter_mut().flatten() {
+                    stop.color = f(stop.color);
+                }
+                Background::Gradient(iced_core::Gradient::Linear(linear))
+            }
         }
     }
 }
 
-/// A single color stop in a gradient: an offset in `0.0..=1.0` and a color.
-#[derive(Clone, Copy, Debug)]
-pub(crate) struct ColorStopEntry {
-    pub offset: f32,
-    pub color: HexColor,
+impl MapColors for Border {
+    fn map_colors(self, f: &impl Fn(Color) -> Color) -> Self {
+        Border { color: f(self.color), ..self }
+    }
+}
+
+impl MapColors for Shadow {
+    fn map_colors(self, f: &impl Fn(Color) -> Color) -> Self {
+        Shadow { color: f(self.color), ..self }
+    }
 }
 
-/// A linear gradient with an angle (in degrees) and up to 8 color stops.
+/// Compares two resolved styles, with every color channel, width, radius,
+/// and other number within `epsilon` of its counterpart, and everything else
+/// exactly equal.
 ///
-/// Uses a fixed-size array to preserve `Copy` throughout the style system.
-/// A custom `Deserialize` reads a TOML vec and packs it into the array,
-/// validating the stop count and offset range.
-#[derive(Clone, Copy, Debug)]
-pub(crate) struct GradientRaw {
-    pub angle: f32,
-    pub stops: [Option<ColorStopEntry>; 8],
+/// This is what [`ThemeConfig::approx_eq`](crate::ThemeConfig::approx_eq) is
+/// built on: like [`MapColors`], every public style type and the native
+/// `iced_widget` styles it wraps implement it.
+pub(crate) trait ApproxEq {
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool;
 }
 
-impl GradientRaw {
-    fn into_background(self) -> Background {
-        let mut linear = Linear::new(Degrees(self.angle));
-        for stop in self.stops.into_iter().flatten() {
-            linear = linear.add_stop(stop.offset, stop.color.0);
-        }
-        Background::Gradient(iced_core::Gradient::Linear(linear))
+impl ApproxEq for f32 {
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        self == other || (self - other).abs() <= epsilon
     }
 }
 
-impl<'de> Deserialize<'de> for GradientRaw {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: serde::Deserializer<'de>,
-    {
-        #[derive(Deserialize)]
-        struct StopHelper {
-            offset: f32,
-            color: HexColor,
-        }
+impl ApproxEq for Color {
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        [self.r, self.g, self.b, self.a].approx_eq(&[other.r, other.g, other.b, other.a], epsilon)
+    }
+}
 
-        #[derive(Deserialize)]
-        struct GradientHelper {
-            angle: f32,
-            stops: Vec<StopHelper>,
+impl<T: ApproxEq> ApproxEq for Option<T> {
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        match (self, other) {
+            (Some(a), Some(b)) => a.approx_eq(b, epsilon),
+            (a, b) => a.is_none() && b.is_none(),
         }
+    }
+}
+
+impl<T: ApproxEq> ApproxEq for [T] {
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        self.len() == other.len() && self.iter().zip(other).all(|(a, b)| a.approx_eq(b, epsilon))
+    }
+}
 
-        let helper = GradientHelper::deserialize(deserializer)?;
+impl<T: ApproxEq, const N: usize> ApproxEq for [T; N] {
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        self[..].approx_eq(&other[..], epsilon)
+    }
+}
 
-        if helper.stops.len() > 8 {
-            return Err(serde::de::Error::custom(format!(
-                "gradient supports at most 8 color stops, got {}",
-                helper.stops.len()
-            )));
+impl<A: ApproxEq, B: ApproxEq> ApproxEq for (A, B) {
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        self.0.approx_eq(&other.0, epsilon) && self.1.approx_eq(&other.1, epsilon)
+    }
+}
+
+impl<T: ApproxEq> ApproxEq for BTreeMap<String, T> {
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        self.len() == other.len()
+            && self.iter().zip(other).all(|((ka, a), (kb, b))| ka == kb && a.approx_eq(b, epsilon))
+    }
+}
+
+/// Implements [`ApproxEq`] as plain equality, for types without numbers
+/// worth a tolerance.
+macro_rules! approx_eq_exactly {
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            impl $crate::style::ApproxEq for $ty {
+                fn approx_eq(&self, other: &Self, _epsilon: f32) -> bool {
+                    self == other
+                }
+            }
+        )+
+    };
+}
+
+pub(crate) use approx_eq_exactly;
+
+approx_eq_exactly!(bool, u16, char, String, Font);
+
+/// Implements [`ApproxEq`] for a struct by comparing the listed fields.
+macro_rules! impl_approx_eq {
+    ($ty:ty { $($(#[$attr:meta])* $field:ident),+ $(,)? }) => {
+        impl $crate::style::ApproxEq for $ty {
+            fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+                $(
+                    $(#[$attr])*
+                    if !$crate::style::ApproxEq::approx_eq(&self.$field, &other.$field, epsilon) {
+                        return false;
+                    }
+                )+
+                true
+            }
+        }
+    };
+}
+
+pub(crate) use impl_approx_eq;
+
+impl_approx_eq!(Vector { x, y });
+impl_approx_eq!(Radius { top_left, top_right, bottom_right, bottom_left });
+impl_approx_eq!(Border { color, width, radius });
+impl_approx_eq!(Shadow { color, offset, blur_radius });
+impl_approx_eq!(Padding { top, right, bottom, left });
+impl_approx_eq!(ColorStop { offset, color });
+impl_approx_eq!(Label { spacing, size, font });
+impl_approx_eq!(Layout { text_size, padding });
+
+macro_rules! variants {
+    ($($(#[$attr:meta])* $field:ident $name:literal $style:ident $raw:ident $resolve:ident $catalog:ident $statuses:tt;)*) => {
+        /// Named `[<widget>.variants.<name>]` styles, each resolved from its
+        /// widget section with the variant's keys merged over it.
+        #[derive(Debug, Clone, Default)]
+        pub(crate) struct Variants {
+            $($(#[$attr])* pub $field: BTreeMap<String, $style>,)*
         }
 
-        let mut arr = [None; 8];
-        for (i, s) in helper.stops.into_iter().enumerate() {
-            if !(0.0..=1.0).contains(&s.offset) {
-                return Err(serde::de::Error::custom(format!(
-                    "color stop offset must be in 0.0..=1.0, got {}",
-                    s.offset
-                )));
+        impl MapColors for Variants {
+            fn map_colors(self, f: &impl Fn(Color) -> Color) -> Self {
+                Variants { $($(#[$attr])* $field: self.$field.map_colors(f),)* }
             }
-            arr[i] = Some(ColorStopEntry {
-                offset: s.offset,
-                color: s.color,
-            });
         }
 
-        Ok(GradientRaw {
-            angle: helper.angle,
-            stops: arr,
-        })
+        impl_approx_eq!(Variants { $($(#[$attr])* $field,)* });
+    };
+}
+widget_sections!(variants);
+
+impl ApproxEq for Background {
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        match (self, other) {
+            (Background::Color(a), Background::Color(b)) => a.approx_eq(b, epsilon),
+            (
+                Background::Gradient(iced_core::Gradient::Linear(a)),
+                Background::Gradient(iced_core::Gradient::Linear(b)),
+            ) => a.angle.0.approx_eq(&b.angle.0, epsilon) && a.stops.approx_eq(&b.stops, epsilon),
+            _ => false,
+        }
     }
 }
 
-/// Resolve border fields from raw Option values, falling back to iced defaults.
-pub(crate) fn resolve_border(
-    width: Option<f32>,
-    color: Option<HexColor>,
-    radius: Option<RadiusRaw>,
-) -> Border {
-    Border {
-        color: color.map(|c| c.0).unwrap_or(iced_core::Color::TRANSPARENT),
-        width: width.unwrap_or(0.0),
-        radius: radius.map(RadiusRaw::into_radius).unwrap_or(0.0.into()),
+/// Theme-wide dimming applied to disabled appearances that a section does not
+/// define explicitly, configured by `[defaults] disabled-opacity` and
+/// `disabled-desaturate`.
+///
+/// Both factors are in `0.0..=1.0`: every color's alpha is multiplied by
+/// `opacity`, and its HSL saturation is reduced by `desaturate`. The default
+/// is the identity (`opacity = 1.0`, `desaturate = 0.0`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct DisabledDimming {
+    pub opacity: f32,
+    pub desaturate: f32,
+}
+
+impl Default for DisabledDimming {
+    fn default() -> Self {
+        DisabledDimming { opacity: 1.0, desaturate: 0.0 }
     }
 }
 
-/// Resolve shadow fields from raw Option values.
-pub(crate) fn resolve_shadow(
-    color: Option<HexColor>,
-    offset_x: Option<f32>,
-    offset_y: Option<f32>,
-    blur_radius: Option<f32>,
-) -> iced_core::Shadow {
-    iced_core::Shadow {
-        color: color.map(|c| c.0).unwrap_or(iced_core::Color::TRANSPARENT),
-        offset: iced_core::Vector::new(offset_x.unwrap_or(0.0), offset_y.unwrap_or(0.0)),
-        blur_radius: blur_radius.unwrap_or(0.0),
+impl DisabledDimming {
+    /// Dim every color in `style`.
+    pub fn apply<S: MapColors>(self, style: S) -> S {
+        if self == DisabledDimming::default() {
+            return style;
+        }
+        style.map_colors(&|c| {
+            let mut hsl = crate::color::to_hsl(c);
+            hsl.s *= 1.0 - self.desaturate;
+            hsl.a *= self.opacity;
+            crate::color::from_hsl(hsl)
+        })
     }
 }
 
@@ -208,131 +554,17 @@ mod tests {
     use super::*;
     use iced_core::Background;
 
-    /// Helper: wraps a TOML value in a `bg = ...` key so we can deserialize
-    /// `BackgroundRaw` from a standalone value via a wrapper struct.
-    #[derive(Deserialize)]
-    struct Wrapper {
-        bg: BackgroundRaw,
-    }
-
-    fn parse_bg(toml_str: &str) -> Result<BackgroundRaw, toml::de::Error> {
-        toml::from_str::<Wrapper>(toml_str).map(|w| w.bg)
-    }
-
     #[test]
-    fn background_raw_parses_solid_color() {
-        let raw = parse_bg(r##"bg = "#FF0000""##).unwrap();
-        assert!(matches!(raw, BackgroundRaw::Color(_)));
+    fn default_dimming_is_identity() {
+        let bg = Background::Color(iced_core::Color::from_rgb8(0x66, 0xC0, 0xF4));
+        assert_eq!(DisabledDimming::default().apply(bg), bg);
     }
 
     #[test]
-    fn background_raw_parses_gradient() {
-        let raw = parse_bg(r##"
-            [bg]
-            angle = 45.0
-            stops = [
-                { offset = 0.0, color = "#ff0000" },
-                { offset = 1.0, color = "#0000ff" },
-            ]
-        "##).unwrap();
-        match raw {
-            BackgroundRaw::Gradient(g) => {
-                assert!((g.angle - 45.0).abs() < f32::EPSILON);
-                assert!(g.stops[0].is_some());
-                assert!(g.stops[1].is_some());
-                assert!(g.stops[2].is_none());
-            }
-            _ => panic!("expected Gradient variant"),
-        }
-    }
-
-    #[test]
-    fn gradient_rejects_more_than_8_stops() {
-        let result: Result<GradientRaw, _> = toml::from_str(r##"
-            angle = 0.0
-            stops = [
-                { offset = 0.0, color = "#000000" },
-                { offset = 0.1, color = "#111111" },
-                { offset = 0.2, color = "#222222" },
-                { offset = 0.3, color = "#333333" },
-                { offset = 0.4, color = "#444444" },
-                { offset = 0.5, color = "#555555" },
-                { offset = 0.6, color = "#666666" },
-                { offset = 0.7, color = "#777777" },
-                { offset = 0.8, color = "#888888" },
-            ]
-        "##);
-        assert!(result.is_err());
-        let err = result.unwrap_err().to_string();
-        assert!(err.contains("at most 8"), "error was: {err}");
-    }
-
-    #[test]
-    fn gradient_rejects_offset_out_of_range() {
-        let result: Result<GradientRaw, _> = toml::from_str(r##"
-            angle = 0.0
-            stops = [
-                { offset = 0.0, color = "#000000" },
-                { offset = 1.5, color = "#ffffff" },
-            ]
-        "##);
-        assert!(result.is_err());
-        let err = result.unwrap_err().to_string();
-        assert!(err.contains("0.0..=1.0"), "error was: {err}");
-    }
-
-    #[test]
-    fn gradient_accepts_8_stops() {
-        let raw: GradientRaw = toml::from_str(r##"
-            angle = 90.0
-            stops = [
-                { offset = 0.0,   color = "#000000" },
-                { offset = 0.143, color = "#111111" },
-                { offset = 0.286, color = "#222222" },
-                { offset = 0.429, color = "#333333" },
-                { offset = 0.571, color = "#444444" },
-                { offset = 0.714, color = "#555555" },
-                { offset = 0.857, color = "#666666" },
-                { offset = 1.0,   color = "#777777" },
-            ]
-        "##).unwrap();
-        assert!(raw.stops.iter().all(|s| s.is_some()));
-    }
-
-    #[test]
-    fn solid_color_converts_to_background_color() {
-        let raw = parse_bg(r##"bg = "#ff0000""##).unwrap();
-        let bg = raw.into_background();
-        match bg {
-            Background::Color(c) => {
-                assert!((c.r - 1.0).abs() < 0.01);
-                assert!(c.g.abs() < 0.01);
-                assert!(c.b.abs() < 0.01);
-            }
-            _ => panic!("expected Background::Color"),
-        }
-    }
-
-    #[test]
-    fn gradient_converts_to_background_gradient() {
-        let raw = parse_bg(r##"
-            [bg]
-            angle = 90.0
-            stops = [
-                { offset = 0.0, color = "#ff0000" },
-                { offset = 1.0, color = "#0000ff" },
-            ]
-        "##).unwrap();
-        let bg = raw.into_background();
-        match bg {
-            Background::Gradient(iced_core::Gradient::Linear(linear)) => {
-                assert!(linear.stops[0].is_some());
-                assert!(linear.stops[1].is_some());
-                let s0 = linear.stops[0].unwrap();
-                assert!(s0.offset.abs() < f32::EPSILON);
-                assert!((s0.color.r - 1.0).abs() < 0.01);
-            }
-            _ => panic!("expected Background::Gradient(Linear(..))"),
-        }
+    fn dimming_scales_alpha_and_saturation() {
+        let dim = DisabledDimming { opacity: 0.5, desaturate: 1.0 };
+        let c = dim.apply(iced_core::Color::from_rgb8(0xFF, 0x00, 0x00));
+        assert!((c.a - 0.5).abs() < 0.01);
+        assert!((c.r - c.g).abs() < 0.01 && (c.g - c.b).abs() < 0.01, "got {c:?}");
     }
 }
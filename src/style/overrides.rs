@@ -0,0 +1,97 @@
+use iced_core::border::Radius;
+use iced_core::{Background, Border, Color, Shadow};
+
+/// Last-minute changes to one widget's style, applied over the theme's values
+/// in every status by `style_fn_with`, for the one button in a form that has
+/// to stand out:
+///
+/// ```no_run
+/// # use iced::widget::button;
+/// # use iced_themer::ThemeConfig;
+/// use iced_themer::style::StatusOverrides;
+///
+/// # let config: ThemeConfig = todo!();
+/// let danger = config.palette_color("danger").unwrap();
+/// let style = config.button().unwrap().style_fn_with(StatusOverrides::new().background(danger));
+/// let delete: iced::Element<'_, ()> = button("Delete").style(style).into();
+/// ```
+///
+/// Fields left unset keep the theme's values, and a field the widget's style
+/// doesn't have, like a radio's shadow, is ignored. The text color sets a
+/// text input's typed value, and the border radius doesn't apply to radios.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct StatusOverrides {
+    pub(crate) background:    Option<Background>,
+    pub(crate) text_color:    Option<Color>,
+    pub(crate) border_color:  Option<Color>,
+    pub(crate) border_width:  Option<f32>,
+    pub(crate) border_radius: Option<Radius>,
+    pub(crate) shadow:        Option<Shadow>,
+}
+
+impl StatusOverrides {
+    /// Overrides that change nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the background, a color or a gradient.
+    pub fn background(mut self, background: impl Into<Background>) -> Self {
+        self.background = Some(background.into());
+        self
+    }
+
+    /// Sets the text color.
+    pub fn text_color(mut self, color: Color) -> Self {
+        self.text_color = Some(color);
+        self
+    }
+
+    /// Sets the border color.
+    pub fn border_color(mut self, color: Color) -> Self {
+        self.border_color = Some(color);
+        self
+    }
+
+    /// Sets the border width.
+    pub fn border_width(mut self, width: f32) -> Self {
+        self.border_width = Some(width);
+        self
+    }
+
+    /// Sets the border radius, for every corner or each one.
+    pub fn border_radius(mut self, radius: impl Into<Radius>) -> Self {
+        self.border_radius = Some(radius.into());
+        self
+    }
+
+    /// Sets the shadow.
+    pub fn shadow(mut self, shadow: Shadow) -> Self {
+        self.shadow = Some(shadow);
+        self
+    }
+
+    /// The background, for a style that only takes a color: a gradient is
+    /// ignored there.
+    pub(crate) fn background_color(&self) -> Option<Color> {
+        match self.background {
+            Some(Background::Color(color)) => Some(color),
+            _ => None,
+        }
+    }
+
+    /// `border` with the border fields that are set.
+    pub(crate) fn border(&self, border: Border) -> Border {
+        Border {
+            color:  self.border_color.unwrap_or(border.color),
+            width:  self.border_width.unwrap_or(border.width),
+            radius: self.border_radius.unwrap_or(border.radius),
+        }
+    }
+}
+
+/// Applies [`StatusOverrides`] to a native `iced_widget` style, or to every
+/// status of a pre-resolved one.
+pub(crate) trait ApplyOverrides {
+    fn apply_overrides(self, overrides: &StatusOverrides) -> Self;
+}
@@ -0,0 +1,164 @@
+use iced_core::border::Radius;
+use iced_core::{Background, Border, Color, Theme};
+use iced_widget::pane_grid;
+use serde::Deserialize;
+
+use crate::color::HexColor;
+use super::{BackgroundRaw, MapColors, RadiusRaw, StatusOverrides, impl_approx_eq};
+
+// -- Layer 1: Serde raw types --
+
+#[derive(Deserialize, Default, Clone, Copy)]
+#[serde(default, rename_all = "kebab-case")]
+pub(crate) struct RegionRaw {
+    background:     Option<BackgroundRaw>,
+    border_width:   Option<f32>,
+    border_color:   Option<HexColor>,
+    border_radius:  Option<RadiusRaw>,
+    border_enabled: Option<bool>,
+}
+
+#[derive(Deserialize, Default, Clone, Copy)]
+#[serde(default, rename_all = "kebab-case")]
+pub(crate) struct SplitRaw {
+    color: Option<HexColor>,
+    width: Option<f32>,
+}
+
+/// Top-level `[pane-grid]` section. It has no fields of its own: the
+/// `hovered-region` sub-table styles the drop target shown while a pane is
+/// dragged, and `picked-split` and `hovered-split` the line between panes
+/// while it is resized or under the pointer.
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "kebab-case")]
+pub(crate) struct PaneGridSection {
+    hovered_region: RegionRaw,
+    picked_split:   SplitRaw,
+    hovered_split:  SplitRaw,
+}
+
+// -- Layer 2: Resolution --
+
+impl PaneGridSection {
+    pub fn resolve(self) -> PaneGridStyle {
+        let region = self.hovered_region;
+        // A switched-off border hides iced's default one too, but keeps the radius.
+        let (border_width, border_color) = if region.border_enabled == Some(false) {
+            (Some(0.0), Some(Color::TRANSPARENT))
+        } else {
+            (region.border_width, region.border_color.map(|c| c.0))
+        };
+        PaneGridStyle {
+            hovered_region: Region {
+                background: region.background.map(BackgroundRaw::into_background),
+                border_width,
+                border_color,
+                border_radius: region.border_radius.map(RadiusRaw::into_radius),
+            },
+            picked_split: split(self.picked_split),
+            hovered_split: split(self.hovered_split),
+        }
+    }
+}
+
+fn split(raw: SplitRaw) -> Split {
+    Split { color: raw.color.map(|c| c.0), width: raw.width }
+}
+
+// -- Layer 3: Public types --
+
+#[derive(Debug, Clone, Copy)]
+struct Region {
+    background:    Option<Background>,
+    border_width:  Option<f32>,
+    border_color:  Option<Color>,
+    border_radius: Option<Radius>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Split {
+    color: Option<Color>,
+    width: Option<f32>,
+}
+
+impl Split {
+    fn over(self, default: pane_grid::Line) -> pane_grid::Line {
+        pane_grid::Line {
+            color: self.color.unwrap_or(default.color),
+            width: self.width.unwrap_or(default.width),
+        }
+    }
+}
+
+/// Pre-resolved pane grid style: the region highlighted while a pane is
+/// dragged over it, and the split lines while picked or hovered.
+///
+/// Anything the theme leaves out keeps iced's default for the current
+/// theme, so `[pane-grid.picked-split]` with only a `color` still draws
+/// iced's usual width.
+#[derive(Debug, Clone, Copy)]
+pub struct PaneGridStyle {
+    hovered_region: Region,
+    picked_split:   Split,
+    hovered_split:  Split,
+}
+
+impl PaneGridStyle {
+    /// Returns a closure suitable for passing to `.style()` on a pane grid widget.
+    pub fn style_fn(&self) -> impl Fn(&Theme) -> pane_grid::Style + Copy + 'static {
+        let s = *self;
+        move |theme| {
+            let default = pane_grid::default(theme);
+            let region = s.hovered_region;
+            let border = default.hovered_region.border;
+            pane_grid::Style {
+                hovered_region: pane_grid::Highlight {
+                    background: region.background.unwrap_or(default.hovered_region.background),
+                    border: Border {
+                        color: region.border_color.unwrap_or(border.color),
+                        width: region.border_width.unwrap_or(border.width),
+                        radius: region.border_radius.unwrap_or(border.radius),
+                    },
+                },
+                picked_split: s.picked_split.over(default.picked_split),
+                hovered_split: s.hovered_split.over(default.hovered_split),
+            }
+        }
+    }
+
+    /// Like [`style_fn`](Self::style_fn), with `overrides` applied to the
+    /// hovered region; see [`StatusOverrides`]. The split lines keep the
+    /// theme's.
+    pub fn style_fn_with(&self, overrides: StatusOverrides) -> impl Fn(&Theme) -> pane_grid::Style + Copy + 'static {
+        let region = self.hovered_region;
+        let s = PaneGridStyle {
+            hovered_region: Region {
+                background:    overrides.background.or(region.background),
+                border_width:  overrides.border_width.or(region.border_width),
+                border_color:  overrides.border_color.or(region.border_color),
+                border_radius: overrides.border_radius.or(region.border_radius),
+            },
+            ..*self
+        };
+        move |theme| s.style_fn()(theme)
+    }
+}
+
+impl MapColors for PaneGridStyle {
+    fn map_colors(self, f: &impl Fn(Color) -> Color) -> Self {
+        let split = |s: Split| Split { color: s.color.map_colors(f), ..s };
+        PaneGridStyle {
+            hovered_region: Region {
+                background: self.hovered_region.background.map_colors(f),
+                border_color: self.hovered_region.border_color.map_colors(f),
+                ..self.hovered_region
+            },
+            picked_split:   split(self.picked_split),
+            hovered_split:  split(self.hovered_split),
+        }
+    }
+}
+
+impl_approx_eq!(Region { background, border_width, border_color, border_radius });
+impl_approx_eq!(Split { color, width });
+impl_approx_eq!(PaneGridStyle { hovered_region, picked_split, hovered_split });
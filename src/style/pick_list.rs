@@ -0,0 +1,146 @@
+use iced_core::{Background, Color, Theme};
+use iced_widget::pick_list;
+use serde::Deserialize;
+
+use crate::color::HexColor;
+use super::{
+    ApplyOverrides, BackgroundRaw, MapColors, RadiusRaw, StatusOverrides, impl_approx_eq, impl_merge, resolve_border,
+};
+
+// -- Layer 1: Serde raw types --
+
+#[derive(Deserialize, Default, Clone, Copy)]
+#[serde(default, rename_all = "kebab-case")]
+pub(crate) struct PickListFieldsRaw {
+    background:        Option<BackgroundRaw>,
+    text_color:        Option<HexColor>,
+    placeholder_color: Option<HexColor>,
+    handle_color:      Option<HexColor>,
+    border_width:      Option<f32>,
+    border_color:      Option<HexColor>,
+    border_radius:     Option<RadiusRaw>,
+    border_enabled:    Option<bool>,
+}
+
+impl_merge!(PickListFieldsRaw {
+    background, text_color, placeholder_color, handle_color,
+    border_width, border_color, border_radius, border_enabled,
+});
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "kebab-case")]
+pub(crate) struct PickListSection {
+    #[serde(flatten)]
+    base: PickListFieldsRaw,
+    hovered: Option<PickListFieldsRaw>,
+    opened:  Option<PickListFieldsRaw>,
+}
+
+// -- Layer 2: Resolution --
+
+impl PickListSection {
+    pub fn resolve(self) -> PickListStyle {
+        let active = into_native(self.base);
+        let hovered = resolve_status(self.base, self.hovered.as_ref());
+        let opened = resolve_status(self.base, self.opened.as_ref());
+
+        PickListStyle { active, hovered, opened }
+    }
+}
+
+fn resolve_status(base: PickListFieldsRaw, status: Option<&PickListFieldsRaw>) -> pick_list::Style {
+    match status {
+        Some(over) => into_native(base.merge(over)),
+        None => into_native(base),
+    }
+}
+
+fn into_native(f: PickListFieldsRaw) -> pick_list::Style {
+    pick_list::Style {
+        text_color: f.text_color.map(|c| c.0).unwrap_or(Color::BLACK),
+        placeholder_color: f.placeholder_color.map(|c| c.0).unwrap_or(Color::from_rgba8(0x80, 0x80, 0x80, 1.0)),
+        handle_color: f.handle_color.map(|c| c.0).unwrap_or(Color::BLACK),
+        background: f.background.map(BackgroundRaw::into_background).unwrap_or(Background::Color(Color::TRANSPARENT)),
+        border: resolve_border(f.border_width, f.border_color, f.border_radius, f.border_enabled),
+    }
+}
+
+// -- Layer 3: Public types --
+
+/// Pre-resolved pick list style with a native `iced_widget` style for each status variant.
+///
+/// It styles the closed pick list; its drop-down menu keeps the theme's
+/// menu style.
+#[derive(Debug, Clone, Copy)]
+pub struct PickListStyle {
+    active:  pick_list::Style,
+    hovered: pick_list::Style,
+    opened:  pick_list::Style,
+}
+
+impl PickListStyle {
+    /// Returns a closure suitable for passing to `.style()` on a pick list widget.
+    pub fn style_fn(&self) -> impl Fn(&Theme, pick_list::Status) -> pick_list::Style + Copy + 'static {
+        let s = *self;
+        move |_theme, status| match status {
+            pick_list::Status::Active  => s.active,
+            pick_list::Status::Hovered => s.hovered,
+            // `Opened` is a unit variant in iced 0.13 and a struct in 0.14.
+            _ => s.opened,
+        }
+    }
+
+    /// Like [`style_fn`](Self::style_fn), with `overrides` applied over every
+    /// status, the opened one included.
+    pub fn style_fn_with(
+        &self,
+        overrides: StatusOverrides,
+    ) -> impl Fn(&Theme, pick_list::Status) -> pick_list::Style + Copy + 'static {
+        let s = self.apply_overrides(&overrides);
+        move |theme, status| s.style_fn()(theme, status)
+    }
+}
+
+impl MapColors for pick_list::Style {
+    fn map_colors(self, f: &impl Fn(Color) -> Color) -> Self {
+        pick_list::Style {
+            text_color: f(self.text_color),
+            placeholder_color: f(self.placeholder_color),
+            handle_color: f(self.handle_color),
+            background: self.background.map_colors(f),
+            border: self.border.map_colors(f),
+        }
+    }
+}
+
+impl ApplyOverrides for pick_list::Style {
+    fn apply_overrides(mut self, o: &StatusOverrides) -> Self {
+        self.background = o.background.unwrap_or(self.background);
+        self.text_color = o.text_color.unwrap_or(self.text_color);
+        self.border = o.border(self.border);
+        self
+    }
+}
+
+impl MapColors for PickListStyle {
+    fn map_colors(self, f: &impl Fn(Color) -> Color) -> Self {
+        PickListStyle {
+            active:  self.active.map_colors(f),
+            hovered: self.hovered.map_colors(f),
+            opened:  self.opened.map_colors(f),
+        }
+    }
+}
+
+impl ApplyOverrides for PickListStyle {
+    fn apply_overrides(self, o: &StatusOverrides) -> Self {
+        PickListStyle {
+            active:  self.active.apply_overrides(o),
+            hovered: self.hovered.apply_overrides(o),
+            opened:  self.opened.apply_overrides(o),
+        }
+    }
+}
+
+impl_approx_eq!(pick_list::Style { text_color, placeholder_color, handle_color, background, border });
+impl_approx_eq!(PickListStyle { active, hovered, opened });
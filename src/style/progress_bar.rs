@@ -1,39 +1,48 @@
 use iced_core::{Background, Color, Theme};
-use iced_widget::progress_bar;
+use iced_widget::{ProgressBar, progress_bar};
 use serde::Deserialize;
 
 use crate::color::HexColor;
-use super::{BackgroundRaw, RadiusRaw, impl_merge, resolve_border};
+use crate::compat;
+use super::{
+    ApplyOverrides, BackgroundRaw, MapColors, RadiusRaw, StatusOverrides, impl_approx_eq, impl_merge, resolve_border,
+};
 
 // -- Layer 1: Serde raw types --
 
 #[derive(Deserialize, Default, Clone, Copy)]
 #[serde(default, rename_all = "kebab-case")]
 pub(crate) struct ProgressBarFieldsRaw {
-    background:    Option<BackgroundRaw>,
-    bar:           Option<BackgroundRaw>,
-    border_width:  Option<f32>,
-    border_color:  Option<HexColor>,
-    border_radius: Option<RadiusRaw>,
+    background:     Option<BackgroundRaw>,
+    bar:            Option<BackgroundRaw>,
+    border_width:   Option<f32>,
+    border_color:   Option<HexColor>,
+    border_radius:  Option<RadiusRaw>,
+    border_enabled: Option<bool>,
 }
 
 impl_merge!(ProgressBarFieldsRaw {
     background, bar,
-    border_width, border_color, border_radius,
+    border_width, border_color, border_radius, border_enabled,
 });
 
 /// Top-level `[progress-bar]` section. No status sub-tables.
 #[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub(crate) struct ProgressBarSection {
     #[serde(flatten)]
     base: ProgressBarFieldsRaw,
+    #[serde(default)]
+    girth: Option<f32>,
+    #[serde(default)]
+    vertical: bool,
 }
 
 // -- Layer 2: Resolution --
 
 impl ProgressBarSection {
     pub fn resolve(self) -> ProgressBarStyle {
-        ProgressBarStyle(into_native(self.base))
+        ProgressBarStyle { style: into_native(self.base), girth: self.girth, vertical: self.vertical }
     }
 }
 
@@ -41,20 +50,78 @@ fn into_native(f: ProgressBarFieldsRaw) -> progress_bar::Style {
     progress_bar::Style {
         background: f.background.map(BackgroundRaw::into_background).unwrap_or(Background::Color(Color::TRANSPARENT)),
         bar: f.bar.map(BackgroundRaw::into_background).unwrap_or(Background::Color(Color::BLACK)),
-        border: resolve_border(f.border_width, f.border_color, f.border_radius),
+        border: resolve_border(f.border_width, f.border_color, f.border_radius, f.border_enabled),
     }
 }
 
 // -- Layer 3: Public types --
 
-/// Pre-resolved progress bar style.
+/// Pre-resolved progress bar style, and the bar's thickness and orientation
+/// as layout hints.
 #[derive(Debug, Clone, Copy)]
-pub struct ProgressBarStyle(progress_bar::Style);
+pub struct ProgressBarStyle {
+    style:    progress_bar::Style,
+    girth:    Option<f32>,
+    vertical: bool,
+}
 
 impl ProgressBarStyle {
     /// Returns a closure suitable for passing to `.style()` on a progress bar widget.
     pub fn style_fn(&self) -> impl Fn(&Theme) -> progress_bar::Style + Copy + 'static {
-        let s = self.0;
+        let s = self.style;
         move |_theme| s
     }
+
+    /// Like [`style_fn`](Self::style_fn), with `overrides` applied; see
+    /// [`StatusOverrides`]. The background is the track behind the bar,
+    /// which keeps its color.
+    pub fn style_fn_with(&self, overrides: StatusOverrides) -> impl Fn(&Theme) -> progress_bar::Style + Copy + 'static {
+        let s = self.style.apply_overrides(&overrides);
+        move |_theme| s
+    }
+
+    /// `girth`: the bar's thickness, in logical pixels.
+    pub fn girth(&self) -> Option<f32> {
+        self.girth
+    }
+
+    /// `vertical`: whether the bar fills from bottom to top rather than left
+    /// to right. iced 0.13 has no vertical progress bars and ignores it.
+    pub fn vertical(&self) -> bool {
+        self.vertical
+    }
+
+    /// Sets `bar`'s girth and orientation where the section does.
+    pub(crate) fn apply_layout<'a>(&self, bar: ProgressBar<'a, Theme>) -> ProgressBar<'a, Theme> {
+        compat::progress_bar_layout(bar, self.girth, self.vertical)
+    }
 }
+
+impl MapColors for progress_bar::Style {
+    fn map_colors(self, f: &impl Fn(Color) -> Color) -> Self {
+        progress_bar::Style {
+            background: self.background.map_colors(f),
+            bar: self.bar.map_colors(f),
+            border: self.border.map_colors(f),
+        }
+    }
+}
+
+impl ApplyOverrides for progress_bar::Style {
+    fn apply_overrides(self, o: &StatusOverrides) -> Self {
+        progress_bar::Style {
+            background: o.background.unwrap_or(self.background),
+            border: o.border(self.border),
+            ..self
+        }
+    }
+}
+
+impl MapColors for ProgressBarStyle {
+    fn map_colors(self, f: &impl Fn(Color) -> Color) -> Self {
+        ProgressBarStyle { style: self.style.map_colors(f), ..self }
+    }
+}
+
+impl_approx_eq!(progress_bar::Style { background, bar, border });
+impl_approx_eq!(ProgressBarStyle { style, girth, vertical });
@@ -1,25 +1,26 @@
-use iced_core::{Background, Color, Theme};
+use iced_core::{Background, Color, Font, Theme};
 use iced_widget::radio;
 use serde::Deserialize;
 
 use crate::color::HexColor;
-use super::{BackgroundRaw, impl_merge};
+use super::{ApplyOverrides, BackgroundRaw, Label, LabelRaw, MapColors, StatusOverrides, impl_approx_eq, impl_merge};
 
 // -- Layer 1: Serde raw types --
 
 #[derive(Deserialize, Default, Clone, Copy)]
 #[serde(default, rename_all = "kebab-case")]
 pub(crate) struct RadioFieldsRaw {
-    background:   Option<BackgroundRaw>,
-    dot_color:    Option<HexColor>,
-    border_width: Option<f32>,
-    border_color: Option<HexColor>,
-    text_color:   Option<HexColor>,
+    background:     Option<BackgroundRaw>,
+    dot_color:      Option<HexColor>,
+    border_width:   Option<f32>,
+    border_color:   Option<HexColor>,
+    border_enabled: Option<bool>,
+    text_color:     Option<HexColor>,
 }
 
 impl_merge!(RadioFieldsRaw {
     background, dot_color,
-    border_width, border_color,
+    border_width, border_color, border_enabled,
     text_color,
 });
 
@@ -28,6 +29,8 @@ impl_merge!(RadioFieldsRaw {
 pub(crate) struct RadioSection {
     #[serde(flatten)]
     base: RadioFieldsRaw,
+    #[serde(flatten)]
+    label: LabelRaw,
     selected: Option<RadioFieldsRaw>,
     hovered: Option<RadioFieldsRaw>,
     disabled: Option<RadioFieldsRaw>,
@@ -69,6 +72,7 @@ impl RadioSection {
             active_selected,
             hovered_unselected,
             hovered_selected,
+            label: self.label.resolve(),
         }
     }
 }
@@ -77,7 +81,7 @@ fn into_native(f: RadioFieldsRaw) -> radio::Style {
     radio::Style {
         background: f.background.map(BackgroundRaw::into_background).unwrap_or(Background::Color(Color::TRANSPARENT)),
         dot_color: f.dot_color.map(|c| c.0).unwrap_or(Color::BLACK),
-        border_width: f.border_width.unwrap_or(1.0),
+        border_width: if f.border_enabled == Some(false) { 0.0 } else { f.border_width.unwrap_or(1.0) },
         border_color: f.border_color.map(|c| c.0).unwrap_or(Color::BLACK),
         text_color: f.text_color.map(|c| c.0),
     }
@@ -85,7 +89,8 @@ fn into_native(f: RadioFieldsRaw) -> radio::Style {
 
 // -- Layer 3: Public types --
 
-/// Pre-resolved radio style with 4 variants (2 statuses x 2 states).
+/// Pre-resolved radio style with 4 variants (2 statuses x 2 states), and the
+/// label's spacing, size, and font.
 ///
 /// iced 0.14's `radio::Status` only has `Active` and `Hovered` — no `Disabled`.
 /// The TOML `[radio.disabled]` and `[radio.disabled-selected]` sections are still
@@ -96,6 +101,7 @@ pub struct RadioStyle {
     active_selected:    radio::Style,
     hovered_unselected: radio::Style,
     hovered_selected:   radio::Style,
+    label:              Label,
 }
 
 impl RadioStyle {
@@ -111,4 +117,78 @@ impl RadioStyle {
             }
         }
     }
+
+    /// Like [`style_fn`](Self::style_fn), with `overrides` applied over every
+    /// status. A radio has no border radius or shadow, so those are ignored.
+    pub fn style_fn_with(
+        &self,
+        overrides: StatusOverrides,
+    ) -> impl Fn(&Theme, radio::Status) -> radio::Style + Copy + 'static {
+        let s = self.apply_overrides(&overrides);
+        move |theme, status| s.style_fn()(theme, status)
+    }
+
+    /// `label-spacing`: the gap between the button and its label.
+    pub fn label_spacing(&self) -> Option<f32> {
+        self.label.spacing
+    }
+
+    /// `label-size`: the label's text size.
+    pub fn label_size(&self) -> Option<f32> {
+        self.label.size
+    }
+
+    /// The `[radio.label-font]` table, the label's font.
+    pub fn label_font(&self) -> Option<Font> {
+        self.label.font
+    }
+}
+
+impl MapColors for radio::Style {
+    fn map_colors(self, f: &impl Fn(Color) -> Color) -> Self {
+        radio::Style {
+            background: self.background.map_colors(f),
+            dot_color: f(self.dot_color),
+            border_color: f(self.border_color),
+            text_color: self.text_color.map_colors(f),
+            ..self
+        }
+    }
 }
+
+impl ApplyOverrides for radio::Style {
+    fn apply_overrides(mut self, o: &StatusOverrides) -> Self {
+        self.background = o.background.unwrap_or(self.background);
+        self.text_color = o.text_color.or(self.text_color);
+        self.border_color = o.border_color.unwrap_or(self.border_color);
+        self.border_width = o.border_width.unwrap_or(self.border_width);
+        self
+    }
+}
+
+impl MapColors for RadioStyle {
+    fn map_colors(self, f: &impl Fn(Color) -> Color) -> Self {
+        RadioStyle {
+            active_unselected:  self.active_unselected.map_colors(f),
+            active_selected:    self.active_selected.map_colors(f),
+            hovered_unselected: self.hovered_unselected.map_colors(f),
+            hovered_selected:   self.hovered_selected.map_colors(f),
+            label:              self.label,
+        }
+    }
+}
+
+impl ApplyOverrides for RadioStyle {
+    fn apply_overrides(self, o: &StatusOverrides) -> Self {
+        RadioStyle {
+            active_unselected:  self.active_unselected.apply_overrides(o),
+            active_selected:    self.active_selected.apply_overrides(o),
+            hovered_unselected: self.hovered_unselected.apply_overrides(o),
+            hovered_selected:   self.hovered_selected.apply_overrides(o),
+            label:              self.label,
+        }
+    }
+}
+
+impl_approx_eq!(radio::Style { background, dot_color, border_width, border_color, text_color });
+impl_approx_eq!(RadioStyle { active_unselected, active_selected, hovered_unselected, hovered_selected, label });
@@ -0,0 +1,142 @@
+use iced_core::{Color, Theme};
+use iced_widget::{Rule, rule};
+use serde::Deserialize;
+
+use crate::color::HexColor;
+use crate::compat;
+use super::{ApproxEq, MapColors, RadiusRaw, StatusOverrides, impl_approx_eq};
+
+// -- Layer 1: Serde raw types --
+
+/// Top-level `[rule]` section. No status sub-tables: rules aren't
+/// interactive.
+#[derive(Deserialize, Default, Clone, Copy)]
+#[serde(default, rename_all = "kebab-case")]
+pub(crate) struct RuleSection {
+    color:        Option<HexColor>,
+    width:        Option<u16>,
+    radius:       Option<RadiusRaw>,
+    fill_mode:    Option<FillModeKindRaw>,
+    fill_percent: Option<f32>,
+    fill_padding: Option<FillPaddingRaw>,
+}
+
+/// Internal serde mirror for fill mode kinds.
+#[derive(Deserialize, Clone, Copy, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum FillModeKindRaw {
+    Full,
+    Percent,
+    Padded,
+}
+
+/// `fill-padding`: the same offset from both ends, or `[start, end]`.
+#[derive(Deserialize, Clone, Copy, Debug)]
+#[serde(untagged, expecting = "a padding, or [start, end] paddings")]
+pub(crate) enum FillPaddingRaw {
+    Uniform(u16),
+    Asymmetric([u16; 2]),
+}
+
+// -- Layer 2: Resolution --
+
+impl RuleSection {
+    pub fn resolve(self) -> RuleStyle {
+        // Like slider handle shapes, a fill parameter alone implies its mode.
+        let implied = match (self.fill_percent, self.fill_padding) {
+            (Some(_), None) => FillModeKindRaw::Percent,
+            (None, Some(_)) => FillModeKindRaw::Padded,
+            _ => FillModeKindRaw::Full,
+        };
+        let fill_mode = match self.fill_mode.unwrap_or(implied) {
+            FillModeKindRaw::Full => rule::FillMode::Full,
+            FillModeKindRaw::Percent => rule::FillMode::Percent(self.fill_percent.unwrap_or(100.0).clamp(0.0, 100.0)),
+            FillModeKindRaw::Padded => match self.fill_padding.unwrap_or(FillPaddingRaw::Uniform(0)) {
+                FillPaddingRaw::Uniform(padding) => rule::FillMode::Padded(padding),
+                FillPaddingRaw::Asymmetric([start, end]) => rule::FillMode::AsymmetricPadding(start, end),
+            },
+        };
+
+        let width = self.width.unwrap_or(1);
+        RuleStyle {
+            style: compat::rule_style(
+                self.color.map(|c| c.0).unwrap_or(Color::BLACK),
+                width,
+                self.radius.map(RadiusRaw::into_radius).unwrap_or(0.0.into()),
+                fill_mode,
+            ),
+            width,
+        }
+    }
+}
+
+// -- Layer 3: Public types --
+
+/// Pre-resolved rule style, and the rule's thickness.
+///
+/// iced 0.14 takes a rule's thickness from the widget rather than its style,
+/// so [`horizontal`](Self::horizontal) and [`vertical`](Self::vertical) build
+/// rules at the theme's `width`; `.themed()` on a rule built elsewhere keeps
+/// that rule's thickness.
+#[derive(Debug, Clone, Copy)]
+pub struct RuleStyle {
+    style: rule::Style,
+    width: u16,
+}
+
+impl RuleStyle {
+    /// Returns a closure suitable for passing to `.style()` on a rule widget.
+    pub fn style_fn(&self) -> impl Fn(&Theme) -> rule::Style + Copy + 'static {
+        let s = self.style;
+        move |_theme| s
+    }
+
+    /// Like [`style_fn`](Self::style_fn), with `overrides` applied; see
+    /// [`StatusOverrides`]. A background color fills the rule, and the
+    /// border radius rounds it.
+    pub fn style_fn_with(&self, overrides: StatusOverrides) -> impl Fn(&Theme) -> rule::Style + Copy + 'static {
+        let s = rule::Style {
+            color: overrides.background_color().unwrap_or(self.style.color),
+            radius: overrides.border_radius.unwrap_or(self.style.radius),
+            ..self.style
+        };
+        move |_theme| s
+    }
+
+    /// The rule's thickness, in logical pixels.
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    /// A horizontal rule of the theme's width, styled by it.
+    pub fn horizontal<'a>(&self) -> Rule<'a, Theme> {
+        compat::horizontal_rule(self.width).style(self.style_fn())
+    }
+
+    /// A vertical rule of the theme's width, styled by it.
+    pub fn vertical<'a>(&self) -> Rule<'a, Theme> {
+        compat::vertical_rule(self.width).style(self.style_fn())
+    }
+}
+
+impl MapColors for RuleStyle {
+    fn map_colors(self, f: &impl Fn(Color) -> Color) -> Self {
+        RuleStyle { style: rule::Style { color: f(self.style.color), ..self.style }, ..self }
+    }
+}
+
+impl ApproxEq for rule::FillMode {
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        match (*self, *other) {
+            (rule::FillMode::Full, rule::FillMode::Full) => true,
+            (rule::FillMode::Percent(a), rule::FillMode::Percent(b)) => a.approx_eq(&b, epsilon),
+            (rule::FillMode::Padded(a), rule::FillMode::Padded(b)) => a == b,
+            (rule::FillMode::AsymmetricPadding(a1, a2), rule::FillMode::AsymmetricPadding(b1, b2)) => {
+                (a1, a2) == (b1, b2)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl_approx_eq!(RuleStyle { style, width });
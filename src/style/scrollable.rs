@@ -0,0 +1,177 @@
+use iced_core::{Background, Color, Theme};
+use iced_widget::scrollable;
+use serde::Deserialize;
+
+use crate::color::HexColor;
+use crate::compat::{self, Scrollbar};
+use super::{
+    ApplyOverrides, BackgroundRaw, MapColors, RadiusRaw, StatusOverrides, impl_approx_eq, impl_merge, resolve_border,
+};
+
+// -- Layer 1: Serde raw types --
+
+#[derive(Deserialize, Default, Clone, Copy)]
+#[serde(default, rename_all = "kebab-case")]
+pub(crate) struct ScrollableFieldsRaw {
+    rail_background:        Option<BackgroundRaw>,
+    rail_border_width:      Option<f32>,
+    rail_border_color:      Option<HexColor>,
+    rail_border_radius:     Option<RadiusRaw>,
+    scroller_background:    Option<BackgroundRaw>,
+    scroller_border_width:  Option<f32>,
+    scroller_border_color:  Option<HexColor>,
+    scroller_border_radius: Option<RadiusRaw>,
+    border_enabled:         Option<bool>,
+}
+
+impl_merge!(ScrollableFieldsRaw {
+    rail_background, rail_border_width, rail_border_color, rail_border_radius,
+    scroller_background, scroller_border_width, scroller_border_color, scroller_border_radius,
+    border_enabled,
+});
+
+/// Top-level `[scrollable]` section. The `hovered` and `dragged` sub-tables
+/// restyle the scrollbar under the pointer, not the whole scrollable.
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "kebab-case")]
+pub(crate) struct ScrollableSection {
+    #[serde(flatten)]
+    base: ScrollableFieldsRaw,
+    gap:     Option<BackgroundRaw>,
+    hovered: Option<ScrollableFieldsRaw>,
+    dragged: Option<ScrollableFieldsRaw>,
+}
+
+// -- Layer 2: Resolution --
+
+impl ScrollableSection {
+    pub fn resolve(self) -> ScrollableStyle {
+        let status = |over: Option<&ScrollableFieldsRaw>| match over {
+            Some(over) => into_rail(self.base.merge(over)),
+            None => into_rail(self.base),
+        };
+
+        ScrollableStyle {
+            active:  into_rail(self.base),
+            hovered: status(self.hovered.as_ref()),
+            dragged: status(self.dragged.as_ref()),
+            gap:     self.gap.map(BackgroundRaw::into_background),
+        }
+    }
+}
+
+fn into_rail(f: ScrollableFieldsRaw) -> scrollable::Rail {
+    // iced's own scrollbars round their corners by 2.
+    let radius = |r: Option<RadiusRaw>| Some(r.unwrap_or(RadiusRaw::Uniform(2.0)));
+    scrollable::Rail {
+        background: f.rail_background.map(BackgroundRaw::into_background),
+        border: resolve_border(
+            f.rail_border_width,
+            f.rail_border_color,
+            radius(f.rail_border_radius),
+            f.border_enabled,
+        ),
+        scroller: compat::scroller(
+            f.scroller_background.map(BackgroundRaw::into_background).unwrap_or(Background::Color(Color::BLACK)),
+            resolve_border(
+                f.scroller_border_width,
+                f.scroller_border_color,
+                radius(f.scroller_border_radius),
+                f.border_enabled,
+            ),
+        ),
+    }
+}
+
+// -- Layer 3: Public types --
+
+/// Pre-resolved scrollable style: a scrollbar for each status, and the
+/// background of the gap where two scrollbars meet.
+///
+/// A hovered or dragged status applies only to the scrollbar the pointer is
+/// on; the other keeps its active style. Everything else, such as the
+/// scrollable's own container, is iced's default for the theme.
+#[derive(Debug, Clone, Copy)]
+pub struct ScrollableStyle {
+    active:  scrollable::Rail,
+    hovered: scrollable::Rail,
+    dragged: scrollable::Rail,
+    gap:     Option<Background>,
+}
+
+impl ScrollableStyle {
+    /// Returns a closure suitable for passing to `.style()` on a scrollable widget.
+    pub fn style_fn(&self) -> impl Fn(&Theme, scrollable::Status) -> scrollable::Style + Copy + 'static {
+        let s = *self;
+        let rail = move |status| match status {
+            Scrollbar::Active  => s.active,
+            Scrollbar::Hovered => s.hovered,
+            Scrollbar::Dragged => s.dragged,
+        };
+        move |theme, status| {
+            let (vertical, horizontal) = compat::scrollbar_status(status);
+            scrollable::Style {
+                vertical_rail: rail(vertical),
+                horizontal_rail: rail(horizontal),
+                gap: s.gap,
+                ..scrollable::default(theme, status)
+            }
+        }
+    }
+
+    /// Like [`style_fn`](Self::style_fn), with `overrides` applied over both
+    /// rails in every status. The background and border set the rail; the
+    /// scroller and the gap keep the theme's.
+    pub fn style_fn_with(
+        &self,
+        overrides: StatusOverrides,
+    ) -> impl Fn(&Theme, scrollable::Status) -> scrollable::Style + Copy + 'static {
+        let s = self.apply_overrides(&overrides);
+        move |theme, status| s.style_fn()(theme, status)
+    }
+}
+
+impl MapColors for scrollable::Rail {
+    fn map_colors(self, f: &impl Fn(Color) -> Color) -> Self {
+        scrollable::Rail {
+            background: self.background.map_colors(f),
+            border: self.border.map_colors(f),
+            scroller: compat::map_scroller(self.scroller, f),
+        }
+    }
+}
+
+impl ApplyOverrides for scrollable::Rail {
+    fn apply_overrides(self, o: &StatusOverrides) -> Self {
+        scrollable::Rail {
+            background: o.background.or(self.background),
+            border: o.border(self.border),
+            ..self
+        }
+    }
+}
+
+impl MapColors for ScrollableStyle {
+    fn map_colors(self, f: &impl Fn(Color) -> Color) -> Self {
+        ScrollableStyle {
+            active:  self.active.map_colors(f),
+            hovered: self.hovered.map_colors(f),
+            dragged: self.dragged.map_colors(f),
+            gap:     self.gap.map_colors(f),
+        }
+    }
+}
+
+impl ApplyOverrides for ScrollableStyle {
+    fn apply_overrides(self, o: &StatusOverrides) -> Self {
+        ScrollableStyle {
+            active:  self.active.apply_overrides(o),
+            hovered: self.hovered.apply_overrides(o),
+            dragged: self.dragged.apply_overrides(o),
+            gap:     self.gap,
+        }
+    }
+}
+
+impl_approx_eq!(scrollable::Rail { background, border, scroller });
+impl_approx_eq!(ScrollableStyle { active, hovered, dragged, gap });
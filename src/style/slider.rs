@@ -3,7 +3,9 @@ use iced_widget::slider;
 use serde::Deserialize;
 
 use crate::color::HexColor;
-use super::{BackgroundRaw, RadiusRaw, impl_merge};
+use super::{
+    ApplyOverrides, ApproxEq, BackgroundRaw, MapColors, RadiusRaw, StatusOverrides, impl_approx_eq, impl_merge,
+};
 
 // -- Layer 1: Serde raw types --
 
@@ -21,12 +23,13 @@ pub(crate) struct SliderFieldsRaw {
     handle_background:    Option<BackgroundRaw>,
     handle_border_width:  Option<f32>,
     handle_border_color:  Option<HexColor>,
+    border_enabled:       Option<bool>,
 }
 
 impl_merge!(SliderFieldsRaw {
     rail_background_1, rail_background_2, rail_width, rail_border_radius,
     handle_shape, handle_radius, handle_width, handle_border_radius,
-    handle_background, handle_border_width, handle_border_color,
+    handle_background, handle_border_width, handle_border_color, border_enabled,
 });
 
 #[derive(Deserialize, Default, Clone, Copy)]
@@ -50,24 +53,50 @@ pub(crate) enum HandleShapeKindRaw {
 
 impl SliderSection {
     pub fn resolve(self) -> SliderStyle {
-        let active = into_native(self.base);
-        let hovered = resolve_status(self.base, self.hovered.as_ref());
-        let dragged = resolve_status(self.base, self.dragged.as_ref());
+        let base = self.base.with_implied_shape();
+        let active = into_native(base);
+        let hovered = resolve_status(base, self.hovered.as_ref());
+        let dragged = resolve_status(base, self.dragged.as_ref());
 
         SliderStyle { active, hovered, dragged }
     }
 }
 
+impl SliderFieldsRaw {
+    /// Sets `handle-shape` from the shape parameters when it's unset and
+    /// they belong to only one shape, so a status table that sets just
+    /// `handle-width` turns a round handle into a rectangle rather than
+    /// having the width ignored.
+    fn with_implied_shape(self) -> Self {
+        let circle = self.handle_radius.is_some();
+        let rectangle = self.handle_width.is_some() || self.handle_border_radius.is_some();
+        let implied = match (circle, rectangle) {
+            (true, false) => Some(HandleShapeKindRaw::Circle),
+            (false, true) => Some(HandleShapeKindRaw::Rectangle),
+            _ => None,
+        };
+        SliderFieldsRaw { handle_shape: self.handle_shape.or(implied), ..self }
+    }
+}
+
 fn resolve_status(base: SliderFieldsRaw, status: Option<&SliderFieldsRaw>) -> slider::Style {
     match status {
-        Some(over) => into_native(base.merge(over)),
+        Some(over) => into_native(base.merge(&over.with_implied_shape())),
         None => into_native(base),
     }
 }
 
 fn into_native(f: SliderFieldsRaw) -> slider::Style {
+    // The rail has no stroke of its own, so only the handle's is switched off.
+    let f = if f.border_enabled == Some(false) {
+        SliderFieldsRaw { handle_border_width: None, handle_border_color: None, ..f }
+    } else {
+        f
+    };
     let rail_border_radius = f.rail_border_radius.map(RadiusRaw::into_radius).unwrap_or(0.0.into());
 
+    // A status that switches shape keeps the base's parameters for the new
+    // shape, if it has any.
     let handle_shape = match f.handle_shape.unwrap_or(HandleShapeKindRaw::Circle) {
         HandleShapeKindRaw::Circle => slider::HandleShape::Circle {
             radius: f.handle_radius.unwrap_or(7.0),
@@ -123,4 +152,93 @@ impl SliderStyle {
             slider::Status::Dragged => s.dragged,
         }
     }
+
+    /// Like [`style_fn`](Self::style_fn), with `overrides` applied over every
+    /// status. The background and border set the handle; the border radius
+    /// rounds the rail.
+    pub fn style_fn_with(
+        &self,
+        overrides: StatusOverrides,
+    ) -> impl Fn(&Theme, slider::Status) -> slider::Style + Copy + 'static {
+        let s = self.apply_overrides(&overrides);
+        move |theme, status| s.style_fn()(theme, status)
+    }
+}
+
+impl MapColors for slider::Style {
+    fn map_colors(self, f: &impl Fn(Color) -> Color) -> Self {
+        slider::Style {
+            rail: slider::Rail {
+                backgrounds: (
+                    self.rail.backgrounds.0.map_colors(f),
+                    self.rail.backgrounds.1.map_colors(f),
+                ),
+                border: self.rail.border.map_colors(f),
+                ..self.rail
+            },
+            handle: slider::Handle {
+                background: self.handle.background.map_colors(f),
+                border_color: f(self.handle.border_color),
+                ..self.handle
+            },
+        }
+    }
 }
+
+impl ApplyOverrides for slider::Style {
+    fn apply_overrides(self, o: &StatusOverrides) -> Self {
+        slider::Style {
+            rail: slider::Rail {
+                border: Border { radius: o.border_radius.unwrap_or(self.rail.border.radius), ..self.rail.border },
+                ..self.rail
+            },
+            handle: slider::Handle {
+                background: o.background.unwrap_or(self.handle.background),
+                border_width: o.border_width.unwrap_or(self.handle.border_width),
+                border_color: o.border_color.unwrap_or(self.handle.border_color),
+                ..self.handle
+            },
+        }
+    }
+}
+
+impl MapColors for SliderStyle {
+    fn map_colors(self, f: &impl Fn(Color) -> Color) -> Self {
+        SliderStyle {
+            active:  self.active.map_colors(f),
+            hovered: self.hovered.map_colors(f),
+            dragged: self.dragged.map_colors(f),
+        }
+    }
+}
+
+impl ApplyOverrides for SliderStyle {
+    fn apply_overrides(self, o: &StatusOverrides) -> Self {
+        SliderStyle {
+            active:  self.active.apply_overrides(o),
+            hovered: self.hovered.apply_overrides(o),
+            dragged: self.dragged.apply_overrides(o),
+        }
+    }
+}
+
+impl_approx_eq!(slider::Style { rail, handle });
+impl_approx_eq!(slider::Rail { backgrounds, width, border });
+impl_approx_eq!(slider::Handle { shape, background, border_width, border_color });
+
+impl ApproxEq for slider::HandleShape {
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        match (self, other) {
+            (slider::HandleShape::Circle { radius: a }, slider::HandleShape::Circle { radius: b }) => {
+                a.approx_eq(b, epsilon)
+            }
+            (
+                slider::HandleShape::Rectangle { width: a, border_radius: ra },
+                slider::HandleShape::Rectangle { width: b, border_radius: rb },
+            ) => a == b && ra.approx_eq(rb, epsilon),
+            _ => false,
+        }
+    }
+}
+
+impl_approx_eq!(SliderStyle { active, hovered, dragged });
@@ -0,0 +1,106 @@
+use iced_core::Color;
+#[cfg(feature = "svg")]
+use iced_core::Theme;
+#[cfg(feature = "svg")]
+use iced_widget::svg;
+use serde::Deserialize;
+
+use crate::color::HexColor;
+#[cfg(feature = "svg")]
+use super::StatusOverrides;
+use super::{MapColors, impl_approx_eq, impl_merge};
+
+// -- Layer 1: Serde raw types --
+
+#[derive(Deserialize, Default, Clone, Copy)]
+#[serde(default, rename_all = "kebab-case")]
+pub(crate) struct SvgFieldsRaw {
+    color: Option<HexColor>,
+}
+
+impl_merge!(SvgFieldsRaw { color });
+
+/// Top-level `[svg]` section: the tint of monochrome icons while idle, and
+/// under the pointer in the `hovered` sub-table.
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "kebab-case")]
+pub(crate) struct SvgSection {
+    #[serde(flatten)]
+    base: SvgFieldsRaw,
+    hovered: Option<SvgFieldsRaw>,
+}
+
+// -- Layer 2: Resolution --
+
+impl SvgSection {
+    pub fn resolve(self) -> SvgStyle {
+        let hovered = match self.hovered {
+            Some(over) => self.base.merge(&over),
+            None => self.base,
+        };
+
+        SvgStyle {
+            idle:    self.base.color.map(|c| c.0),
+            hovered: hovered.color.map(|c| c.0),
+        }
+    }
+}
+
+// -- Layer 3: Public types --
+
+/// Pre-resolved svg style: the color icons are tinted with in each status.
+/// Without one, an icon keeps the colors it was drawn with.
+///
+/// Styling the `Svg` widget needs the `svg` feature, which turns on iced's
+/// own; the tints can be read with [`idle`](Self::idle) and
+/// [`hovered`](Self::hovered) either way.
+#[derive(Debug, Clone, Copy)]
+pub struct SvgStyle {
+    idle:    Option<Color>,
+    hovered: Option<Color>,
+}
+
+impl SvgStyle {
+    /// The tint of an icon the pointer isn't over.
+    pub fn idle(&self) -> Option<Color> {
+        self.idle
+    }
+
+    /// The tint of an icon under the pointer.
+    pub fn hovered(&self) -> Option<Color> {
+        self.hovered
+    }
+
+    /// Returns a closure suitable for passing to `.style()` on an svg widget.
+    #[cfg(feature = "svg")]
+    pub fn style_fn(&self) -> impl Fn(&Theme, svg::Status) -> svg::Style + Copy + 'static {
+        let s = *self;
+        move |_theme, status| match status {
+            svg::Status::Idle    => svg::Style { color: s.idle },
+            svg::Status::Hovered => svg::Style { color: s.hovered },
+        }
+    }
+
+    /// Like [`style_fn`](Self::style_fn), with the text color of `overrides`
+    /// tinting the icon in both statuses; an icon has no other style to
+    /// override.
+    #[cfg(feature = "svg")]
+    pub fn style_fn_with(
+        &self,
+        overrides: StatusOverrides,
+    ) -> impl Fn(&Theme, svg::Status) -> svg::Style + Copy + 'static {
+        let s = SvgStyle { idle: overrides.text_color.or(self.idle), hovered: overrides.text_color.or(self.hovered) };
+        move |theme, status| s.style_fn()(theme, status)
+    }
+}
+
+impl MapColors for SvgStyle {
+    fn map_colors(self, f: &impl Fn(Color) -> Color) -> Self {
+        SvgStyle {
+            idle:    self.idle.map_colors(f),
+            hovered: self.hovered.map_colors(f),
+        }
+    }
+}
+
+impl_approx_eq!(SvgStyle { idle, hovered });
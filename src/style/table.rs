@@ -0,0 +1,225 @@
+use iced_core::{Background, Color, Theme};
+#[cfg(feature = "iced-0-14")]
+use iced_widget::table;
+use iced_widget::container;
+use serde::Deserialize;
+
+use crate::color::HexColor;
+use super::{ApplyOverrides, BackgroundRaw, MapColors, StatusOverrides, impl_approx_eq};
+
+// -- Layer 1: Serde raw types --
+
+#[derive(Deserialize, Default, Clone, Copy)]
+#[serde(default, rename_all = "kebab-case")]
+pub(crate) struct TableFieldsRaw {
+    header_background: Option<BackgroundRaw>,
+    header_text_color: Option<HexColor>,
+    background:        Option<BackgroundRaw>,
+    stripe_background: Option<BackgroundRaw>,
+    text_color:        Option<HexColor>,
+    divider_color:     Option<HexColor>,
+    divider_width:     Option<f32>,
+}
+
+/// A `hovered` or `selected` sub-table. The header and dividers don't change
+/// with a row's status, so their keys are rejected here.
+#[derive(Deserialize, Default, Clone, Copy)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub(crate) struct TableRowRaw {
+    background:        Option<BackgroundRaw>,
+    stripe_background: Option<BackgroundRaw>,
+    text_color:        Option<HexColor>,
+}
+
+/// Top-level `[table]` section. The `hovered` and `selected` sub-tables
+/// restyle individual rows.
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "kebab-case")]
+pub(crate) struct TableSection {
+    #[serde(flatten)]
+    base: TableFieldsRaw,
+    hovered:  Option<TableRowRaw>,
+    selected: Option<TableRowRaw>,
+}
+
+// -- Layer 2: Resolution --
+
+impl TableSection {
+    pub fn resolve(self) -> TableStyle {
+        let base = TableRowRaw {
+            background:        self.base.background,
+            stripe_background: self.base.stripe_background,
+            text_color:        self.base.text_color,
+        };
+        let rows = |over: TableRowRaw| {
+            let even = TableRow {
+                background: over.background.or(base.background).map(BackgroundRaw::into_background),
+                text_color: over.text_color.or(base.text_color).map(|c| c.0),
+            };
+            // A status's own `background` outranks the stripe it would
+            // otherwise inherit from the section.
+            let stripe = over.stripe_background.or(over.background).or(base.stripe_background);
+            let odd = TableRow {
+                background: stripe.map(BackgroundRaw::into_background).or(even.background),
+                ..even
+            };
+            [even, odd]
+        };
+
+        TableStyle {
+            header: TableRow {
+                background: self.base.header_background.map(BackgroundRaw::into_background),
+                text_color: self.base.header_text_color.map(|c| c.0),
+            },
+            active: rows(TableRowRaw::default()),
+            hovered: rows(self.hovered.unwrap_or_default()),
+            selected: rows(self.selected.unwrap_or_default()),
+            divider_color: self.base.divider_color.map(|c| c.0),
+            divider_width: self.base.divider_width,
+        }
+    }
+}
+
+// -- Layer 3: Public types --
+
+/// The look of a table row: its background and text color, either of which
+/// can be left to the surrounding style.
+///
+/// Table widgets lay out cells, not rows, so apply it by wrapping each cell
+/// of the row in a container styled from it, with
+/// [`style_fn`](Self::style_fn).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TableRow {
+    pub background: Option<Background>,
+    pub text_color: Option<Color>,
+}
+
+impl TableRow {
+    /// Returns a closure suitable for passing to `.style()` on a container
+    /// widget wrapping a cell.
+    pub fn style_fn(&self) -> impl Fn(&Theme) -> container::Style + Copy + 'static {
+        let s = *self;
+        move |_theme| container::Style {
+            background: s.background,
+            text_color: s.text_color,
+            ..container::Style::default()
+        }
+    }
+
+    /// Like [`style_fn`](Self::style_fn), with the background and text color
+    /// of `overrides` applied, for one row that differs from the rest.
+    pub fn style_fn_with(&self, overrides: StatusOverrides) -> impl Fn(&Theme) -> container::Style + Copy + 'static {
+        let s = self.apply_overrides(&overrides);
+        move |theme| s.style_fn()(theme)
+    }
+}
+
+/// Which style a table row is drawn with. A row that is both hovered and
+/// selected should use `Selected`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableRowStatus {
+    Active,
+    Hovered,
+    Selected,
+}
+
+/// Pre-resolved table style: the header row, even and odd body rows for
+/// each [`TableRowStatus`], and the dividers between cells.
+///
+/// It isn't tied to a table widget: look up a row's style with
+/// [`row`](Self::row) and apply it to the row's cells. iced's own `table`
+/// draws its dividers from this style under [`ThemerTheme`](crate::ThemerTheme),
+/// and `.themed()` sets their width. There's no integration with third-party
+/// table crates such as `iced_table`; their cell closures can wrap cells with
+/// [`TableRow::style_fn`] the same way.
+#[derive(Debug, Clone, Copy)]
+pub struct TableStyle {
+    header:        TableRow,
+    active:        [TableRow; 2],
+    hovered:       [TableRow; 2],
+    selected:      [TableRow; 2],
+    divider_color: Option<Color>,
+    divider_width: Option<f32>,
+}
+
+impl TableStyle {
+    /// The style of the header row.
+    pub fn header(&self) -> TableRow {
+        self.header
+    }
+
+    /// The style of the body row at `index`, counting from 0; odd rows take
+    /// `stripe-background` when the theme sets one.
+    pub fn row(&self, index: usize, status: TableRowStatus) -> TableRow {
+        let rows = match status {
+            TableRowStatus::Active => self.active,
+            TableRowStatus::Hovered => self.hovered,
+            TableRowStatus::Selected => self.selected,
+        };
+        rows[index % 2]
+    }
+
+    /// The color of the lines between cells, if the theme sets one.
+    pub fn divider_color(&self) -> Option<Color> {
+        self.divider_color
+    }
+
+    /// The width of the lines between cells, if the theme sets one.
+    pub fn divider_width(&self) -> Option<f32> {
+        self.divider_width
+    }
+
+    /// Returns a closure producing iced's table style, with the theme's
+    /// divider color or else iced's default.
+    #[cfg(feature = "iced-0-14")]
+    pub fn style_fn(&self) -> impl Fn(&Theme) -> table::Style + Copy + 'static {
+        let divider = self.divider_color;
+        move |theme| match divider {
+            Some(color) => table::Style { separator_x: color.into(), separator_y: color.into() },
+            None => table::default(theme),
+        }
+    }
+
+    /// Like [`style_fn`](Self::style_fn), with the border color of
+    /// `overrides` as the divider color. Row styles are looked up with
+    /// [`row`](Self::row), so override those with [`TableRow::style_fn_with`].
+    #[cfg(feature = "iced-0-14")]
+    pub fn style_fn_with(&self, overrides: StatusOverrides) -> impl Fn(&Theme) -> table::Style + Copy + 'static {
+        let s = TableStyle { divider_color: overrides.border_color.or(self.divider_color), ..*self };
+        move |theme| s.style_fn()(theme)
+    }
+}
+
+impl MapColors for TableRow {
+    fn map_colors(self, f: &impl Fn(Color) -> Color) -> Self {
+        TableRow {
+            background: self.background.map_colors(f),
+            text_color: self.text_color.map_colors(f),
+        }
+    }
+}
+
+impl ApplyOverrides for TableRow {
+    fn apply_overrides(self, o: &StatusOverrides) -> Self {
+        TableRow {
+            background: o.background.or(self.background),
+            text_color: o.text_color.or(self.text_color),
+        }
+    }
+}
+
+impl MapColors for TableStyle {
+    fn map_colors(self, f: &impl Fn(Color) -> Color) -> Self {
+        TableStyle {
+            header:        self.header.map_colors(f),
+            active:        self.active.map(|row| row.map_colors(f)),
+            hovered:       self.hovered.map(|row| row.map_colors(f)),
+            selected:      self.selected.map(|row| row.map_colors(f)),
+            divider_color: self.divider_color.map_colors(f),
+            divider_width: self.divider_width,
+        }
+    }
+}
+
+impl_approx_eq!(TableRow { background, text_color });
+impl_approx_eq!(TableStyle { header, active, hovered, selected, divider_color, divider_width });
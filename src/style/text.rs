@@ -0,0 +1,58 @@
+use iced_core::widget::text;
+use iced_core::{Color, Theme};
+use serde::Deserialize;
+
+use crate::color::HexColor;
+use super::{ApproxEq, MapColors, StatusOverrides};
+
+// -- Layer 1: Serde raw types --
+
+/// Top-level `[text]` section, the default style of text. Classes of text
+/// like `muted` or `heading` are its variants, `[text.variants.muted]`.
+#[derive(Deserialize, Default, Clone, Copy)]
+#[serde(default, rename_all = "kebab-case")]
+pub(crate) struct TextSection {
+    color: Option<HexColor>,
+}
+
+// -- Layer 2: Resolution --
+
+impl TextSection {
+    pub fn resolve(self) -> TextStyle {
+        TextStyle(text::Style { color: self.color.map(|c| c.0) })
+    }
+}
+
+// -- Layer 3: Public types --
+
+/// Pre-resolved text style. Without a `color`, text keeps the color it
+/// inherits from its container.
+#[derive(Debug, Clone, Copy)]
+pub struct TextStyle(text::Style);
+
+impl TextStyle {
+    /// Returns a closure suitable for passing to `.style()` on a text widget.
+    pub fn style_fn(&self) -> impl Fn(&Theme) -> text::Style + Copy + 'static {
+        let s = self.0;
+        move |_theme| s
+    }
+
+    /// Like [`style_fn`](Self::style_fn), with the text color of `overrides`
+    /// applied; a text has no other style to override.
+    pub fn style_fn_with(&self, overrides: StatusOverrides) -> impl Fn(&Theme) -> text::Style + Copy + 'static {
+        let s = text::Style { color: overrides.text_color.or(self.0.color) };
+        move |_theme| s
+    }
+}
+
+impl MapColors for TextStyle {
+    fn map_colors(self, f: &impl Fn(Color) -> Color) -> Self {
+        TextStyle(text::Style { color: self.0.color.map_colors(f) })
+    }
+}
+
+impl ApproxEq for TextStyle {
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        self.0.color.approx_eq(&other.0.color, epsilon)
+    }
+}
@@ -1,9 +1,12 @@
-use iced_core::{Background, Color, Theme};
+use iced_core::{Background, Color, Padding, Theme};
 use iced_widget::text_input;
 use serde::Deserialize;
 
 use crate::color::HexColor;
-use super::{BackgroundRaw, RadiusRaw, impl_merge, resolve_border};
+use super::{
+    ApplyOverrides, BackgroundRaw, DisabledDimming, Layout, LayoutRaw, MapColors, RadiusRaw, StatusOverrides,
+    impl_approx_eq, impl_merge, resolve_border,
+};
 
 // -- Layer 1: Serde raw types --
 
@@ -14,6 +17,7 @@ pub(crate) struct TextInputFieldsRaw {
     border_width:      Option<f32>,
     border_color:      Option<HexColor>,
     border_radius:     Option<RadiusRaw>,
+    border_enabled:    Option<bool>,
     icon_color:        Option<HexColor>,
     placeholder_color: Option<HexColor>,
     value_color:       Option<HexColor>,
@@ -21,7 +25,7 @@ pub(crate) struct TextInputFieldsRaw {
 }
 
 impl_merge!(TextInputFieldsRaw {
-    background, border_width, border_color, border_radius,
+    background, border_width, border_color, border_radius, border_enabled,
     icon_color, placeholder_color, value_color, selection_color,
 });
 
@@ -30,6 +34,9 @@ impl_merge!(TextInputFieldsRaw {
 pub(crate) struct TextInputSection {
     #[serde(flatten)]
     base: TextInputFieldsRaw,
+    #[serde(flatten)]
+    layout: LayoutRaw,
+    hovered:  Option<TextInputFieldsRaw>,
     focused:  Option<TextInputFieldsRaw>,
     disabled: Option<TextInputFieldsRaw>,
 }
@@ -37,12 +44,16 @@ pub(crate) struct TextInputSection {
 // -- Layer 2: Resolution --
 
 impl TextInputSection {
-    pub fn resolve(self) -> TextInputStyle {
+    pub fn resolve(self, dim: DisabledDimming) -> TextInputStyle {
         let active = into_native(self.base);
+        let hovered = resolve_status(self.base, self.hovered.as_ref());
         let focused = resolve_status(self.base, self.focused.as_ref());
-        let disabled = resolve_status(self.base, self.disabled.as_ref());
+        let disabled = match self.disabled {
+            Some(over) => into_native(self.base.merge(&over)),
+            None => dim.apply(active),
+        };
 
-        TextInputStyle { active, focused, disabled }
+        TextInputStyle { active, hovered, focused, disabled, layout: self.layout.resolve() }
     }
 }
 
@@ -56,7 +67,7 @@ fn resolve_status(base: TextInputFieldsRaw, status: Option<&TextInputFieldsRaw>)
 fn into_native(f: TextInputFieldsRaw) -> text_input::Style {
     text_input::Style {
         background: f.background.map(BackgroundRaw::into_background).unwrap_or(Background::Color(Color::TRANSPARENT)),
-        border: resolve_border(f.border_width, f.border_color, f.border_radius),
+        border: resolve_border(f.border_width, f.border_color, f.border_radius, f.border_enabled),
         icon: f.icon_color.map(|c| c.0).unwrap_or(Color::BLACK),
         placeholder: f.placeholder_color.map(|c| c.0).unwrap_or(Color::from_rgba8(0x80, 0x80, 0x80, 1.0)),
         value: f.value_color.map(|c| c.0).unwrap_or(Color::BLACK),
@@ -67,25 +78,99 @@ fn into_native(f: TextInputFieldsRaw) -> text_input::Style {
 // -- Layer 3: Public types --
 
 /// Pre-resolved text input style with a native `iced_widget` style for each status variant.
+///
+/// It also carries the section's padding and text size as layout hints.
 #[derive(Debug, Clone, Copy)]
 pub struct TextInputStyle {
     active:   text_input::Style,
+    hovered:  text_input::Style,
     focused:  text_input::Style,
     disabled: text_input::Style,
+    layout:   Layout,
 }
 
 impl TextInputStyle {
     /// Returns a closure suitable for passing to `.style()` on a text input widget.
     ///
-    /// The `Hovered` status maps to the active style, and `Focused { is_hovered: _ }`
-    /// maps to the focused style, matching iced's status semantics.
+    /// `Focused { is_hovered: _ }` maps to the focused style: a focused input
+    /// keeps its focus highlight while hovered.
     pub fn style_fn(&self) -> impl Fn(&Theme, text_input::Status) -> text_input::Style + Copy + 'static {
         let s = *self;
         move |_theme, status| match status {
             text_input::Status::Active  => s.active,
-            text_input::Status::Hovered => s.active,
-            text_input::Status::Focused { .. } => s.focused,
+            text_input::Status::Hovered => s.hovered,
             text_input::Status::Disabled => s.disabled,
+            // `Focused` is a unit variant in iced 0.13 and a struct in 0.14.
+            _ => s.focused,
+        }
+    }
+
+    /// Like [`style_fn`](Self::style_fn), with `overrides` applied over every
+    /// status. The text color sets the typed value, not the placeholder.
+    pub fn style_fn_with(
+        &self,
+        overrides: StatusOverrides,
+    ) -> impl Fn(&Theme, text_input::Status) -> text_input::Style + Copy + 'static {
+        let s = self.apply_overrides(&overrides);
+        move |theme, status| s.style_fn()(theme, status)
+    }
+
+    /// `padding`: the space around the input's text.
+    pub fn padding(&self) -> Option<Padding> {
+        self.layout.padding
+    }
+
+    /// `text-size`: the size of the input's text.
+    pub fn text_size(&self) -> Option<f32> {
+        self.layout.text_size
+    }
+}
+
+impl MapColors for text_input::Style {
+    fn map_colors(self, f: &impl Fn(Color) -> Color) -> Self {
+        text_input::Style {
+            background: self.background.map_colors(f),
+            border: self.border.map_colors(f),
+            icon: f(self.icon),
+            placeholder: f(self.placeholder),
+            value: f(self.value),
+            selection: f(self.selection),
         }
     }
 }
+
+impl ApplyOverrides for text_input::Style {
+    fn apply_overrides(mut self, o: &StatusOverrides) -> Self {
+        self.background = o.background.unwrap_or(self.background);
+        self.value = o.text_color.unwrap_or(self.value);
+        self.border = o.border(self.border);
+        self
+    }
+}
+
+impl MapColors for TextInputStyle {
+    fn map_colors(self, f: &impl Fn(Color) -> Color) -> Self {
+        TextInputStyle {
+            active:   self.active.map_colors(f),
+            hovered:  self.hovered.map_colors(f),
+            focused:  self.focused.map_colors(f),
+            disabled: self.disabled.map_colors(f),
+            layout:   self.layout,
+        }
+    }
+}
+
+impl ApplyOverrides for TextInputStyle {
+    fn apply_overrides(self, o: &StatusOverrides) -> Self {
+        TextInputStyle {
+            active:   self.active.apply_overrides(o),
+            hovered:  self.hovered.apply_overrides(o),
+            focused:  self.focused.apply_overrides(o),
+            disabled: self.disabled.apply_overrides(o),
+            layout:   self.layout,
+        }
+    }
+}
+
+impl_approx_eq!(text_input::Style { background, border, icon, placeholder, value, selection });
+impl_approx_eq!(TextInputStyle { active, hovered, focused, disabled, layout });
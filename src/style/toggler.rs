@@ -3,7 +3,10 @@ use iced_widget::toggler;
 use serde::Deserialize;
 
 use crate::color::HexColor;
-use super::{BackgroundRaw, impl_merge};
+use crate::compat::{self, Interaction, TogglerParts};
+use super::{
+    ApplyOverrides, BackgroundRaw, DisabledDimming, MapColors, RadiusRaw, StatusOverrides, impl_approx_eq, impl_merge,
+};
 
 // -- Layer 1: Serde raw types --
 
@@ -16,15 +19,16 @@ pub(crate) struct TogglerFieldsRaw {
     background_border_color: Option<HexColor>,
     foreground_border_width: Option<f32>,
     foreground_border_color: Option<HexColor>,
-    border_radius:           Option<f32>,
+    border_radius:           Option<RadiusRaw>,
     text_color:              Option<HexColor>,
+    border_enabled:          Option<bool>,
 }
 
 impl_merge!(TogglerFieldsRaw {
     background, foreground,
     background_border_width, background_border_color,
     foreground_border_width, foreground_border_color,
-    border_radius, text_color,
+    border_radius, text_color, border_enabled,
 });
 
 #[derive(Deserialize, Default)]
@@ -62,13 +66,19 @@ fn cascade(
 }
 
 impl TogglerSection {
-    pub fn resolve(self) -> TogglerStyle {
+    pub fn resolve(self, dim: DisabledDimming) -> TogglerStyle {
         let active_untoggled = into_native(self.base);
         let active_toggled = cascade(self.base, self.toggled.as_ref(), None, None);
         let hovered_untoggled = cascade(self.base, None, self.hovered.as_ref(), None);
         let hovered_toggled = cascade(self.base, self.toggled.as_ref(), self.hovered.as_ref(), self.hovered_toggled.as_ref());
-        let disabled_untoggled = cascade(self.base, None, self.disabled.as_ref(), None);
-        let disabled_toggled = cascade(self.base, self.toggled.as_ref(), self.disabled.as_ref(), self.disabled_toggled.as_ref());
+        let disabled_untoggled = match self.disabled {
+            Some(_) => cascade(self.base, None, self.disabled.as_ref(), None),
+            None => dim.apply(active_untoggled),
+        };
+        let disabled_toggled = match (self.disabled, self.disabled_toggled) {
+            (None, None) => dim.apply(active_toggled),
+            _ => cascade(self.base, self.toggled.as_ref(), self.disabled.as_ref(), self.disabled_toggled.as_ref()),
+        };
 
         TogglerStyle {
             active_untoggled,
@@ -82,17 +92,27 @@ impl TogglerSection {
 }
 
 fn into_native(f: TogglerFieldsRaw) -> toggler::Style {
-    toggler::Style {
+    let f = if f.border_enabled == Some(false) {
+        TogglerFieldsRaw {
+            background_border_width: None,
+            background_border_color: None,
+            foreground_border_width: None,
+            foreground_border_color: None,
+            ..f
+        }
+    } else {
+        f
+    };
+    compat::toggler_style(TogglerParts {
         background: f.background.map(BackgroundRaw::into_background).unwrap_or(Background::Color(Color::TRANSPARENT)),
         foreground: f.foreground.map(BackgroundRaw::into_background).unwrap_or(Background::Color(Color::BLACK)),
         background_border_width: f.background_border_width.unwrap_or(0.0),
         background_border_color: f.background_border_color.map(|c| c.0).unwrap_or(Color::TRANSPARENT),
         foreground_border_width: f.foreground_border_width.unwrap_or(0.0),
         foreground_border_color: f.foreground_border_color.map(|c| c.0).unwrap_or(Color::TRANSPARENT),
-        border_radius: f.border_radius.map(|r| r.into()),
+        border_radius: f.border_radius.map(RadiusRaw::into_radius),
         text_color: f.text_color.map(|c| c.0),
-        padding_ratio: 0.36,
-    }
+    })
 }
 
 // -- Layer 3: Public types --
@@ -112,16 +132,58 @@ impl TogglerStyle {
     /// Returns a closure suitable for passing to `.style()` on a toggler widget.
     pub fn style_fn(&self) -> impl Fn(&Theme, toggler::Status) -> toggler::Style + Copy + 'static {
         let s = *self;
-        move |_theme, status| match status {
-            toggler::Status::Active { is_toggled } => {
+        move |_theme, status| match compat::toggler_status(status) {
+            (Interaction::Active, is_toggled) => {
                 if is_toggled { s.active_toggled } else { s.active_untoggled }
             }
-            toggler::Status::Hovered { is_toggled } => {
+            (Interaction::Hovered, is_toggled) => {
                 if is_toggled { s.hovered_toggled } else { s.hovered_untoggled }
             }
-            toggler::Status::Disabled { is_toggled } => {
+            (Interaction::Disabled, is_toggled) => {
                 if is_toggled { s.disabled_toggled } else { s.disabled_untoggled }
             }
         }
     }
+
+    /// Like [`style_fn`](Self::style_fn), with `overrides` applied over every
+    /// status, toggled or not. The background and border set the track. iced
+    /// 0.13 togglers take only a background color, no label color, and no
+    /// border radius, so those overrides are ignored there.
+    pub fn style_fn_with(
+        &self,
+        overrides: StatusOverrides,
+    ) -> impl Fn(&Theme, toggler::Status) -> toggler::Style + Copy + 'static {
+        let s = self.apply_overrides(&overrides);
+        move |theme, status| s.style_fn()(theme, status)
+    }
+}
+
+impl MapColors for TogglerStyle {
+    fn map_colors(self, f: &impl Fn(Color) -> Color) -> Self {
+        TogglerStyle {
+            active_untoggled:   self.active_untoggled.map_colors(f),
+            active_toggled:     self.active_toggled.map_colors(f),
+            hovered_untoggled:  self.hovered_untoggled.map_colors(f),
+            hovered_toggled:    self.hovered_toggled.map_colors(f),
+            disabled_untoggled: self.disabled_untoggled.map_colors(f),
+            disabled_toggled:   self.disabled_toggled.map_colors(f),
+        }
+    }
+}
+
+impl ApplyOverrides for TogglerStyle {
+    fn apply_overrides(self, o: &StatusOverrides) -> Self {
+        TogglerStyle {
+            active_untoggled:   self.active_untoggled.apply_overrides(o),
+            active_toggled:     self.active_toggled.apply_overrides(o),
+            hovered_untoggled:  self.hovered_untoggled.apply_overrides(o),
+            hovered_toggled:    self.hovered_toggled.apply_overrides(o),
+            disabled_untoggled: self.disabled_untoggled.apply_overrides(o),
+            disabled_toggled:   self.disabled_toggled.apply_overrides(o),
+        }
+    }
 }
+
+impl_approx_eq!(TogglerStyle {
+    active_untoggled, active_toggled, hovered_untoggled, hovered_toggled, disabled_untoggled, disabled_toggled,
+});
@@ -0,0 +1,86 @@
+//! A short, human-readable description of a loaded theme.
+
+use std::fmt;
+
+use crate::ThemeConfig;
+use crate::color::HexColor;
+use crate::compat;
+
+/// What [`ThemeConfig::summary`] returns: displays as a few lines naming the
+/// theme, whether it's light or dark, its palette colors, and the widget
+/// sections it styles.
+///
+/// ```text
+/// Paper (light)
+/// palette: background #FAFAFA, text #202020, primary #1E88E5, success #43A047, warning #FB8C00, danger #E53935
+/// sections: button, container, text-input
+/// ```
+#[derive(Clone, Copy)]
+pub struct ConfigSummary<'a>(&'a ThemeConfig);
+
+impl fmt::Display for ConfigSummary<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let config = self.0;
+        let variant = if config.is_dark() { "dark" } else { "light" };
+        writeln!(f, "{} ({variant})", config.name())?;
+
+        let swatches: Vec<String> = compat::palette_slots(&config.theme().palette())
+            .into_iter()
+            .map(|(slot, color)| format!("{slot} {}", HexColor(color)))
+            .collect();
+        writeln!(f, "palette: {}", swatches.join(", "))?;
+
+        let coverage = config.coverage();
+        let sections: Vec<&str> = coverage.defined().collect();
+        if sections.is_empty() {
+            write!(f, "sections: none")
+        } else {
+            write!(f, "sections: {}", sections.join(", "))
+        }
+    }
+}
+
+impl fmt::Debug for ConfigSummary<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl ThemeConfig {
+    /// A compact description of this theme for debug overlays and
+    /// diagnostic output; see [`ConfigSummary`] for what it shows.
+    ///
+    /// ```
+    /// # use iced_themer::ThemeConfig;
+    /// # let config: ThemeConfig = "[palette]\nbackground = \"#FAFAFA\"\ntext = \"#202020\"\n\
+    /// #     primary = \"#1E88E5\"\nsuccess = \"#43A047\"\nwarning = \"#FB8C00\"\ndanger = \"#E53935\"\n\
+    /// #     [button]\nbackground = \"@primary\"\n".parse().unwrap();
+    /// let summary = config.summary().to_string();
+    /// assert!(summary.starts_with("Custom (light)\npalette: background #FAFAFA, text #202020,"));
+    /// assert!(summary.ends_with("\nsections: button"));
+    /// ```
+    pub fn summary(&self) -> ConfigSummary<'_> {
+        ConfigSummary(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::PALETTE;
+
+    #[test]
+    fn summary_names_the_variant_palette_and_sections() {
+        let toml = format!("name = \"Paper\"\n{PALETTE}\n[text-input]\nbackground = \"@background\"\n\n[root]\n");
+        let config: ThemeConfig = toml.parse().unwrap();
+        let summary = config.summary().to_string();
+        let lines: Vec<&str> = summary.lines().collect();
+        assert_eq!(lines[0], "Paper (light)");
+        assert!(lines[1].starts_with("palette: background #FAFAFA, text #202020, primary #1E88E5, success #43A047"));
+        assert!(lines[1].ends_with("danger #E53935"));
+        assert_eq!(lines[2], "sections: root, text-input");
+
+        let bare: ThemeConfig = PALETTE.parse().unwrap();
+        assert!(bare.summary().to_string().ends_with("\nsections: none"));
+    }
+}
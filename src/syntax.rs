@@ -0,0 +1,236 @@
+//! Syntax highlighting colors, from the theme's optional `[syntax]` section
+//! with the rest derived from the palette.
+
+use iced_core::Color;
+#[cfg(feature = "iced-0-14")]
+use iced_core::Font;
+use iced_core::text::highlighter::Format;
+use iced_core::theme::Palette;
+use serde::Deserialize;
+
+use crate::ThemeConfig;
+use crate::color::{self, HexColor};
+use crate::style::{MapColors, impl_approx_eq};
+
+/// Raw `[syntax]` section: a color for any of the token categories, each
+/// replacing the derived one.
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub(crate) struct SyntaxRaw {
+    keyword:     Option<HexColor>,
+    string:      Option<HexColor>,
+    comment:     Option<HexColor>,
+    number:      Option<HexColor>,
+    constant:    Option<HexColor>,
+    function:    Option<HexColor>,
+    r#type:      Option<HexColor>,
+    variable:    Option<HexColor>,
+    operator:    Option<HexColor>,
+    punctuation: Option<HexColor>,
+}
+
+impl SyntaxRaw {
+    /// The colors set here over those derived from `palette` and its
+    /// `warning` color.
+    pub(crate) fn resolve(self, palette: &Palette, warning: Color) -> SyntaxTheme {
+        let pick = |set: Option<HexColor>, derived: Color| set.map_or(derived, |c| c.0);
+
+        SyntaxTheme {
+            keyword: pick(self.keyword, palette.primary),
+            string: pick(self.string, palette.success),
+            comment: pick(self.comment, color::mix(palette.text, palette.background, 0.5)),
+            number: pick(self.number, warning),
+            constant: pick(self.constant, warning),
+            function: pick(self.function, color::mix(palette.primary, palette.success, 0.5)),
+            r#type: pick(self.r#type, color::mix(palette.danger, palette.primary, 0.5)),
+            variable: pick(self.variable, palette.text),
+            operator: pick(self.operator, palette.text),
+            punctuation: pick(self.punctuation, color::mix(palette.text, palette.background, 0.3)),
+        }
+    }
+}
+
+/// A category of source code token, the keys of the `[syntax]` section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SyntaxToken {
+    Keyword,
+    String,
+    Comment,
+    Number,
+    Constant,
+    Function,
+    Type,
+    Variable,
+    Operator,
+    Punctuation,
+}
+
+impl SyntaxToken {
+    /// The category of a highlighter's scope name, either a TextMate scope
+    /// like `"keyword.control.rust"` (as syntect reports them) or a
+    /// tree-sitter capture name like `"function.method"`. `None` for scopes
+    /// with no category, which keep the editor's text color.
+    pub fn from_scope(scope: &str) -> Option<SyntaxToken> {
+        const SCOPES: [(&str, SyntaxToken); 17] = [
+            ("keyword.operator", SyntaxToken::Operator),
+            ("constant.numeric", SyntaxToken::Number),
+            ("entity.name.function", SyntaxToken::Function),
+            ("entity.name.type", SyntaxToken::Type),
+            ("storage.type", SyntaxToken::Keyword),
+            ("support.function", SyntaxToken::Function),
+            ("support.type", SyntaxToken::Type),
+            ("keyword", SyntaxToken::Keyword),
+            ("string", SyntaxToken::String),
+            ("comment", SyntaxToken::Comment),
+            ("number", SyntaxToken::Number),
+            ("constant", SyntaxToken::Constant),
+            ("function", SyntaxToken::Function),
+            ("type", SyntaxToken::Type),
+            ("variable", SyntaxToken::Variable),
+            ("operator", SyntaxToken::Operator),
+            ("punctuation", SyntaxToken::Punctuation),
+        ];
+        let matches = |prefix: &str| scope == prefix || scope.strip_prefix(prefix).is_some_and(|r| r.starts_with('.'));
+        SCOPES.into_iter().find(|(prefix, _)| matches(prefix)).map(|(_, token)| token)
+    }
+
+    /// The format of this token under `theme`. Its signature fits
+    /// `text_editor`'s `highlight_with`, for highlighters that report
+    /// `SyntaxToken`s:
+    ///
+    /// ```ignore
+    /// text_editor(&state.content).highlight_with::<MyHighlighter>(settings, SyntaxToken::format)
+    /// ```
+    #[cfg(feature = "iced-0-14")]
+    pub fn format(&self, theme: &crate::ThemerTheme) -> Format<Font> {
+        theme.0.syntax().format(*self)
+    }
+}
+
+/// The colors a theme gives each [`SyntaxToken`] category, for highlighting
+/// source code in a `text_editor`.
+///
+/// Every theme has one. Categories the `[syntax]` section doesn't set are
+/// derived from the palette: keywords take the primary color, strings the
+/// success color, numbers and constants the warning color, and comments and
+/// punctuation are faded text.
+///
+/// Under [`ThemerTheme`](crate::ThemerTheme), pass
+/// [`SyntaxToken::format`] to `highlight_with`. With iced's own `Theme`,
+/// which can't carry these colors, use the `SyntaxTheme` as (part of) the
+/// highlighter's settings and have it report [`Format`]s directly.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SyntaxTheme {
+    keyword:     Color,
+    string:      Color,
+    comment:     Color,
+    number:      Color,
+    constant:    Color,
+    function:    Color,
+    r#type:      Color,
+    variable:    Color,
+    operator:    Color,
+    punctuation: Color,
+}
+
+impl SyntaxTheme {
+    /// The color of `token`.
+    pub fn color(&self, token: SyntaxToken) -> Color {
+        match token {
+            SyntaxToken::Keyword => self.keyword,
+            SyntaxToken::String => self.string,
+            SyntaxToken::Comment => self.comment,
+            SyntaxToken::Number => self.number,
+            SyntaxToken::Constant => self.constant,
+            SyntaxToken::Function => self.function,
+            SyntaxToken::Type => self.r#type,
+            SyntaxToken::Variable => self.variable,
+            SyntaxToken::Operator => self.operator,
+            SyntaxToken::Punctuation => self.punctuation,
+        }
+    }
+
+    /// The highlighter format of `token`: its color, in the editor's font.
+    pub fn format<F>(&self, token: SyntaxToken) -> Format<F> {
+        Format { color: Some(self.color(token)), font: None }
+    }
+
+    /// The highlighter format of a scope name, as mapped by
+    /// [`SyntaxToken::from_scope`]. Scopes with no category get the default
+    /// format, which leaves the text as it is.
+    pub fn scope_format<F>(&self, scope: &str) -> Format<F> {
+        SyntaxToken::from_scope(scope).map(|token| self.format(token)).unwrap_or_default()
+    }
+}
+
+impl MapColors for SyntaxTheme {
+    fn map_colors(self, f: &impl Fn(Color) -> Color) -> Self {
+        SyntaxTheme {
+            keyword:     f(self.keyword),
+            string:      f(self.string),
+            comment:     f(self.comment),
+            number:      f(self.number),
+            constant:    f(self.constant),
+            function:    f(self.function),
+            r#type:      f(self.r#type),
+            variable:    f(self.variable),
+            operator:    f(self.operator),
+            punctuation: f(self.punctuation),
+        }
+    }
+}
+
+impl_approx_eq!(SyntaxTheme {
+    keyword, string, comment, number, constant, function, r#type, variable, operator, punctuation,
+});
+
+impl ThemeConfig {
+    /// The theme's syntax highlighting colors: its `[syntax]` section, with
+    /// any category it doesn't set derived from the palette.
+    pub fn syntax(&self) -> &SyntaxTheme {
+        &self.syntax
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::PALETTE;
+
+    #[test]
+    fn syntax_section_overrides_derived_colors() {
+        let config: ThemeConfig = format!("{PALETTE}[syntax]\nkeyword = \"#C678DD\"\ntype = \"$palette.danger\"\n")
+            .parse()
+            .unwrap();
+        let syntax = config.syntax();
+        assert_eq!(syntax.color(SyntaxToken::Keyword), Color::from_rgb8(0xC6, 0x78, 0xDD));
+        assert_eq!(syntax.color(SyntaxToken::Type), Color::from_rgb8(0xE5, 0x39, 0x35));
+        assert_eq!(syntax.color(SyntaxToken::String), Color::from_rgb8(0x43, 0xA0, 0x47));
+
+        let err = format!("{PALETTE}[syntax]\nregex = \"#FF0000\"\n").parse::<ThemeConfig>().err().unwrap();
+        assert_eq!(err.field(), Some("syntax.regex"));
+    }
+
+    #[test]
+    fn scopes_map_to_the_most_specific_category() {
+        assert_eq!(SyntaxToken::from_scope("keyword.control.rust"), Some(SyntaxToken::Keyword));
+        assert_eq!(SyntaxToken::from_scope("keyword.operator.arithmetic"), Some(SyntaxToken::Operator));
+        assert_eq!(SyntaxToken::from_scope("constant.numeric.integer"), Some(SyntaxToken::Number));
+        assert_eq!(SyntaxToken::from_scope("punctuation.bracket"), Some(SyntaxToken::Punctuation));
+        assert_eq!(SyntaxToken::from_scope("storage.type.function.rust"), Some(SyntaxToken::Keyword));
+        assert_eq!(SyntaxToken::from_scope("entity.name.type.struct"), Some(SyntaxToken::Type));
+        assert_eq!(SyntaxToken::from_scope("function.method"), Some(SyntaxToken::Function));
+        assert_eq!(SyntaxToken::from_scope("keywords"), None);
+
+        let config: ThemeConfig = PALETTE.parse().unwrap();
+        assert_eq!(config.syntax().scope_format::<()>("markup.heading"), Format::default());
+    }
+
+    #[cfg(feature = "iced-0-14")]
+    #[test]
+    fn token_format_reads_the_themer_theme() {
+        let config: ThemeConfig = format!("{PALETTE}[syntax]\ncomment = \"#808080\"\n").parse().unwrap();
+        let theme = crate::ThemerTheme::from(config);
+        assert_eq!(SyntaxToken::Comment.format(&theme).color, Some(Color::from_rgb8(0x80, 0x80, 0x80)));
+    }
+}
@@ -0,0 +1,107 @@
+//! A theme's ANSI colors in the form terminal widgets such as iced_term
+//! take them.
+
+use iced_core::Color;
+
+use crate::ThemeConfig;
+use crate::color::HexColor;
+
+/// A theme's [`AnsiPalette`](crate::AnsiPalette) as `#RRGGBB` strings, or
+/// `#RRGGBBAA` for translucent ones, the form terminal widgets take their
+/// color schemes in:
+///
+/// ```
+/// # use iced_themer::ThemeConfig;
+/// # let config: ThemeConfig = r##"
+/// # [palette]
+/// # background = "#1B2838"
+/// # text       = "#C7D5E0"
+/// # primary    = "#66C0F4"
+/// # success    = "#4CAF50"
+/// # warning    = "#FFC107"
+/// # danger     = "#F44336"
+/// # "##.parse().unwrap();
+/// let palette = config.terminal_palette();
+/// assert_eq!(palette.red, "#F44336");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TerminalPalette {
+    pub foreground: String,
+    pub background: String,
+    pub black: String,
+    pub red: String,
+    pub green: String,
+    pub yellow: String,
+    pub blue: String,
+    pub magenta: String,
+    pub cyan: String,
+    pub white: String,
+    pub bright_black: String,
+    pub bright_red: String,
+    pub bright_green: String,
+    pub bright_yellow: String,
+    pub bright_blue: String,
+    pub bright_magenta: String,
+    pub bright_cyan: String,
+    pub bright_white: String,
+}
+
+impl ThemeConfig {
+    /// The theme's [`ansi`](Self::ansi) colors as hex strings, for an embedded terminal widget.
+    pub fn terminal_palette(&self) -> TerminalPalette {
+        let hex = |color: Color| HexColor(color).to_string();
+        let ansi = self.ansi();
+        TerminalPalette {
+            foreground: hex(ansi.foreground),
+            background: hex(ansi.background),
+            black: hex(ansi.black),
+            red: hex(ansi.red),
+            green: hex(ansi.green),
+            yellow: hex(ansi.yellow),
+            blue: hex(ansi.blue),
+            magenta: hex(ansi.magenta),
+            cyan: hex(ansi.cyan),
+            white: hex(ansi.white),
+            bright_black: hex(ansi.bright_black),
+            bright_red: hex(ansi.bright_red),
+            bright_green: hex(ansi.bright_green),
+            bright_yellow: hex(ansi.bright_yellow),
+            bright_blue: hex(ansi.bright_blue),
+            bright_magenta: hex(ansi.bright_magenta),
+            bright_cyan: hex(ansi.bright_cyan),
+            bright_white: hex(ansi.bright_white),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::PALETTE;
+
+    #[test]
+    fn colors_derive_from_the_palette() {
+        let terminal = PALETTE.parse::<ThemeConfig>().unwrap().terminal_palette();
+        assert_eq!(terminal.background, "#FAFAFA");
+        assert_eq!(terminal.blue, "#1E88E5");
+        assert_eq!(terminal.red, "#E53935");
+    }
+
+    #[test]
+    fn ansi_section_overrides_and_keeps_alpha() {
+        let config: ThemeConfig =
+            format!("{PALETTE}[ansi]\nred = \"#FF0000\"\nblack = \"#00000080\"\nbright-black = \"$palette.primary\"\n")
+                .parse()
+                .unwrap();
+        let terminal = config.terminal_palette();
+        assert_eq!(terminal.red, "#FF0000");
+        assert_eq!(terminal.black, "#00000080");
+        assert_eq!(terminal.bright_black, "#1E88E5");
+    }
+
+    #[test]
+    fn invalid_entries_fail_the_load() {
+        let err = format!("{PALETTE}[ansi]\npurple = \"#FF00FF\"\n").parse::<ThemeConfig>().err().unwrap();
+        assert_eq!(err.field(), Some("ansi.purple"));
+    }
+}
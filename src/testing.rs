@@ -0,0 +1,38 @@
+//! Fixtures shared by the test modules.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// A complete `[palette]` table, for test themes that need one; tests that
+/// need a dark theme swap its background and text colors. It's a macro so
+/// that fixture constants can `concat!` it.
+macro_rules! palette {
+    () => {
+        r##"
+[palette]
+background = "#FAFAFA"
+text       = "#202020"
+primary    = "#1E88E5"
+success    = "#43A047"
+warning    = "#FB8C00"
+danger     = "#E53935"
+"##
+    };
+}
+pub(crate) use palette;
+
+/// The text of [`palette!`].
+pub(crate) const PALETTE: &str = palette!();
+
+/// A path in the temp directory, unique to this test run.
+pub(crate) fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("iced-themer-{}-{name}", std::process::id()))
+}
+
+/// Writes `contents` to `path`, modified `age` seconds ago.
+pub(crate) fn write(path: &Path, contents: &str, age: u64) {
+    fs::write(path, contents).unwrap();
+    let time = SystemTime::now() - Duration::from_secs(age);
+    fs::File::options().write(true).open(path).unwrap().set_modified(time).unwrap();
+}
@@ -1,9 +1,54 @@
-use iced_widget::{Button, Checkbox, Container, ProgressBar, Radio, Slider, TextInput, Toggler};
+use iced_core::widget::{Text, text};
+use iced_core::{Element, Length, Theme};
+use iced_widget::overlay::menu;
+use iced_widget::{
+    Button, Checkbox, ComboBox, Container, PaneGrid, PickList, ProgressBar, Radio, Rule, Scrollable, Slider, TextInput,
+    Toggler, VerticalSlider, button, checkbox, container, pane_grid, pick_list, progress_bar, radio, rule, scrollable,
+    slider, text_input, toggler,
+};
 
+use crate::{ThemeConfig, ThemeHandle};
 use crate::style::{
-    ButtonStyle, CheckboxStyle, ContainerStyle, ProgressBarStyle, RadioStyle, SliderStyle,
-    TextInputStyle, TogglerStyle,
+    ButtonStyle, CheckboxStyle, ComboBoxStyle, ContainerStyle, MenuStyle, PaneGridStyle, PickListStyle,
+    ProgressBarStyle, RadioStyle, RuleStyle, ScrollableStyle, SliderStyle, TextInputStyle, TextStyle, TogglerStyle,
+    root_style_fn,
 };
+#[cfg(feature = "svg")]
+use iced_widget::{Svg, svg};
+#[cfg(feature = "svg")]
+use crate::style::SvgStyle;
+#[cfg(all(feature = "iced-0-14", feature = "table"))]
+use crate::style::TableStyle;
+
+impl ThemeConfig {
+    /// Wraps `content` in a container that fills the window with the theme's
+    /// background.
+    ///
+    /// The container is styled from `[root]`, or `[container]` when there is no
+    /// `[root]` section; a background or text color neither sets comes from
+    /// the palette.
+    ///
+    /// ```no_run
+    /// use iced::Element;
+    /// use iced::widget::text;
+    /// use iced_themer::ThemeConfig;
+    ///
+    /// # let config = ThemeConfig::from_file("theme.toml").unwrap();
+    /// let view: Element<'_, ()> = config.themed_root(text("Hello")).padding(20).into();
+    /// ```
+    pub fn themed_root<'a, M, R>(
+        &self,
+        content: impl Into<Element<'a, M, iced_core::Theme, R>>,
+    ) -> Container<'a, M, iced_core::Theme, R>
+    where
+        R: iced_core::Renderer,
+    {
+        Container::new(content)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .style(root_style_fn(self.root.or(self.container)))
+    }
+}
 
 /// Applies an optional theme style to a widget inline in the builder chain.
 ///
@@ -40,11 +85,14 @@ where
     }
 }
 
-impl<'a, M, R> Themed<ButtonStyle> for Button<'a, M, iced_core::Theme, R>
+/// The `[slider]` section styles vertical sliders too; its `rail-width` is
+/// the rail's thickness either way.
+impl<'a, T, M> Themed<SliderStyle> for VerticalSlider<'a, T, M>
 where
-    R: iced_core::Renderer,
+    T: Copy + From<u8> + PartialOrd,
+    M: Clone,
 {
-    fn themed(self, style: Option<&ButtonStyle>) -> Self {
+    fn themed(self, style: Option<&SliderStyle>) -> Self {
         match style {
             Some(s) => self.style(s.style_fn()),
             None => self,
@@ -52,6 +100,23 @@ where
     }
 }
 
+/// Also sets the padding where the section does. A button's content can be
+/// any element, so its `text-size` is applied by theming the label with the
+/// same style: `button(text("Save").themed(style)).themed(style)`.
+impl<'a, M, R> Themed<ButtonStyle> for Button<'a, M, iced_core::Theme, R>
+where
+    R: iced_core::Renderer,
+{
+    fn themed(self, style: Option<&ButtonStyle>) -> Self {
+        let Some(s) = style else { return self };
+        let button = self.style(s.style_fn());
+        match s.padding() {
+            Some(padding) => button.padding(padding),
+            None => button,
+        }
+    }
+}
+
 impl<'a, M, R> Themed<ContainerStyle> for Container<'a, M, iced_core::Theme, R>
 where
     R: iced_core::Renderer,
@@ -64,28 +129,47 @@ where
     }
 }
 
+/// Also sets the padding and text size where the section does.
 impl<'a, M, R> Themed<TextInputStyle> for TextInput<'a, M, iced_core::Theme, R>
 where
     M: Clone,
     R: iced_core::text::Renderer,
 {
     fn themed(self, style: Option<&TextInputStyle>) -> Self {
-        match style {
-            Some(s) => self.style(s.style_fn()),
-            None => self,
+        let Some(s) = style else { return self };
+        let mut input = self.style(s.style_fn());
+        if let Some(padding) = s.padding() {
+            input = input.padding(padding);
         }
+        if let Some(size) = s.text_size() {
+            input = input.size(size);
+        }
+        input
     }
 }
 
+/// Also sets the label's spacing, size, and font where the section does.
 impl<'a, M, R> Themed<CheckboxStyle> for Checkbox<'a, M, iced_core::Theme, R>
 where
     R: iced_core::text::Renderer,
+    iced_core::Font: Into<R::Font>,
 {
     fn themed(self, style: Option<&CheckboxStyle>) -> Self {
-        match style {
-            Some(s) => self.style(s.style_fn()),
-            None => self,
+        let Some(s) = style else { return self };
+        let mut checkbox = self.style(s.style_fn());
+        if let Some(spacing) = s.label_spacing() {
+            checkbox = checkbox.spacing(spacing);
+        }
+        if let Some(size) = s.label_size() {
+            checkbox = checkbox.text_size(size);
+        }
+        if let Some(font) = s.label_font() {
+            checkbox = checkbox.font(font);
         }
+        if let Some(icon) = s.icon() {
+            checkbox = checkbox.icon(icon.to_icon(R::ICON_FONT, R::CHECKMARK_ICON));
+        }
+        checkbox
     }
 }
 
@@ -101,12 +185,50 @@ where
     }
 }
 
+/// Also sets the label's spacing, size, and font where the section does.
 impl<'a, M, R> Themed<RadioStyle> for Radio<'a, M, iced_core::Theme, R>
 where
     M: Clone,
     R: iced_core::text::Renderer,
+    iced_core::Font: Into<R::Font>,
 {
     fn themed(self, style: Option<&RadioStyle>) -> Self {
+        let Some(s) = style else { return self };
+        let mut radio = self.style(s.style_fn());
+        if let Some(spacing) = s.label_spacing() {
+            radio = radio.spacing(spacing);
+        }
+        if let Some(size) = s.label_size() {
+            radio = radio.text_size(size);
+        }
+        if let Some(font) = s.label_font() {
+            radio = radio.font(font);
+        }
+        radio
+    }
+}
+
+impl<'a, M, R> Themed<ScrollableStyle> for Scrollable<'a, M, iced_core::Theme, R>
+where
+    R: iced_core::text::Renderer,
+{
+    fn themed(self, style: Option<&ScrollableStyle>) -> Self {
+        match style {
+            Some(s) => self.style(s.style_fn()),
+            None => self,
+        }
+    }
+}
+
+impl<'a, T, L, V, M, R> Themed<PickListStyle> for PickList<'a, T, L, V, M, iced_core::Theme, R>
+where
+    T: ToString + PartialEq + Clone,
+    L: std::borrow::Borrow<[T]> + 'a,
+    V: std::borrow::Borrow<T> + 'a,
+    M: Clone,
+    R: iced_core::text::Renderer,
+{
+    fn themed(self, style: Option<&PickListStyle>) -> Self {
         match style {
             Some(s) => self.style(s.style_fn()),
             None => self,
@@ -114,11 +236,378 @@ where
     }
 }
 
+/// Styles the pick list's drop-down menu, alongside `Themed<PickListStyle>`
+/// for the pick list itself.
+impl<'a, T, L, V, M, R> Themed<MenuStyle> for PickList<'a, T, L, V, M, iced_core::Theme, R>
+where
+    T: ToString + PartialEq + Clone,
+    L: std::borrow::Borrow<[T]> + 'a,
+    V: std::borrow::Borrow<T> + 'a,
+    M: Clone,
+    R: iced_core::text::Renderer,
+{
+    fn themed(self, style: Option<&MenuStyle>) -> Self {
+        match style {
+            Some(s) => self.menu_style(s.style_fn()),
+            None => self,
+        }
+    }
+}
+
+impl<'a, T, M, R> Themed<MenuStyle> for ComboBox<'a, T, M, iced_core::Theme, R>
+where
+    T: std::fmt::Display + Clone,
+    R: iced_core::text::Renderer,
+{
+    fn themed(self, style: Option<&MenuStyle>) -> Self {
+        match style {
+            Some(s) => self.menu_style(s.style_fn()),
+            None => self,
+        }
+    }
+}
+
+/// Styles the input field, and the drop-down menu when the section has a
+/// `menu` table. Also sets the input's padding and text size where the
+/// section does.
+impl<'a, T, M, R> Themed<ComboBoxStyle> for ComboBox<'a, T, M, iced_core::Theme, R>
+where
+    T: std::fmt::Display + Clone,
+    R: iced_core::text::Renderer,
+{
+    fn themed(self, style: Option<&ComboBoxStyle>) -> Self {
+        let Some(s) = style else { return self };
+        let mut combo_box = self.input_style(s.input().style_fn()).themed(s.menu());
+        if let Some(padding) = s.input().padding() {
+            combo_box = combo_box.padding(padding);
+        }
+        if let Some(size) = s.input().text_size() {
+            combo_box = combo_box.size(size);
+        }
+        combo_box
+    }
+}
+
+impl<'a, M, R> Themed<PaneGridStyle> for PaneGrid<'a, M, iced_core::Theme, R>
+where
+    R: iced_core::Renderer,
+{
+    fn themed(self, style: Option<&PaneGridStyle>) -> Self {
+        match style {
+            Some(s) => self.style(s.style_fn()),
+            None => self,
+        }
+    }
+}
+
+/// Also sets the bar's girth and orientation where the section does.
 impl<'a> Themed<ProgressBarStyle> for ProgressBar<'a> {
     fn themed(self, style: Option<&ProgressBarStyle>) -> Self {
+        match style {
+            Some(s) => s.apply_layout(self.style(s.style_fn())),
+            None => self,
+        }
+    }
+}
+
+/// Sets the rule's color, radius, and fill mode. On iced 0.14 its thickness
+/// stays what it was built with; see [`RuleStyle::horizontal`].
+impl<'a> Themed<RuleStyle> for Rule<'a> {
+    fn themed(self, style: Option<&RuleStyle>) -> Self {
+        match style {
+            Some(s) => self.style(s.style_fn()),
+            None => self,
+        }
+    }
+}
+
+impl<'a, R> Themed<TextStyle> for Text<'a, iced_core::Theme, R>
+where
+    R: iced_core::text::Renderer,
+{
+    fn themed(self, style: Option<&TextStyle>) -> Self {
+        match style {
+            Some(s) => self.style(s.style_fn()),
+            None => self,
+        }
+    }
+}
+
+/// Sizes a button's label from `[button] text-size`; the label's colors
+/// come from the button's style.
+impl<'a, R> Themed<ButtonStyle> for Text<'a, iced_core::Theme, R>
+where
+    R: iced_core::text::Renderer,
+{
+    fn themed(self, style: Option<&ButtonStyle>) -> Self {
+        match style.and_then(ButtonStyle::text_size) {
+            Some(size) => self.size(size),
+            None => self,
+        }
+    }
+}
+
+#[cfg(feature = "svg")]
+impl<'a> Themed<SvgStyle> for Svg<'a, iced_core::Theme> {
+    fn themed(self, style: Option<&SvgStyle>) -> Self {
         match style {
             Some(s) => self.style(s.style_fn()),
             None => self,
         }
     }
 }
+
+/// iced's table takes its colors only from the theme catalog (see
+/// [`ThemerTheme`](crate::ThemerTheme)), so this sets just the divider width.
+/// Style rows by wrapping their cells with [`TableStyle::row`].
+#[cfg(all(feature = "iced-0-14", feature = "table"))]
+impl<'a, M, T, R> Themed<TableStyle> for iced_widget::table::Table<'a, M, T, R>
+where
+    T: iced_widget::table::Catalog,
+    R: iced_core::Renderer,
+{
+    fn themed(self, style: Option<&TableStyle>) -> Self {
+        match style.and_then(TableStyle::divider_width) {
+            Some(width) => self.separator(width),
+            None => self,
+        }
+    }
+}
+
+/// Like [`Themed`], but reads the style from a [`ThemeHandle`] every time the
+/// widget is drawn, so a reload restyles widgets that were built once and
+/// kept, not only those rebuilt in `view`.
+///
+/// Each draw reads the handle's current config; widgets whose section the
+/// current theme lacks get iced's default style.
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use iced::widget::button;
+/// use iced_themer::{ThemeWatcher, ThemedLive};
+///
+/// let handle = ThemeWatcher::new("theme.toml")?.spawn(Duration::from_millis(500));
+/// let save: iced::widget::Button<'_, ()> = button("Save").themed_live(&handle);
+/// # Ok::<(), iced_themer::Error>(())
+/// ```
+pub trait ThemedLive: Sized {
+    fn themed_live(self, handle: &ThemeHandle) -> Self;
+}
+
+/// A style function reading `section` of the handle's current config, or
+/// `fallback` when the config has no such section.
+fn live<S: 'static, Status: 'static, Style: 'static>(
+    handle: &ThemeHandle,
+    section: fn(&ThemeConfig) -> Option<&S>,
+    styled: fn(&S, &Theme, Status) -> Style,
+    fallback: fn(&Theme, Status) -> Style,
+) -> impl Fn(&Theme, Status) -> Style + 'static {
+    let handle = handle.clone();
+    move |theme, status| match section(&handle.current()) {
+        Some(style) => styled(style, theme, status),
+        None => fallback(theme, status),
+    }
+}
+
+/// [`live`] for widgets styled without a status.
+fn live_static<S: 'static, Style: 'static>(
+    handle: &ThemeHandle,
+    section: fn(&ThemeConfig) -> Option<&S>,
+    styled: fn(&S, &Theme) -> Style,
+    fallback: fn(&Theme) -> Style,
+) -> impl Fn(&Theme) -> Style + 'static {
+    let handle = handle.clone();
+    move |theme| match section(&handle.current()) {
+        Some(style) => styled(style, theme),
+        None => fallback(theme),
+    }
+}
+
+impl<'a, M, R> ThemedLive for Button<'a, M, Theme, R>
+where
+    R: iced_core::Renderer,
+{
+    fn themed_live(self, handle: &ThemeHandle) -> Self {
+        self.style(live(handle, ThemeConfig::button, |s, t, status| s.style_fn()(t, status), button::primary))
+    }
+}
+
+impl<'a, M, R> ThemedLive for Container<'a, M, Theme, R>
+where
+    R: iced_core::Renderer,
+{
+    fn themed_live(self, handle: &ThemeHandle) -> Self {
+        self.style(live_static(handle, ThemeConfig::container, |s, t| s.style_fn()(t), |_| container::Style::default()))
+    }
+}
+
+impl<'a, M, R> ThemedLive for TextInput<'a, M, Theme, R>
+where
+    M: Clone,
+    R: iced_core::text::Renderer,
+{
+    fn themed_live(self, handle: &ThemeHandle) -> Self {
+        self.style(live(handle, ThemeConfig::text_input, |s, t, status| s.style_fn()(t, status), text_input::default))
+    }
+}
+
+impl<'a, M, R> ThemedLive for Checkbox<'a, M, Theme, R>
+where
+    R: iced_core::text::Renderer,
+{
+    fn themed_live(self, handle: &ThemeHandle) -> Self {
+        self.style(live(handle, ThemeConfig::checkbox, |s, t, status| s.style_fn()(t, status), checkbox::primary))
+    }
+}
+
+impl<'a, M, R> ThemedLive for Toggler<'a, M, Theme, R>
+where
+    R: iced_core::text::Renderer,
+{
+    fn themed_live(self, handle: &ThemeHandle) -> Self {
+        self.style(live(handle, ThemeConfig::toggler, |s, t, status| s.style_fn()(t, status), toggler::default))
+    }
+}
+
+impl<'a, T, M> ThemedLive for Slider<'a, T, M>
+where
+    T: Copy + From<u8> + PartialOrd,
+    M: Clone,
+{
+    fn themed_live(self, handle: &ThemeHandle) -> Self {
+        self.style(live(handle, ThemeConfig::slider, |s, t, status| s.style_fn()(t, status), slider::default))
+    }
+}
+
+impl<'a, T, M> ThemedLive for VerticalSlider<'a, T, M>
+where
+    T: Copy + From<u8> + PartialOrd,
+    M: Clone,
+{
+    fn themed_live(self, handle: &ThemeHandle) -> Self {
+        self.style(live(handle, ThemeConfig::slider, |s, t, status| s.style_fn()(t, status), slider::default))
+    }
+}
+
+impl<'a, M, R> ThemedLive for PaneGrid<'a, M, Theme, R>
+where
+    R: iced_core::Renderer,
+{
+    fn themed_live(self, handle: &ThemeHandle) -> Self {
+        self.style(live_static(handle, ThemeConfig::pane_grid, |s, t| s.style_fn()(t), pane_grid::default))
+    }
+}
+
+impl<'a> ThemedLive for ProgressBar<'a> {
+    fn themed_live(self, handle: &ThemeHandle) -> Self {
+        self.style(live_static(handle, ThemeConfig::progress_bar, |s, t| s.style_fn()(t), progress_bar::primary))
+    }
+}
+
+impl<'a, R> ThemedLive for Text<'a, Theme, R>
+where
+    R: iced_core::text::Renderer,
+{
+    fn themed_live(self, handle: &ThemeHandle) -> Self {
+        self.style(live_static(handle, ThemeConfig::text, |s, t| s.style_fn()(t), text::default))
+    }
+}
+
+#[cfg(feature = "svg")]
+impl<'a> ThemedLive for Svg<'a, Theme> {
+    fn themed_live(self, handle: &ThemeHandle) -> Self {
+        self.style(live(handle, ThemeConfig::svg, |s, t, status| s.style_fn()(t, status), |_, _| svg::Style::default()))
+    }
+}
+
+impl<'a> ThemedLive for Rule<'a> {
+    fn themed_live(self, handle: &ThemeHandle) -> Self {
+        self.style(live_static(handle, ThemeConfig::rule, |s, t| s.style_fn()(t), rule::default))
+    }
+}
+
+impl<'a, M, R> ThemedLive for Radio<'a, M, Theme, R>
+where
+    M: Clone,
+    R: iced_core::text::Renderer,
+{
+    fn themed_live(self, handle: &ThemeHandle) -> Self {
+        self.style(live(handle, ThemeConfig::radio, |s, t, status| s.style_fn()(t, status), radio::default))
+    }
+}
+
+impl<'a, M, R> ThemedLive for Scrollable<'a, M, Theme, R>
+where
+    R: iced_core::text::Renderer,
+{
+    fn themed_live(self, handle: &ThemeHandle) -> Self {
+        self.style(live(handle, ThemeConfig::scrollable, |s, t, status| s.style_fn()(t, status), scrollable::default))
+    }
+}
+
+/// Styles both the pick list, from `[pick-list]`, and its drop-down menu, from
+/// `[menu]`.
+impl<'a, T, L, V, M, R> ThemedLive for PickList<'a, T, L, V, M, Theme, R>
+where
+    T: ToString + PartialEq + Clone,
+    L: std::borrow::Borrow<[T]> + 'a,
+    V: std::borrow::Borrow<T> + 'a,
+    M: Clone,
+    R: iced_core::text::Renderer,
+{
+    fn themed_live(self, handle: &ThemeHandle) -> Self {
+        self.style(live(handle, ThemeConfig::pick_list, |s, t, status| s.style_fn()(t, status), pick_list::default))
+            .menu_style(live_static(handle, ThemeConfig::menu, |s, t| s.style_fn()(t), menu::default))
+    }
+}
+
+/// Styles both the combo box's input and its drop-down menu from
+/// `[combo-box]`, or from `[text-input]` and `[menu]` for whatever that
+/// section leaves out.
+impl<'a, T, M, R> ThemedLive for ComboBox<'a, T, M, Theme, R>
+where
+    T: std::fmt::Display + Clone,
+    R: iced_core::text::Renderer,
+{
+    fn themed_live(self, handle: &ThemeHandle) -> Self {
+        fn input(config: &ThemeConfig) -> Option<&TextInputStyle> {
+            config.combo_box().map(ComboBoxStyle::input).or(config.text_input())
+        }
+        fn drop_down(config: &ThemeConfig) -> Option<&MenuStyle> {
+            config.combo_box().and_then(ComboBoxStyle::menu).or(config.menu())
+        }
+        self.input_style(live(handle, input, |s, t, status| s.style_fn()(t, status), text_input::default))
+            .menu_style(live_static(handle, drop_down, |s, t| s.style_fn()(t), menu::default))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iced_core::Color;
+
+    use super::*;
+    use crate::testing::PALETTE;
+
+    #[test]
+    fn live_style_follows_the_handle() {
+        let config = |color: &str| -> ThemeConfig {
+            format!("{PALETTE}[button]\nbackground = \"{color}\"\n").parse().unwrap()
+        };
+        let handle = ThemeHandle::new(config("#FF0000"));
+        let style = live(&handle, ThemeConfig::button, |s, t, status| s.style_fn()(t, status), button::primary);
+        let background = || style(&Theme::Dark, button::Status::Active).background;
+
+        assert_eq!(background(), Some(Color::from_rgb8(0xFF, 0x00, 0x00).into()));
+        handle.replace(config("#0000FF"));
+        assert_eq!(background(), Some(Color::from_rgb8(0x00, 0x00, 0xFF).into()));
+        handle.replace(PALETTE.parse().unwrap());
+        assert_eq!(background(), button::primary(&Theme::Dark, button::Status::Active).background);
+    }
+
+    #[test]
+    fn themed_none_infers_the_style_type() {
+        // Each widget has one `Themed` impl, so `None` needs no annotation.
+        let _: Button<'_, (), Theme, ()> = iced_widget::button("x").themed(None);
+        let _: Container<'_, (), Theme, ()> = iced_widget::container("x").themed(None);
+    }
+}
@@ -0,0 +1,690 @@
+//! Whole-theme transforms that produce a new [`ThemeConfig`] from an existing one.
+//!
+//! Transforms that act on resolved colors (like [`ThemeConfig::derive_dark`]
+//! and [`ThemeConfig::quantized`])
+//! are recorded as [`Derivation`]s, so a config rebuilt from its TOML source
+//! (e.g. by [`ThemeConfig::with_accent`]) ends up transformed the same way.
+
+use iced_core::Color;
+use iced_core::theme::Theme;
+use toml::Value;
+
+use crate::ThemeConfig;
+use crate::color::{HexColor, invert_lightness, parse_color};
+use crate::compat;
+use crate::error::Error;
+use crate::quantize::ColorDepth;
+use crate::style::{MapColors, WIDGET_SECTIONS, widget_sections};
+
+/// A color transform applied on top of a config's TOML source.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Derivation {
+    Dark,
+    Quantize(ColorDepth),
+}
+
+impl Derivation {
+    fn apply(self, config: &ThemeConfig) -> ThemeConfig {
+        let name = match self {
+            Derivation::Dark => format!("{} Dark", config.name),
+            Derivation::Quantize(_) => config.name.clone(),
+        };
+        let mut derived = config.map_colors(name, |c| self.map(c));
+        derived.derivations.push(self);
+        derived
+    }
+
+    /// Maps a source color to the color it becomes in the derived theme.
+    pub(crate) fn map(self, color: Color) -> Color {
+        match self {
+            Derivation::Dark => invert_lightness(color),
+            Derivation::Quantize(depth) => depth.quantize(color),
+        }
+    }
+
+    /// Maps a color in the derived theme back to the source color that
+    /// produces it. Quantizing can't be undone, so it keeps the color, which
+    /// is snapped again when the derivation is re-applied.
+    fn invert(self, color: Color) -> Color {
+        match self {
+            Derivation::Dark => invert_lightness(color),
+            Derivation::Quantize(_) => color,
+        }
+    }
+}
+
+/// The overrides to resolve a theme with: any combination of a platform, a
+/// scope, right-to-left layout, and dark mode, applied together by
+/// [`ThemeConfig::resolve_in`].
+///
+/// Every axis is unset by default. They apply in a fixed order, each merged
+/// over the result of the one before, so where two set the same key the
+/// later one wins:
+///
+/// 1. `[platform.<name>]`, for the operating system.
+/// 2. `[scope.<name>]`, for a region of the app, like a sidebar.
+/// 3. `[rtl]`, for right-to-left layouts.
+/// 4. Dark mode, [`derive_dark`](ThemeConfig::derive_dark) over the merged
+///    theme, so overridden colors are darkened along with the rest.
+///
+/// ```no_run
+/// use iced_themer::{ResolutionContext, ThemeConfig};
+///
+/// let config = ThemeConfig::from_file("theme.toml")?;
+/// let context = ResolutionContext::new().current_platform().scope("sidebar").dark(true);
+/// let sidebar = config.resolve_in(&context)?;
+/// # Ok::<(), iced_themer::Error>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResolutionContext {
+    platform: Option<String>,
+    scope:    Option<String>,
+    rtl:      bool,
+    dark:     bool,
+}
+
+impl ResolutionContext {
+    /// A context with no axis set, resolving a theme as it is.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Merges `[platform.<name>]`, e.g. `"macos"`.
+    pub fn platform(mut self, name: impl Into<String>) -> Self {
+        self.platform = Some(name.into());
+        self
+    }
+
+    /// Merges the table for the running operating system, named as Rust's
+    /// `std::env::consts::OS`: `"linux"`, `"macos"`, `"windows"`, and so on.
+    pub fn current_platform(self) -> Self {
+        self.platform(std::env::consts::OS)
+    }
+
+    /// Merges `[scope.<name>]`.
+    pub fn scope(mut self, name: impl Into<String>) -> Self {
+        self.scope = Some(name.into());
+        self
+    }
+
+    /// Merges `[rtl]` when `rtl` is true.
+    pub fn rtl(mut self, rtl: bool) -> Self {
+        self.rtl = rtl;
+        self
+    }
+
+    /// Derives a dark variant when `dark` is true.
+    pub fn dark(mut self, dark: bool) -> Self {
+        self.dark = dark;
+        self
+    }
+}
+
+/// Sets `[palette] <slot>` in `source` to `color`. When the slot is a
+/// `"$variable"`, the variable is set instead, following indirections, so
+/// every value using it re-derives.
+fn set_palette_slot(source: &mut Value, slot: &str, color: Color) {
+    let hex = Value::String(HexColor(color).to_string());
+    let mut target = source.get("palette").and_then(|p| p.get(slot)).cloned();
+    let mut variable = None;
+    while let Some(name) = target.as_ref().and_then(Value::as_str).and_then(|s| s.strip_prefix('$')) {
+        let name = name.to_string();
+        target = source.get("variables").and_then(|v| v.get(&name)).cloned();
+        variable = Some(name);
+    }
+
+    match (variable, source.as_table_mut()) {
+        (Some(name), Some(root)) => {
+            if let Some(Value::Table(vars)) = root.get_mut("variables") {
+                vars.insert(name, hex);
+            }
+        }
+        (None, Some(root)) => {
+            if let Some(Value::Table(palette)) = root.get_mut("palette") {
+                palette.insert(slot.to_string(), hex);
+            }
+        }
+        (_, None) => {}
+    }
+}
+
+/// The `name` table of a `[platform]` or `[scope]` table.
+fn named(tables: Option<Value>, name: &str) -> Option<Value> {
+    match tables {
+        Some(Value::Table(mut tables)) => tables.remove(name),
+        _ => None,
+    }
+}
+
+impl ThemeConfig {
+    /// Derive a dark variant from a light theme.
+    ///
+    /// Every color in the palette and in every widget section has its
+    /// perceptual (Oklch) lightness mirrored, while hue, chroma, and alpha are
+    /// kept. Because lightness differences are preserved, so are the contrast
+    /// relationships the theme author designed. The result is named
+    /// `"<name> Dark"`.
+    ///
+    /// A theme whose background is already dark is returned unchanged.
+    pub fn derive_dark(&self) -> ThemeConfig {
+        if self.theme.extended_palette().is_dark {
+            return self.clone();
+        }
+        Derivation::Dark.apply(self)
+    }
+
+    /// Returns a copy of this theme with every color snapped to `depth`, for
+    /// displays that show only a few colors, such as e-ink panels or
+    /// 256-color terminals.
+    ///
+    /// Like [`derive_dark`](Self::derive_dark), quantizing acts on resolved
+    /// colors and survives later changes: [`with_accent`](Self::with_accent)
+    /// and [`apply_patch`](Self::apply_patch) on the result are quantized too.
+    ///
+    /// ```no_run
+    /// # use iced_themer::{ColorDepth, ThemeConfig};
+    /// # let config: ThemeConfig = todo!();
+    /// let e_ink = config.quantized(ColorDepth::Grays(16));
+    /// ```
+    pub fn quantized(&self, depth: ColorDepth) -> ThemeConfig {
+        Derivation::Quantize(depth).apply(self)
+    }
+
+    /// Returns a copy of this theme with `accent` as the primary color.
+    ///
+    /// Everything derived from the primary slot is re-derived: values written
+    /// as `"$palette.primary"` or `"@primary"`, and, when `[palette] primary`
+    /// is itself a `"$variable"`, every value and expression using that
+    /// variable. This makes it possible to offer a user-facing accent picker on
+    /// top of any theme.
+    ///
+    /// Returns an error only if re-resolving the theme fails, which cannot
+    /// happen for a theme that loaded successfully.
+    pub fn with_accent(&self, accent: Color) -> Result<ThemeConfig, Error> {
+        let mut source = self.source.clone();
+        set_palette_slot(&mut source, "primary", self.underived(accent));
+        self.rebuild(source)
+    }
+
+    /// Returns a copy of this theme with the palette of `theme`, an iced
+    /// built-in like `Theme::Dracula` or a custom theme, in place of its own.
+    ///
+    /// Each palette color is replaced the way
+    /// [`with_accent`](Self::with_accent) replaces the primary one, so every
+    /// widget value derived from the palette follows it, while radii, widths,
+    /// shadows, and literal colors stay. The extended palette is generated
+    /// the way iced generates it for its built-in themes, whatever
+    /// `[palette] generator` the theme sets. On iced 0.13, whose palettes
+    /// have no warning color, the theme's own `warning` stays.
+    ///
+    /// ```no_run
+    /// # use iced_themer::ThemeConfig;
+    /// # let config: ThemeConfig = todo!();
+    /// let nord = config.merge_palette_from(&iced::Theme::Nord)?;
+    /// # Ok::<(), iced_themer::Error>(())
+    /// ```
+    ///
+    /// Returns an error only if re-resolving the theme fails, which cannot
+    /// happen for a theme that loaded successfully.
+    pub fn merge_palette_from(&self, theme: &Theme) -> Result<ThemeConfig, Error> {
+        let mut source = self.source.clone();
+        for (slot, color) in compat::palette_slots(&theme.palette()) {
+            set_palette_slot(&mut source, slot, self.underived(color));
+        }
+        if let Some(Value::Table(palette)) = source.get_mut("palette") {
+            palette.remove("generator");
+        }
+        self.rebuild(source)
+    }
+
+    /// `color` with this config's derivations undone, so it comes out as
+    /// `color` once they are re-applied.
+    fn underived(&self, color: Color) -> Color {
+        self.derivations.iter().rev().fold(color, |c, d| d.invert(c))
+    }
+
+    /// Returns a copy of this theme with a TOML fragment merged over its source.
+    ///
+    /// Tables merge key by key and any other value replaces what was there, so
+    /// a patch only needs the keys it changes:
+    ///
+    /// ```no_run
+    /// # use iced_themer::ThemeConfig;
+    /// # let config: ThemeConfig = todo!();
+    /// let tweaked = config.apply_patch("[button]\nbackground = '#FF0000'")?;
+    /// # Ok::<(), iced_themer::Error>(())
+    /// ```
+    ///
+    /// The patch may use and override `[variables]`; everything depending on
+    /// a patched value is re-derived. On a derived theme (e.g. from
+    /// [`derive_dark`](Self::derive_dark)) the patch applies to the original
+    /// source and is transformed along with it.
+    pub fn apply_patch(&self, patch: &str) -> Result<ThemeConfig, Error> {
+        let patch: Value = toml::from_str(patch)?;
+        let mut source = self.source.clone();
+        merge(&mut source, patch);
+        self.rebuild(source)
+    }
+
+    /// Returns a copy of this theme for a right-to-left layout, with the
+    /// theme's `[rtl]` table merged over it like [`apply_patch`](Self::apply_patch).
+    ///
+    /// `[rtl]` holds whatever differs when the layout is mirrored, typically
+    /// asymmetric corner radii and shadow offsets:
+    ///
+    /// ```toml
+    /// [button]
+    /// border-radius = [8.0, 0.0, 0.0, 8.0]
+    ///
+    /// [rtl.button]
+    /// border-radius = [0.0, 8.0, 8.0, 0.0]
+    /// ```
+    ///
+    /// A theme without `[rtl]` is returned unchanged. Errors in `[rtl]` are
+    /// reported here, not when the theme loads. Shorthand for
+    /// [`resolve_in`](Self::resolve_in) with only the `rtl` axis set.
+    pub fn rtl(&self) -> Result<ThemeConfig, Error> {
+        self.resolve_in(&ResolutionContext::new().rtl(true))
+    }
+
+    /// Returns a copy of this theme with the overrides `context` selects
+    /// merged over it, in the order [`ResolutionContext`] documents.
+    ///
+    /// Override tables the theme doesn't have are skipped, so a theme without
+    /// any returns unchanged (or only darkened). Errors in the selected
+    /// tables are reported here, not when the theme loads. The result no
+    /// longer carries any override tables; resolve other contexts from the
+    /// original config.
+    pub fn resolve_in(&self, context: &ResolutionContext) -> Result<ThemeConfig, Error> {
+        let mut source = self.source.clone();
+        let layers = match source.as_table_mut() {
+            Some(root) => {
+                let (platforms, scopes, rtl) = (root.remove("platform"), root.remove("scope"), root.remove("rtl"));
+                [
+                    context.platform.as_deref().and_then(|name| named(platforms, name)),
+                    context.scope.as_deref().and_then(|name| named(scopes, name)),
+                    rtl.filter(|_| context.rtl),
+                ]
+            }
+            None => [None, None, None],
+        };
+
+        let mut config = if layers.iter().all(Option::is_none) {
+            self.clone()
+        } else {
+            for layer in layers.into_iter().flatten() {
+                merge(&mut source, layer);
+            }
+            self.rebuild(source)?
+        };
+        if context.dark {
+            config = config.derive_dark();
+        }
+        Ok(config)
+    }
+
+    /// Returns a copy of this theme with only the style sections named in
+    /// `sections`, like `["button", "text-input"]`, for handing a plugin or
+    /// embedded component just the part of the theme it draws with.
+    ///
+    /// ```no_run
+    /// # use iced_themer::ThemeConfig;
+    /// # let config: ThemeConfig = todo!();
+    /// let for_plugin = config.subset(&["button", "text-input"])?;
+    /// assert!(for_plugin.container().is_none());
+    /// # Ok::<(), iced_themer::Error>(())
+    /// ```
+    ///
+    /// Style sections are the widget sections, `[root]`, `[ansi]`,
+    /// `[syntax]`, and `[icons]`; names that aren't one are ignored. The
+    /// palette, `[variables]`, `[font]`, `[defaults]`, and the other
+    /// theme-wide tables are always kept, and so are the `[platform]`,
+    /// `[scope]`, and `[rtl]` overrides of the kept sections.
+    ///
+    /// Returns an error only if re-resolving the theme fails, which cannot
+    /// happen for a theme that loaded successfully.
+    pub fn subset(&self, sections: &[&str]) -> Result<ThemeConfig, Error> {
+        let mut source = self.source.clone();
+        if let Some(root) = source.as_table_mut() {
+            retain_sections(root, sections);
+            for key in ["platform", "scope"] {
+                let Some(Value::Table(named)) = root.get_mut(key) else { continue };
+                for (_, overrides) in named.iter_mut() {
+                    let Some(overrides) = overrides.as_table_mut() else { continue };
+                    retain_sections(overrides, sections);
+                }
+            }
+            if let Some(Value::Table(rtl)) = root.get_mut("rtl") {
+                retain_sections(rtl, sections);
+            }
+        }
+        self.rebuild(source)
+    }
+
+    /// Builds a config from `source` and re-applies this config's derivations.
+    pub(crate) fn rebuild(&self, source: Value) -> Result<ThemeConfig, Error> {
+        let base = ThemeConfig::from_value(source)?;
+        Ok(self.derivations.iter().fold(base, |config, d| d.apply(&config)))
+    }
+
+    /// Returns a copy of this config with `f` applied to every palette and
+    /// widget color. The [`Theme`] is rebuilt with the same palette generator.
+    pub(crate) fn map_colors(&self, name: String, f: impl Fn(Color) -> Color) -> ThemeConfig {
+        let palette = compat::map_palette(self.theme.palette(), &f);
+        let generator = self.generator;
+
+        let mut config = ThemeConfig {
+            theme: Theme::custom_with_fn(name.clone(), palette, |p| generator.generate(p)),
+            name,
+            generator,
+            custom_slots: self.custom_slots.iter().map(|(slot, color)| (slot.clone(), f(*color))).collect(),
+            warning: f(self.warning),
+            ansi: self.ansi.map_colors(&f),
+            syntax: self.syntax.map_colors(&f),
+            icons: self.icons.clone().map_colors(&f),
+            variables: self
+                .variables
+                .iter()
+                .map(|(name, value)| match parse_color(value) {
+                    Ok(color) => (name.clone(), HexColor(f(color)).to_string()),
+                    Err(_) => (name.clone(), value.clone()),
+                })
+                .collect(),
+            variants: self.variants.clone().map_colors(&f),
+            ..self.clone()
+        };
+        config.map_section_colors(&f);
+        config
+    }
+}
+
+macro_rules! map_section_colors {
+    ($($(#[$attr:meta])* $field:ident $name:literal $style:ident $raw:ident $resolve:ident $catalog:ident $statuses:tt;)*) => {
+        impl ThemeConfig {
+            /// Applies `f` to the colors of every widget section style.
+            fn map_section_colors(&mut self, f: &impl Fn(Color) -> Color) {
+                self.root = self.root.map_colors(f);
+                $($(#[$attr])* {
+                    self.$field = self.$field.map_colors(f);
+                })*
+            }
+        }
+    };
+}
+widget_sections!(map_section_colors);
+
+/// The top-level tables besides the widget sections that
+/// [`ThemeConfig::subset`] can drop.
+const OTHER_STYLE_SECTIONS: [&str; 3] = ["ansi", "icons", "syntax"];
+
+/// Removes the style sections of `table` that aren't in `keep`.
+fn retain_sections(table: &mut toml::Table, keep: &[&str]) {
+    table.retain(|key, _| {
+        let style = WIDGET_SECTIONS.contains(&key) || OTHER_STYLE_SECTIONS.contains(&key);
+        !style || keep.contains(&key)
+    });
+}
+
+/// Deep-merges `patch` into `base`: tables merge recursively, anything else
+/// in `patch` replaces the value in `base`.
+pub(crate) fn merge(base: &mut Value, patch: Value) {
+    match (base, patch) {
+        (Value::Table(base), Value::Table(patch)) => {
+            for (key, value) in patch {
+                match base.get_mut(&key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, patch) => *base = patch,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iced_core::Background;
+    use iced_widget::button;
+
+    use crate::{PaletteGenerator, ThemeDiff};
+    use crate::color::{contrast_ratio, to_oklch};
+    use crate::testing::palette;
+
+    const LIGHT: &str = concat!(
+        "\nname = \"Paper\"\n",
+        palette!(),
+        r##"
+[button]
+background = "#1E88E5"
+text-color = "#FFFFFF"
+"##
+    );
+
+    #[test]
+    fn rtl_merges_overrides() {
+        let toml = format!("{LIGHT}border-radius = [8.0, 0.0, 0.0, 8.0]\n\n[rtl.button]\nborder-radius = [0.0, 8.0, 8.0, 0.0]\n");
+        let ltr: ThemeConfig = toml.parse().unwrap();
+        let rtl = ltr.rtl().unwrap();
+        let radius = |config: &ThemeConfig| config.button().unwrap().style_fn()(&config.theme(), button::Status::Active).border.radius;
+        assert_eq!(radius(&ltr).top_left, 8.0);
+        assert_eq!(radius(&rtl).top_left, 0.0);
+        assert_eq!(radius(&rtl).top_right, 8.0);
+        assert!(ltr.resolved.get("rtl").is_none());
+    }
+
+    #[test]
+    fn rtl_without_overrides_is_unchanged() {
+        let config: ThemeConfig = LIGHT.parse().unwrap();
+        assert!(ThemeDiff::between(&config, &config.rtl().unwrap()).is_empty());
+    }
+
+    #[test]
+    fn resolution_context_layers_platform_scope_rtl_then_dark() {
+        let toml = format!(
+            "{LIGHT}\n[platform.macos.button]\nbackground = \"#FF0000\"\nborder-width = 2.0\n\n\
+             [scope.sidebar.button]\nbackground = \"#00FF00\"\n\n\
+             [rtl.button]\nborder-width = 3.0\n"
+        );
+        let config: ThemeConfig = toml.parse().unwrap();
+        let style = |config: &ThemeConfig| config.button().unwrap().style_fn()(&config.theme(), button::Status::Active);
+        let background = |config: &ThemeConfig| style(config).background;
+
+        let macos = config.resolve_in(&ResolutionContext::new().platform("macos")).unwrap();
+        assert_eq!(background(&macos), Some(Background::from(Color::from_rgb8(0xFF, 0x00, 0x00))));
+        assert_eq!(style(&macos).border.width, 2.0);
+
+        let context = ResolutionContext::new().platform("macos").scope("sidebar").rtl(true);
+        let all = config.resolve_in(&context).unwrap();
+        assert_eq!(background(&all), Some(Background::from(Color::from_rgb8(0x00, 0xFF, 0x00))));
+        assert_eq!(style(&all).border.width, 3.0);
+
+        let dark = config.resolve_in(&context.clone().dark(true)).unwrap();
+        assert_eq!(background(&dark), background(&all.derive_dark()));
+        assert!(dark.theme().extended_palette().is_dark);
+
+        let unknown = config.resolve_in(&ResolutionContext::new().platform("haiku").scope("footer")).unwrap();
+        assert!(ThemeDiff::between(&config, &unknown).is_empty());
+    }
+
+    #[test]
+    fn subset_keeps_only_the_named_sections() {
+        let toml = format!(
+            "{LIGHT}border-radius = \"$radius\"\n\n[variables]\nradius = 6.0\n\n\
+             [container]\nbackground = \"#EEEEEE\"\n\n[container.variants.card]\nborder-width = 1.0\n\n\
+             [platform.macos.container]\nborder-width = 2.0\n\n[rtl.button]\nborder-width = 3.0\n"
+        );
+        let config: ThemeConfig = toml.parse().unwrap();
+        let subset = config.derive_dark().subset(&["button", "text-input"]).unwrap();
+        let style = |config: &ThemeConfig| config.button().unwrap().style_fn()(&config.theme(), button::Status::Active);
+        assert_eq!(subset.name(), "Paper Dark");
+        assert!(subset.container().is_none() && subset.container_variant("card").is_none());
+        assert!(subset.text_input().is_none());
+        assert_eq!(style(&subset).border.radius.top_left, 6.0);
+        assert_eq!(subset.theme().palette(), config.derive_dark().theme().palette());
+        let macos = subset.source.get("platform").and_then(|p| p.get("macos"));
+        assert!(macos.is_some_and(|m| m.get("container").is_none()));
+        assert_eq!(style(&subset.rtl().unwrap()).border.width, 3.0);
+    }
+
+    #[test]
+    fn override_tables_must_hold_tables() {
+        let err = format!("{LIGHT}\n[platform]\nmacos = 3\n").parse::<ThemeConfig>().err().unwrap();
+        assert_eq!(err.field(), Some("platform.macos"));
+        let err = format!("scope = \"sidebar\"\n{LIGHT}").parse::<ThemeConfig>().err().unwrap();
+        assert_eq!(err.field(), Some("scope"));
+    }
+
+    #[test]
+    fn derive_dark_flips_background() {
+        let light: ThemeConfig = LIGHT.parse().unwrap();
+        let dark = light.derive_dark();
+        assert!(dark.theme().extended_palette().is_dark);
+        assert_eq!(dark.name(), "Paper Dark");
+    }
+
+    #[test]
+    fn derive_dark_keeps_text_readable() {
+        let dark: ThemeConfig = LIGHT.parse::<ThemeConfig>().unwrap().derive_dark();
+        let p = dark.theme().palette();
+        // iced 0.14's `Color::is_readable_on`, which iced 0.13 lacks.
+        assert!(contrast_ratio(p.text, p.background) >= 6.0);
+    }
+
+    #[test]
+    fn derive_dark_transforms_widget_styles() {
+        let light: ThemeConfig = LIGHT.parse().unwrap();
+        let dark = light.derive_dark();
+        let before = format!("{:?}", light.button().unwrap());
+        let after = format!("{:?}", dark.button().unwrap());
+        assert_ne!(before, after);
+    }
+
+    #[test]
+    fn derive_dark_transforms_variables() {
+        let light: ThemeConfig = format!("[variables]\nink = \"#202020\"\n{LIGHT}").parse().unwrap();
+        let dark = light.derive_dark();
+        let (before, after) = (light.variable("ink").unwrap(), dark.variable("ink").unwrap());
+        assert!(to_oklch(after).l > to_oklch(before).l);
+    }
+
+    #[test]
+    fn quantized_colors_survive_with_accent() {
+        let quantized = LIGHT.parse::<ThemeConfig>().unwrap().quantized(ColorDepth::Bits(1));
+        assert_eq!(quantized.theme().palette().primary, Color::from_rgb(0.0, 1.0, 1.0));
+        let button = quantized.button().unwrap().style_fn()(&quantized.theme(), button::Status::Active);
+        assert_eq!(button.text_color, Color::WHITE);
+
+        let accented = quantized.with_accent(Color::from_rgb8(0x8E, 0x24, 0xAA)).unwrap();
+        assert_eq!(accented.theme().palette().primary, Color::from_rgb(1.0, 0.0, 1.0));
+        assert_ne!(quantized.fingerprint(), LIGHT.parse::<ThemeConfig>().unwrap().fingerprint());
+    }
+
+    #[test]
+    fn with_accent_replaces_primary() {
+        let config: ThemeConfig = LIGHT.parse().unwrap();
+        let accent = Color::from_rgb8(0x8E, 0x24, 0xAA);
+        let accented = config.with_accent(accent).unwrap();
+        assert_eq!(HexColor(accented.theme().palette().primary).to_string(), "#8E24AA");
+    }
+
+    #[test]
+    fn with_accent_rederives_dependent_values() {
+        let config: ThemeConfig = r##"
+[variables]
+brand   = "#1E88E5"
+brand-h = "lighten($brand, 10%)"
+
+[palette]
+background = "#FAFAFA"
+text       = "#202020"
+primary    = "$brand"
+success    = "#43A047"
+warning    = "#FB8C00"
+danger     = "#E53935"
+
+[button]
+background = "@primary"
+
+[button.hovered]
+background = "$brand-h"
+"##
+        .parse()
+        .unwrap();
+        let accented = config.with_accent(Color::from_rgb8(0x8E, 0x24, 0xAA)).unwrap();
+
+        let before = format!("{:?}", config.button().unwrap());
+        let after = format!("{:?}", accented.button().unwrap());
+        assert_ne!(before, after);
+        assert_eq!(HexColor(accented.theme().palette().primary).to_string(), "#8E24AA");
+    }
+
+    #[test]
+    fn with_accent_on_derived_theme_keeps_accent() {
+        let dark = LIGHT.parse::<ThemeConfig>().unwrap().derive_dark();
+        let accented = dark.with_accent(Color::from_rgb8(0x8E, 0x24, 0xAA)).unwrap();
+        assert_eq!(accented.name(), "Paper Dark");
+        let primary = HexColor(accented.theme().palette().primary).to_string();
+        let expected = Color::from_rgb8(0x8E, 0x24, 0xAA);
+        let actual = accented.theme().palette().primary;
+        assert!((actual.r - expected.r).abs() < 0.01, "got {primary}");
+        assert!((actual.g - expected.g).abs() < 0.01, "got {primary}");
+        assert!((actual.b - expected.b).abs() < 0.01, "got {primary}");
+    }
+
+    #[test]
+    fn merge_palette_from_keeps_widget_overrides() {
+        let toml = LIGHT.replace("background = \"#1E88E5\"", "background = \"@primary\"\nborder-radius = 6.0");
+        let config: ThemeConfig = toml.replace("danger     = \"#E53935\"", "danger = \"#E53935\"\ngenerator = \"hsl\"")
+            .parse()
+            .unwrap();
+        let dracula = config.merge_palette_from(&Theme::Dracula).unwrap();
+        assert_eq!(dracula.theme().palette(), Theme::Dracula.palette());
+        assert_eq!(dracula.palette_generator(), PaletteGenerator::default());
+        assert_eq!(dracula.name(), "Paper");
+
+        let style = dracula.button().unwrap().style_fn()(&dracula.theme(), button::Status::Active);
+        assert_eq!(style.background, Some(Background::Color(Theme::Dracula.palette().primary)));
+        assert_eq!(style.border.radius, 6.0.into());
+        assert_eq!(style.text_color, Color::WHITE);
+    }
+
+    #[test]
+    fn derive_dark_on_dark_theme_is_identity() {
+        let light: ThemeConfig = LIGHT.parse().unwrap();
+        let dark = light.derive_dark();
+        let again = dark.derive_dark();
+        assert_eq!(again.name(), dark.name());
+        assert_eq!(again.theme().palette(), dark.theme().palette());
+    }
+
+    #[test]
+    fn apply_patch_overrides_only_patched_keys() {
+        let config: ThemeConfig = LIGHT.parse().unwrap();
+        let patched = config.apply_patch("[button]\nbackground = '#FF0000'").unwrap();
+        let style = patched.button().unwrap().style_fn()(&patched.theme(), button::Status::Active);
+        assert_eq!(style.background, Some(iced_core::Background::Color(Color::from_rgb8(0xFF, 0, 0))));
+        assert_eq!(
+            ThemeDiff::between(&config, &patched).sections().collect::<Vec<_>>(),
+            ["button"]
+        );
+    }
+
+    #[test]
+    fn apply_patch_rederives_variables() {
+        let config: ThemeConfig =
+            format!("[variables]\nbrand = \"#1E88E5\"\n{}", LIGHT.replace("\"#1E88E5\"", "\"$brand\"")).parse().unwrap();
+        let patched = config.apply_patch("[variables]\nbrand = '#8E24AA'").unwrap();
+        assert_eq!(patched.theme().palette().primary, Color::from_rgb8(0x8E, 0x24, 0xAA));
+        assert_eq!(patched.variable("brand"), Some(Color::from_rgb8(0x8E, 0x24, 0xAA)));
+    }
+
+    #[test]
+    fn apply_patch_rejects_invalid_values() {
+        let config: ThemeConfig = LIGHT.parse().unwrap();
+        let err = config.apply_patch("[button]\nbackground = '#XYZ'").err().unwrap();
+        assert_eq!(err.field(), Some("button.background"));
+    }
+}
@@ -3,54 +3,130 @@
 //! Parses the `[variables]` table, resolves variable-to-variable references,
 //! then substitutes every `"$name"` string value throughout the TOML tree before
 //! serde touches it. This keeps all other parsing logic unchanged.
+//!
+//! Palette slots are exposed as variables too: `"$palette.primary"`, or the
+//! shorthand `"@primary"`, resolve to whatever `[palette] primary` resolves to.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use toml::Value;
 
+use crate::error::{Error, Reason};
+use crate::presets::is_pair_field;
+
 /// Removes `[variables]` from `root` and substitutes all `"$name"` references
-/// in the remaining tree. Returns an error string on undefined variables or cycles.
-pub(crate) fn resolve(root: &mut Value) -> Result<(), String> {
+/// in the remaining tree. Returns the user-defined variables with references
+/// and expressions resolved, or an error on undefined variables or cycles.
+pub(crate) fn resolve(root: &mut Value) -> Result<BTreeMap<String, String>, Error> {
     let vars = extract(root)?;
     if vars.is_empty() {
-        return Ok(());
+        return Ok(BTreeMap::new());
     }
     let vars = evaluate(vars)?;
-    substitute(root, &vars)
+    if let Some(table) = root.as_table_mut() {
+        for (key, value) in table.iter_mut() {
+            substitute(value, key, &vars)?;
+        }
+    }
+    Ok(vars.into_iter().filter(|(name, _)| !name.starts_with("palette.")).collect())
 }
 
-/// Removes the `[variables]` table from `root` and returns its key→value pairs.
-fn extract(root: &mut Value) -> Result<HashMap<String, String>, String> {
+/// Returns the variable a reference string points at: `"$name"` → `name`,
+/// `"@slot"` → `palette.slot`. Returns `None` for anything else.
+pub(crate) fn reference(s: &str) -> Option<String> {
+    if let Some(name) = s.strip_prefix('$') {
+        Some(name.to_string())
+    } else {
+        s.strip_prefix('@')
+            .filter(|slot| !slot.is_empty() && slot.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'))
+            .map(|slot| format!("palette.{slot}"))
+    }
+}
+
+/// Removes the `[variables]` table from `root` and returns its key→value pairs,
+/// together with every string-valued `[palette]` entry as `palette.<key>`.
+fn extract(root: &mut Value) -> Result<HashMap<String, String>, Error> {
     let table = match root.as_table_mut() {
         Some(t) => t,
         None => return Ok(HashMap::new()),
     };
 
+    let mut vars = HashMap::new();
+
+    if let Some(palette) = table.get("palette").and_then(Value::as_table) {
+        for (key, val) in palette {
+            if let Some(s) = literal(val) {
+                vars.insert(format!("palette.{key}"), s);
+            }
+        }
+    }
+
     let vars_value = match table.remove("variables") {
         Some(v) => v,
-        None => return Ok(HashMap::new()),
+        None => return Ok(vars),
     };
 
-    let vars_table = vars_value
-        .as_table()
-        .ok_or_else(|| "[variables] must be a TOML table".to_string())?;
+    let vars_table = vars_value.as_table().ok_or_else(|| Error::InvalidValue {
+        field: "variables".to_string(),
+        value: vars_value.to_string(),
+        reason: Reason::NotATable,
+    })?;
 
-    let mut vars = HashMap::new();
     for (key, val) in vars_table {
-        match val.as_str() {
+        match literal(val) {
             Some(s) => {
-                vars.insert(key.clone(), s.to_string());
+                vars.insert(key.clone(), s);
+            }
+            None => {
+                return Err(Error::InvalidValue {
+                    field: field(key),
+                    value: val.to_string(),
+                    reason: Reason::NotAString,
+                });
             }
-            None => return Err(format!("variable `{key}` must be a string value")),
         }
     }
 
     Ok(vars)
 }
 
+/// A variable's value: a string, a number as written, an array color
+/// literal as its hex string, or an `[x, y]` pair as written. Integers stay
+/// numbers, so `fade = 1` can fill `border-width`; color fields and color
+/// expressions read them as `0xRRGGBB` literals.
+fn literal(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Float(n) => Some(n.to_string()),
+        Value::Integer(n) => Some(n.to_string()),
+        Value::Array(items) => {
+            let channels = items
+                .iter()
+                .map(|item| item.as_float().or_else(|| item.as_integer().map(|n| n as f64)).map(|n| n as f32))
+                .collect::<Option<Vec<_>>>()?;
+            if channels.len() == 2 {
+                return Some(value.to_string());
+            }
+            let color = crate::color::array_color(&channels).ok()?;
+            Some(crate::color::HexColor(color).to_string())
+        }
+        _ => None,
+    }
+}
+
+/// The `[x, y]` pair a variable holds, back as a TOML array.
+fn pair(s: &str) -> Option<Value> {
+    let (x, y) = s.strip_prefix('[')?.strip_suffix(']')?.split_once(',')?;
+    let number = |n: &str| {
+        let n = n.trim();
+        n.parse::<i64>().map(Value::Integer).or_else(|_| n.parse::<f64>().map(Value::Float)).ok()
+    };
+    Some(Value::Array(vec![number(x)?, number(y)?]))
+}
+
 /// Resolves variable-to-variable references iteratively, then evaluates any
 /// color transformation expressions (e.g. `"darken($primary, 20%)"`).
 /// Detects cycles and undefined references, returning a descriptive error.
-fn evaluate(mut vars: HashMap<String, String>) -> Result<HashMap<String, String>, String> {
+fn evaluate(mut vars: HashMap<String, String>) -> Result<HashMap<String, String>, Error> {
     // Phase 1: resolve plain `$name` references iteratively.
     // One pass per variable is sufficient for any non-cyclic chain.
     for _ in 0..=vars.len() {
@@ -58,17 +134,19 @@ fn evaluate(mut vars: HashMap<String, String>) -> Result<HashMap<String, String>
         let mut changed = false;
 
         for (key, val) in vars.iter_mut() {
-            if let Some(name) = val.strip_prefix('$') {
-                match snapshot.get(name) {
+            if let Some(name) = reference(val) {
+                match snapshot.get(&name) {
                     Some(resolved) if resolved != val => {
                         *val = resolved.clone();
                         changed = true;
                     }
                     Some(_) => {} // value unchanged; will be caught by post-loop check
                     None => {
-                        return Err(format!(
-                            "undefined variable `${name}` (referenced from `${key}`)"
-                        ));
+                        return Err(Error::InvalidColor {
+                            field: field(key),
+                            value: val.clone(),
+                            reason: Reason::UndefinedVariable(val.clone()),
+                        });
                     }
                 }
             }
@@ -80,63 +158,115 @@ fn evaluate(mut vars: HashMap<String, String>) -> Result<HashMap<String, String>
     }
 
     // Any remaining `$ref` values indicate a cycle.
-    let cyclic: Vec<String> = vars
-        .iter()
-        .filter(|(_, v)| v.starts_with('$'))
-        .map(|(k, _)| format!("`${k}`"))
-        .collect();
-
-    if !cyclic.is_empty() {
-        return Err(format!(
-            "cyclic variable references: {}",
-            cyclic.join(", ")
-        ));
+    let mut cyclic: Vec<&String> = vars.keys().filter(|k| reference(&vars[*k]).is_some()).collect();
+    cyclic.sort();
+
+    if let Some(first) = cyclic.first() {
+        return Err(Error::InvalidColor {
+            field: field(first),
+            value: vars[*first].clone(),
+            reason: Reason::CyclicVariables(cyclic.iter().map(|k| k.to_string()).collect()),
+        });
     }
 
     // Phase 2: evaluate any color expression values (e.g. `"darken($primary, 20%)"`).
-    // The snapshot here has all $refs resolved, so expression args can be looked up.
-    let snapshot = vars.clone();
-    for (key, val) in vars.iter_mut() {
-        if is_expr(val) {
-            *val = crate::expr::evaluate(val, &snapshot)
-                .map_err(|e| format!("variable `{key}`: {e}"))?;
+    // Each pass evaluates the expressions whose arguments are all evaluated, so
+    // `h = "hue(@primary)"` is ready before `"hsl($h, 50%, 50%)"` reads it.
+    let mut pending: Vec<String> = vars.keys().filter(|k| is_expr(&vars[*k])).cloned().collect();
+    pending.sort();
+    while !pending.is_empty() {
+        let (ready, waiting): (Vec<String>, Vec<String>) = pending
+            .iter()
+            .cloned()
+            .partition(|key| arguments(&vars[key]).all(|name| !pending.contains(&name)));
+
+        // Expressions left waiting on each other form a cycle.
+        if ready.is_empty() {
+            return Err(Error::InvalidColor {
+                field: field(&waiting[0]),
+                value: vars[&waiting[0]].clone(),
+                reason: Reason::CyclicVariables(waiting),
+            });
+        }
+        for key in ready {
+            let value = vars[&key].clone();
+            let evaluated = crate::expr::evaluate(&value, &vars).map_err(|reason| Error::InvalidColor {
+                field: field(&key),
+                value,
+                reason,
+            })?;
+            vars.insert(key, evaluated);
         }
+        pending = waiting;
     }
 
     Ok(vars)
 }
 
+/// The variables an expression reads, by their keys in `vars`.
+fn arguments(expr: &str) -> impl Iterator<Item = String> + '_ {
+    expr.split(|c: char| matches!(c, '(' | ')' | ',' | '[' | ']') || c.is_whitespace())
+        .filter_map(reference)
+}
+
 /// Returns true if `s` looks like a color expression function call.
 fn is_expr(s: &str) -> bool {
     s.contains('(') && s.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
 }
 
-/// Walks `value` recursively, replacing:
-/// - `"$name"` strings with the resolved color from `vars`
+/// The key path of a variable: `variables.<name>`, or `palette.<slot>` for
+/// the palette entries exposed as variables.
+fn field(key: &str) -> String {
+    if key.starts_with("palette.") {
+        key.to_string()
+    } else {
+        format!("variables.{key}")
+    }
+}
+
+/// Walks `value` (found at key path `path`) recursively, replacing:
+/// - `"$name"` and `"@slot"` strings with the resolved color from `vars`
 /// - `"fn(...)"` strings with the result of evaluating the expression
 ///
+/// Results that are plain numbers, like `"alpha($overlay)"`, become TOML
+/// numbers so they can fill numeric fields, and `[x, y]` pairs become arrays
+/// in the fields that take one, like `shadow-offset`.
+///
 /// Returns an error for undefined variables or invalid expressions.
-fn substitute(value: &mut Value, vars: &HashMap<String, String>) -> Result<(), String> {
+fn substitute(value: &mut Value, path: &str, vars: &HashMap<String, String>) -> Result<(), Error> {
     match value {
         Value::String(s) => {
-            if let Some(name) = s.strip_prefix('$') {
-                match vars.get(name) {
-                    Some(resolved) => *s = resolved.clone(),
-                    None => return Err(format!("undefined variable `${name}`")),
-                }
+            let resolved = if let Some(name) = reference(s) {
+                vars.get(&name).cloned().ok_or_else(|| Reason::UndefinedVariable(s.clone()))
             } else if is_expr(s) {
-                *s = crate::expr::evaluate(s, vars)
-                    .map_err(|e| format!("in expression `{s}`: {e}"))?;
-            }
+                crate::expr::evaluate(s, vars)
+            } else {
+                return Ok(());
+            };
+            let resolved = resolved.map_err(|reason| Error::InvalidColor {
+                field: path.to_string(),
+                value: s.clone(),
+                reason,
+            })?;
+            let key = path.rsplit('.').next().unwrap_or(path);
+            *value = if let Ok(n) = resolved.parse::<i64>() {
+                Value::Integer(n)
+            } else if let Ok(n) = resolved.parse::<f64>() {
+                Value::Float(n)
+            } else if let Some(pair) = pair(&resolved).filter(|_| is_pair_field(key)) {
+                pair
+            } else {
+                Value::String(resolved)
+            };
         }
         Value::Array(arr) => {
             for item in arr {
-                substitute(item, vars)?;
+                substitute(item, path, vars)?;
             }
         }
         Value::Table(table) => {
-            for (_, val) in table.iter_mut() {
-                substitute(val, vars)?;
+            for (key, val) in table.iter_mut() {
+                substitute(val, &format!("{path}.{key}"), vars)?;
             }
         }
         _ => {}
@@ -175,11 +305,122 @@ primary = "#66C0F4"
 background = "$primary"
 "##,
         );
-        resolve(&mut v).unwrap();
+        let vars = resolve(&mut v).unwrap();
         assert!(v.as_table().unwrap().get("variables").is_none());
+        assert_eq!(vars.get("primary").map(String::as_str), Some("#66C0F4"));
         assert_eq!(v["button"]["background"].as_str(), Some("#66C0F4"));
     }
 
+    #[test]
+    fn color_literals_are_variables() {
+        let mut v = parse(
+            r##"
+[variables]
+accent = 0x66C0F4
+overlay = [0.0, 0.0, 0.0, 0.5]
+
+[palette]
+primary = 0x1B2838
+
+[button]
+background = "$accent"
+text-color = "@primary"
+border-color = "$overlay"
+"##,
+        );
+        resolve(&mut v).unwrap();
+        assert_eq!(v["button"]["background"].as_integer(), Some(0x66C0F4));
+        assert_eq!(v["button"]["text-color"].as_integer(), Some(0x1B2838));
+        assert_eq!(v["button"]["border-color"].as_str(), Some("#00000080"));
+    }
+
+    #[test]
+    fn integer_variables_stay_numbers() {
+        let mut v = parse(
+            r##"
+[variables]
+fade = 1
+accent = 0x66C0F4
+
+[button]
+border-width = "$fade"
+background = "darken($accent, 10%)"
+"##,
+        );
+        resolve(&mut v).unwrap();
+        assert_eq!(v["button"]["border-width"].as_integer(), Some(1));
+        let darker = crate::expr::evaluate("darken(#66C0F4, 10%)", &HashMap::new()).unwrap();
+        assert_eq!(v["button"]["background"].as_str(), Some(darker.as_str()));
+    }
+
+    #[test]
+    fn pair_variables_become_arrays_only_in_pair_fields() {
+        let mut v = parse(
+            r##"
+[variables]
+lift = [0, 2]
+caption = "[1, 2]"
+
+[button]
+shadow-offset = "$lift"
+
+[metadata]
+description = "$caption"
+"##,
+        );
+        resolve(&mut v).unwrap();
+        assert_eq!(v["button"]["shadow-offset"], Value::Array(vec![Value::Integer(0), Value::Integer(2)]));
+        assert_eq!(v["metadata"]["description"].as_str(), Some("[1, 2]"));
+    }
+
+    #[test]
+    fn numeric_results_become_numbers() {
+        let mut v = parse(
+            r##"
+[variables]
+overlay = "#00000080"
+
+[defaults]
+disabled-opacity = "alpha($overlay)"
+"##,
+        );
+        resolve(&mut v).unwrap();
+        assert_eq!(v["defaults"]["disabled-opacity"].as_float(), Some(0.502));
+    }
+
+    #[test]
+    fn expressions_read_other_expressions() {
+        let mut v = parse(
+            r##"
+[palette]
+primary = "#FF0000"
+
+[variables]
+accent = "hsl($h, 100%, $l)"
+h      = "add(hue(@primary), 120)"
+l      = "lightness(@primary)"
+
+[button]
+background = "$accent"
+"##,
+        );
+        resolve(&mut v).unwrap();
+        assert_eq!(v["button"]["background"].as_str(), Some("#00FF00"));
+    }
+
+    #[test]
+    fn expressions_reading_each_other_are_cyclic() {
+        let mut v = parse(
+            r##"
+[variables]
+a = "darken($b, 10%)"
+b = "lighten($a, 10%)"
+"##,
+        );
+        let err = resolve(&mut v).unwrap_err();
+        assert!(matches!(err.reason(), Some(Reason::CyclicVariables(names)) if names[..] == ["a", "b"]), "got: {err}");
+    }
+
     #[test]
     fn variable_to_variable_reference() {
         let mut v = parse(
@@ -218,6 +459,29 @@ stops = [
         assert_eq!(stops[1]["color"].as_str(), Some("#2A3F5F"));
     }
 
+    #[test]
+    fn gradient_offsets_take_numeric_variables() {
+        let mut v = parse(
+            r##"
+[variables]
+fade-point = 0.4
+fade-end   = "add($fade-point, 0.2)"
+
+[container.background]
+angle = 90
+stops = [
+  { offset = 0.0,           color = "#000000" },
+  { offset = "$fade-point", color = "#000000" },
+  { offset = "$fade-end",   color = "#FFFFFF" },
+]
+"##,
+        );
+        resolve(&mut v).unwrap();
+        let stops = v["container"]["background"]["stops"].as_array().unwrap();
+        assert_eq!(stops[1]["offset"].as_float(), Some(0.4));
+        assert_eq!(stops[2]["offset"].as_float(), Some(0.6));
+    }
+
     #[test]
     fn undefined_variable_returns_error() {
         let mut v = parse(
@@ -230,7 +494,7 @@ background = "$primary"
 "#,
         );
         let err = resolve(&mut v).unwrap_err();
-        assert!(err.contains("undefined variable"), "got: {err}");
+        assert!(matches!(err.reason(), Some(Reason::UndefinedVariable(_))), "got: {err}");
     }
 
     #[test]
@@ -245,7 +509,8 @@ background = "$undefined"
 "##,
         );
         let err = resolve(&mut v).unwrap_err();
-        assert!(err.contains("undefined variable"), "got: {err}");
+        assert!(matches!(err.reason(), Some(Reason::UndefinedVariable(_))), "got: {err}");
+        assert_eq!(err.field(), Some("button.background"));
     }
 
     #[test]
@@ -261,7 +526,61 @@ primary = "$a"
 "#,
         );
         let err = resolve(&mut v).unwrap_err();
-        assert!(err.contains("cyclic"), "got: {err}");
+        assert_eq!(err.field(), Some("variables.a"));
+        assert!(matches!(err.reason(), Some(Reason::CyclicVariables(names)) if names[..2] == ["a", "b"]), "got: {err}");
+    }
+
+    #[test]
+    fn palette_reference() {
+        let mut v = parse(
+            r##"
+[palette]
+primary = "#66C0F4"
+
+[button]
+background = "$palette.primary"
+text-color = "@primary"
+"##,
+        );
+        resolve(&mut v).unwrap();
+        assert_eq!(v["button"]["background"].as_str(), Some("#66C0F4"));
+        assert_eq!(v["button"]["text-color"].as_str(), Some("#66C0F4"));
+    }
+
+    #[test]
+    fn palette_reference_follows_variables() {
+        let mut v = parse(
+            r##"
+[variables]
+brand = "#66C0F4"
+hover = "lighten(@primary, 10%)"
+
+[palette]
+primary = "$brand"
+
+[button]
+background = "$hover"
+"##,
+        );
+        resolve(&mut v).unwrap();
+        assert_eq!(v["palette"]["primary"].as_str(), Some("#66C0F4"));
+        assert!(v["button"]["background"].as_str().unwrap().starts_with('#'));
+    }
+
+    #[test]
+    fn undefined_palette_slot_returns_error() {
+        let mut v = parse(
+            r##"
+[palette]
+primary = "#66C0F4"
+
+[button]
+background = "@accent"
+"##,
+        );
+        let err = resolve(&mut v).unwrap_err();
+        assert_eq!(err.field(), Some("button.background"));
+        assert_eq!(err.reason(), Some(&Reason::UndefinedVariable("@accent".to_string())));
     }
 
     #[test]
@@ -278,4 +597,23 @@ family = "Arial"
         resolve(&mut v).unwrap();
         assert_eq!(v["font"]["family"].as_str(), Some("Arial"));
     }
+
+    #[test]
+    fn resolved_variables_are_returned() {
+        let mut v = parse(
+            r##"
+[variables]
+primary = "#66C0F4"
+muted   = "$primary"
+dark    = "darken($primary, 20%)"
+
+[palette]
+primary = "$primary"
+"##,
+        );
+        let vars = resolve(&mut v).unwrap();
+        assert_eq!(vars.keys().collect::<Vec<_>>(), ["dark", "muted", "primary"]);
+        assert_eq!(vars["muted"], "#66C0F4");
+        assert!(vars["dark"].starts_with('#'));
+    }
 }
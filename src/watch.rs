@@ -0,0 +1,543 @@
+//! Hot reloading: a shared [`ThemeHandle`] and a polling [`ThemeWatcher`] for
+//! a single file, and a [`DirectoryWatcher`] keeping a [`ThemeLibrary`] in
+//! sync with a themes directory.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError, RwLock, Weak};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::ThemeConfig;
+use crate::diff::ThemeDiff;
+use crate::error::Error;
+use crate::include;
+use crate::library::{ThemeLibrary, theme_files};
+
+type ReloadHook = Box<dyn Fn(&ThemeConfig, &ThemeConfig, &ThemeDiff) + Send + Sync>;
+type EventHook = Box<dyn Fn(&LibraryEvent) + Send>;
+
+/// A cheaply cloneable, thread-safe slot holding the current [`ThemeConfig`].
+///
+/// Keep one in your app state and read [`current()`](Self::current) from the
+/// `.theme()` closure; a [`ThemeWatcher`] (or your own code, via
+/// [`replace()`](Self::replace)) swaps in new configs behind it.
+#[derive(Clone)]
+pub struct ThemeHandle {
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    current: RwLock<Arc<ThemeConfig>>,
+    hooks: Mutex<Vec<(u64, ReloadHook)>>,
+    next_hook: AtomicU64,
+}
+
+impl ThemeHandle {
+    pub fn new(config: ThemeConfig) -> Self {
+        ThemeHandle {
+            shared: Arc::new(Shared {
+                current: RwLock::new(Arc::new(config)),
+                hooks: Mutex::new(Vec::new()),
+                next_hook: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// Returns the current config.
+    pub fn current(&self) -> Arc<ThemeConfig> {
+        let current = self.shared.current.read().unwrap_or_else(PoisonError::into_inner);
+        Arc::clone(&current)
+    }
+
+    /// Register a hook called with `(old, new, diff)` after every successful
+    /// reload, so apps can react selectively:
+    ///
+    /// ```no_run
+    /// # use iced_themer::ThemeHandle;
+    /// # let handle: ThemeHandle = todo!();
+    /// handle.on_reload(|_old, new, diff| {
+    ///     if diff.changed("font") {
+    ///         println!("font is now {:?}", new.font());
+    ///     }
+    /// });
+    /// ```
+    ///
+    /// Hooks run on the thread doing the reload, in registration order. A hook
+    /// must not register further hooks.
+    pub fn on_reload(&self, hook: impl Fn(&ThemeConfig, &ThemeConfig, &ThemeDiff) + Send + Sync + 'static) {
+        self.register(Box::new(hook));
+    }
+
+    /// Like [`on_reload()`](Self::on_reload), but the hook is removed again
+    /// when the returned guard is dropped.
+    #[cfg(feature = "application")]
+    pub(crate) fn on_reload_scoped(
+        &self,
+        hook: impl Fn(&ThemeConfig, &ThemeConfig, &ThemeDiff) + Send + Sync + 'static,
+    ) -> HookGuard {
+        HookGuard { shared: Arc::downgrade(&self.shared), id: self.register(Box::new(hook)) }
+    }
+
+    fn register(&self, hook: ReloadHook) -> u64 {
+        let id = self.shared.next_hook.fetch_add(1, Ordering::Relaxed);
+        self.shared.hooks.lock().unwrap_or_else(PoisonError::into_inner).push((id, hook));
+        id
+    }
+
+    /// Swap in `config`, run the reload hooks, and return what changed.
+    pub fn replace(&self, config: ThemeConfig) -> ThemeDiff {
+        let new = Arc::new(config);
+        let old = {
+            let mut current = self.shared.current.write().unwrap_or_else(PoisonError::into_inner);
+            std::mem::replace(&mut *current, Arc::clone(&new))
+        };
+
+        let diff = ThemeDiff::between(&old, &new);
+        for (_, hook) in self.shared.hooks.lock().unwrap_or_else(PoisonError::into_inner).iter() {
+            hook(&old, &new, &diff);
+        }
+        diff
+    }
+
+    /// Identifies the shared slot, so clones of one handle compare equal.
+    #[cfg(feature = "application")]
+    pub(crate) fn id(&self) -> usize {
+        Arc::as_ptr(&self.shared) as usize
+    }
+}
+
+/// Removes a hook registered by [`ThemeHandle::on_reload_scoped`] when dropped.
+#[cfg(feature = "application")]
+pub(crate) struct HookGuard {
+    shared: Weak<Shared>,
+    id: u64,
+}
+
+#[cfg(feature = "application")]
+impl Drop for HookGuard {
+    fn drop(&mut self) {
+        if let Some(shared) = self.shared.upgrade() {
+            shared.hooks.lock().unwrap_or_else(PoisonError::into_inner).retain(|(id, _)| *id != self.id);
+        }
+    }
+}
+
+impl fmt::Debug for ThemeHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ThemeHandle").field("name", &self.current().name()).finish_non_exhaustive()
+    }
+}
+
+/// Reloads a theme file into a [`ThemeHandle`] whenever its modification time,
+/// or that of any file it `extends`, changes.
+///
+/// Call [`poll()`](Self::poll) yourself (e.g. from an iced subscription tick),
+/// or hand the watcher to a background thread with [`spawn()`](Self::spawn):
+///
+/// ```no_run
+/// # use std::time::Duration;
+/// # use iced_themer::ThemeWatcher;
+/// let handle = ThemeWatcher::new("theme.toml")?
+///     .on_reload(|_old, _new, diff| eprintln!("theme reloaded: {diff:?}"))
+///     .spawn(Duration::from_millis(500));
+/// # Ok::<(), iced_themer::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct ThemeWatcher {
+    path: PathBuf,
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+    handle: ThemeHandle,
+}
+
+impl ThemeWatcher {
+    /// Load the theme at `path` and start tracking it.
+    pub fn new(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref().to_path_buf();
+        let (config, files) = load_tracked(&path, None)?;
+        let handle = ThemeHandle::new(config);
+        Ok(ThemeWatcher { path, files: stamp(files, &[]), handle })
+    }
+
+    /// The watched file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// A handle that always holds the most recently loaded config.
+    pub fn handle(&self) -> ThemeHandle {
+        self.handle.clone()
+    }
+
+    /// Register a reload hook; see [`ThemeHandle::on_reload`].
+    pub fn on_reload(self, hook: impl Fn(&ThemeConfig, &ThemeConfig, &ThemeDiff) + Send + Sync + 'static) -> Self {
+        self.handle.on_reload(hook);
+        self
+    }
+
+    /// Reload the file if it, or a file it extends, changed since the last
+    /// check.
+    ///
+    /// Returns `Ok(None)` if nothing changed. If the new contents fail to
+    /// load, the error is returned, the handle keeps the previous config, and
+    /// the file is not retried until it changes again.
+    pub fn poll(&mut self) -> Result<Option<ThemeDiff>, Error> {
+        check(&self.path, &mut self.files, &self.handle)
+    }
+
+    /// Poll every `interval` on a background thread and return the handle.
+    ///
+    /// Reload errors are skipped, keeping the last good config. The thread
+    /// exits once every clone of the returned handle has been dropped.
+    pub fn spawn(self, interval: Duration) -> ThemeHandle {
+        let ThemeWatcher { path, mut files, handle } = self;
+        let shared: Weak<Shared> = Arc::downgrade(&handle.shared);
+
+        thread::spawn(move || {
+            loop {
+                thread::sleep(interval);
+                let Some(shared) = shared.upgrade() else { break };
+                let _ = check(&path, &mut files, &ThemeHandle { shared });
+            }
+        });
+
+        handle
+    }
+}
+
+/// The modification time of `path`, or `None` if it's missing or unknown.
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+/// Pairs `files` with their modification times, reusing those in `known`.
+fn stamp(files: Vec<PathBuf>, known: &[(PathBuf, Option<SystemTime>)]) -> Vec<(PathBuf, Option<SystemTime>)> {
+    files
+        .into_iter()
+        .map(|file| {
+            let time = known.iter().find(|(path, _)| *path == file).map_or_else(|| modified(&file), |(_, time)| *time);
+            (file, time)
+        })
+        .collect()
+}
+
+/// Reloads the theme at `path` if any of the files it was last loaded from
+/// changed, then tracks the files the new version reads.
+fn check(
+    path: &Path,
+    files: &mut Vec<(PathBuf, Option<SystemTime>)>,
+    handle: &ThemeHandle,
+) -> Result<Option<ThemeDiff>, Error> {
+    let current: Vec<_> = files.iter().map(|(file, _)| (file.clone(), modified(file))).collect();
+    if current == *files {
+        return Ok(None);
+    }
+    *files = current;
+    let (config, tracked) = load_tracked(path, Some(&handle.current()))?;
+    *files = stamp(tracked, files);
+    Ok(Some(handle.replace(config)))
+}
+
+/// Loads the theme file at `path`, reusing whatever didn't change since
+/// `previous` if the file had loaded before.
+fn load(path: &Path, previous: Option<&ThemeConfig>) -> Result<ThemeConfig, Error> {
+    Ok(load_tracked(path, previous)?.0)
+}
+
+/// Like [`load`], also returning every file the theme was read from.
+fn load_tracked(path: &Path, previous: Option<&ThemeConfig>) -> Result<(ThemeConfig, Vec<PathBuf>), Error> {
+    let (source, files) = include::read_tracked(path, include::root_of(path))?;
+    let config = match previous {
+        Some(previous) => previous.reload(source)?,
+        None => ThemeConfig::from_value(source)?,
+    };
+    Ok((config, files))
+}
+
+/// A change to a themes directory, reported by [`DirectoryWatcher::poll`].
+#[derive(Debug)]
+pub enum LibraryEvent {
+    /// A new theme file loaded successfully.
+    Added(PathBuf),
+    /// A theme file was modified and reloaded.
+    Changed(PathBuf, ThemeDiff),
+    /// A theme file was deleted (or renamed away).
+    Removed(PathBuf),
+    /// A new or modified file failed to load. If an earlier version had
+    /// loaded, the library keeps it.
+    Failed(PathBuf, Error),
+}
+
+/// Watches a directory of `*.toml` theme files, keeping a [`ThemeLibrary`] up
+/// to date as files appear, change, or are deleted.
+///
+/// Nothing is loaded until the first [`poll()`](Self::poll), which reports
+/// every existing file as [`LibraryEvent::Added`] (or `Failed`).
+///
+/// ```no_run
+/// # use std::time::Duration;
+/// # use iced_themer::{DirectoryWatcher, LibraryEvent};
+/// let library = DirectoryWatcher::new("themes")
+///     .on_event(|event| {
+///         if let LibraryEvent::Failed(path, error) = event {
+///             eprintln!("{}: {error}", path.display());
+///         }
+///     })
+///     .spawn(Duration::from_secs(1))?;
+///
+/// for (path, config) in library.current().iter() {
+///     println!("{}: {}", path.display(), config.name());
+/// }
+/// # Ok::<(), iced_themer::Error>(())
+/// ```
+pub struct DirectoryWatcher {
+    dir: PathBuf,
+    seen: BTreeMap<PathBuf, Option<SystemTime>>,
+    library: ThemeLibrary,
+    hooks: Vec<EventHook>,
+}
+
+impl DirectoryWatcher {
+    pub fn new(dir: impl AsRef<Path>) -> Self {
+        DirectoryWatcher {
+            dir: dir.as_ref().to_path_buf(),
+            seen: BTreeMap::new(),
+            library: ThemeLibrary::new(),
+            hooks: Vec::new(),
+        }
+    }
+
+    /// The watched directory.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The themes loaded so far.
+    pub fn library(&self) -> &ThemeLibrary {
+        &self.library
+    }
+
+    /// Register a hook called for every event, in order, from
+    /// [`poll()`](Self::poll).
+    pub fn on_event(mut self, hook: impl Fn(&LibraryEvent) + Send + 'static) -> Self {
+        self.hooks.push(Box::new(hook));
+        self
+    }
+
+    /// Rescan the directory and load whatever was added or modified since the
+    /// last call. Returns an error only if the directory itself can't be read.
+    pub fn poll(&mut self) -> Result<Vec<LibraryEvent>, Error> {
+        let mut current = BTreeMap::new();
+        for path in theme_files(&self.dir)? {
+            let modified = modified(&path);
+            current.insert(path, modified);
+        }
+
+        let mut events = Vec::new();
+
+        for path in self.seen.keys().filter(|path| !current.contains_key(*path)) {
+            if self.library.remove(path).is_some() {
+                events.push(LibraryEvent::Removed(path.clone()));
+            }
+        }
+
+        for (path, modified) in &current {
+            if self.seen.get(path) == Some(modified) {
+                continue;
+            }
+            let event = match load(path, self.library.get(path).map(|config| &**config)) {
+                Ok(config) => {
+                    let new = config.clone();
+                    match self.library.insert(path.clone(), config) {
+                        Some(old) => LibraryEvent::Changed(path.clone(), ThemeDiff::between(&old, &new)),
+                        None => LibraryEvent::Added(path.clone()),
+                    }
+                }
+                Err(error) => LibraryEvent::Failed(path.clone(), error),
+            };
+            events.push(event);
+        }
+
+        self.seen = current;
+        for event in &events {
+            for hook in &self.hooks {
+                hook(event);
+            }
+        }
+        Ok(events)
+    }
+
+    /// Scan once, then rescan every `interval` on a background thread.
+    ///
+    /// The initial scan runs before this returns, so the handle starts out
+    /// populated. Later directory read errors are skipped. The thread exits
+    /// once every clone of the returned handle has been dropped.
+    pub fn spawn(mut self, interval: Duration) -> Result<LibraryHandle, Error> {
+        self.poll()?;
+        let handle = LibraryHandle {
+            current: Arc::new(RwLock::new(Arc::new(self.library.clone()))),
+        };
+        let current = Arc::downgrade(&handle.current);
+
+        thread::spawn(move || {
+            loop {
+                thread::sleep(interval);
+                let Some(current) = current.upgrade() else { break };
+                if let Ok(events) = self.poll()
+                    && !events.is_empty()
+                {
+                    *current.write().unwrap_or_else(PoisonError::into_inner) = Arc::new(self.library.clone());
+                }
+            }
+        });
+
+        Ok(handle)
+    }
+}
+
+impl fmt::Debug for DirectoryWatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DirectoryWatcher")
+            .field("dir", &self.dir)
+            .field("themes", &self.library.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A cheaply cloneable handle to a [`ThemeLibrary`] kept up to date by
+/// [`DirectoryWatcher::spawn`].
+#[derive(Clone)]
+pub struct LibraryHandle {
+    current: Arc<RwLock<Arc<ThemeLibrary>>>,
+}
+
+impl LibraryHandle {
+    /// A snapshot of the library as of the latest scan.
+    pub fn current(&self) -> Arc<ThemeLibrary> {
+        Arc::clone(&self.current.read().unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
+impl fmt::Debug for LibraryHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LibraryHandle").field("themes", &self.current().len()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::testing::{palette, temp_path, write};
+
+    const THEME: &str = concat!(palette!(), "\n[font]\nfamily = \"serif\"\n");
+
+    #[test]
+    fn replace_runs_hooks_with_diff() {
+        let handle = ThemeHandle::new(THEME.parse().unwrap());
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        handle.on_reload(move |old, new, diff| {
+            assert!(old.font().is_some());
+            assert!(new.font().is_none());
+            assert_eq!(diff.sections().collect::<Vec<_>>(), ["font"]);
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+        let without_font = THEME.replace("[font]\nfamily = \"serif\"\n", "");
+        handle.replace(without_font.parse().unwrap());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(handle.current().font().is_none());
+    }
+
+    #[cfg(feature = "application")]
+    #[test]
+    fn scoped_hooks_unregister_on_drop() {
+        let handle = ThemeHandle::new(THEME.parse().unwrap());
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let guard = handle.on_reload_scoped(move |_, _, _| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+        handle.replace(THEME.parse().unwrap());
+        drop(guard);
+        handle.replace(THEME.parse().unwrap());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn poll_reloads_only_when_modified() {
+        let path = temp_path("watch.toml");
+        write(&path, THEME, 60);
+
+        let mut watcher = ThemeWatcher::new(&path).unwrap();
+        assert!(watcher.poll().unwrap().is_none());
+
+        write(&path, &THEME.replace("\"serif\"", "\"monospace\""), 30);
+        let diff = watcher.poll().unwrap().unwrap();
+        assert!(diff.changed("font"));
+        assert!(watcher.poll().unwrap().is_none());
+
+        write(&path, "not a theme", 0);
+        assert!(watcher.poll().is_err());
+        assert!(watcher.handle().current().font().is_some());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn poll_reloads_when_an_extended_file_changes() {
+        let dir = temp_path("watch-extends");
+        fs::create_dir_all(&dir).unwrap();
+        let (base, child) = (dir.join("base.toml"), dir.join("theme.toml"));
+        write(&base, THEME, 60);
+        write(&child, "extends = \"base.toml\"\nname = \"Child\"\n", 60);
+
+        let mut watcher = ThemeWatcher::new(&child).unwrap();
+        assert!(watcher.poll().unwrap().is_none());
+
+        write(&base, &THEME.replace("\"serif\"", "\"monospace\""), 30);
+        let diff = watcher.poll().unwrap().unwrap();
+        assert!(diff.changed("font"));
+        assert!(watcher.poll().unwrap().is_none());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn directory_watcher_tracks_additions_changes_and_removals() {
+        let dir = temp_path("library");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir(&dir).unwrap();
+        let (a, b) = (dir.join("a.toml"), dir.join("b.toml"));
+        write(&a, &format!("name = \"A\"\n{THEME}"), 60);
+        fs::write(dir.join("notes.txt"), "not a theme").unwrap();
+
+        let mut watcher = DirectoryWatcher::new(&dir);
+        let events = watcher.poll().unwrap();
+        assert!(matches!(&events[..], [LibraryEvent::Added(path)] if *path == a));
+        assert!(watcher.poll().unwrap().is_empty());
+
+        write(&b, "broken", 60);
+        assert!(matches!(&watcher.poll().unwrap()[..], [LibraryEvent::Failed(path, _)] if *path == b));
+
+        write(&a, &format!("name = \"A2\"\n{THEME}"), 30);
+        write(&b, &format!("name = \"B\"\n{THEME}"), 30);
+        let events = watcher.poll().unwrap();
+        assert!(matches!(&events[0], LibraryEvent::Changed(path, diff) if *path == a && diff.changed("name")));
+        assert!(matches!(&events[1], LibraryEvent::Added(path) if *path == b));
+        assert_eq!(watcher.library().by_name("A2").map(|c| c.name()), Some("A2"));
+
+        fs::remove_file(&a).unwrap();
+        assert!(matches!(&watcher.poll().unwrap()[..], [LibraryEvent::Removed(path)] if *path == a));
+        assert_eq!(watcher.library().len(), 1);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}